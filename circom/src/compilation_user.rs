@@ -4,6 +4,7 @@ use compiler::compiler_interface::{Config, VCP};
 use program_structure::error_definition::Report;
 use program_structure::error_code::ReportCode;
 use program_structure::file_definition::FileLibrary;
+use program_structure::lint_config::LintConfig;
 use crate::VERSION;
 
 
@@ -26,6 +27,7 @@ pub struct CompilerConfig {
     pub sanity_check_style: usize,
 
     pub prime: String,
+    pub lint_config: LintConfig,
 }
 
 pub fn compile(config: CompilerConfig) -> Result<(), ()> {
@@ -104,7 +106,7 @@ pub fn compile(config: CompilerConfig) -> Result<(), ()> {
                 let result = wat_to_wasm(&config.wat_file, &config.wasm_file);
                 match result {
                     Result::Err(report) => {
-                        Report::print_reports(&[report], &FileLibrary::new());
+                        Report::print_reports(&[report], &FileLibrary::new(), &config.lint_config);
                         return Err(());
                     }
                     Result::Ok(()) => {
@@ -118,7 +120,7 @@ pub fn compile(config: CompilerConfig) -> Result<(), ()> {
                 std::fs::remove_file(&config.wat_file).unwrap();
                 match result {
                     Result::Err(report) => {
-                        Report::print_reports(&[report], &FileLibrary::new());
+                        Report::print_reports(&[report], &FileLibrary::new(), &config.lint_config);
                         return Err(());
                     }
                     Result::Ok(()) => {
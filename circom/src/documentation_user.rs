@@ -0,0 +1,431 @@
+use ansi_term::Colour;
+use program_structure::ast::{DocComment, Expression, Statement, VariableType};
+use program_structure::constants::UsefulConstants;
+use program_structure::error_definition::Report;
+use program_structure::file_definition::{FileID, FileLibrary};
+use program_structure::lint_config::LintConfig;
+use program_structure::program_archive::ProgramArchive;
+use program_structure::template_data::TemplateData;
+use program_structure::wire_data::{WireData, WireDeclarationOrder, WireInfo, WireType};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use crate::VERSION;
+
+// The numbers of the definitions are only kept in the documentation as they are written
+const DOC_PRIME: &str = "bn128";
+
+pub struct DocConfig {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub format: DocFormat,
+    pub link_libraries: Vec<PathBuf>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+impl DocFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DocFormat::Markdown => "md",
+            DocFormat::Html => "html",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ItemKind {
+    Template,
+    Bus,
+    Function,
+}
+
+impl ItemKind {
+    fn title(&self) -> &'static str {
+        match self {
+            ItemKind::Template => "Templates",
+            ItemKind::Bus => "Buses",
+            ItemKind::Function => "Functions",
+        }
+    }
+    fn anchor(&self, name: &str) -> String {
+        match self {
+            ItemKind::Template => format!("template-{}", name),
+            ItemKind::Bus => format!("bus-{}", name),
+            ItemKind::Function => format!("function-{}", name),
+        }
+    }
+}
+
+struct WireDoc {
+    name: String,
+    direction: &'static str,
+    bus: Option<String>,
+    dimensions: Vec<String>,
+    tags: Vec<String>,
+    text: Option<String>,
+}
+
+struct ItemDoc {
+    kind: ItemKind,
+    name: String,
+    file: String,
+    params: Vec<String>,
+    text: Option<String>,
+    wires: Vec<WireDoc>,
+    instantiates: Vec<String>,
+}
+
+// The project is read as a library, so it does not need a main component. Returns the
+// exit code of the command.
+pub fn document(config: &DocConfig) -> i32 {
+    let result = parser::run_library_parser(
+        config.input.to_str().unwrap().to_string(),
+        VERSION,
+        config.link_libraries.clone(),
+        &UsefulConstants::new(&DOC_PRIME.to_string()),
+    );
+    let lint_config = LintConfig::new(false);
+    let program_archive = match result {
+        Result::Err((file_library, reports)) => {
+            Report::print_reports(&reports, &file_library, &lint_config);
+            return 1;
+        }
+        Result::Ok((program_archive, warnings)) => {
+            if Report::print_reports(&warnings, &program_archive.file_library, &lint_config) {
+                return 1;
+            }
+            program_archive
+        }
+    };
+    let items = collect_items(&program_archive);
+    let content = match config.format {
+        DocFormat::Markdown => render_markdown(&items),
+        DocFormat::Html => render_html(&items),
+    };
+    if std::fs::write(&config.output, content).is_err() {
+        eprintln!("{}", Colour::Red.paint("Could not write the documentation file"));
+        return 1;
+    }
+    println!("{} {}", Colour::Green.paint("Written successfully:"), config.output.display());
+    0
+}
+
+fn collect_items(program_archive: &ProgramArchive) -> Vec<ItemDoc> {
+    let library = program_archive.get_file_library();
+    let mut items = Vec::new();
+    for data in program_archive.get_templates().values() {
+        let dimensions = wire_dimensions(data.get_body(), data.get_file_id(), library);
+        let mut wires = collect_wires(
+            data.get_declaration_inputs(),
+            data.get_inputs(),
+            "input",
+            &dimensions,
+            data.get_docs(),
+        );
+        wires.append(&mut collect_wires(
+            data.get_declaration_outputs(),
+            data.get_outputs(),
+            "output",
+            &dimensions,
+            data.get_docs(),
+        ));
+        let mut instantiates = BTreeSet::new();
+        instantiated_templates(data.get_body(), program_archive, &mut instantiates);
+        items.push(ItemDoc {
+            kind: ItemKind::Template,
+            name: data.get_name().to_string(),
+            file: file_name(data.get_file_id(), library),
+            params: template_signature_params(data),
+            text: data.get_docs().get_text().map(|t| t.to_string()),
+            wires,
+            instantiates: instantiates.into_iter().collect(),
+        });
+    }
+    for data in program_archive.get_buses().values() {
+        let dimensions = wire_dimensions(data.get_body(), data.get_file_id(), library);
+        let wires = collect_wires(
+            data.get_declaration_fields(),
+            data.get_fields(),
+            "field",
+            &dimensions,
+            data.get_docs(),
+        );
+        items.push(ItemDoc {
+            kind: ItemKind::Bus,
+            name: data.get_name().to_string(),
+            file: file_name(data.get_file_id(), library),
+            params: data.get_name_of_params().clone(),
+            text: data.get_docs().get_text().map(|t| t.to_string()),
+            wires,
+            instantiates: Vec::new(),
+        });
+    }
+    for data in program_archive.get_functions().values() {
+        items.push(ItemDoc {
+            kind: ItemKind::Function,
+            name: data.get_name().to_string(),
+            file: file_name(data.get_file_id(), library),
+            params: data.get_name_of_params().clone(),
+            text: data.get_docs().get_text().map(|t| t.to_string()),
+            wires: Vec::new(),
+            instantiates: Vec::new(),
+        });
+    }
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    items
+}
+
+fn collect_wires(
+    order: &WireDeclarationOrder,
+    info: &WireInfo,
+    direction: &'static str,
+    dimensions: &HashMap<String, Vec<String>>,
+    docs: &DocComment,
+) -> Vec<WireDoc> {
+    let mut wires = Vec::new();
+    for (name, _) in order {
+        let data: &WireData = info.get(name).unwrap();
+        let bus = match data.get_type() {
+            WireType::Signal => Option::None,
+            WireType::Bus(bus_name) => Option::Some(bus_name),
+        };
+        let mut tags: Vec<String> = data.get_tags().iter().cloned().collect();
+        tags.sort();
+        wires.push(WireDoc {
+            name: name.clone(),
+            direction,
+            bus,
+            dimensions: dimensions.get(name).cloned().unwrap_or_default(),
+            tags,
+            text: docs.get_wire_text(name).map(|t| t.to_string()),
+        });
+    }
+    wires
+}
+
+fn file_name(file_id: FileID, library: &FileLibrary) -> String {
+    library.get_name(file_id).map(|name| name.trim_matches('"').to_string()).unwrap_or_default()
+}
+
+// The dimensions of the wires are shown as they were written in the source code
+fn wire_dimensions(body: &Statement, file_id: FileID, library: &FileLibrary) -> HashMap<String, Vec<String>> {
+    let mut dimensions = HashMap::new();
+    if let Option::Some(source) = library.get_source(file_id) {
+        collect_dimensions(body, source, &mut dimensions);
+    }
+    dimensions
+}
+
+fn collect_dimensions(stmt: &Statement, source: &str, dimensions: &mut HashMap<String, Vec<String>>) {
+    use Statement::*;
+    match stmt {
+        IfThenElse { if_case, else_case, .. } => {
+            collect_dimensions(if_case, source, dimensions);
+            if let Option::Some(else_stmt) = else_case {
+                collect_dimensions(else_stmt, source, dimensions);
+            }
+        }
+        While { stmt, .. } => collect_dimensions(stmt, source, dimensions),
+        Block { stmts, .. } => {
+            for s in stmts {
+                collect_dimensions(s, source, dimensions);
+            }
+        }
+        InitializationBlock { initializations, .. } => {
+            for init in initializations {
+                collect_dimensions(init, source, dimensions);
+            }
+        }
+        Declaration { xtype: VariableType::Signal(..), name, dimensions: dims, .. }
+        | Declaration { xtype: VariableType::Bus(..), name, dimensions: dims, .. } => {
+            let texts = dims
+                .iter()
+                .map(|dim| {
+                    let meta = dim.get_meta();
+                    source.get(meta.start..meta.end).unwrap_or("?").trim().to_string()
+                })
+                .collect();
+            dimensions.insert(name.clone(), texts);
+        }
+        _ => {}
+    }
+}
+
+fn instantiated_templates(stmt: &Statement, program_archive: &ProgramArchive, found: &mut BTreeSet<String>) {
+    use Statement::*;
+    match stmt {
+        IfThenElse { if_case, else_case, .. } => {
+            instantiated_templates(if_case, program_archive, found);
+            if let Option::Some(else_stmt) = else_case {
+                instantiated_templates(else_stmt, program_archive, found);
+            }
+        }
+        While { stmt, .. } => instantiated_templates(stmt, program_archive, found),
+        Block { stmts, .. } => {
+            for s in stmts {
+                instantiated_templates(s, program_archive, found);
+            }
+        }
+        InitializationBlock { initializations, .. } => {
+            for init in initializations {
+                instantiated_templates(init, program_archive, found);
+            }
+        }
+        Substitution { rhe, .. } => templates_in_expression(rhe, program_archive, found),
+        MultSubstitution { rhe, .. } => templates_in_expression(rhe, program_archive, found),
+        _ => {}
+    }
+}
+
+fn templates_in_expression(expr: &Expression, program_archive: &ProgramArchive, found: &mut BTreeSet<String>) {
+    use Expression::*;
+    match expr {
+        Call { id, .. } if program_archive.contains_template(id) => {
+            found.insert(id.clone());
+        }
+        AnonymousComp { id, .. } => {
+            found.insert(id.clone());
+        }
+        ParallelOp { rhe, .. } => templates_in_expression(rhe, program_archive, found),
+        InlineSwitchOp { if_true, if_false, .. } => {
+            templates_in_expression(if_true, program_archive, found);
+            templates_in_expression(if_false, program_archive, found);
+        }
+        ArrayInLine { values, .. } | Tuple { values, .. } => {
+            for value in values {
+                templates_in_expression(value, program_archive, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+// The parameters of a template as written in its definition, including the ones whose
+// value is a template
+fn template_signature_params(data: &TemplateData) -> Vec<String> {
+    let mut params = data.get_name_of_params().clone();
+    for (position, name, interface) in data.get_template_params() {
+        params.insert(*position, format!("template {}: {}", name, interface));
+    }
+    params
+}
+
+fn params_signature(item: &ItemDoc) -> String {
+    format!("{}({})", item.name, item.params.join(", "))
+}
+
+fn wire_dimensions_text(wire: &WireDoc) -> String {
+    wire.dimensions.iter().map(|dim| format!("[{}]", dim)).collect()
+}
+
+fn render_markdown(items: &[ItemDoc]) -> String {
+    let mut out = String::from("# Circuit documentation\n");
+    for kind in [ItemKind::Template, ItemKind::Bus, ItemKind::Function] {
+        let of_kind: Vec<&ItemDoc> = items.iter().filter(|item| item.kind == kind).collect();
+        if of_kind.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n## {}\n", kind.title()));
+        for item in of_kind {
+            out.push_str(&format!("\n<a id=\"{}\"></a>\n", kind.anchor(&item.name)));
+            out.push_str(&format!("### `{}`\n\n", params_signature(item)));
+            out.push_str(&format!("Defined in `{}`\n\n", item.file));
+            if let Option::Some(text) = &item.text {
+                out.push_str(text);
+                out.push_str("\n\n");
+            }
+            if !item.wires.is_empty() {
+                out.push_str("| Name | Kind | Type | Dimensions | Tags | Description |\n");
+                out.push_str("|------|------|------|------------|------|-------------|\n");
+                for wire in &item.wires {
+                    let wire_type = match &wire.bus {
+                        Option::Some(bus) => format!("[{}](#{})", bus, ItemKind::Bus.anchor(bus)),
+                        Option::None => "signal".to_string(),
+                    };
+                    out.push_str(&format!(
+                        "| `{}` | {} | {} | {} | {} | {} |\n",
+                        wire.name,
+                        wire.direction,
+                        wire_type,
+                        wire_dimensions_text(wire),
+                        wire.tags.join(", "),
+                        wire.text.as_deref().unwrap_or("").replace('\n', " "),
+                    ));
+                }
+                out.push('\n');
+            }
+            if !item.instantiates.is_empty() {
+                let links: Vec<String> = item
+                    .instantiates
+                    .iter()
+                    .map(|t| format!("[{}](#{})", t, ItemKind::Template.anchor(t)))
+                    .collect();
+                out.push_str(&format!("Instantiates: {}\n", links.join(", ")));
+            }
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn render_html(items: &[ItemDoc]) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Circuit documentation</title>\n</head>\n<body>\n<h1>Circuit documentation</h1>\n",
+    );
+    for kind in [ItemKind::Template, ItemKind::Bus, ItemKind::Function] {
+        let of_kind: Vec<&ItemDoc> = items.iter().filter(|item| item.kind == kind).collect();
+        if of_kind.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("<h2>{}</h2>\n", kind.title()));
+        for item in of_kind {
+            out.push_str(&format!(
+                "<h3 id=\"{}\"><code>{}</code></h3>\n",
+                kind.anchor(&item.name),
+                escape_html(&params_signature(item))
+            ));
+            out.push_str(&format!("<p>Defined in <code>{}</code></p>\n", escape_html(&item.file)));
+            if let Option::Some(text) = &item.text {
+                out.push_str(&format!("<p>{}</p>\n", escape_html(text).replace('\n', "<br>\n")));
+            }
+            if !item.wires.is_empty() {
+                out.push_str("<table>\n<tr><th>Name</th><th>Kind</th><th>Type</th><th>Dimensions</th><th>Tags</th><th>Description</th></tr>\n");
+                for wire in &item.wires {
+                    let wire_type = match &wire.bus {
+                        Option::Some(bus) => {
+                            format!("<a href=\"#{}\">{}</a>", ItemKind::Bus.anchor(bus), escape_html(bus))
+                        }
+                        Option::None => "signal".to_string(),
+                    };
+                    out.push_str(&format!(
+                        "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                        escape_html(&wire.name),
+                        wire.direction,
+                        wire_type,
+                        escape_html(&wire_dimensions_text(wire)),
+                        escape_html(&wire.tags.join(", ")),
+                        escape_html(wire.text.as_deref().unwrap_or("")),
+                    ));
+                }
+                out.push_str("</table>\n");
+            }
+            if !item.instantiates.is_empty() {
+                let links: Vec<String> = item
+                    .instantiates
+                    .iter()
+                    .map(|t| format!("<a href=\"#{}\">{}</a>", ItemKind::Template.anchor(t), escape_html(t)))
+                    .collect();
+                out.push_str(&format!("<p>Instantiates: {}</p>\n", links.join(", ")));
+            }
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
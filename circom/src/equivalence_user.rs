@@ -0,0 +1,536 @@
+use ansi_term::Colour;
+use circom_algebra::algebra::{normalize, Constraint};
+use circom_algebra::field_element::FieldElement;
+use circom_algebra::modular_arithmetic;
+use circom_algebra::num_bigint::BigInt;
+use circom_algebra::num_traits::{One, Zero};
+use constraint_writers::r1cs_reader::read_r1cs;
+use constraint_writers::sym_reader::read_sym;
+use dag::is_boolean;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeSet, HashMap};
+
+type LinearCombination = HashMap<usize, BigInt>;
+type CanonicalConstraint = Vec<Vec<(usize, FieldElement)>>;
+// Differences shown for each kind of check
+const MAX_SHOWN_DIFFERENCES: usize = 3;
+
+pub struct EquivalenceConfig {
+    pub first: String,
+    pub first_sym: String,
+    pub second: String,
+    pub second_sym: String,
+    pub samples: usize,
+    pub seed: u64,
+}
+
+// A constraint system read from a r1cs file, with the names of its wires taken from
+// the sym file
+struct System {
+    file: String,
+    field: BigInt,
+    constraints: Vec<(LinearCombination, LinearCombination, LinearCombination)>,
+    names: Vec<String>,
+    by_name: HashMap<String, usize>,
+    // names of the inputs and outputs of the main component, even if the
+    // simplification removed their wires
+    outputs: BTreeSet<String>,
+    inputs: BTreeSet<String>,
+    // constraints in which each wire appears
+    appearances: Vec<Vec<usize>>,
+    // wires constrained to be 0 or 1
+    boolean: Vec<bool>,
+}
+
+impl System {
+    fn load(r1cs: &str, sym: &str) -> Result<System, String> {
+        let data = read_r1cs(r1cs).map_err(|e| format!("Could not read {}: {}", r1cs, e))?;
+        let symbols = read_sym(sym).map_err(|e| format!("Could not read {}: {}", sym, e))?;
+        let mut label_names = HashMap::new();
+        for elem in symbols {
+            label_names.insert(elem.original as usize, elem.symbol);
+        }
+        let header = data.header();
+        // The labels of the outputs come first, followed by the public and private inputs
+        let first_input = 1 + header.public_outputs;
+        let end_of_inputs = first_input + header.public_inputs + header.private_inputs;
+        let interface = |labels: std::ops::Range<usize>| -> BTreeSet<String> {
+            labels.map(|label| label_names.get(&label).cloned().unwrap_or_else(|| format!("label {}", label))).collect()
+        };
+        let outputs = interface(1..first_input);
+        let inputs = interface(first_input..end_of_inputs);
+        let mut names = Vec::with_capacity(header.total_wires);
+        for (wire, label) in data.wire_labels().iter().enumerate() {
+            let name = match label_names.remove(label) {
+                _ if wire == 0 => "one".to_string(),
+                Some(name) => name,
+                None => format!("wire {}", wire),
+            };
+            names.push(name);
+        }
+        let by_name = names.iter().enumerate().map(|(wire, name)| (name.clone(), wire)).collect();
+        let mut appearances = vec![Vec::new(); names.len()];
+        let mut boolean = vec![false; names.len()];
+        for (index, (a, b, c)) in data.constraints().iter().enumerate() {
+            let wires: BTreeSet<usize> = a.keys().chain(b.keys()).chain(c.keys()).cloned().filter(|w| *w != 0).collect();
+            if wires.len() == 1 {
+                let wire = *wires.iter().next().unwrap();
+                let field = &header.field;
+                let constraint = Constraint::new(in_field(a, field), in_field(b, field), in_field(c, field));
+                boolean[wire] |= is_boolean(&constraint, wire, &header.field);
+            }
+            for wire in wires {
+                appearances[wire].push(index);
+            }
+        }
+        Ok(System {
+            file: r1cs.to_string(),
+            field: header.field.clone(),
+            constraints: data.constraints().clone(),
+            names,
+            by_name,
+            outputs,
+            inputs,
+            appearances,
+            boolean,
+        })
+    }
+
+    // The inputs or outputs whose wires were not removed
+    fn present(&self, names: &BTreeSet<String>) -> Vec<String> {
+        names.iter().filter(|name| self.by_name.contains_key(*name)).cloned().collect()
+    }
+}
+
+// Values of the boolean wires of the equation sum(coefficient * wire) = target when
+// the coefficients, all with the same sign, are superincreasing and add up to less
+// than p, as in a binary decomposition. Then, at most one assignment exists: the
+// result is None if there is none, and it is not found when the coefficients are
+// not of that form
+fn decompose(terms: &[(usize, BigInt)], target: &BigInt, field: &BigInt) -> Result<Option<Vec<(usize, bool)>>, ()> {
+    for negate in [false, true] {
+        let mut terms: Vec<(BigInt, usize)> = terms
+            .iter()
+            .map(|(wire, value)| (if negate { modular_arithmetic::prefix_sub(value, field) } else { value.clone() }, *wire))
+            .collect();
+        terms.sort();
+        let mut sum = BigInt::zero();
+        let superincreasing = terms.iter().all(|(value, _)| {
+            let bigger = value > &sum;
+            sum += value;
+            bigger
+        });
+        if !superincreasing || &sum >= field {
+            continue;
+        }
+        let mut rest = if negate { modular_arithmetic::prefix_sub(target, field) } else { target.clone() };
+        let mut assignment = Vec::with_capacity(terms.len());
+        for (value, wire) in terms.into_iter().rev() {
+            let bit = rest >= value;
+            if bit {
+                rest -= value;
+            }
+            assignment.push((wire, bit));
+        }
+        return Ok(if rest.is_zero() { Some(assignment) } else { None });
+    }
+    Err(())
+}
+
+enum Solution {
+    Complete(Vec<BigInt>),
+    // A constraint does not hold for the only possible values of its wires
+    Violated(usize),
+    // The value of some wire does not follow from a linear equation
+    Incomplete,
+}
+
+// The coefficients of a linear combination read from a r1cs file as elements of its field
+fn in_field(lc: &LinearCombination, field: &BigInt) -> HashMap<usize, FieldElement> {
+    lc.iter().map(|(wire, value)| (*wire, FieldElement::new(value, field))).collect()
+}
+
+// Value of the known part of the linear combination and coefficients of the
+// unknown wires
+fn evaluate(lc: &LinearCombination, values: &[Option<BigInt>], unknowns: &[usize], field: &BigInt) -> (BigInt, Vec<BigInt>) {
+    let mut known = BigInt::zero();
+    for (wire, coefficient) in lc {
+        if let Some(value) = &values[*wire] {
+            known = modular_arithmetic::add(&known, &modular_arithmetic::mul(coefficient, value, field), field);
+        }
+    }
+    let factors = unknowns
+        .iter()
+        .map(|wire| lc.get(wire).map_or_else(BigInt::zero, |value| modular_arithmetic::mul(value, &BigInt::one(), field)))
+        .collect();
+    (known, factors)
+}
+
+// Completes the witness from the values of the fixed wires, finding one by one the
+// wires that are the only unknown one of a constraint in which they appear linearly.
+// Every value found is the only one that satisfies the constraints, so a constraint
+// that does not hold proves that the system has no witness with the fixed values
+fn solve(system: &System, fixed: &HashMap<usize, BigInt>) -> Solution {
+    let field = &system.field;
+    let mut values: Vec<Option<BigInt>> = vec![None; system.names.len()];
+    values[0] = Some(BigInt::one());
+    for (wire, value) in fixed {
+        values[*wire] = Some(value.clone());
+    }
+    let mut pending: Vec<usize> = (0..system.constraints.len()).rev().collect();
+    while let Some(index) = pending.pop() {
+        let (a, b, c) = &system.constraints[index];
+        let unknowns: BTreeSet<usize> =
+            a.keys().chain(b.keys()).chain(c.keys()).filter(|w| values[**w].is_none()).cloned().collect();
+        let product_is_known = a.keys().chain(b.keys()).all(|w| values[*w].is_some());
+        let found = if unknowns.len() <= 1 {
+            let unknowns: Vec<usize> = unknowns.into_iter().collect();
+            let linear = |lc: &LinearCombination| -> (BigInt, BigInt) {
+                let (known, factors) = evaluate(lc, &values, &unknowns, field);
+                (known, factors.into_iter().next().unwrap_or_else(BigInt::zero))
+            };
+            let ((a0, a1), (b0, b1), (c0, c1)) = (linear(a), linear(b), linear(c));
+            if !modular_arithmetic::mul(&a1, &b1, field).is_zero() {
+                continue;
+            }
+            let factor = modular_arithmetic::sub(
+                &modular_arithmetic::add(&modular_arithmetic::mul(&a0, &b1, field), &modular_arithmetic::mul(&a1, &b0, field), field),
+                &c1,
+                field,
+            );
+            let rest = modular_arithmetic::sub(&c0, &modular_arithmetic::mul(&a0, &b0, field), field);
+            match (factor.is_zero(), rest.is_zero()) {
+                (true, true) => Vec::new(),
+                (true, false) => return Solution::Violated(index),
+                (false, _) => vec![(unknowns[0], modular_arithmetic::div(&rest, &factor, field).ok().unwrap())],
+            }
+        } else if product_is_known && unknowns.iter().all(|w| system.boolean[*w]) {
+            let unknowns: Vec<usize> = unknowns.into_iter().collect();
+            let (a0, _) = evaluate(a, &values, &[], field);
+            let (b0, _) = evaluate(b, &values, &[], field);
+            let (c0, c1) = evaluate(c, &values, &unknowns, field);
+            let target = modular_arithmetic::sub(&modular_arithmetic::mul(&a0, &b0, field), &c0, field);
+            let terms: Vec<(usize, BigInt)> = unknowns.into_iter().zip(c1).collect();
+            match decompose(&terms, &target, field) {
+                Ok(Some(assignment)) => {
+                    assignment.into_iter().map(|(wire, bit)| (wire, BigInt::from(bit as u8))).collect()
+                }
+                Ok(None) => return Solution::Violated(index),
+                Err(()) => continue,
+            }
+        } else {
+            continue;
+        };
+        for (wire, value) in found {
+            values[wire] = Some(value);
+            pending.extend(system.appearances[wire].iter().rev().cloned());
+        }
+    }
+    if values.iter().any(Option::is_none) {
+        Solution::Incomplete
+    } else {
+        Solution::Complete(values.into_iter().map(Option::unwrap).collect())
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Sampling {
+    Field,
+    Bits(usize),
+}
+
+// The inputs are taken from all the field and from small ranges, as many circuits
+// only accept inputs of a given number of bits
+const SAMPLINGS: [Sampling; 5] = [Sampling::Field, Sampling::Bits(1), Sampling::Bits(8), Sampling::Bits(32), Sampling::Bits(64)];
+
+fn random_value(rng: &mut StdRng, sampling: Sampling, field: &BigInt) -> BigInt {
+    match sampling {
+        Sampling::Field => {
+            let mut value = BigInt::zero();
+            for _ in 0..(field.bits() / 64 + 2) {
+                value = (value << 64) + BigInt::from(rng.gen::<u64>());
+            }
+            value % field
+        }
+        Sampling::Bits(64) => BigInt::from(rng.gen::<u64>()),
+        Sampling::Bits(bits) => BigInt::from(rng.gen::<u64>() & ((1 << bits) - 1)),
+    }
+}
+
+fn format_values(names: &[String], values: &HashMap<String, BigInt>) -> String {
+    names.iter().map(|name| format!("{} = {}", name, values[name])).collect::<Vec<_>>().join(", ")
+}
+
+#[derive(Default)]
+struct SamplingResult {
+    checked: usize,
+    // inputs without witness in the first system
+    rejected: usize,
+    // samples that could not be completed by linear solving in any of the systems
+    skipped: usize,
+    differences: Vec<String>,
+}
+
+// Samples witnesses of the system from and checks that the system to accepts the
+// same inputs and outputs
+fn check_samples(from: &System, to: &System, config: &EquivalenceConfig, rng: &mut StdRng) -> SamplingResult {
+    let mut result = SamplingResult::default();
+    let input_names = from.present(&from.inputs);
+    let output_names = from.present(&from.outputs);
+    for sample in 0..config.samples {
+        let sampling = SAMPLINGS[sample % SAMPLINGS.len()];
+        let inputs: HashMap<String, BigInt> =
+            input_names.iter().map(|name| (name.clone(), random_value(rng, sampling, &from.field))).collect();
+        let fixed = inputs.iter().map(|(name, value)| (from.by_name[name], value.clone())).collect();
+        let witness = match solve(from, &fixed) {
+            Solution::Complete(witness) => witness,
+            Solution::Violated(_) => {
+                result.rejected += 1;
+                continue;
+            }
+            Solution::Incomplete => {
+                result.skipped += 1;
+                continue;
+            }
+        };
+        let outputs: HashMap<String, BigInt> =
+            output_names.iter().map(|name| (name.clone(), witness[from.by_name[name]].clone())).collect();
+        let in_to = |values: &HashMap<String, BigInt>| -> Vec<(usize, BigInt)> {
+            values.iter().filter_map(|(name, value)| to.by_name.get(name).map(|wire| (*wire, value.clone()))).collect()
+        };
+        let mut fixed: HashMap<usize, BigInt> = in_to(&inputs).into_iter().collect();
+        let difference = match solve(to, &fixed) {
+            Solution::Complete(other) => {
+                let different: Vec<String> = output_names
+                    .iter()
+                    .filter(|name| to.by_name.get(*name).is_some_and(|wire| other[*wire] != outputs[*name]))
+                    .cloned()
+                    .collect();
+                if different.is_empty() {
+                    None
+                } else {
+                    let other = different.iter().map(|name| (name.clone(), other[to.by_name[name]].clone())).collect();
+                    Some(format!(
+                        "for the inputs {}, {} gives {} but {} gives {}",
+                        format_values(&input_names, &inputs),
+                        from.file,
+                        format_values(&different, &outputs),
+                        to.file,
+                        format_values(&different, &other)
+                    ))
+                }
+            }
+            Solution::Violated(index) => Some(format!(
+                "{} has no witness for the inputs {}, accepted by {}: its constraint {} does not hold",
+                to.file,
+                format_values(&input_names, &inputs),
+                from.file,
+                format_constraint(to, index)
+            )),
+            Solution::Incomplete => {
+                fixed.extend(in_to(&outputs));
+                match solve(to, &fixed) {
+                    Solution::Violated(index) => Some(format!(
+                        "{} does not accept the outputs {} for the inputs {}, given by {}: its constraint {} does not hold",
+                        to.file,
+                        format_values(&output_names, &outputs),
+                        format_values(&input_names, &inputs),
+                        from.file,
+                        format_constraint(to, index)
+                    )),
+                    Solution::Complete(_) => None,
+                    Solution::Incomplete => {
+                        result.skipped += 1;
+                        continue;
+                    }
+                }
+            }
+        };
+        result.checked += 1;
+        result.differences.extend(difference);
+    }
+    result
+}
+
+// The constraints of both systems written over a common numbering of the names of
+// their wires, so that the canonical forms can be compared
+fn canonical_constraints(system: &System, ids: &HashMap<&str, usize>) -> HashMap<CanonicalConstraint, Vec<usize>> {
+    let mut canonical: HashMap<CanonicalConstraint, Vec<usize>> = HashMap::new();
+    for (index, (a, b, c)) in system.constraints.iter().enumerate() {
+        let rename = |lc: &LinearCombination| -> HashMap<usize, FieldElement> {
+            lc.iter()
+                .map(|(wire, value)| (ids[system.names[*wire].as_str()], FieldElement::new(value, &system.field)))
+                .collect()
+        };
+        let constraint = normalize(Constraint::new(rename(a), rename(b), rename(c)), &system.field);
+        let key = [constraint.a(), constraint.b(), constraint.c()]
+            .iter()
+            .map(|lc| {
+                let mut sorted: Vec<(usize, FieldElement)> = lc.iter().map(|(s, v)| (*s, v.clone())).collect();
+                sorted.sort();
+                sorted
+            })
+            .collect();
+        canonical.entry(key).or_default().push(index);
+    }
+    canonical
+}
+
+// Constraints of the first system without an equal one in the second, counting
+// repetitions
+fn unmatched(first: &HashMap<CanonicalConstraint, Vec<usize>>, second: &HashMap<CanonicalConstraint, Vec<usize>>) -> Vec<usize> {
+    let mut unmatched = Vec::new();
+    for (key, indexes) in first {
+        let matched = second.get(key).map_or(0, Vec::len);
+        unmatched.extend(indexes.iter().skip(matched));
+    }
+    unmatched.sort_unstable();
+    unmatched
+}
+
+fn format_linear(lc: &LinearCombination, system: &System) -> String {
+    let field = &system.field;
+    let half = field / BigInt::from(2);
+    let mut terms: Vec<(usize, bool, String)> = Vec::new();
+    for (wire, value) in lc {
+        let value = modular_arithmetic::mul(value, &BigInt::one(), field);
+        if value.is_zero() {
+            continue;
+        }
+        let (negative, magnitude) = if value > half { (true, field - &value) } else { (false, value) };
+        let term = match (*wire, magnitude.is_one()) {
+            (0, _) => magnitude.to_string(),
+            (wire, true) => system.names[wire].clone(),
+            (wire, false) => format!("{}*{}", magnitude, system.names[wire]),
+        };
+        terms.push((*wire, negative, term));
+    }
+    terms.sort();
+    let mut text = String::new();
+    for (position, (_, negative, term)) in terms.into_iter().enumerate() {
+        let sign = match (position, negative) {
+            (0, true) => "-",
+            (0, false) => "",
+            (_, true) => " - ",
+            (_, false) => " + ",
+        };
+        text.push_str(sign);
+        text.push_str(&term);
+    }
+    if text.is_empty() {
+        "0".to_string()
+    } else {
+        text
+    }
+}
+
+fn format_constraint(system: &System, index: usize) -> String {
+    let (a, b, c) = &system.constraints[index];
+    let c = format_linear(c, system);
+    if a.values().chain(b.values()).all(|value| (value % &system.field).is_zero()) {
+        format!("0 = {}", c)
+    } else {
+        format!("({}) * ({}) = {}", format_linear(a, system), format_linear(b, system), c)
+    }
+}
+
+fn print_difference(message: &str) {
+    println!("{}", Colour::Red.paint(message));
+}
+
+// Checks that the interfaces of two circuits match and then compares them with
+// random witnesses and structurally. Returns the exit code of the compiler
+pub fn check_equivalence(config: &EquivalenceConfig) -> i32 {
+    let systems = System::load(&config.first, &config.first_sym)
+        .and_then(|first| System::load(&config.second, &config.second_sym).map(|second| (first, second)));
+    let (first, second) = match systems {
+        Ok(systems) => systems,
+        Err(message) => {
+            eprintln!("{}", Colour::Red.paint(message));
+            return 1;
+        }
+    };
+    if first.field != second.field {
+        print_difference("The circuits are defined over different prime fields");
+        return 1;
+    }
+    let mut different_interfaces = false;
+    for (kind, first_names, second_names) in [
+        ("inputs", &first.inputs, &second.inputs),
+        ("outputs", &first.outputs, &second.outputs),
+    ] {
+        for (file, missing) in [
+            (&second.file, first_names.difference(second_names).collect::<Vec<_>>()),
+            (&first.file, second_names.difference(first_names).collect::<Vec<_>>()),
+        ] {
+            if !missing.is_empty() {
+                let missing: Vec<&str> = missing.iter().map(|name| name.as_str()).collect();
+                print_difference(&format!("The {} {} are missing in {}", kind, missing.join(", "), file));
+                different_interfaces = true;
+            }
+        }
+    }
+    if different_interfaces {
+        print_difference("The circuits have different interfaces");
+        return 1;
+    }
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut differences = Vec::new();
+    let mut checked = 0;
+    for (from, to) in [(&first, &second), (&second, &first)] {
+        let result = check_samples(from, to, config, &mut rng);
+        println!(
+            "Random witnesses of {}: {} checked, {} with inputs that have no witness, {} that could not be completed by linear solving",
+            from.file, result.checked, result.rejected, result.skipped
+        );
+        checked += result.checked;
+        differences.extend(result.differences);
+    }
+    for difference in differences.iter().take(MAX_SHOWN_DIFFERENCES) {
+        print_difference(&format!("Difference found: {}", difference));
+    }
+
+    let mut names: Vec<&str> = first.names.iter().chain(second.names.iter()).map(String::as_str).collect();
+    names.sort_unstable();
+    names.dedup();
+    // The constant wire keeps the id 0
+    let ids: HashMap<&str, usize> = std::iter::once("one")
+        .chain(names.into_iter().filter(|name| *name != "one"))
+        .enumerate()
+        .map(|(id, name)| (name, id))
+        .collect();
+    let first_canonical = canonical_constraints(&first, &ids);
+    let second_canonical = canonical_constraints(&second, &ids);
+    let only_first = unmatched(&first_canonical, &second_canonical);
+    let only_second = unmatched(&second_canonical, &first_canonical);
+    let structurally_equal = only_first.is_empty() && only_second.is_empty();
+    if structurally_equal {
+        println!("The constraints of both circuits are equal after normalization");
+    } else {
+        for (system, indexes, other) in [(&first, &only_first, &second), (&second, &only_second, &first)] {
+            println!(
+                "{} of the {} constraints of {} have no equal constraint in {}",
+                indexes.len(),
+                system.constraints.len(),
+                system.file,
+                other.file
+            );
+            for index in indexes.iter().take(MAX_SHOWN_DIFFERENCES) {
+                println!("    {}", format_constraint(system, *index));
+            }
+        }
+    }
+
+    if !differences.is_empty() {
+        print_difference(&format!("The circuits are not equivalent: {} differences found", differences.len()));
+        1
+    } else if checked == 0 && !structurally_equal {
+        print_difference("No random witness could be checked and the constraints are not equal: the equivalence is unknown");
+        1
+    } else {
+        println!("{}", Colour::Green.paint("No difference found between the circuits"));
+        0
+    }
+}
@@ -2,12 +2,15 @@ use ansi_term::Colour;
 use compiler::hir::very_concrete_program::VCP;
 use constraint_writers::debug_writer::DebugWriter;
 use constraint_writers::ConstraintExporter;
+use constraint_generation::{CircuitBudgets, ExecutionLimits};
+use program_structure::lint_config::LintConfig;
 use program_structure::program_archive::ProgramArchive;
 
 
 pub struct ExecutionConfig {
     pub r1cs: String,
     pub sym: String,
+    pub extended_sym: String,
     pub json_constraints: String,
     pub json_substitutions: String,
     pub no_rounds: usize,
@@ -16,12 +19,18 @@ pub struct ExecutionConfig {
     pub flag_p: bool,
     pub flag_old_heuristics:bool,
     pub flag_verbose: bool,
-    pub inspect_constraints_flag: bool,
     pub sym_flag: bool,
+    pub extended_sym_flag: bool,
+    pub keep_signals: Vec<String>,
+    pub verify_simplification_flag: bool,
+    pub low_memory: bool,
     pub r1cs_flag: bool,
     pub json_substitution_flag: bool,
     pub json_constraint_flag: bool,
     pub prime: String,
+    pub limits: ExecutionLimits,
+    pub budgets: CircuitBudgets,
+    pub lint_config: LintConfig,
 }
 
 pub fn execute_project(
@@ -38,9 +47,16 @@ pub fn execute_project(
         flag_f: config.flag_f,
         flag_p: config.flag_p,
         flag_verbose: config.flag_verbose,
-        inspect_constraints: config.inspect_constraints_flag,
+        flag_extended_sym: config.extended_sym_flag,
+        extended_sym: config.extended_sym,
+        keep_signals: config.keep_signals,
+        flag_verify_simplification: config.verify_simplification_flag,
         flag_old_heuristics: config.flag_old_heuristics,
+        low_memory: config.low_memory,
         prime : config.prime,
+        limits: config.limits,
+        budgets: config.budgets,
+        lint_config: config.lint_config,
     };
     let custom_gates = program_archive.custom_gates;
     let (exporter, vcp) = build_circuit(program_archive, build_config)?;
@@ -0,0 +1,16 @@
+use ansi_term::Colour;
+use program_structure::error_explanation::explanations_of;
+
+// Prints the explanation of a report code and returns the exit code of the compiler
+pub fn explain(code: &str) -> i32 {
+    let explanations = explanations_of(code);
+    if explanations.is_empty() {
+        eprintln!("{}", Colour::Red.paint(format!("Unknown error code {}", code)));
+        return 1;
+    }
+    let separator = format!("\n{}\n\n", "-".repeat(80));
+    println!("{}", code.trim().to_uppercase());
+    println!();
+    print!("{}", explanations.join(&separator));
+    0
+}
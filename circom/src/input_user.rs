@@ -1,4 +1,6 @@
 use std::path::PathBuf;
+use program_structure::lint_config::LintConfig;
+use constraint_generation::{CircuitBudgets, ExecutionLimits};
 
 pub struct Input {
     pub input_program: PathBuf,
@@ -14,6 +16,7 @@ pub struct Input {
     pub out_c_code: PathBuf,
     pub out_c_dat: PathBuf,
     pub out_sym: PathBuf,
+    pub out_extended_sym: PathBuf,
     //pub field: &'static str,
     pub c_flag: bool,
     pub wasm_flag: bool,
@@ -22,20 +25,27 @@ pub struct Input {
     pub sanity_check_style: usize,
     pub r1cs_flag: bool,
     pub sym_flag: bool,
+    pub extended_sym_flag: bool,
     pub json_constraint_flag: bool,
     pub json_substitution_flag: bool,
+    pub verify_simplification_flag: bool,
+    pub verify_reproducible_flag: bool,
+    pub low_memory: bool,
     pub main_inputs_flag: bool,
     pub print_ir_flag: bool,
     pub fast_flag: bool,
     pub reduced_simplification_flag: bool,
     pub parallel_simplification_flag: bool,
     pub flag_old_heuristics: bool,
-    pub inspect_constraints_flag: bool,
     pub no_rounds: usize,
     pub flag_verbose: bool,
     pub flag_no_init: bool,
     pub prime: String,
-    pub link_libraries : Vec<PathBuf>
+    pub link_libraries : Vec<PathBuf>,
+    pub lint_config: LintConfig,
+    pub execution_limits: ExecutionLimits,
+    pub budgets: CircuitBudgets,
+    pub keep_signals: Vec<String>,
 }
 
 
@@ -54,6 +64,17 @@ impl Input {
         use ansi_term::Colour;
         use input_processing::SimplificationStyle;
         let matches = input_processing::view();
+        if let Option::Some(code) = matches.value_of("explain") {
+            std::process::exit(crate::explain_user::explain(code));
+        }
+        if let Option::Some(equivalence_matches) = matches.subcommand_matches("equivalence") {
+            let config = input_processing::get_equivalence_config(equivalence_matches)?;
+            std::process::exit(crate::equivalence_user::check_equivalence(&config));
+        }
+        if let Option::Some(doc_matches) = matches.subcommand_matches("doc") {
+            let config = input_processing::get_doc_config(doc_matches)?;
+            std::process::exit(crate::documentation_user::document(&config));
+        }
         let input = input_processing::get_input(&matches)?;
         let mut file_name = input.file_stem().unwrap().to_str().unwrap().to_string();
         let output_path = input_processing::get_output_path(&matches)?;
@@ -69,6 +90,10 @@ impl Input {
         let o_style = input_processing::get_simplification_style(&matches)?;
         let sanity_check_style = input_processing::get_sanity_check_style(&matches)?;
         let link_libraries = input_processing::get_link_libraries(&matches);
+        let inspect_constraints_flag = input_processing::get_inspect_constraints(&matches);
+        let lint_config = input_processing::get_lint_config(&matches, &input, inspect_constraints_flag)?;
+        let execution_limits = input_processing::get_execution_limits(&matches)?;
+        let budgets = input_processing::get_budgets(&matches)?;
         Result::Ok(Input {
             //field: P_BN128,
             input_program: input,
@@ -82,6 +107,11 @@ impl Input {
             out_c_code: Input::build_output(&output_c_path, &file_name, CPP),
             out_c_dat: Input::build_output(&output_c_path, &file_name, DAT),
             out_sym: Input::build_output(&output_path, &file_name, SYM),
+            out_extended_sym: Input::build_output(
+                &output_path,
+                &format!("{}_extended", file_name),
+                SYM,
+            ),
             out_json_constraints: Input::build_output(
                 &output_path,
                 &format!("{}_constraints", file_name),
@@ -99,20 +129,29 @@ impl Input {
             sanity_check_style: sanity_check_style as usize,
             r1cs_flag: input_processing::get_r1cs(&matches),
             sym_flag: input_processing::get_sym(&matches),
+            extended_sym_flag: input_processing::get_extended_sym(&matches),
             main_inputs_flag: input_processing::get_main_inputs_log(&matches),
             json_constraint_flag: input_processing::get_json_constraints(&matches),
-            json_substitution_flag: input_processing::get_json_substitutions(&matches),
+            // the verification reads the substitutions from their json file
+            json_substitution_flag: input_processing::get_json_substitutions(&matches)
+                || input_processing::get_verify_simplification(&matches),
+            verify_simplification_flag: input_processing::get_verify_simplification(&matches),
+            verify_reproducible_flag: input_processing::get_verify_reproducible(&matches),
+            low_memory: input_processing::get_low_memory(&matches),
             print_ir_flag: input_processing::get_ir(&matches),
             no_rounds: if let SimplificationStyle::O2(r) = o_style { r } else { 0 },
             fast_flag: o_style == SimplificationStyle::O0,
             reduced_simplification_flag: o_style == SimplificationStyle::O1,
             parallel_simplification_flag: input_processing::get_parallel_simplification(&matches),
-            inspect_constraints_flag: input_processing::get_inspect_constraints(&matches),
             flag_old_heuristics: input_processing::get_flag_old_heuristics(&matches),
             flag_verbose: input_processing::get_flag_verbose(&matches), 
             flag_no_init: input_processing::get_flag_no_init(&matches), 
             prime: input_processing::get_prime(&matches)?,
-            link_libraries
+            link_libraries,
+            lint_config,
+            execution_limits,
+            budgets,
+            keep_signals: input_processing::get_keep_signals(&matches),
         })
     }
 
@@ -142,6 +181,9 @@ impl Input {
     pub fn sym_file(&self) -> &str {
         self.out_sym.to_str().unwrap()
     }
+    pub fn extended_sym_file(&self) -> &str {
+        self.out_extended_sym.to_str().unwrap()
+    }
     pub fn wat_file(&self) -> &str {
         self.out_wat_code.to_str().unwrap()
     }
@@ -201,21 +243,39 @@ impl Input {
     pub fn json_substitutions_flag(&self) -> bool {
         self.json_substitution_flag
     }
+    pub fn verify_simplification_flag(&self) -> bool {
+        self.verify_simplification_flag
+    }
+    pub fn verify_reproducible_flag(&self) -> bool {
+        self.verify_reproducible_flag
+    }
+    pub fn low_memory(&self) -> bool {
+        self.low_memory
+    }
     pub fn main_inputs_flag(&self) -> bool {
         self.main_inputs_flag
     }
+    pub fn keep_signals(&self) -> &Vec<String> {
+        &self.keep_signals
+    }
     pub fn sym_flag(&self) -> bool {
         self.sym_flag
     }
+    pub fn extended_sym_flag(&self) -> bool {
+        self.extended_sym_flag
+    }
     pub fn print_ir_flag(&self) -> bool {
         self.print_ir_flag
     }
-    pub fn inspect_constraints_flag(&self) -> bool {
-        self.inspect_constraints_flag
-    }
     pub fn flag_verbose(&self) -> bool {
         self.flag_verbose
     }
+    pub fn execution_limits(&self) -> ExecutionLimits {
+        self.execution_limits
+    }
+    pub fn budgets(&self) -> &CircuitBudgets {
+        &self.budgets
+    }
     pub fn flag_no_init(&self) -> bool {
         self.flag_no_init
     }
@@ -234,12 +294,23 @@ impl Input {
     pub fn prime(&self) -> String{
         self.prime.clone()
     }
+    pub fn lint_config(&self) -> &LintConfig {
+        &self.lint_config
+    }
 }
 mod input_processing {
     use ansi_term::Colour;
-    use clap::{App, Arg, ArgMatches};
+    use clap::{App, Arg, ArgMatches, SubCommand};
     use std::path::{Path, PathBuf};
     use crate::VERSION;
+    use crate::documentation_user::{DocConfig, DocFormat};
+    use crate::equivalence_user::EquivalenceConfig;
+    use program_structure::lint_config::{LintConfig, LintLevel};
+    use constraint_generation::{CircuitBudgets, ExecutionLimits};
+    use program_structure::template_budget::TemplateBudget;
+
+    // Configuration file looked up in the directory of the input when --lint_config is not given
+    const LINT_CONFIG_FILE: &str = ".circomlint";
 
     pub fn get_input(matches: &ArgMatches) -> Result<PathBuf, ()> {
         let route = Path::new(matches.value_of("input").unwrap()).to_path_buf();
@@ -318,10 +389,36 @@ mod input_processing {
         matches.is_present("print_json_sub")
     }
 
+    pub fn get_verify_simplification(matches: &ArgMatches) -> bool {
+        matches.is_present("verify_simplification")
+    }
+
+    pub fn get_verify_reproducible(matches: &ArgMatches) -> bool {
+        matches.is_present("verify_reproducible")
+    }
+
+    pub fn get_low_memory(matches: &ArgMatches) -> bool {
+        matches.is_present("low_memory")
+    }
+
     pub fn get_sym(matches: &ArgMatches) -> bool {
         matches.is_present("print_sym")
     }
 
+    pub fn get_keep_signals(matches: &ArgMatches) -> Vec<String> {
+        let mut patterns = Vec::new();
+        if let Option::Some(values) = matches.values_of("keep_signals") {
+            for value in values {
+                patterns.extend(value.split(',').map(str::trim).filter(|p| !p.is_empty()).map(String::from));
+            }
+        }
+        patterns
+    }
+
+    pub fn get_extended_sym(matches: &ArgMatches) -> bool {
+        matches.is_present("print_extended_sym")
+    }
+
     pub fn get_r1cs(matches: &ArgMatches) -> bool {
         matches.is_present("print_r1cs")
     }
@@ -394,6 +491,112 @@ mod input_processing {
         }
     }
 
+    // The levels of the configuration file are overridden by the flags -A, -W and -D,
+    // which are applied in the order they are given
+    pub fn get_lint_config(matches: &ArgMatches, input: &Path, inspect: bool) -> Result<LintConfig, ()> {
+        let mut config = LintConfig::new(inspect);
+        let file = match matches.value_of("lint_config") {
+            Option::Some(file) => Option::Some(PathBuf::from(file)),
+            Option::None => {
+                let default = input.parent().unwrap_or(Path::new("")).join(LINT_CONFIG_FILE);
+                if default.is_file() { Option::Some(default) } else { Option::None }
+            }
+        };
+        if let Option::Some(file) = file {
+            config.load_file(&file).map_err(|e| eprintln!("{}", Colour::Red.paint(e)))?;
+        }
+        let mut levels = Vec::new();
+        for (flag, level) in [("allow_lint", LintLevel::Allow), ("warn_lint", LintLevel::Warn), ("deny_lint", LintLevel::Deny)] {
+            if let (Option::Some(indices), Option::Some(values)) = (matches.indices_of(flag), matches.values_of(flag)) {
+                levels.extend(indices.zip(values).map(|(index, lint)| (index, lint, level)));
+            }
+        }
+        levels.sort_by_key(|(index, _, _)| *index);
+        for (_, lint, level) in levels {
+            config.set_level(lint, level).map_err(|e| eprintln!("{}", Colour::Red.paint(e)))?;
+        }
+        Result::Ok(config)
+    }
+
+    pub fn get_execution_limits(matches: &ArgMatches) -> Result<ExecutionLimits, ()> {
+        let mut limits = ExecutionLimits::default();
+        for (option, limit) in [
+            ("max_loop_iterations", &mut limits.max_loop_iterations),
+            ("max_recursion_depth", &mut limits.max_recursion_depth),
+            ("max_steps", &mut limits.max_steps),
+        ] {
+            if let Option::Some(value) = matches.value_of(option) {
+                *limit = value.parse().map_err(|_| {
+                    eprintln!("{}", Colour::Red.paint(format!("invalid value {} for --{}", value, option.replace('_', "-"))))
+                })?;
+            }
+        }
+        Result::Ok(limits)
+    }
+
+    pub fn get_budgets(matches: &ArgMatches) -> Result<CircuitBudgets, ()> {
+        let mut budgets = CircuitBudgets::default();
+        for (option, limit) in [("max_constraints", &mut budgets.max_constraints), ("max_wires", &mut budgets.max_wires)] {
+            if let Option::Some(value) = matches.value_of(option) {
+                *limit = value.parse().map_err(|_| {
+                    eprintln!("{}", Colour::Red.paint(format!("invalid value {} for --{}", value, option.replace('_', "-"))))
+                })?;
+            }
+        }
+        if let Option::Some(values) = matches.values_of("template_budget") {
+            for value in values {
+                let budget = TemplateBudget::parse(value).ok_or_else(|| {
+                    eprintln!("{}", Colour::Red.paint(format!("invalid value {} for --template_budget", value)))
+                })?;
+                budgets.templates.push(budget);
+            }
+        }
+        Result::Ok(budgets)
+    }
+
+    pub fn get_doc_format(matches: &ArgMatches) -> Result<DocFormat, ()> {
+        match matches.value_of("doc_format").unwrap() {
+            "markdown" | "md" => Ok(DocFormat::Markdown),
+            "html" => Ok(DocFormat::Html),
+            _ => Result::Err(eprintln!("{}", Colour::Red.paint("invalid documentation format"))),
+        }
+    }
+
+    // The documentation is written next to the other outputs, in a file named as the input
+    pub fn get_doc_config(matches: &ArgMatches) -> Result<DocConfig, ()> {
+        let input = get_input(matches)?;
+        let format = get_doc_format(matches)?;
+        let file_name = input.file_stem().unwrap().to_str().unwrap().to_string();
+        let output = get_output_path(matches)?.join(format!("{}.{}", file_name, format.extension()));
+        Ok(DocConfig { input, output, format, link_libraries: get_link_libraries(matches) })
+    }
+
+    // The sym files are looked for next to the r1cs files when they are not given
+    pub fn get_equivalence_config(matches: &ArgMatches) -> Result<EquivalenceConfig, ()> {
+        let sym_of = |r1cs: &str, option: &str| -> String {
+            match matches.value_of(option) {
+                Option::Some(sym) => sym.to_string(),
+                Option::None => Path::new(r1cs).with_extension("sym").to_str().unwrap().to_string(),
+            }
+        };
+        let number = |option: &str| -> Result<u64, ()> {
+            let value = matches.value_of(option).unwrap();
+            value.parse().map_err(|_| {
+                eprintln!("{}", Colour::Red.paint(format!("invalid value {} for --{}", value, option)))
+            })
+        };
+        let first = matches.value_of("first").unwrap().to_string();
+        let second = matches.value_of("second").unwrap().to_string();
+        Ok(EquivalenceConfig {
+            first_sym: sym_of(&first, "first_sym"),
+            second_sym: sym_of(&second, "second_sym"),
+            first,
+            second,
+            samples: number("samples")? as usize,
+            seed: number("seed")?,
+        })
+    }
+
     pub fn view() -> ArgMatches<'static> {
         App::new("circom compiler")
             .version(VERSION)
@@ -437,6 +640,15 @@ mod input_processing {
                     .help("Maximum number of rounds of the simplification process")
                     .display_order(500)
             )
+            .arg(
+                Arg::with_name("keep_signals")
+                    .long("keep_signals")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .display_order(510)
+                    .help("Signals that are not removed by the simplification, as a comma separated list of qualified names (e.g. main.hasher.out). A name of an array or a component keeps all its signals and * matches any text"),
+            )
             .arg(
                 Arg::with_name("output")
                     .short("o")
@@ -475,6 +687,27 @@ mod input_processing {
                     .display_order(980)
                     .help("Outputs the substitution applied in the simplification phase in json format"),
             )
+            .arg(
+                Arg::with_name("verify_simplification")
+                    .long("verify_simplification")
+                    .takes_value(false)
+                    .display_order(981)
+                    .help("Checks that the simplified constraints and the substitutions are equivalent to the original constraints. Implies --simplification_substitution"),
+            )
+            .arg(
+                Arg::with_name("verify_reproducible")
+                    .long("verify_reproducible")
+                    .takes_value(false)
+                    .display_order(982)
+                    .help("Compiles the circuit twice and checks that every output file is written with the same contents"),
+            )
+            .arg(
+                Arg::with_name("low_memory")
+                    .long("low_memory")
+                    .takes_value(false)
+                    .display_order(983)
+                    .help("Keeps the constraints in temporary files instead of in memory, for circuits that do not fit in RAM"),
+            )
             .arg(
                 Arg::with_name("print_sym")
                     .long("sym")
@@ -482,6 +715,13 @@ mod input_processing {
                     .display_order(60)
                     .help("Outputs witness in sym format"),
             )
+            .arg(
+                Arg::with_name("print_extended_sym")
+                    .long("extended_sym")
+                    .takes_value(false)
+                    .display_order(61)
+                    .help("Outputs the sym file with the number of bits and maximum value proven for each signal"),
+            )
             .arg(
                 Arg::with_name("print_r1cs")
                     .long("r1cs")
@@ -581,6 +821,163 @@ mod input_processing {
                     .display_order(300)
                     .help("To choose the prime number to use to generate the circuit. Receives the name of the curve (bn128, bls12377, bls12381, goldilocks, grumpkin, pallas, secq256r1, vesta)"),
             )
+            .arg(
+                Arg::with_name("allow_lint")
+                    .short("A")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .display_order(400)
+                    .help("Does not report the given warning. Receives its name (e.g. UnconstrainedSignal), its code (e.g. CA01) or warnings to refer to all the warnings reported by default"),
+            )
+            .arg(
+                Arg::with_name("warn_lint")
+                    .short("W")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .display_order(401)
+                    .help("Reports the given warning"),
+            )
+            .arg(
+                Arg::with_name("deny_lint")
+                    .short("D")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .display_order(402)
+                    .help("Reports the given warning as an error"),
+            )
+            .arg(
+                Arg::with_name("lint_config")
+                    .long("lint_config")
+                    .takes_value(true)
+                    .display_order(403)
+                    .help("File with the level of each warning. By default, the file .circomlint in the directory of the input is used if it exists"),
+            )
+            .arg(
+                Arg::with_name("max_loop_iterations")
+                    .long("max_loop_iterations")
+                    .takes_value(true)
+                    .display_order(820)
+                    .help("Maximum number of iterations of a loop during the constraint generation, 0 for no limit [default: 1000000]"),
+            )
+            .arg(
+                Arg::with_name("max_recursion_depth")
+                    .long("max_recursion_depth")
+                    .takes_value(true)
+                    .display_order(821)
+                    .help("Maximum depth of nested function calls during the constraint generation, 0 for no limit [default: 1000]"),
+            )
+            .arg(
+                Arg::with_name("max_steps")
+                    .long("max_steps")
+                    .takes_value(true)
+                    .display_order(822)
+                    .help("Maximum number of statements executed during the constraint generation, 0 for no limit [default: 0]"),
+            )
+            .arg(
+                Arg::with_name("max_constraints")
+                    .long("max_constraints")
+                    .takes_value(true)
+                    .display_order(830)
+                    .help("Maximum number of constraints of the generated circuit, 0 for no limit [default: 0]"),
+            )
+            .arg(
+                Arg::with_name("max_wires")
+                    .long("max_wires")
+                    .takes_value(true)
+                    .display_order(831)
+                    .help("Maximum number of wires of the generated circuit, 0 for no limit [default: 0]"),
+            )
+            .arg(
+                Arg::with_name("template_budget")
+                    .long("template_budget")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .display_order(832)
+                    .help("Maximum size of every instance of a template, including its subcomponents, given as Template:constraints=N,signals=M"),
+            )
+            .arg(
+                Arg::with_name("explain")
+                    .long("explain")
+                    .takes_value(true)
+                    .display_order(404)
+                    .help("Prints a detailed explanation of the given error or warning code (e.g. T2021) and exits"),
+            )
+            .subcommand(
+                SubCommand::with_name("doc")
+                    .about("Generates the documentation of the templates, buses and functions of a project")
+                    .arg(
+                        Arg::with_name("input")
+                            .multiple(false)
+                            .default_value("./circuit.circom")
+                            .help("Path to the main file of the project, which does not need a main component"),
+                    )
+                    .arg(
+                        Arg::with_name("output")
+                            .short("o")
+                            .long("output")
+                            .takes_value(true)
+                            .default_value(".")
+                            .display_order(1)
+                            .help("Path to the directory where the documentation will be written"),
+                    )
+                    .arg(
+                        Arg::with_name("doc_format")
+                            .long("format")
+                            .takes_value(true)
+                            .default_value("markdown")
+                            .display_order(10)
+                            .help("Format of the documentation. It receives the value markdown or html"),
+                    )
+                    .arg(
+                        Arg::with_name("link_libraries")
+                            .short("l")
+                            .takes_value(true)
+                            .multiple(true)
+                            .number_of_values(1)
+                            .display_order(330)
+                            .help("Adds directory to library search path"),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("equivalence")
+                    .about("Checks that two compiled circuits accept the same inputs and outputs, with random witnesses and comparing their constraints")
+                    .arg(Arg::with_name("first").required(true).help("Path to the r1cs file of the first circuit"))
+                    .arg(Arg::with_name("second").required(true).help("Path to the r1cs file of the second circuit"))
+                    .arg(
+                        Arg::with_name("first_sym")
+                            .long("first_sym")
+                            .takes_value(true)
+                            .display_order(10)
+                            .help("Path to the sym file of the first circuit, by default next to its r1cs file"),
+                    )
+                    .arg(
+                        Arg::with_name("second_sym")
+                            .long("second_sym")
+                            .takes_value(true)
+                            .display_order(11)
+                            .help("Path to the sym file of the second circuit, by default next to its r1cs file"),
+                    )
+                    .arg(
+                        Arg::with_name("samples")
+                            .long("samples")
+                            .takes_value(true)
+                            .default_value("20")
+                            .display_order(12)
+                            .help("Number of random witnesses sampled from each circuit"),
+                    )
+                    .arg(
+                        Arg::with_name("seed")
+                            .long("seed")
+                            .takes_value(true)
+                            .default_value("0")
+                            .display_order(13)
+                            .help("Seed of the random witnesses"),
+                    ),
+            )
             .get_matches()
     }
 
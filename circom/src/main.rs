@@ -1,7 +1,11 @@
 mod compilation_user;
+mod documentation_user;
+mod equivalence_user;
 mod execution_user;
+mod explain_user;
 mod input_user;
 mod parser_user;
+mod reproducibility_user;
 mod type_analysis_user;
 
 const VERSION: &'static str = env!("CARGO_PKG_VERSION");
@@ -9,8 +13,16 @@ const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 
 use ansi_term::Colour;
 use input_user::Input;
+// The execution of the program is recursive, so the compiler runs in a thread whose
+// stack is large enough for the default limit of nested function calls
+const STACK_SIZE: usize = 1 << 30;
+
 fn main() {
-    let result = start();
+    let compiler = std::thread::Builder::new().stack_size(STACK_SIZE).spawn(start).unwrap();
+    let result = match compiler.join() {
+        Result::Ok(result) => result,
+        Result::Err(panic) => std::panic::resume_unwind(panic),
+    };
     if result.is_err() {
         eprintln!("{}", Colour::Red.paint("previous errors were found"));
         std::process::exit(1);
@@ -21,11 +33,18 @@ fn main() {
 }
 
 fn start() -> Result<(), ()> {
+    let user_input = Input::new()?;
+    if user_input.verify_reproducible_flag() {
+        return reproducibility_user::verify_reproducible(&user_input, compile_project);
+    }
+    compile_project(&user_input)
+}
+
+fn compile_project(user_input: &Input) -> Result<(), ()> {
     use compilation_user::CompilerConfig;
     use execution_user::ExecutionConfig;
-    let user_input = Input::new()?;
-    let mut program_archive = parser_user::parse_project(&user_input)?;
-    type_analysis_user::analyse_project(&mut program_archive)?;
+    let (mut program_archive, lint_config) = parser_user::parse_project(user_input)?;
+    type_analysis_user::analyse_project(&mut program_archive, &lint_config)?;
 
     let config = ExecutionConfig {
         no_rounds: user_input.no_rounds(),
@@ -33,17 +52,24 @@ fn start() -> Result<(), ()> {
         flag_s: user_input.reduced_simplification_flag(),
         flag_f: user_input.unsimplified_flag(),
         flag_old_heuristics: user_input.flag_old_heuristics(),
+        keep_signals: user_input.keep_signals().clone(),
+        verify_simplification_flag: user_input.verify_simplification_flag(),
+        low_memory: user_input.low_memory(),
         flag_verbose: user_input.flag_verbose(),
-        inspect_constraints_flag: user_input.inspect_constraints_flag(),
         r1cs_flag: user_input.r1cs_flag(),
         json_constraint_flag: user_input.json_constraints_flag(),
         json_substitution_flag: user_input.json_substitutions_flag(),
         sym_flag: user_input.sym_flag(),
         sym: user_input.sym_file().to_string(),
+        extended_sym_flag: user_input.extended_sym_flag(),
+        extended_sym: user_input.extended_sym_file().to_string(),
         r1cs: user_input.r1cs_file().to_string(),
         json_constraints: user_input.json_constraints_file().to_string(),
         json_substitutions: user_input.json_substitutions_file().to_string(),
         prime: user_input.prime(),        
+        limits: user_input.execution_limits(),
+        budgets: user_input.budgets().clone(),
+        lint_config: lint_config.clone(),
     };
     let circuit = execution_user::execute_project(program_archive, config)?;
     let compilation_config = CompilerConfig {
@@ -64,6 +90,7 @@ fn start() -> Result<(), ()> {
         sanity_check_style: user_input.sanity_check_style(),
         no_asm_flag: user_input.no_asm_flag(),
         prime: user_input.prime(),        
+        lint_config,
     };
     compilation_user::compile(compilation_config)?;
     Result::Ok(())
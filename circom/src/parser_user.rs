@@ -1,30 +1,38 @@
 use super::input_user::Input;
 use program_structure::constants::UsefulConstants;
 use program_structure::error_definition::Report;
+use program_structure::lint_config::LintConfig;
 use program_structure::program_archive::ProgramArchive;
 use crate::VERSION;
 
 
-pub fn parse_project(input_info: &Input) -> Result<ProgramArchive, ()> {
+// The lint configuration of the input is completed with the levels given by the
+// comments of the program
+pub fn parse_project(input_info: &Input) -> Result<(ProgramArchive, LintConfig), ()> {
     let initial_file = input_info.input_file().to_string();
     //We get the prime number from the input
-    let prime = UsefulConstants::new(&input_info.prime()).get_p().clone();
+    let constants = UsefulConstants::new(&input_info.prime());
     let flag_no_init = input_info.flag_no_init();
     let result_program_archive = parser::run_parser(
         initial_file, 
         VERSION, 
         input_info.get_link_libraries().to_vec(), 
-        &prime,
+        &constants,
         flag_no_init
     );
+    let mut lint_config: LintConfig = input_info.lint_config().clone();
     match result_program_archive {
         Result::Err((file_library, report_collection)) => {
-            Report::print_reports(&report_collection, &file_library);
+            Report::print_reports(&report_collection, &file_library, &lint_config);
             Result::Err(())
         }
         Result::Ok((program_archive, warnings)) => {
-            Report::print_reports(&warnings, &program_archive.file_library);
-            Result::Ok(program_archive)
+            lint_config.add_scopes(&program_archive.lint_scopes);
+            if Report::print_reports(&warnings, &program_archive.file_library, &lint_config) {
+                Result::Err(())
+            } else {
+                Result::Ok((program_archive, lint_config))
+            }
         }
     }
 }
@@ -0,0 +1,106 @@
+use crate::input_user::Input;
+use ansi_term::Colour;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// Hash of the contents of every artifact, by path
+type ArtifactHashes = BTreeMap<PathBuf, u64>;
+
+// 64-bit FNV-1a, enough to compare the artifacts of two compilations
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    let mut hash = OFFSET_BASIS;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+// The files and folders written for the given flags
+fn artifacts(input: &Input) -> Vec<PathBuf> {
+    let mut artifacts = Vec::new();
+    let files = [
+        (input.r1cs_flag(), input.r1cs_file()),
+        (input.sym_flag(), input.sym_file()),
+        (input.extended_sym_flag(), input.extended_sym_file()),
+        (input.json_constraints_flag(), input.json_constraints_file()),
+        (input.json_substitutions_flag(), input.json_substitutions_file()),
+        (input.c_flag(), input.c_folder()),
+        (input.wasm_flag() || input.wat_flag(), input.js_folder()),
+    ];
+    for (flag, path) in files {
+        if flag {
+            artifacts.push(PathBuf::from(path));
+        }
+    }
+    artifacts
+}
+
+fn hash_path(path: &Path, hashes: &mut ArtifactHashes) -> std::io::Result<()> {
+    if path.is_dir() {
+        for entry in std::fs::read_dir(path)? {
+            hash_path(&entry?.path(), hashes)?;
+        }
+    } else if path.exists() {
+        hashes.insert(path.to_path_buf(), fnv1a(&std::fs::read(path)?));
+    }
+    Result::Ok(())
+}
+
+fn hash_artifacts(artifacts: &[PathBuf]) -> Result<ArtifactHashes, ()> {
+    let mut hashes = ArtifactHashes::new();
+    for artifact in artifacts {
+        if let Result::Err(error) = hash_path(artifact, &mut hashes) {
+            let msg = format!("Could not read {}: {}", artifact.display(), error);
+            eprintln!("{}", Colour::Red.paint(msg));
+            return Result::Err(());
+        }
+    }
+    Result::Ok(hashes)
+}
+
+// Paths whose contents differ between the two compilations, or that were only
+// written by one of them
+fn differences(first: &ArtifactHashes, second: &ArtifactHashes) -> Vec<String> {
+    let mut differences = Vec::new();
+    for (path, hash) in first {
+        match second.get(path) {
+            Option::Some(other) if other != hash => {
+                differences.push(format!("{}: {:016x} != {:016x}", path.display(), hash, other))
+            }
+            Option::None => differences.push(format!("{}: only written by the first compilation", path.display())),
+            _ => {}
+        }
+    }
+    for path in second.keys().filter(|path| !first.contains_key(*path)) {
+        differences.push(format!("{}: only written by the second compilation", path.display()));
+    }
+    differences
+}
+
+// Compiles the circuit twice in the same process and checks that every artifact
+// is written with the same contents both times
+pub fn verify_reproducible(input: &Input, compile: fn(&Input) -> Result<(), ()>) -> Result<(), ()> {
+    let artifacts = artifacts(input);
+    compile(input)?;
+    let first = hash_artifacts(&artifacts)?;
+    println!("{}", Colour::Green.paint("Compiling again to verify the reproducibility of the output"));
+    compile(input)?;
+    let second = hash_artifacts(&artifacts)?;
+    let differences = differences(&first, &second);
+    if differences.is_empty() {
+        println!("{} {} files", Colour::Green.paint("Reproducibility verified:"), first.len());
+        for (path, hash) in &first {
+            println!("    {:016x}  {}", hash, path.display());
+        }
+        Result::Ok(())
+    } else {
+        for difference in differences {
+            eprintln!("{}", Colour::Red.paint(difference));
+        }
+        eprintln!("{}", Colour::Red.paint("The output of the compiler is not reproducible"));
+        Result::Err(())
+    }
+}
@@ -0,0 +1,41 @@
+pragma circom 2.1.0;
+template Lin(n) {
+    signal input in[n];
+    signal output out[n];
+    signal m[n];
+    for (var i = 0; i < n; i++) {
+        m[i] <== in[i] * in[(i + 1) % n];
+    }
+    for (var i = 0; i < n; i++) {
+        var s = 0;
+        for (var j = 0; j < n; j++) {
+            s += (i + j + 1) * m[j];
+        }
+        out[i] <== s + in[i];
+    }
+}
+template Chain(n) {
+    signal input in;
+    signal output out;
+    signal c[n];
+    c[0] <== in;
+    for (var i = 1; i < n; i++) {
+        c[i] <== c[i - 1];
+    }
+    out <== c[n - 1];
+}
+template Main() {
+    signal input x[6];
+    signal output y[6];
+    signal output w[6];
+    component l = Lin(6);
+    l.in <== x;
+    component ch[6];
+    for (var i = 0; i < 6; i++) {
+        ch[i] = Chain(5);
+        ch[i].in <== l.out[i];
+        y[i] <== ch[i].out;
+        w[i] <== l.out[i] * l.out[(i + 2) % 6] + 2 * ch[i].out;
+    }
+}
+component main = Main();
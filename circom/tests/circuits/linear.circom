@@ -0,0 +1,29 @@
+pragma circom 2.1.0;
+
+// Many instances of a template with a long chain of linear constraints, which are
+// simplified in small clusters
+template Chain(n) {
+    signal input a;
+    signal input b;
+    signal output c;
+    signal t[n];
+    t[0] <== a + 2 * b;
+    for (var i = 1; i < n; i++) {
+        t[i] <== 3 * t[i - 1] + 5 * b + i;
+    }
+    c <== t[n - 1] + a;
+}
+
+template Main(n) {
+    signal input in[2 * n];
+    signal output out[n];
+    component chains[n];
+    for (var i = 0; i < n; i++) {
+        chains[i] = Chain(200);
+        chains[i].a <== in[2 * i];
+        chains[i].b <== in[2 * i + 1];
+        out[i] <== chains[i].c;
+    }
+}
+
+component main = Main(500);
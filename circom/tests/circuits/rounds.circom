@@ -0,0 +1,65 @@
+pragma circom 2.1.0;
+template Num2Bits(n) {
+    signal input in;
+    signal output out[n];
+    var lc1 = 0;
+    var e2 = 1;
+    for (var i = 0; i < n; i++) {
+        out[i] <-- (in >> i) & 1;
+        out[i] * (out[i] - 1) === 0;
+        lc1 += out[i] * e2;
+        e2 = e2 + e2;
+    }
+    lc1 === in;
+}
+template IsZero() {
+    signal input in;
+    signal output out;
+    signal inv;
+    inv <-- in != 0 ? 1 / in : 0;
+    out <== -in * inv + 1;
+    in * out === 0;
+}
+template LessThan(n) {
+    signal input in[2];
+    signal output out;
+    component n2b = Num2Bits(n + 1);
+    n2b.in <== in[0] + (1 << n) - in[1];
+    out <== 1 - n2b.out[n];
+}
+template Round(k) {
+    signal input x;
+    signal input key;
+    signal output y;
+    signal t <== x + key + k;
+    signal t2 <== t * t;
+    signal t4 <== t2 * t2;
+    y <== t4 * t;
+}
+template Main(N) {
+    signal input a[N];
+    signal input key;
+    signal output h;
+    signal output lt[N];
+    signal output z[N];
+    component r[N];
+    component c[N];
+    component iz[N];
+    var acc = 0;
+    for (var i = 0; i < N; i++) {
+        r[i] = Round(i);
+        r[i].x <== a[i];
+        r[i].key <== key;
+        c[i] = LessThan(16);
+        c[i].in[0] <== a[i];
+        c[i].in[1] <== key;
+        lt[i] <== c[i].out;
+        iz[i] = IsZero();
+        iz[i].in <== a[i] - key;
+        z[i] <== iz[i].out;
+        acc += r[i].y;
+    }
+    signal s <== acc;
+    h <== s * s;
+}
+component main {public [key]} = Main(12);
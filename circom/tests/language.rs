@@ -0,0 +1,647 @@
+// Compiles small circuits and checks the reports of the compiler for the features
+// of the language and its analyses.
+use std::path::PathBuf;
+use std::process::Command;
+
+struct Compilation {
+    success: bool,
+    // stdout and stderr, where the reports are printed
+    output: String,
+}
+
+impl Compilation {
+    fn reports(&self, code: &str) -> usize {
+        self.output.matches(&format!("[{}]", code)).count()
+    }
+}
+
+fn folder(name: &str) -> PathBuf {
+    let folder = std::env::temp_dir().join(format!("circom_language_{}_{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&folder);
+    std::fs::create_dir_all(&folder).unwrap();
+    folder
+}
+
+fn compile_with(name: &str, source: &str, args: &[&str]) -> Compilation {
+    compile_with_files(name, source, &[], args)
+}
+
+// Compiles the source with other files, given by name and contents, next to it
+fn compile_with_files(name: &str, source: &str, files: &[(&str, &str)], args: &[&str]) -> Compilation {
+    let folder = folder(name);
+    let file = folder.join(format!("{}.circom", name));
+    std::fs::write(&file, source).unwrap();
+    for (other, contents) in files {
+        std::fs::write(folder.join(other), contents).unwrap();
+    }
+    let output = Command::new(env!("CARGO_BIN_EXE_circom")).arg(&file).arg("-o").arg(&folder).args(args).output().unwrap();
+    std::fs::remove_dir_all(&folder).unwrap();
+    Compilation {
+        success: output.status.success(),
+        output: format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr)),
+    }
+}
+
+fn compile(name: &str, source: &str) -> Compilation {
+    compile_with(name, source, &[])
+}
+
+#[test]
+fn global_constants_can_depend_on_other_constants() {
+    let compilation = compile(
+        "constants",
+        "pragma circom 2.1.0;
+        const N = 4;
+        const M = 2 * N + 1;
+        template T() { signal input x[M]; signal output y; y <== x[N] * N; }
+        component main = T();",
+    );
+    assert!(compilation.success, "{}", compilation.output);
+}
+
+#[test]
+fn cyclic_global_constants_are_rejected() {
+    let compilation = compile(
+        "cyclic_constants",
+        "pragma circom 2.1.0;
+        const A = B + 1;
+        const B = A * 2;
+        template T() { signal input x; signal output y; y <== x * A; }
+        component main = T();",
+    );
+    assert!(!compilation.success);
+    assert_eq!(compilation.reports("T2065"), 2, "{}", compilation.output);
+}
+
+// Runs circom doc on a project and returns its output and the markdown written
+fn document(name: &str, source: &str) -> (Compilation, String) {
+    let folder = folder(name);
+    let file = folder.join(format!("{}.circom", name));
+    std::fs::write(&file, source).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_circom")).arg("doc").arg(&file).arg("-o").arg(&folder).output().unwrap();
+    let markdown = std::fs::read_to_string(folder.join(format!("{}.md", name))).unwrap_or_default();
+    std::fs::remove_dir_all(&folder).unwrap();
+    let compilation = Compilation {
+        success: output.status.success(),
+        output: format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr)),
+    };
+    (compilation, markdown)
+}
+
+#[test]
+fn libraries_are_documented_from_their_doc_comments() {
+    let (documentation, markdown) = document(
+        "library",
+        "pragma circom 2.1.0;
+        /// Adds two numbers.
+        function add(a, b) { return a + b; }
+        /// Checks that the input is a bit.
+        template IsBit() {
+            /// The value to check
+            signal input in;
+            in * (in - 1) === 0;
+        }
+        /// Decomposes a number in n bits.
+        template Num2Bits(n) {
+            /// The number and a second input
+            signal input in, extra;
+            /// The bits, least significant first
+            signal output out[n];
+            component checks[n];
+            for (var i = 0; i < n; i++) { checks[i] = IsBit(); }
+        }",
+    );
+    assert!(documentation.success, "{}", documentation.output);
+    assert!(!documentation.output.contains("Everything went okay"), "{}", documentation.output);
+    for text in [
+        "### `Num2Bits(n)`",
+        "library.circom`\n\nDecomposes a number in n bits.",
+        "| `in` | input | signal |  |  | The number and a second input |",
+        "| `extra` | input | signal |  |  |  |",
+        "| `out` | output | signal | [n] |  | The bits, least significant first |",
+        "Instantiates: [IsBit](#template-IsBit)",
+        "| `in` | input | signal |  |  | The value to check |",
+        "## Functions",
+        "### `add(a, b)`",
+        "library.circom`\n\nAdds two numbers.",
+    ] {
+        assert!(markdown.contains(text), "{} is not in\n{}", text, markdown);
+    }
+}
+
+#[test]
+fn failing_static_assertions_report_their_message_and_call_trace() {
+    let template = "pragma circom 2.1.0;
+        template B(n) { static_assert(n > 2, \"n must be greater than two\"); signal input x; signal output y; y <== x; }
+        template A(n) { signal input x; signal output y; component b = B(n - 1); b.x <== x; y <== b.y; }";
+    let holds = compile("static_assert_holds", &format!("{}\ncomponent main = A(4);", template));
+    assert!(holds.success, "{}", holds.output);
+    let fails = compile("static_assert_fails", &format!("{}\ncomponent main = A(3);", template));
+    assert!(!fails.success);
+    assert_eq!(fails.reports("T3003"), 1, "{}", fails.output);
+    assert!(fails.output.contains("Static assertion failed: n must be greater than two"), "{}", fails.output);
+    assert!(fails.output.contains("main = A(n = 3)"), "{}", fails.output);
+    assert!(fails.output.contains("main.b = B(n = 2)"), "{}", fails.output);
+}
+
+#[test]
+fn static_assertions_must_be_known_and_inside_templates() {
+    let on_signals = compile(
+        "static_assert_on_signals",
+        "pragma circom 2.1.0;
+        template T() { signal input x; signal output y; static_assert(x > 2, \"x is too small\"); y <== x * x; }
+        component main = T();",
+    );
+    assert!(!on_signals.success);
+    assert_eq!(on_signals.reports("T2066"), 1, "{}", on_signals.output);
+    let in_function = compile(
+        "static_assert_in_function",
+        "pragma circom 2.1.0;
+        function f(n) { static_assert(n > 1, \"n must be greater than one\"); return n; }
+        template T(n) { signal input x; signal output y; y <== x * f(n); }
+        component main = T(3);",
+    );
+    assert!(!in_function.success);
+    assert_eq!(in_function.reports("T2001"), 1, "{}", in_function.output);
+    assert!(in_function.output.contains("Static assertions can only be used in templates"), "{}", in_function.output);
+}
+
+#[test]
+fn requires_contracts_are_checked_at_instantiation() {
+    let template = "pragma circom 2.1.0;
+        template T(n) requires n > 2 { signal input x; signal output y; y <== x * n; }";
+    let accepted = compile("requires_holds", &format!("{}\ncomponent main = T(3);", template));
+    assert!(accepted.success, "{}", accepted.output);
+    let violated = compile("requires_violated", &format!("{}\ncomponent main = T(1);", template));
+    assert!(!violated.success);
+    assert_eq!(violated.reports("T3004"), 1, "{}", violated.output);
+    assert!(violated.output.contains("T(n = 1)"), "{}", violated.output);
+}
+
+#[test]
+fn log_strings_are_literal_unless_they_are_format_strings() {
+    let compilation = compile_with(
+        "log_strings",
+        "pragma circom 2.1.0;
+        template T() {
+            signal input x; signal output y;
+            y <== x * 2;
+            var a = 7;
+            log(\"value {x} is\", a);
+            log(\"{\", a);
+            log(\"a = {}, b = {}\", a);
+            log(\"a = {:x}!\", a + 8);
+        }
+        component main = T();",
+        &["--verbose"],
+    );
+    assert!(compilation.success, "{}", compilation.output);
+    assert!(compilation.output.contains("value {x} is 7"), "{}", compilation.output);
+    assert!(compilation.output.contains("{ 7"), "{}", compilation.output);
+    assert!(compilation.output.contains("a = {}, b = {} 7"), "{}", compilation.output);
+    assert!(compilation.output.contains("a = f!"), "{}", compilation.output);
+    assert_eq!(compilation.reports("T2068"), 1, "{}", compilation.output);
+}
+
+#[test]
+fn field_constants_depend_on_the_prime() {
+    let source = "pragma circom 2.1.0;
+        template T() {
+            signal input x; signal output y[FIELD_ID];
+            assert(FIELD_MAX + 1 == 0);
+            for (var i = 0; i < FIELD_ID; i++) { y[i] <== x * FIELD_BITS; }
+            log(FIELD_ID, FIELD_BITS);
+        }
+        component main = T();";
+    let bn128 = compile_with("field_bn128", source, &["--verbose"]);
+    assert!(bn128.success, "{}", bn128.output);
+    assert!(bn128.output.contains("1 254"), "{}", bn128.output);
+    let goldilocks = compile_with("field_goldilocks", source, &["--verbose", "--prime", "goldilocks"]);
+    assert!(goldilocks.success, "{}", goldilocks.output);
+    assert!(goldilocks.output.contains("3 64"), "{}", goldilocks.output);
+}
+
+#[test]
+fn field_constant_names_are_reserved() {
+    let compilation = compile(
+        "reserved_names",
+        "pragma circom 2.1.0;
+        template T(FIELD_MAX) { signal input x; signal output y; var FIELD_BITS = 3; y <== x * FIELD_BITS; }
+        component main = T(1);",
+    );
+    assert!(!compilation.success);
+    assert_eq!(compilation.reports("T2070"), 2, "{}", compilation.output);
+    assert_eq!(compilation.reports("T2008"), 0, "{}", compilation.output);
+}
+
+const HASH_INTERFACE: &str = "pragma circom 2.1.0;
+    template Hash2() { signal input in[2]; signal output out; }
+    template Sum2() { signal input in[2]; signal output out; out <== in[0] + in[1]; }
+    template Sum3() { signal input in[2]; signal input k; signal output out; out <== in[0] + in[1] + k; }";
+
+#[test]
+fn generic_templates_are_instantiated_with_matching_templates() {
+    let compilation = compile(
+        "generic_templates",
+        &format!(
+            "{}
+            template Apply(n, template H: Hash2) {{
+                signal input a; signal output b;
+                component c = H(); c.in[0] <== a; c.in[1] <== n; b <== c.out;
+            }}
+            template Unused(template H: Hash2) {{ signal input a; component c = H(); c.in[0] <== a; c.in[1] <== a; }}
+            component main = Apply(3, Sum2);",
+            HASH_INTERFACE
+        ),
+    );
+    assert!(compilation.success, "{}", compilation.output);
+    assert_eq!(compilation.reports("DC01"), 2, "{}", compilation.output);
+    assert!(compilation.output.contains("The template Unused is never used"), "{}", compilation.output);
+    assert!(compilation.output.contains("The template Sum3 is never used"), "{}", compilation.output);
+}
+
+#[test]
+fn generic_templates_are_checked_against_the_interfaces() {
+    let wrong_argument = compile(
+        "generic_wrong_argument",
+        &format!(
+            "{}
+            template Apply(template H: Hash2) {{ signal input a; signal output b; component c = H(); c.in[0] <== a; c.in[1] <== a; b <== c.out; }}
+            component main = Apply(Sum3);",
+            HASH_INTERFACE
+        ),
+    );
+    assert!(!wrong_argument.success);
+    assert_eq!(wrong_argument.reports("T2069"), 1, "{}", wrong_argument.output);
+    assert!(wrong_argument.output.contains("the input k is not in the interface"), "{}", wrong_argument.output);
+    // The body of a generic template is checked even if it is never instantiated
+    let wrong_body = compile(
+        "generic_wrong_body",
+        &format!(
+            "{}
+            template Broken(template H: Hash2) {{ signal input a; component c = H(); c.k <== a; }}
+            template Main() {{ signal input a; signal output b; component c = Sum2(); c.in[0] <== a; c.in[1] <== a; b <== c.out; }}
+            component main = Main();",
+            HASH_INTERFACE
+        ),
+    );
+    assert!(!wrong_body.success);
+    assert!(wrong_body.output.contains("k is not defined in c"), "{}", wrong_body.output);
+}
+
+// A is reported with CA05, B only if its lint comment is overridden
+const LINT_SCOPES: &str = "pragma circom 2.1.0;
+    template A() { signal input x; signal output y; y <-- x * x; }
+    // lint: allow(SignalAssignmentNotConstrained)
+    template B() { signal input x; signal output y; y <-- x * x; }
+    template Main() {
+        signal input x; signal output y;
+        component a = A(); component b = B();
+        a.x <== x; b.x <== x; y <== a.y * b.y;
+    }
+    component main = Main();";
+
+#[test]
+fn lint_levels_are_set_from_the_command_line() {
+    let warned = compile("lint_warned", LINT_SCOPES);
+    assert!(warned.success, "{}", warned.output);
+    assert_eq!(warned.reports("CA05"), 1, "{}", warned.output);
+    assert!(warned.output.contains("warning[CA05]"), "{}", warned.output);
+    let silenced = compile_with("lint_silenced", LINT_SCOPES, &["-A", "CA05"]);
+    assert!(silenced.success, "{}", silenced.output);
+    assert_eq!(silenced.reports("CA05"), 0, "{}", silenced.output);
+    let denied = compile_with("lint_denied", LINT_SCOPES, &["-D", "SignalAssignmentNotConstrained"]);
+    assert!(!denied.success);
+    assert!(denied.output.contains("error[CA05]"), "{}", denied.output);
+    let overridden = compile_with("lint_overridden", LINT_SCOPES, &["-D", "warnings", "-W", "CA05"]);
+    assert!(overridden.success, "{}", overridden.output);
+    assert!(overridden.output.contains("warning[CA05]"), "{}", overridden.output);
+    let unknown = compile_with("lint_unknown", LINT_SCOPES, &["-A", "NoSuchLint"]);
+    assert!(!unknown.success);
+    assert!(unknown.output.contains("Unknown lint NoSuchLint"), "{}", unknown.output);
+}
+
+#[test]
+fn lint_levels_are_read_from_the_configuration_file() {
+    let file = [(".circomlint", "# reviewed\nCA05 = deny\n")];
+    let denied = compile_with_files("lint_file", LINT_SCOPES, &file, &[]);
+    assert!(!denied.success);
+    assert_eq!(denied.reports("CA05"), 1, "{}", denied.output);
+    assert!(denied.output.contains("error[CA05]"), "{}", denied.output);
+    let overridden = compile_with_files("lint_file_overridden", LINT_SCOPES, &file, &["-W", "CA05"]);
+    assert!(overridden.success, "{}", overridden.output);
+    let invalid = compile_with_files("lint_file_invalid", LINT_SCOPES, &[(".circomlint", "CA05 = never\n")], &[]);
+    assert!(!invalid.success);
+    assert!(invalid.output.contains("invalid lint configuration"), "{}", invalid.output);
+}
+
+#[test]
+fn lint_comments_set_the_level_inside_their_template() {
+    let raised = compile(
+        "lint_comment_deny",
+        &LINT_SCOPES.replace("template A()", "// lint: deny(CA05)\n    template A()"),
+    );
+    assert!(!raised.success);
+    assert_eq!(raised.reports("CA05"), 1, "{}", raised.output);
+    assert!(raised.output.contains("error[CA05]"), "{}", raised.output);
+    let without = compile("lint_comment_removed", &LINT_SCOPES.replace("// lint: allow(SignalAssignmentNotConstrained)", ""));
+    assert_eq!(without.reports("CA05"), 2, "{}", without.output);
+}
+
+#[test]
+fn denying_warnings_does_not_enable_the_inspect_lints() {
+    let source = "pragma circom 2.1.0;
+        template A() { signal input x; signal input z; signal output y; y <== x * x; }
+        template Main() { signal input x; signal output y; component a = A(); a.x <== x; a.z <== x; y <== a.y; }
+        component main = Main();";
+    let denied = compile_with("deny_warnings", source, &["-D", "warnings"]);
+    assert!(denied.success, "{}", denied.output);
+    assert_eq!(denied.reports("CA01"), 0, "{}", denied.output);
+    let inspected = compile_with("deny_warnings_inspect", source, &["-D", "warnings", "--inspect"]);
+    assert!(!inspected.success);
+    assert!(inspected.output.contains("error[CA01]"), "{}", inspected.output);
+    let explicit = compile_with("deny_unconstrained", source, &["-D", "warnings", "-D", "CA01"]);
+    assert!(!explicit.success);
+    assert!(explicit.output.contains("error[CA01]"), "{}", explicit.output);
+}
+
+#[test]
+fn signals_assigned_without_constraints_are_reported() {
+    let compilation = compile(
+        "unconstrained_assignments",
+        "pragma circom 2.1.0;
+        template T() {
+            signal input in; signal output inv; signal output x[2];
+            inv <-- 1 / in;
+            x[0] <-- in * 2;
+            x[1] <-- in * 3;
+            x[1] === in * 3;
+        }
+        component main = T();",
+    );
+    assert!(compilation.success, "{}", compilation.output);
+    assert_eq!(compilation.reports("CA05"), 2, "{}", compilation.output);
+    assert!(compilation.output.contains("The signal inv does not appear"), "{}", compilation.output);
+    assert!(compilation.output.contains("The signal x[0] does not appear"), "{}", compilation.output);
+}
+
+#[test]
+fn signals_assigned_in_loops_and_in_libraries_are_covered() {
+    let folder = folder("unconstrained_library");
+    std::fs::write(
+        folder.join("inverse.circom"),
+        "pragma circom 2.1.0;
+        template Inverse() { signal input in; signal output out; out <-- 1 / in; }",
+    )
+    .unwrap();
+    let compilation = compile_with(
+        "constrained_assignments",
+        "pragma circom 2.1.0;
+        include \"inverse.circom\";
+        template Bits(n) {
+            signal input in; signal output bits[n]; signal output inv;
+            var sum = 0;
+            for (var i = 0; i < n; i++) {
+                bits[i] <-- (in >> i) & 1;
+                bits[i] * (bits[i] - 1) === 0;
+                sum += bits[i] * 2 ** i;
+            }
+            sum === in;
+            component c = Inverse(); c.in <== in; inv <== c.out;
+        }
+        component main = Bits(4);",
+        &["-l", folder.to_str().unwrap()],
+    );
+    std::fs::remove_dir_all(&folder).unwrap();
+    assert!(compilation.success, "{}", compilation.output);
+    assert_eq!(compilation.reports("CA05"), 0, "{}", compilation.output);
+}
+
+#[test]
+fn unused_symbols_are_found_by_declaration() {
+    let folder = folder("unused_library");
+    std::fs::write(
+        folder.join("square.circom"),
+        "pragma circom 2.1.0;
+        function square(x) { var unused = 1; return x * x; }",
+    )
+    .unwrap();
+    let compilation = compile_with(
+        "unused_symbols",
+        "pragma circom 2.1.0;
+include \"square.circom\";
+template Never() { signal input in; }
+template T(n) {
+    signal input in; signal output out;
+    var acc = 0;
+    for (var i = 0; i < n; i++) { var t = i; acc += t; }
+    for (var i = 0; i < n; i++) { var t = i; }
+    out <== in * square(acc);
+}
+component main = T(2);",
+        &["-l", folder.to_str().unwrap()],
+    );
+    std::fs::remove_dir_all(&folder).unwrap();
+    assert!(compilation.success, "{}", compilation.output);
+    assert_eq!(compilation.reports("DC02"), 1, "{}", compilation.output);
+    assert!(compilation.output.contains("unused_symbols.circom\":8:35"), "{}", compilation.output);
+    assert_eq!(compilation.reports("DC01"), 1, "{}", compilation.output);
+    assert!(compilation.output.contains("unused_symbols.circom\":3:10"), "{}", compilation.output);
+}
+
+#[test]
+fn errors_of_independent_templates_are_all_reported() {
+    let compilation = compile(
+        "independent_errors",
+        "pragma circom 2.1.0;
+        template A() { signal input in; signal output out; out <== in * undefined_name; }
+        template B() { signal input in[2]; signal output out; out <== in; }
+        template C() { signal input in; signal output out; var v = in; if (v > 0) { out <== in; } else { out <== 0; } }
+        template Main() {
+            signal input in; signal output out[3];
+            component a = A(); a.in <== in; out[0] <== a.out;
+            component b = B(); b.in[0] <== in; b.in[1] <== in; out[1] <== b.out;
+            component c = C(); c.in <== in; out[2] <== c.out;
+        }
+        component main = Main();",
+    );
+    assert!(!compilation.success);
+    // An undeclared symbol, a typing error and an unknown condition of a constraint
+    assert_eq!(compilation.reports("T2021"), 1, "{}", compilation.output);
+    assert_eq!(compilation.reports("T2061"), 1, "{}", compilation.output);
+    assert_eq!(compilation.reports("T2005"), 1, "{}", compilation.output);
+}
+
+// Runs a loop of n iterations and calls a function recursively n times
+const BOUNDED_EXECUTION: &str = "pragma circom 2.1.0;
+    function depth(n) { if (n == 0) { return 0; } return depth(n - 1) + 1; }
+    template T(n) {
+        signal input x; signal output y;
+        var count = 0;
+        for (var i = 0; i < n; i++) { count += 1; }
+        y <== x * (count + depth(n));
+    }
+    component main = T(50);";
+
+#[test]
+fn loops_longer_than_the_limit_are_reported() {
+    let exceeded = compile_with("loop_limit", BOUNDED_EXECUTION, &["--max_loop_iterations", "20"]);
+    assert!(!exceeded.success);
+    assert_eq!(exceeded.reports("T3005"), 1, "{}", exceeded.output);
+    assert!(exceeded.output.contains("The loop did not finish after 20 iterations"), "{}", exceeded.output);
+    assert!(exceeded.output.contains("--max_loop_iterations"), "{}", exceeded.output);
+    let raised = compile_with("loop_limit_raised", BOUNDED_EXECUTION, &["--max_loop_iterations", "100"]);
+    assert!(raised.success, "{}", raised.output);
+    let disabled = compile_with("loop_limit_disabled", BOUNDED_EXECUTION, &["--max_loop_iterations", "0"]);
+    assert!(disabled.success, "{}", disabled.output);
+}
+
+#[test]
+fn recursions_deeper_than_the_limit_are_reported() {
+    let exceeded = compile_with("recursion_limit", BOUNDED_EXECUTION, &["--max_recursion_depth", "20"]);
+    assert!(!exceeded.success);
+    assert_eq!(exceeded.reports("T3005"), 1, "{}", exceeded.output);
+    assert!(exceeded.output.contains("The depth of nested function calls exceeds 20"), "{}", exceeded.output);
+    let raised = compile_with("recursion_limit_raised", BOUNDED_EXECUTION, &["--max_recursion_depth", "100"]);
+    assert!(raised.success, "{}", raised.output);
+}
+
+#[test]
+fn executions_longer_than_the_limit_are_reported() {
+    let exceeded = compile_with("steps_limit", BOUNDED_EXECUTION, &["--max_steps", "100"]);
+    assert!(!exceeded.success);
+    assert_eq!(exceeded.reports("T3005"), 1, "{}", exceeded.output);
+    assert!(exceeded.output.contains("executed more than 100 statements"), "{}", exceeded.output);
+    let raised = compile_with("steps_limit_raised", BOUNDED_EXECUTION, &["--max_steps", "10000"]);
+    assert!(raised.success, "{}", raised.output);
+}
+
+#[test]
+fn kept_signals_that_do_not_exist_are_reported() {
+    let source = "pragma circom 2.1.0;
+        template A() { signal input in; signal output out; signal square; square <== in * in; out <== square * in; }
+        component main = A();";
+    let misspelled = compile_with("keep_misspelled", source, &["--O2", "--keep_signals", "main.sqare"]);
+    assert!(misspelled.success, "{}", misspelled.output);
+    assert_eq!(misspelled.reports("T3009"), 1, "{}", misspelled.output);
+    let kept = compile_with("keep_existing", source, &["--O2", "--keep_signals", "main.square"]);
+    assert!(kept.success, "{}", kept.output);
+    assert_eq!(kept.reports("T3009"), 0, "{}", kept.output);
+}
+
+#[test]
+fn exceeded_budgets_name_their_biggest_contributors() {
+    let templates = "pragma circom 2.1.0;
+        template Square() { signal input in; signal output out; out <== in * in; }
+        template Chain(n) {
+            signal input in; signal output out; component s[n];
+            for (var i = 0; i < n; i++) { s[i] = Square(); s[i].in <== i == 0 ? in : s[i - 1].out; }
+            out <== s[n - 1].out;
+        }";
+    let main = "template Main() {
+            signal input in; signal output out; component small = Chain(2); component big = Chain(6);
+            small.in <== in; big.in <== small.out; out <== big.out;
+        }
+        component main = Main();";
+    let declared = compile("declared_budget", &format!("{}\n// budget: constraints = 5\n{}", templates, main));
+    assert!(!declared.success);
+    assert_eq!(declared.reports("T3006"), 1, "{}", declared.output);
+    assert!(declared.output.contains("has 21 constraints, over its budget of 5"), "{}", declared.output);
+    assert!(declared.output.contains("main.big (Chain(6)): 13 constraints"), "{}", declared.output);
+    // Only the instance of Chain(6) is over the budget of the template
+    let template = compile_with("template_budget", &format!("{}\n{}", templates, main), &["--template_budget", "Chain: constraints = 10"]);
+    assert!(!template.success);
+    assert_eq!(template.reports("T3006"), 1, "{}", template.output);
+    assert!(template.output.contains("main.big.s[0] (Square()): 1 constraints"), "{}", template.output);
+    let within = compile_with("within_budget", &format!("{}\n{}", templates, main), &["--template_budget", "Chain: constraints = 13"]);
+    assert!(within.success, "{}", within.output);
+    let circuit = compile_with("circuit_budget", &format!("{}\n{}", templates, main), &["--max_constraints", "4"]);
+    assert!(!circuit.success);
+    assert!(circuit.output.contains("over the limit of 4 of --max_constraints"), "{}", circuit.output);
+    assert!(circuit.output.contains("main.big (Chain(6))"), "{}", circuit.output);
+}
+
+#[test]
+fn constant_constraints_are_reported() {
+    let never = compile(
+        "never_holds",
+        "pragma circom 2.1.0;
+        template Check(n) { signal input in; signal output out; out <== in; if (n == 0) { 0 === 1; } }
+        template Main() { signal input in; signal output out; component c = Check(0); c.in <== in; out <== c.out; }
+        component main = Main();",
+    );
+    assert!(!never.success);
+    assert_eq!(never.reports("T3007"), 1, "{}", never.output);
+    assert!(never.output.contains("never_holds.circom\":2:91"), "{}", never.output);
+    assert!(never.output.contains("main.c = Check(n = 0)"), "{}", never.output);
+    let always = compile(
+        "always_holds",
+        "pragma circom 2.1.0;
+        template Main() { signal input a; signal output b; b <== a; a - a === 0; }
+        component main = Main();",
+    );
+    assert!(always.success, "{}", always.output);
+    assert_eq!(always.reports("T3007"), 0, "{}", always.output);
+    assert_eq!(always.reports("T3008"), 1, "{}", always.output);
+    assert!(always.output.contains("always_holds.circom\":2:69"), "{}", always.output);
+}
+
+#[test]
+fn outputs_not_determined_by_the_inputs_are_reported() {
+    let compilation = compile_with(
+        "uniqueness",
+        "pragma circom 2.1.0;
+        template Num2Bits(n) {
+            signal input in; signal output out[n]; var lc = 0; var e = 1;
+            for (var i = 0; i < n; i++) { out[i] <-- (in >> i) & 1; out[i] * (out[i] - 1) === 0; lc += out[i] * e; e = e + e; }
+            lc === in;
+        }
+        template Sqrt() { signal input s; signal output b; b <-- s; b * b === s; }
+        template Main() {
+            signal input in; signal output bits[4]; signal output root;
+            component n = Num2Bits(4); n.in <== in; bits <== n.out;
+            component q = Sqrt(); q.s <== in; root <== q.b;
+        }
+        component main = Main();",
+        &["--inspect"],
+    );
+    assert!(compilation.success, "{}", compilation.output);
+    // The bits are determined by the binary decomposition, while b and -b are both roots
+    assert_eq!(compilation.reports("CA07"), 1, "{}", compilation.output);
+    assert!(compilation.output.contains("In template \"Sqrt()\": Output signal b is not proven"), "{}", compilation.output);
+    assert!(!compilation.output.contains("In template \"Num2Bits(4)\""), "{}", compilation.output);
+    assert!(!compilation.output.contains("In template \"Main()\""), "{}", compilation.output);
+}
+
+#[test]
+fn equivalence_detects_a_changed_constant() {
+    let folder = folder("equivalence");
+    let circuits = [
+        ("base", "signal sq; sq <== in * in; out <== sq + 3;"),
+        ("reordered", "signal sq; sq <== in * in; out <== 3 + sq;"),
+        ("changed", "signal sq; sq <== in * in; out <== sq + 4;"),
+    ];
+    for (name, body) in circuits {
+        let file = folder.join(format!("{}.circom", name));
+        let source = format!("pragma circom 2.1.0;\ntemplate T() {{ signal input in; signal output out; {} }}\ncomponent main = T();", body);
+        std::fs::write(&file, source).unwrap();
+        let compiled = Command::new(env!("CARGO_BIN_EXE_circom")).arg(&file).args(["--r1cs", "--sym", "-o"]).arg(&folder).output().unwrap();
+        assert!(compiled.status.success(), "{}", String::from_utf8_lossy(&compiled.stderr));
+    }
+    let equivalence = |second: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_circom"))
+            .current_dir(&folder)
+            .args(["equivalence", "base.r1cs", &format!("{}.r1cs", second)])
+            .output()
+            .unwrap();
+        Compilation { success: output.status.success(), output: String::from_utf8_lossy(&output.stdout).to_string() }
+    };
+    let reordered = equivalence("reordered");
+    let changed = equivalence("changed");
+    std::fs::remove_dir_all(&folder).unwrap();
+    assert!(reordered.success, "{}", reordered.output);
+    assert!(reordered.output.contains("The constraints of both circuits are equal after normalization"), "{}", reordered.output);
+    assert!(!changed.success, "{}", changed.output);
+    assert!(changed.output.contains("base.r1cs gives main.out = 3 but changed.r1cs gives main.out = 4"), "{}", changed.output);
+    assert!(changed.output.contains("1 of the 2 constraints of changed.r1cs have no equal constraint"), "{}", changed.output);
+}
@@ -0,0 +1,108 @@
+// Checks that compiling with --low_memory, which keeps the constraints in temporary
+// files, writes the same files as compiling in memory. The circuits are simplified in
+// several clusters and rounds, which are read back from disk in that mode. That no
+// constraint stays in memory is checked by the tests of dag, the peak memory of the
+// compiler is only measured on demand, as it depends on the allocator and the system.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const CIRCUITS: [&str; 2] = ["clusters", "rounds"];
+const SIMPLIFICATIONS: [&str; 3] = ["--O0", "--O1", "--O2"];
+
+fn circuit(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("circuits").join(format!("{}.circom", name))
+}
+
+fn output_folder(name: &str) -> PathBuf {
+    let folder = std::env::temp_dir().join(format!("circom_memory_{}_{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&folder);
+    std::fs::create_dir_all(&folder).unwrap();
+    folder
+}
+
+fn read_files(folder: &Path, files: &mut BTreeMap<PathBuf, Vec<u8>>, root: &Path) {
+    for entry in std::fs::read_dir(folder).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            read_files(&path, files, root);
+        } else {
+            files.insert(path.strip_prefix(root).unwrap().to_path_buf(), std::fs::read(&path).unwrap());
+        }
+    }
+}
+
+fn compiled_files(name: &str, simplification: &str, run: &str, extra: &[&str]) -> BTreeMap<PathBuf, Vec<u8>> {
+    let output = output_folder(&format!("{}{}_{}", name, simplification, run));
+    let compiled = Command::new(env!("CARGO_BIN_EXE_circom"))
+        .arg(circuit(name))
+        .args(["--r1cs", "--sym", "--c", "--wasm", "--json", "--simplification_substitution"])
+        .arg(simplification)
+        .arg("-o")
+        .arg(&output)
+        .args(extra)
+        .output()
+        .unwrap()
+        .status
+        .success();
+    assert!(compiled, "{} {} does not compile", name, simplification);
+    let mut files = BTreeMap::new();
+    read_files(&output, &mut files, &output);
+    std::fs::remove_dir_all(&output).unwrap();
+    files
+}
+
+#[test]
+fn low_memory_writes_the_same_files() {
+    for name in CIRCUITS {
+        for simplification in SIMPLIFICATIONS {
+            let in_memory = compiled_files(name, simplification, "memory", &[]);
+            let on_disk = compiled_files(name, simplification, "disk", &["--low_memory"]);
+            assert_eq!(on_disk.keys().collect::<Vec<_>>(), in_memory.keys().collect::<Vec<_>>());
+            for (path, contents) in &on_disk {
+                assert!(
+                    contents == &in_memory[path],
+                    "{} differs between {} {} with and without --low_memory",
+                    path.display(),
+                    name,
+                    simplification
+                );
+            }
+        }
+    }
+}
+
+// Maximum resident set size of the children that have finished, which only grows, so
+// the compilation that is expected to use less memory runs first
+#[cfg(unix)]
+fn peak_memory_of_children() -> i64 {
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    assert_eq!(unsafe { libc::getrusage(libc::RUSAGE_CHILDREN, &mut usage) }, 0);
+    usage.ru_maxrss as i64
+}
+
+#[cfg(unix)]
+fn peak_memory(extra: &[&str]) -> i64 {
+    let output = output_folder(&format!("linear_{}", extra.len()));
+    let status = Command::new(env!("CARGO_BIN_EXE_circom"))
+        .arg(circuit("linear"))
+        .arg("--r1cs")
+        .arg("-o")
+        .arg(&output)
+        .args(extra)
+        .output()
+        .unwrap()
+        .status;
+    std::fs::remove_dir_all(&output).unwrap();
+    assert!(status.success());
+    peak_memory_of_children()
+}
+
+#[cfg(unix)]
+#[test]
+#[ignore = "slow, and the resident set size depends on the system"]
+fn low_memory_lowers_the_peak_memory() {
+    let on_disk = peak_memory(&["--low_memory"]);
+    let in_memory = peak_memory(&[]);
+    assert!(on_disk * 4 < in_memory * 3, "{} KB with --low_memory and {} KB without it", on_disk, in_memory);
+}
@@ -0,0 +1,85 @@
+// Checks that compiling the same circuit in different processes writes the same
+// files. The circuits are simplified in several clusters, with some of them big
+// enough to use the heuristic that counts the occurrences of the signals.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const CIRCUITS: [&str; 2] = ["clusters", "rounds"];
+const SIMPLIFICATIONS: [&str; 3] = ["--O0", "--O1", "--O2"];
+const RUNS: usize = 3;
+
+fn circuit(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("circuits").join(format!("{}.circom", name))
+}
+
+fn output_folder(name: &str) -> PathBuf {
+    let folder = std::env::temp_dir().join(format!("circom_reproducibility_{}_{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&folder);
+    std::fs::create_dir_all(&folder).unwrap();
+    folder
+}
+
+fn read_files(folder: &Path, files: &mut BTreeMap<PathBuf, Vec<u8>>, root: &Path) {
+    for entry in std::fs::read_dir(folder).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            read_files(&path, files, root);
+        } else {
+            files.insert(path.strip_prefix(root).unwrap().to_path_buf(), std::fs::read(&path).unwrap());
+        }
+    }
+}
+
+fn compile(name: &str, simplification: &str, output: &Path, extra: &[&str]) -> bool {
+    Command::new(env!("CARGO_BIN_EXE_circom"))
+        .arg(circuit(name))
+        .args(["--r1cs", "--sym", "--c", "--wasm", "--json", "--simplification_substitution"])
+        .arg(simplification)
+        .arg("-o")
+        .arg(output)
+        .args(extra)
+        .output()
+        .unwrap()
+        .status
+        .success()
+}
+
+#[test]
+fn output_is_the_same_in_every_run() {
+    for name in CIRCUITS {
+        for simplification in SIMPLIFICATIONS {
+            let mut outputs = Vec::new();
+            for run in 0..RUNS {
+                let output = output_folder(&format!("{}{}_{}", name, simplification, run));
+                assert!(compile(name, simplification, &output, &[]), "{} {} does not compile", name, simplification);
+                let mut files = BTreeMap::new();
+                read_files(&output, &mut files, &output);
+                std::fs::remove_dir_all(&output).unwrap();
+                outputs.push(files);
+            }
+            for files in &outputs[1..] {
+                assert_eq!(files.keys().collect::<Vec<_>>(), outputs[0].keys().collect::<Vec<_>>());
+                for (path, contents) in files {
+                    assert!(
+                        contents == &outputs[0][path],
+                        "{} differs between runs of {} {}",
+                        path.display(),
+                        name,
+                        simplification
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn verify_reproducible_accepts_the_circuits() {
+    for name in CIRCUITS {
+        let output = output_folder(&format!("{}_verify", name));
+        let verified = compile(name, "--O2", &output, &["--verify_reproducible"]);
+        std::fs::remove_dir_all(&output).unwrap();
+        assert!(verified, "{} is not verified as reproducible", name);
+    }
+}
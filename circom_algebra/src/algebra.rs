@@ -1,7 +1,8 @@
+use super::field_element::{prime_field, FieldElement, PrimeField};
 use super::modular_arithmetic;
 pub use super::modular_arithmetic::ArithmeticError;
 use num_bigint::BigInt;
-use num_traits::{ToPrimitive, Zero};
+use num_traits::ToPrimitive;
 use std::collections::{HashMap, HashSet, BTreeSet};
 use std::fmt::{Display, Formatter};
 use std::hash::Hash;
@@ -18,17 +19,17 @@ where
     },
     Linear {
         // Represents the expression: c1*s1 + .. + cn*sn + C
-        // where c1..cn are integers modulo a prime and
+        // where c1..cn are elements of the field and
         // s1..sn are signals. C is a constant value
-        coefficients: HashMap<C, BigInt>,
+        coefficients: HashMap<C, FieldElement>,
     },
     Quadratic {
         // Is a quadratic expression of the form:
         //              a*b + c
         // Where a,b and c are linear expression
-        a: HashMap<C, BigInt>,
-        b: HashMap<C, BigInt>,
-        c: HashMap<C, BigInt>,
+        a: HashMap<C, FieldElement>,
+        b: HashMap<C, FieldElement>,
+        c: HashMap<C, FieldElement>,
     },
     NonQuadratic,
 }
@@ -92,15 +93,15 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
     }
 
     // printing utils
-    fn string_from_coefficients(coefficients: &HashMap<C, BigInt>) -> String {
+    fn string_from_coefficients(coefficients: &HashMap<C, FieldElement>) -> String {
         let mut string_coefficients = "".to_string();
         for (signal, value) in coefficients {
             let component_string = if value.is_zero() {
                 "".to_string()
             } else if signal.eq(&ArithmeticExpression::constant_coefficient()) {
-                format!("{}+", value.to_str_radix(10))
+                format!("{}+", value)
             } else {
-                format!("{}*{}+", signal, value.to_str_radix(10))
+                format!("{}*{}+", signal, value)
             };
             string_coefficients.push_str(component_string.as_str());
         }
@@ -115,6 +116,7 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
         field: &BigInt,
     ) -> Option<Constraint<C>> {
         use ArithmeticExpression::*;
+        let field = prime_field(field);
         let mut a = HashMap::new();
         let mut b = HashMap::new();
         let mut c = HashMap::new();
@@ -131,16 +133,16 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
                 c = old_c;
             }
             Number { value } => {
-                c.insert(ArithmeticExpression::constant_coefficient(), value);
+                c.insert(ArithmeticExpression::constant_coefficient(), field.element(&value));
             }
             Signal { symbol } => {
-                c.insert(symbol, BigInt::from(1));
+                c.insert(symbol, FieldElement::one());
             }
             Linear { coefficients } => {
                 c = coefficients;
             }
         }
-        ArithmeticExpression::multiply_coefficients_by_constant(&BigInt::from(-1), &mut c, field);
+        ArithmeticExpression::negate_coefficients(&mut c, &field);
         Option::Some(Constraint::new(a, b, c))
     }
 
@@ -155,54 +157,50 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
     fn constant_coefficient() -> C {
         C::default()
     }
-    fn initialize_hashmap_for_expression(initial: &mut HashMap<C, BigInt>) {
+    fn initialize_hashmap_for_expression(initial: &mut HashMap<C, FieldElement>) {
         initial
             .entry(ArithmeticExpression::constant_coefficient())
-            .or_insert_with(|| BigInt::from(0));
+            .or_insert_with(FieldElement::zero);
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(initial));
     }
-    fn valid_hashmap_for_expression(h: &HashMap<C, BigInt>) -> bool {
+    fn valid_hashmap_for_expression(h: &HashMap<C, FieldElement>) -> bool {
         let cc = ArithmeticExpression::constant_coefficient();
         h.contains_key(&cc)
     }
-    fn initialize_symbol_in_coefficients(symbol: &C, coefficients: &mut HashMap<C, BigInt>) {
+    fn initialize_symbol_in_coefficients(symbol: &C, coefficients: &mut HashMap<C, FieldElement>) {
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients));
         if !coefficients.contains_key(symbol) {
-            coefficients.insert(symbol.clone(), BigInt::from(0));
+            coefficients.insert(symbol.clone(), FieldElement::zero());
         }
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients));
     }
     fn add_constant_to_coefficients(
         value: &BigInt,
-        coefficients: &mut HashMap<C, BigInt>,
-        field: &BigInt,
+        coefficients: &mut HashMap<C, FieldElement>,
+        field: &PrimeField,
     ) {
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients));
         let cc: C = ArithmeticExpression::constant_coefficient();
-        coefficients.insert(
-            cc.clone(),
-            modular_arithmetic::add(coefficients.get(&cc).unwrap(), value, field),
-        );
+        let constant = coefficients.get_mut(&cc).unwrap();
+        *constant = field.add(constant, &field.element(value));
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients));
     }
     fn add_symbol_to_coefficients(
         symbol: &C,
-        coefficient: &BigInt,
-        coefficients: &mut HashMap<C, BigInt>,
-        field: &BigInt,
+        coefficient: &FieldElement,
+        coefficients: &mut HashMap<C, FieldElement>,
+        field: &PrimeField,
     ) {
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients));
         ArithmeticExpression::initialize_symbol_in_coefficients(symbol, coefficients);
-        coefficients.insert(
-            symbol.clone(),
-            modular_arithmetic::add(coefficients.get(symbol).unwrap(), coefficient, field),
-        );
+        let value = coefficients.get_mut(symbol).unwrap();
+        *value = field.add(value, coefficient);
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients));
     }
     fn add_coefficients_to_coefficients(
-        coefficients_0: &HashMap<C, BigInt>,
-        coefficients_1: &mut HashMap<C, BigInt>,
-        field: &BigInt,
+        coefficients_0: &HashMap<C, FieldElement>,
+        coefficients_1: &mut HashMap<C, FieldElement>,
+        field: &PrimeField,
     ) {
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients_0));
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients_1));
@@ -218,27 +216,28 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients_1));
     }
     fn multiply_coefficients_by_constant(
-        constant: &BigInt,
-        coefficients: &mut HashMap<C, BigInt>,
-        field: &BigInt,
+        constant: &FieldElement,
+        coefficients: &mut HashMap<C, FieldElement>,
+        field: &PrimeField,
     ) {
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients));
         for value in coefficients.values_mut() {
-            *value = modular_arithmetic::mul(value, constant, field);
+            *value = field.mul(value, constant);
         }
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients));
     }
+    fn negate_coefficients(coefficients: &mut HashMap<C, FieldElement>, field: &PrimeField) {
+        for value in coefficients.values_mut() {
+            *value = field.neg(value);
+        }
+    }
     fn divide_coefficients_by_constant(
-        constant: &BigInt,
-        coefficients: &mut HashMap<C, BigInt>,
-        field: &BigInt,
+        constant: &FieldElement,
+        coefficients: &mut HashMap<C, FieldElement>,
+        field: &PrimeField,
     ) -> Result<(), ArithmeticError> {
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients));
-        let inverse_constant = modular_arithmetic::div(
-            &BigInt::from(1),
-            constant,
-            &field
-        )?;
+        let inverse_constant = field.inverse(constant).ok_or(ArithmeticError::DivisionByZero)?;
         ArithmeticExpression::multiply_coefficients_by_constant(&inverse_constant, coefficients, field);
         debug_assert!(ArithmeticExpression::valid_hashmap_for_expression(coefficients));
         Result::Ok(())
@@ -250,12 +249,13 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
         field: &BigInt,
     ) -> ArithmeticExpression<C> {
         use ArithmeticExpression::*;
+        let field = &*prime_field(field);
         match (left, right) {
             (NonQuadratic, _) | (_, NonQuadratic) | (Quadratic { .. }, Quadratic { .. }) => {
                 NonQuadratic
             }
             (Number { value: v_0 }, Number { value: v_1 }) => {
-                Number { value: modular_arithmetic::add(v_0, v_1, field) }
+                Number { value: modular_arithmetic::add(v_0, v_1, field.prime()) }
             }
             (Number { value }, Signal { symbol }) | (Signal { symbol }, Number { value }) => {
                 let mut coefficients = HashMap::new();
@@ -263,7 +263,7 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
                 ArithmeticExpression::add_constant_to_coefficients(value, &mut coefficients, field);
                 ArithmeticExpression::add_symbol_to_coefficients(
                     symbol,
-                    &BigInt::from(1),
+                    &FieldElement::one(),
                     &mut coefficients,
                     field,
                 );
@@ -290,13 +290,13 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
                 ArithmeticExpression::initialize_hashmap_for_expression(&mut coefficients);
                 ArithmeticExpression::add_symbol_to_coefficients(
                     symbol_0,
-                    &BigInt::from(1),
+                    &FieldElement::one(),
                     &mut coefficients,
                     field,
                 );
                 ArithmeticExpression::add_symbol_to_coefficients(
                     symbol_1,
-                    &BigInt::from(1),
+                    &FieldElement::one(),
                     &mut coefficients,
                     field,
                 );
@@ -307,7 +307,7 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
                 let mut n_coefficients = coefficients.clone();
                 ArithmeticExpression::add_symbol_to_coefficients(
                     symbol,
-                    &BigInt::from(1),
+                    &FieldElement::one(),
                     &mut n_coefficients,
                     field,
                 );
@@ -318,7 +318,7 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
                 let mut coefficients = c.clone();
                 ArithmeticExpression::add_symbol_to_coefficients(
                     symbol,
-                    &BigInt::from(1),
+                    &FieldElement::one(),
                     &mut coefficients,
                     field,
                 );
@@ -352,6 +352,7 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
         field: &BigInt,
     ) -> ArithmeticExpression<C> {
         use ArithmeticExpression::*;
+        let field = &*prime_field(field);
         match (left, right) {
             (NonQuadratic, _)
             | (_, NonQuadratic)
@@ -361,14 +362,14 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
             | (Quadratic { .. }, Signal { .. })
             | (Signal { .. }, Quadratic { .. }) => NonQuadratic,
             (Number { value: value_0 }, Number { value: value_1 }) => {
-                Number { value: modular_arithmetic::mul(value_0, value_1, field) }
+                Number { value: modular_arithmetic::mul(value_0, value_1, field.prime()) }
             }
             (Number { value }, Signal { symbol }) | (Signal { symbol }, Number { value }) => {
                 let mut coefficients = HashMap::new();
                 ArithmeticExpression::initialize_hashmap_for_expression(&mut coefficients);
                 ArithmeticExpression::add_symbol_to_coefficients(
                     symbol,
-                    value,
+                    &field.element(value),
                     &mut coefficients,
                     field,
                 );
@@ -378,7 +379,7 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
             | (Linear { coefficients }, Number { value }) => {
                 let mut n_coefficients = coefficients.clone();
                 ArithmeticExpression::multiply_coefficients_by_constant(
-                    value,
+                    &field.element(value),
                     &mut n_coefficients,
                     field,
                 );
@@ -386,11 +387,12 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
             }
             (Number { value }, Quadratic { a, b, c })
             | (Quadratic { a, b, c }, Number { value }) => {
+                let value = field.element(value);
                 let mut n_a = a.clone();
                 let n_b = b.clone();
                 let mut n_c = c.clone();
-                ArithmeticExpression::multiply_coefficients_by_constant(value, &mut n_a, field);
-                ArithmeticExpression::multiply_coefficients_by_constant(value, &mut n_c, field);
+                ArithmeticExpression::multiply_coefficients_by_constant(&value, &mut n_a, field);
+                ArithmeticExpression::multiply_coefficients_by_constant(&value, &mut n_c, field);
                 Quadratic { a: n_a, b: n_b, c: n_c }
             }
             (Signal { symbol: symbol_0 }, Signal { symbol: symbol_1 }) => {
@@ -402,13 +404,13 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
                 ArithmeticExpression::initialize_hashmap_for_expression(&mut c);
                 ArithmeticExpression::add_symbol_to_coefficients(
                     symbol_0,
-                    &BigInt::from(1),
+                    &FieldElement::one(),
                     &mut a,
                     field,
                 );
                 ArithmeticExpression::add_symbol_to_coefficients(
                     symbol_1,
-                    &BigInt::from(1),
+                    &FieldElement::one(),
                     &mut b,
                     field,
                 );
@@ -423,7 +425,7 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
                 ArithmeticExpression::initialize_hashmap_for_expression(&mut c);
                 ArithmeticExpression::add_symbol_to_coefficients(
                     symbol,
-                    &BigInt::from(1),
+                    &FieldElement::one(),
                     &mut b,
                     field,
                 );
@@ -455,9 +457,10 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
         field: &BigInt,
     ) -> Result<ArithmeticExpression<C>, ArithmeticError> {
         use ArithmeticExpression::*;
+        let field = &*prime_field(field);
         match (left, right) {
             (Number { value: value_0 }, Number { value: value_1 }) => {
-                let value = modular_arithmetic::div(value_0, value_1, field)?;
+                let value = modular_arithmetic::div(value_0, value_1, field.prime())?;
                 Result::Ok(Number { value })
             }
             (Signal { symbol }, Number { value }) => {
@@ -465,12 +468,12 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
                 ArithmeticExpression::initialize_hashmap_for_expression(&mut coefficients);
                 ArithmeticExpression::add_symbol_to_coefficients(
                     symbol,
-                    &BigInt::from(1),
+                    &FieldElement::one(),
                     &mut coefficients,
                     field,
                 );
                 ArithmeticExpression::divide_coefficients_by_constant(
-                    value,
+                    &field.element(value),
                     &mut coefficients,
                     field,
                 )?;
@@ -479,18 +482,19 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
             (Linear { coefficients }, Number { value }) => {
                 let mut coefficients = coefficients.clone();
                 ArithmeticExpression::divide_coefficients_by_constant(
-                    value,
+                    &field.element(value),
                     &mut coefficients,
                     field,
                 )?;
                 Result::Ok(Linear { coefficients })
             }
             (Quadratic { a, b, c }, Number { value }) => {
+                let value = field.element(value);
                 let mut a = a.clone();
                 let b = b.clone();
                 let mut c = c.clone();
-                ArithmeticExpression::divide_coefficients_by_constant(value, &mut a, field)?;
-                ArithmeticExpression::divide_coefficients_by_constant(value, &mut c, field)?;
+                ArithmeticExpression::divide_coefficients_by_constant(&value, &mut a, field)?;
+                ArithmeticExpression::divide_coefficients_by_constant(&value, &mut c, field)?;
                 Result::Ok(Quadratic { a, b, c })
             }
             _ => Result::Ok(NonQuadratic),
@@ -761,6 +765,7 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
         field: &BigInt,
     ) {
         use ArithmeticExpression::*;
+        let field = &*prime_field(field);
         match expr {
             Linear { coefficients } => {
                raw_substitution(coefficients, substitution, field);
@@ -801,17 +806,17 @@ impl<C: Default + Clone + Display + Hash + Eq> ArithmeticExpression<C> {
         matches!(self, ArithmeticExpression::Linear { .. })
     }
 
-    pub fn hashmap_into_arith(mut map: HashMap<C, BigInt>) -> ArithmeticExpression<C> {
+    pub fn hashmap_into_arith(mut map: HashMap<C, FieldElement>) -> ArithmeticExpression<C> {
         let c: C = ArithmeticExpression::constant_coefficient();
         let expr = if HashMap::len(&map) == 1 && HashMap::contains_key(&map, &c) {
-            let value = HashMap::remove(&mut map, &c).unwrap();
+            let value = HashMap::remove(&mut map, &c).unwrap().to_bigint();
             ArithmeticExpression::Number { value }
         } else if HashMap::len(&map) == 1 {
             let mut values: Vec<_> = map.values().cloned().collect();
             let mut symbols: Vec<_> = map.keys().cloned().collect();
             let symbol = symbols.pop().unwrap();
             let value = values.pop().unwrap();
-            if value == BigInt::from(1) {
+            if value.is_one() {
                 ArithmeticExpression::Signal { symbol }
             } else {
                 ArithmeticExpression::initialize_hashmap_for_expression(&mut map);
@@ -837,21 +842,22 @@ where
     C: Hash + Eq,
 {
     pub(crate) from: C,
-    pub(crate) to: HashMap<C, BigInt>,
+    pub(crate) to: HashMap<C, FieldElement>,
 }
 impl<C: Default + Clone + Display + Hash + Eq> Substitution<C> {
     // Substitution public utils
+    // The numbers come from operations in the field, so they are already reduced
     pub fn new(from: C, to: ArithmeticExpression<C>) -> Option<Substitution<C>> {
         use ArithmeticExpression::*;
         match to {
             Number { value } => {
                 let mut to = HashMap::new();
-                to.insert(ArithmeticExpression::constant_coefficient(), value);
+                to.insert(ArithmeticExpression::constant_coefficient(), FieldElement::from_reduced(value));
                 Option::Some(Substitution { from, to })
             }
             Signal { symbol } => {
                 let mut to = HashMap::new();
-                to.insert(symbol, BigInt::from(1));
+                to.insert(symbol, FieldElement::one());
                 Option::Some(Substitution { from, to })
             }
             Linear { coefficients: to } if !to.contains_key(&from) => {
@@ -888,7 +894,7 @@ impl<C: Default + Clone + Display + Hash + Eq> Substitution<C> {
     }
 
     pub fn apply_substitution(src: &mut Substitution<C>, change: &Substitution<C>, field: &BigInt) {
-        raw_substitution(&mut src.to, change, field);
+        raw_substitution(&mut src.to, change, &prime_field(field));
     }
 
     pub fn substitution_into_constraint(
@@ -898,7 +904,7 @@ impl<C: Default + Clone + Display + Hash + Eq> Substitution<C> {
         let symbol = substitution.from;
         let mut coefficients = substitution.to;
         ArithmeticExpression::initialize_hashmap_for_expression(&mut coefficients);
-        coefficients.insert(symbol, BigInt::from(-1 % field));
+        coefficients.insert(symbol, FieldElement::new(&BigInt::from(-1), field));
         let arith = ArithmeticExpression::Linear { coefficients };
         ArithmeticExpression::transform_expression_to_constraint_form(arith, field).unwrap()
     }
@@ -907,14 +913,14 @@ impl<C: Default + Clone + Display + Hash + Eq> Substitution<C> {
         let c: C = ArithmeticExpression::constant_coefficient();
         let mut to = substitution.to;
         let right = if HashMap::len(&to) == 1 && HashMap::contains_key(&to, &c) {
-            let value = HashMap::remove(&mut to, &c).unwrap();
+            let value = HashMap::remove(&mut to, &c).unwrap().to_bigint();
             ArithmeticExpression::Number { value }
         } else if HashMap::len(&to) == 1 {
             let mut values: Vec<_> = to.values().cloned().collect();
             let mut symbols: Vec<_> = to.keys().cloned().collect();
             let symbol = symbols.pop().unwrap();
             let value = values.pop().unwrap();
-            if value == BigInt::from(1) {
+            if value.is_one() {
                 ArithmeticExpression::Signal { symbol }
             } else {
                 ArithmeticExpression::initialize_hashmap_for_expression(&mut to);
@@ -940,7 +946,7 @@ impl<C: Default + Clone + Display + Hash + Eq> Substitution<C> {
         &self.from
     }
 
-    pub fn to(&self) -> &HashMap<C, BigInt> {
+    pub fn to(&self) -> &HashMap<C, FieldElement> {
         &self.to
     }
 
@@ -1003,13 +1009,13 @@ pub struct Constraint<C>
 where
     C: Hash + Eq,
 {
-    pub(crate) a: HashMap<C, BigInt>,
-    pub(crate) b: HashMap<C, BigInt>,
-    pub(crate) c: HashMap<C, BigInt>,
+    pub(crate) a: HashMap<C, FieldElement>,
+    pub(crate) b: HashMap<C, FieldElement>,
+    pub(crate) c: HashMap<C, FieldElement>,
 }
 
 impl<C: Default + Clone + Display + Hash + Eq> Constraint<C> {
-    fn new(a: HashMap<C, BigInt>, b: HashMap<C, BigInt>, c: HashMap<C, BigInt>) -> Constraint<C> {
+    pub fn new(a: HashMap<C, FieldElement>, b: HashMap<C, FieldElement>, c: HashMap<C, FieldElement>) -> Constraint<C> {
         Constraint { a, b, c }
     }
 
@@ -1060,7 +1066,7 @@ impl<C: Default + Clone + Display + Hash + Eq> Constraint<C> {
     ) -> Substitution<C> {
         debug_assert!(Constraint::is_linear(&constraint));
         debug_assert!(constraint.c.contains_key(signal));
-        let raw_expression = Constraint::clear_signal(constraint.c, &signal, field);
+        let raw_expression = Constraint::clear_signal(constraint.c, &signal, &prime_field(field));
         Substitution { from: signal.clone(), to: raw_expression }
     }
 
@@ -1068,10 +1074,10 @@ impl<C: Default + Clone + Display + Hash + Eq> Constraint<C> {
         constraint: Constraint<C>,
         signal: &C,
         field: &BigInt,
-    ) -> (BigInt, Substitution<C>) {
+    ) -> (FieldElement, Substitution<C>) {
         debug_assert!(Constraint::is_linear(&constraint));
         debug_assert!(constraint.c.contains_key(signal));
-        let (coefficient, raw_expression) = Constraint::clear_signal_not_normalized(constraint.c, &signal, field);
+        let (coefficient, raw_expression) = Constraint::clear_signal_not_normalized(constraint.c, &signal, &prime_field(field));
         (coefficient, Substitution {from: signal.clone(), to: raw_expression})
     }
 
@@ -1106,13 +1112,13 @@ impl<C: Default + Clone + Display + Hash + Eq> Constraint<C> {
     }
 
     fn clear_signal(
-        mut symbols: HashMap<C, BigInt>,
+        mut symbols: HashMap<C, FieldElement>,
         key: &C,
-        field: &BigInt,
-    ) -> HashMap<C, BigInt> {
+        field: &PrimeField,
+    ) -> HashMap<C, FieldElement> {
         let key_value = symbols.remove(&key).unwrap();
         assert!(!key_value.is_zero());
-        let value_to_the_right = modular_arithmetic::mul(&key_value, &BigInt::from(-1), field);
+        let value_to_the_right = field.neg(&key_value);
         ArithmeticExpression::initialize_hashmap_for_expression(&mut symbols);
         let arithmetic_result = ArithmeticExpression::divide_coefficients_by_constant(
             &value_to_the_right,
@@ -1124,13 +1130,13 @@ impl<C: Default + Clone + Display + Hash + Eq> Constraint<C> {
     }
 
     fn clear_signal_not_normalized(
-        mut symbols: HashMap<C, BigInt>,
+        mut symbols: HashMap<C, FieldElement>,
         key: &C,
-        field: &BigInt,
-    ) -> (BigInt, HashMap<C, BigInt>) {
+        field: &PrimeField,
+    ) -> (FieldElement, HashMap<C, FieldElement>) {
         let key_value = symbols.remove(&key).unwrap();
         assert!(!key_value.is_zero());
-        let value_to_the_right = modular_arithmetic::mul(&key_value, &BigInt::from(-1), field);
+        let value_to_the_right = field.neg(&key_value);
         ArithmeticExpression::initialize_hashmap_for_expression(&mut symbols);
         (value_to_the_right, symbols)
     }
@@ -1140,6 +1146,7 @@ impl<C: Default + Clone + Display + Hash + Eq> Constraint<C> {
         substitution: &Substitution<C>,
         field: &BigInt,
     ) {
+        let field = &*prime_field(field);
         raw_substitution(&mut constraint.a, substitution, field);
         raw_substitution(&mut constraint.b, substitution, field);
         raw_substitution(&mut constraint.c, substitution, field);
@@ -1153,7 +1160,7 @@ impl<C: Default + Clone + Display + Hash + Eq> Constraint<C> {
     }
 
     pub fn fix_constraint(constraint: &mut Constraint<C>, field: &BigInt) {
-        fix_raw_constraint(&mut constraint.a, &mut constraint.b, &mut constraint.c, field);
+        fix_raw_constraint(&mut constraint.a, &mut constraint.b, &mut constraint.c, &prime_field(field));
     }
 
     pub fn is_empty(&self) -> bool {
@@ -1166,19 +1173,19 @@ impl<C: Default + Clone + Display + Hash + Eq> Constraint<C> {
             || self.a.contains_key(&Constraint::constant_coefficient())
     }
 
-    pub fn a(&self) -> &HashMap<C, BigInt> {
+    pub fn a(&self) -> &HashMap<C, FieldElement> {
         &self.a
     }
-    pub fn b(&self) -> &HashMap<C, BigInt> {
+    pub fn b(&self) -> &HashMap<C, FieldElement> {
         &self.b
     }
 
-    pub fn c(&self) -> &HashMap<C, BigInt> {
+    pub fn c(&self) -> &HashMap<C, FieldElement> {
         &self.c
     }
 
     pub fn is_equality(&self, field: &BigInt) -> bool {
-        signal_equals_signal(&self.a, &self.b, &self.c, field)
+        signal_equals_signal(&self.a, &self.b, &self.c, &prime_field(field))
     }
 
     pub fn is_constant_equality(&self) -> bool {
@@ -1229,12 +1236,12 @@ impl Constraint<usize> {
 }
 
 // model utils
-type RawExpr<C> = HashMap<C, BigInt>;
+type RawExpr<C> = HashMap<C, FieldElement>;
 
 fn apply_vectored_correspondence(
-    symbols: &HashMap<usize, BigInt>,
+    symbols: &HashMap<usize, FieldElement>,
     map: &Vec<usize>,
-) -> HashMap<usize, BigInt> {
+) -> HashMap<usize, FieldElement> {
     let mut mapped = HashMap::new();
     for (s, v) in symbols {
         mapped.insert(map[*s], v.clone());
@@ -1243,9 +1250,9 @@ fn apply_vectored_correspondence(
 }
 
 fn apply_raw_correspondence<C, K>(
-    symbols: &HashMap<C, BigInt>,
+    symbols: &HashMap<C, FieldElement>,
     map: &HashMap<C, K>,
-) -> HashMap<K, BigInt>
+) -> HashMap<K, FieldElement>
 where
     K: Default + Clone + Display + Hash + Eq,
     C: Default + Clone + Display + Hash + Eq,
@@ -1263,7 +1270,7 @@ where
     coefficients_as_correspondence
 }
 
-fn apply_raw_offset(h: &HashMap<usize, BigInt>, offset: usize) -> HashMap<usize, BigInt> {
+fn apply_raw_offset(h: &HashMap<usize, FieldElement>, offset: usize) -> HashMap<usize, FieldElement> {
     let mut new = HashMap::new();
     let constant: usize = Constraint::constant_coefficient();
     for (k, v) in h {
@@ -1277,23 +1284,23 @@ fn apply_raw_offset(h: &HashMap<usize, BigInt>, offset: usize) -> HashMap<usize,
 }
 
 fn raw_substitution<C>(
-    change: &mut HashMap<C, BigInt>,
+    change: &mut HashMap<C, FieldElement>,
     substitution: &Substitution<C>,
-    field: &BigInt,
+    field: &PrimeField,
 ) where
     C: Default + Clone + Display + Hash + Eq,
 {
     ArithmeticExpression::initialize_hashmap_for_expression(change);
     if let Option::Some(val) = change.remove(&substitution.from) {
-        let mut coefficients = substitution.to.clone();
-        ArithmeticExpression::initialize_hashmap_for_expression(&mut coefficients);
-        ArithmeticExpression::multiply_coefficients_by_constant(&val, &mut coefficients, field);
-        ArithmeticExpression::add_coefficients_to_coefficients(&coefficients, change, field);
+        for (symbol, coefficient) in &substitution.to {
+            let product = field.mul(&val, coefficient);
+            ArithmeticExpression::add_symbol_to_coefficients(symbol, &product, change, field);
+        }
     }
     //*change = remove_zero_value_coefficients(std::mem::take(change));
 }
 
-fn remove_zero_value_coefficients<C>(raw_expression: HashMap<C, BigInt>) -> HashMap<C, BigInt>
+fn remove_zero_value_coefficients<C>(raw_expression: HashMap<C, FieldElement>) -> HashMap<C, FieldElement>
 where
     C: Default + Clone + Display + Hash + Eq,
 {
@@ -1306,7 +1313,7 @@ where
     clean_raw
 }
 
-fn fix_raw_constraint<C>(a: &mut RawExpr<C>, b: &mut RawExpr<C>, c: &mut RawExpr<C>, field: &BigInt)
+fn fix_raw_constraint<C>(a: &mut RawExpr<C>, b: &mut RawExpr<C>, c: &mut RawExpr<C>, field: &PrimeField)
 where
     C: Default + Clone + Display + Hash + Eq,
 {
@@ -1327,7 +1334,7 @@ fn constant_linear_linear_reduction<C>(
     a: &mut RawExpr<C>,
     b: &mut RawExpr<C>,
     c: &mut RawExpr<C>,
-    field: &BigInt,
+    field: &PrimeField,
 ) where
     C: Default + Clone + Display + Hash + Eq,
 {
@@ -1335,15 +1342,14 @@ fn constant_linear_linear_reduction<C>(
     ArithmeticExpression::initialize_hashmap_for_expression(c);
     ArithmeticExpression::initialize_hashmap_for_expression(b);
     let constant = HashMap::remove(a, &cq).unwrap();
-    ArithmeticExpression::multiply_coefficients_by_constant(&constant, b, field);
-    ArithmeticExpression::multiply_coefficients_by_constant(&BigInt::from(-1), b, field);
+    ArithmeticExpression::multiply_coefficients_by_constant(&field.neg(&constant), b, field);
     ArithmeticExpression::add_coefficients_to_coefficients(b, c, field);
     *c = remove_zero_value_coefficients(std::mem::take(c));
     HashMap::clear(a);
     HashMap::clear(b);
 }
 
-fn signal_equals_signal<C>(a: &RawExpr<C>, b: &RawExpr<C>, c: &RawExpr<C>, field: &BigInt) -> bool
+fn signal_equals_signal<C>(a: &RawExpr<C>, b: &RawExpr<C>, c: &RawExpr<C>, field: &PrimeField) -> bool
 where
     C: Default + Clone + Display + Hash + Eq,
 {
@@ -1352,7 +1358,7 @@ where
         let signals: Vec<_> = c.keys().cloned().collect();
         let c0 = HashMap::get(c, &signals[0]).unwrap();
         let c1 = HashMap::get(c, &signals[1]).unwrap();
-        let c1_p = modular_arithmetic::mul(&BigInt::from(-1), c1, field);
+        let c1_p = field.neg(c1);
         c1_p == *c0
     } else {
         false
@@ -1379,18 +1385,53 @@ where
     HashMap::contains_key(expr, &cq) && HashMap::len(expr) == 1
 }
 
-pub fn normalize(c: Constraint<usize>, _field: &BigInt) -> Constraint<usize> {
-    use std::collections::LinkedList;
-    let _a: LinkedList<_> = c.a.iter().clone().collect();
-    let _b: LinkedList<_> = c.b.iter().clone().collect();
-    let _c: LinkedList<_> = c.c.iter().clone().collect();
-    todo!()
+// Multiplies the expression by the inverse of the coefficient of its smallest
+// signal, which is returned
+fn make_monic(expr: &mut RawExpr<usize>, field: &PrimeField) -> FieldElement {
+    let first = match expr.keys().min() {
+        Some(signal) => expr[signal].clone(),
+        None => return FieldElement::one(),
+    };
+    let inverse = field.inverse(&first).unwrap();
+    for value in expr.values_mut() {
+        *value = field.mul(value, &inverse);
+    }
+    first
+}
+
+fn sorted_expression(expr: &RawExpr<usize>) -> Vec<(usize, FieldElement)> {
+    let mut sorted: Vec<_> = expr.iter().map(|(s, v)| (*s, v.clone())).collect();
+    sorted.sort();
+    sorted
+}
+
+// Canonical form of a constraint, so that two constraints that only differ in the
+// order of the products, in constant factors or in the products by constants are
+// equal: the products by a constant are moved to C, the smallest signal of A, B
+// and of C in linear constraints has coefficient 1, and A is smaller than B.
+pub fn normalize(c: Constraint<usize>, field: &BigInt) -> Constraint<usize> {
+    let field = &*prime_field(field);
+    let (mut a, mut b, mut c) = (c.a, c.b, c.c);
+    fix_raw_constraint(&mut a, &mut b, &mut c, field);
+    if a.is_empty() {
+        make_monic(&mut c, field);
+    } else {
+        let factor = field.mul(&make_monic(&mut a, field), &make_monic(&mut b, field));
+        let inverse = field.inverse(&factor).unwrap();
+        for value in c.values_mut() {
+            *value = field.mul(value, &inverse);
+        }
+        if sorted_expression(&a) > sorted_expression(&b) {
+            std::mem::swap(&mut a, &mut b);
+        }
+    }
+    Constraint::new(a, b, c)
 }
 
 #[cfg(test)]
 mod test {
     use crate::algebra::{ArithmeticExpression, Constraint, Substitution};
-    use crate::modular_arithmetic;
+    use crate::field_element::FieldElement;
     use num_bigint::BigInt;
     use std::collections::HashMap;
     const FIELD: &str = "257";
@@ -1405,8 +1446,8 @@ mod test {
         let a = HashMap::new();
         let b = HashMap::new();
         let mut c = HashMap::new();
-        c.insert(C::constant_coefficient(), BigInt::from(12));
-        c.insert(x, BigInt::from(3));
+        c.insert(C::constant_coefficient(), FieldElement::from(12));
+        c.insert(x, FieldElement::from(3));
         let constraint = C::new(a, b, c);
         let constraint_with_offset = constraint.apply_offset(offset);
         assert!(constraint_with_offset.a.is_empty());
@@ -1416,7 +1457,7 @@ mod test {
         let new_x = x + offset;
         assert!(constraint_with_offset.c.contains_key(&new_x));
         let value = constraint_with_offset.c.get(&new_x).unwrap();
-        assert!(value.eq(&BigInt::from(3)));
+        assert!(value.eq(&FieldElement::from(3)));
     }
 
     #[test]
@@ -1424,11 +1465,11 @@ mod test {
         let field = BigInt::parse_bytes(FIELD.as_bytes(), 10)
             .expect("generating the big int was not possible");
         let constant = C::constant_coefficient();
-        let c_coefficient = BigInt::from(3);
+        let c_coefficient = FieldElement::from(3);
         let x = 1;
-        let x_coefficient = BigInt::from(1);
+        let x_coefficient = FieldElement::from(1);
         let y = 2;
-        let y_coefficient = BigInt::from(1);
+        let y_coefficient = FieldElement::from(1);
         let a = HashMap::new();
         let b = HashMap::new();
         let mut c = HashMap::new();
@@ -1440,11 +1481,11 @@ mod test {
         // sub: x = -y -3  ==> x = 256*y + 254
         let sub = C::clear_signal_from_linear(constraint, &x, &field);
         assert_eq!(sub.from, x);
-        let y_new_coefficient = modular_arithmetic::sub(&field, &BigInt::from(1), &field);
+        let y_new_coefficient = FieldElement::from(256);
         let sub_value = sub.to.get(&y).unwrap();
         assert_eq!(*sub_value, y_new_coefficient);
 
-        let constant_new_coefficient = BigInt::from(254);
+        let constant_new_coefficient = FieldElement::from(254);
         let sub_value = sub.to.get(&constant).unwrap();
         assert_eq!(*sub_value, constant_new_coefficient);
     }
@@ -1459,9 +1500,9 @@ mod test {
         let constant = C::constant_coefficient();
 
         // constraint: x + y + 4 = 0
-        let x_c = BigInt::from(1);
-        let y_c = BigInt::from(1);
-        let constant_c = BigInt::from(4);
+        let x_c = FieldElement::from(1);
+        let y_c = FieldElement::from(1);
+        let constant_c = FieldElement::from(4);
         let a = HashMap::new();
         let b = HashMap::new();
         let mut c = HashMap::new();
@@ -1471,8 +1512,8 @@ mod test {
         let mut constraint = C::new(a, b, c);
 
         // substitution: x = 2y + 3
-        let y_c = BigInt::from(2);
-        let constant_c = BigInt::from(3);
+        let y_c = FieldElement::from(2);
+        let constant_c = FieldElement::from(3);
         let from = x;
         let mut to_raw = HashMap::new();
         to_raw.insert(y, y_c);
@@ -1481,8 +1522,8 @@ mod test {
         let substitution = S::new(from, to).unwrap();
 
         // result: 3y + 7 = 0
-        let expected_y_c = BigInt::from(3);
-        let expected_constant_c = BigInt::from(7);
+        let expected_y_c = FieldElement::from(3);
+        let expected_constant_c = FieldElement::from(7);
         C::apply_substitution(&mut constraint, &substitution, &field);
         let y_c = constraint.c.get(&y).unwrap();
         let constant_c = constraint.c.get(&constant).unwrap();
@@ -1491,4 +1532,33 @@ mod test {
         assert_eq!(*y_c, expected_y_c);
         assert_eq!(*constant_c, expected_constant_c);
     }
+
+    #[test]
+    fn algebra_normalize_equivalent_constraints() {
+        let field = BigInt::parse_bytes(FIELD.as_bytes(), 10)
+            .expect("generating the big int was not possible");
+        let constant = C::constant_coefficient();
+        let (x, y, z) = (1, 2, 3);
+        let expr = |pairs: &[(usize, i64)]| -> HashMap<usize, FieldElement> {
+            pairs.iter().map(|(s, v)| (*s, FieldElement::new(&BigInt::from(*v), &field))).collect()
+        };
+
+        // (2x + 2) * (3y) = 6z and (y) * (x + 1) = z
+        let first = C::new(expr(&[(x, 2), (constant, 2)]), expr(&[(y, 3)]), expr(&[(z, 6)]));
+        let second = C::new(expr(&[(y, 1)]), expr(&[(x, 1), (constant, 1)]), expr(&[(z, 1)]));
+        let first = super::normalize(first, &field);
+        let second = super::normalize(second, &field);
+        assert_eq!(first.a, second.a);
+        assert_eq!(first.b, second.b);
+        assert_eq!(first.c, second.c);
+
+        // (4) * (x + 1) = 2z - 2 and 0 = 2x - z + 3, which is the same constraint scaled
+        let first = C::new(expr(&[(constant, 4)]), expr(&[(x, 1), (constant, 1)]), expr(&[(z, 2), (constant, -2)]));
+        let second = C::new(HashMap::new(), HashMap::new(), expr(&[(x, 2), (z, -1), (constant, 3)]));
+        let first = super::normalize(first, &field);
+        let second = super::normalize(second, &field);
+        assert!(first.a.is_empty() && first.b.is_empty());
+        assert_eq!(first.c, second.c);
+        assert_eq!(first.c.get(&constant), Some(&FieldElement::from(1)));
+    }
 }
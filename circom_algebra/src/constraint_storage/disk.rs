@@ -0,0 +1,123 @@
+use super::{CompressedConstraint, CompressedExpr};
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Result, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+static NEXT_FILE: AtomicUsize = AtomicUsize::new(0);
+
+struct Access {
+    writer: BufWriter<File>,
+    reader: BufReader<File>,
+    // position of the reader, to avoid seeking when the constraints are read in order
+    reading_at: u64,
+    end: u64,
+    flushed: bool,
+}
+
+// Temporary file where the compressed constraints are written one after the other.
+// Every number is encoded as a LEB128 varint, so the ids of the coefficients and the
+// signals usually take from one to four bytes. The file is removed when it is dropped.
+pub struct ConstraintFile {
+    path: PathBuf,
+    access: Mutex<Access>,
+}
+
+impl ConstraintFile {
+    pub fn new() -> Result<ConstraintFile> {
+        let name = format!("circom_constraints_{}_{}.bin", std::process::id(), NEXT_FILE.fetch_add(1, Ordering::Relaxed));
+        let path = std::env::temp_dir().join(name);
+        // The file is opened twice, as the reader and the writer need their own position
+        let writer = BufWriter::new(OpenOptions::new().append(true).create_new(true).open(&path)?);
+        let reader = BufReader::new(File::open(&path)?);
+        let access = Access { writer, reader, reading_at: 0, end: 0, flushed: true };
+        Result::Ok(ConstraintFile { path, access: Mutex::new(access) })
+    }
+
+    // Appends the constraint and returns its position in the file
+    pub fn push(&self, constraint: &CompressedConstraint) -> Result<u64> {
+        let mut bytes = Vec::new();
+        let (a, b, c) = constraint;
+        for expr in [a, b, c].iter() {
+            encode_expr(expr, &mut bytes);
+        }
+        let mut access = self.access.lock().unwrap();
+        let position = access.end;
+        access.writer.write_all(&bytes)?;
+        access.end += bytes.len() as u64;
+        access.flushed = false;
+        Result::Ok(position)
+    }
+
+    // Number of bytes written to the file
+    #[cfg(test)]
+    pub fn size(&self) -> u64 {
+        self.access.lock().unwrap().end
+    }
+
+    pub fn read(&self, position: u64) -> Result<CompressedConstraint> {
+        let mut access = self.access.lock().unwrap();
+        if !access.flushed {
+            access.writer.flush()?;
+            access.flushed = true;
+        }
+        if access.reading_at != position {
+            access.reader.seek(SeekFrom::Start(position))?;
+        }
+        let mut read = 0;
+        let a = decode_expr(&mut access.reader, &mut read)?;
+        let b = decode_expr(&mut access.reader, &mut read)?;
+        let c = decode_expr(&mut access.reader, &mut read)?;
+        access.reading_at = position + read;
+        Result::Ok((a, b, c))
+    }
+}
+
+impl Drop for ConstraintFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn encode_number(mut value: usize, bytes: &mut Vec<u8>) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn decode_number(reader: &mut impl Read, read: &mut u64) -> Result<usize> {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let mut byte = [0u8];
+        reader.read_exact(&mut byte)?;
+        *read += 1;
+        value |= ((byte[0] & 0x7f) as usize) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Result::Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+fn encode_expr(expr: &CompressedExpr, bytes: &mut Vec<u8>) {
+    encode_number(expr.len(), bytes);
+    for (coefficient, signal) in expr {
+        encode_number(*coefficient, bytes);
+        encode_number(*signal, bytes);
+    }
+}
+
+fn decode_expr(reader: &mut impl Read, read: &mut u64) -> Result<CompressedExpr> {
+    let length = decode_number(reader, read)?;
+    let mut expr = Vec::with_capacity(length);
+    for _ in 0..length {
+        let coefficient = decode_number(reader, read)?;
+        let signal = decode_number(reader, read)?;
+        expr.push((coefficient, signal));
+    }
+    Result::Ok(expr)
+}
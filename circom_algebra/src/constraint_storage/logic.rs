@@ -1,11 +1,10 @@
 use super::*;
 use std::collections::HashMap;
 
-pub fn code_expression(expr: HashMap<S, BigInt>, tracker: &mut FieldTracker) -> CompressedExpr {
+pub fn code_expression(expr: HashMap<S, FieldElement>, tracker: &mut FieldTracker) -> CompressedExpr {
     let mut c_expr = CompressedExpr::new();
     for (var, coeff) in expr {
-        let raw_coeff = coeff.to_signed_bytes_le();
-        let coeff_id = tracker.insert(raw_coeff);
+        let coeff_id = tracker.insert(coeff);
         c_expr.push((coeff_id, var));
     }
     c_expr
@@ -18,12 +17,11 @@ pub fn code_constraint(constraint: C, tracker: &mut FieldTracker) -> CompressedC
     (a, b, c)
 }
 
-pub fn decode_expr(c_expr: &CompressedExpr, tracker: &FieldTracker) -> HashMap<S, BigInt> {
+pub fn decode_expr(c_expr: &CompressedExpr, tracker: &FieldTracker) -> HashMap<S, FieldElement> {
     let mut decoded_expr = HashMap::new();
     for (coeff_id, var) in c_expr {
-        let raw_coeff = tracker.get_constant(*coeff_id).unwrap();
-        let coeff = BigInt::from_signed_bytes_le(raw_coeff);
-        decoded_expr.insert(*var, coeff);
+        let coeff = tracker.get_constant(*coeff_id).unwrap();
+        decoded_expr.insert(*var, coeff.clone());
     }
     decoded_expr
 }
@@ -1,69 +1,265 @@
 use crate::algebra::Constraint;
-use crate::num_bigint::BigInt;
+use crate::field_element::FieldElement;
 use constant_tracking::{ConstantTracker, CID};
+use disk::ConstraintFile;
 use std::collections::LinkedList;
 
+mod disk;
 mod logic;
 
-type RawField = Vec<u8>;
-type FieldTracker = ConstantTracker<RawField>;
+type FieldTracker = ConstantTracker<FieldElement>;
 type S = usize;
 type C = Constraint<usize>;
 
 type CompressedExpr = Vec<(CID, S)>;
 type CompressedConstraint = (CompressedExpr, CompressedExpr, CompressedExpr); // A, B, C
 
+// The compressed constraints are kept in memory, or in a temporary file with the
+// position of every constraint when the storage is created with new_on_disk. In the
+// file, a replaced constraint is written again and the old version is not removed
+// until the storage is compacted.
+enum Compressed {
+    Memory(Vec<CompressedConstraint>),
+    Disk(ConstraintFile, Vec<u64>),
+}
+
 pub type ConstraintID = usize;
 pub struct ConstraintStorage {
     field_tracker: FieldTracker,
-    constraints: Vec<CompressedConstraint>,
+    constraints: Compressed,
 }
 
+const DISK_ERROR: &str = "Could not access the temporary file of the constraints";
+
 impl ConstraintStorage {
     pub fn new() -> ConstraintStorage {
-        ConstraintStorage { field_tracker: FieldTracker::new(), constraints: Vec::new() }
+        ConstraintStorage { field_tracker: FieldTracker::new(), constraints: Compressed::Memory(Vec::new()) }
+    }
+
+    pub fn new_on_disk() -> std::io::Result<ConstraintStorage> {
+        let constraints = Compressed::Disk(ConstraintFile::new()?, Vec::new());
+        Result::Ok(ConstraintStorage { field_tracker: FieldTracker::new(), constraints })
+    }
+
+    // An empty storage kept in the same place as this one
+    pub fn new_like(&self) -> ConstraintStorage {
+        if self.is_on_disk() {
+            ConstraintStorage::new_on_disk().expect(DISK_ERROR)
+        } else {
+            ConstraintStorage::new()
+        }
+    }
+
+    pub fn is_on_disk(&self) -> bool {
+        matches!(self.constraints, Compressed::Disk(..))
     }
 
     pub fn add_constraint(&mut self, constraint: C) -> ConstraintID {
-        let id = self.constraints.len();
         let compressed = logic::code_constraint(constraint, &mut self.field_tracker);
-        self.constraints.push(compressed);
-        id
+        match &mut self.constraints {
+            Compressed::Memory(constraints) => {
+                constraints.push(compressed);
+                constraints.len() - 1
+            }
+            Compressed::Disk(file, positions) => {
+                positions.push(file.push(&compressed).expect(DISK_ERROR));
+                positions.len() - 1
+            }
+        }
     }
 
     pub fn read_constraint(&self, id: ConstraintID) -> Option<C> {
-        if id < self.constraints.len() {
-            Some(logic::decode_constraint(&self.constraints[id], &self.field_tracker))
-        } else {
-            None
+        match &self.constraints {
+            Compressed::Memory(constraints) if id < constraints.len() => {
+                Some(logic::decode_constraint(&constraints[id], &self.field_tracker))
+            }
+            Compressed::Disk(file, positions) if id < positions.len() => {
+                let compressed = file.read(positions[id]).expect(DISK_ERROR);
+                Some(logic::decode_constraint(&compressed, &self.field_tracker))
+            }
+            _ => None,
         }
     }
 
     pub fn replace(&mut self, id: ConstraintID, new: C) {
-        if id < self.constraints.len() {
-            self.constraints[id] = logic::code_constraint(new, &mut self.field_tracker);
+        match &mut self.constraints {
+            Compressed::Memory(constraints) if id < constraints.len() => {
+                constraints[id] = logic::code_constraint(new, &mut self.field_tracker);
+            }
+            Compressed::Disk(file, positions) if id < positions.len() => {
+                let compressed = logic::code_constraint(new, &mut self.field_tracker);
+                positions[id] = file.push(&compressed).expect(DISK_ERROR);
+            }
+            _ => {}
+        }
+    }
+
+    // The signals of the constraint, with the constant one and with repetitions, read
+    // without decoding the coefficients
+    pub fn read_signals(&self, id: ConstraintID) -> Option<Vec<S>> {
+        let signals = |(a, b, c): &CompressedConstraint| a.iter().chain(b).chain(c).map(|(_, signal)| *signal).collect();
+        match &self.constraints {
+            Compressed::Memory(constraints) if id < constraints.len() => Some(signals(&constraints[id])),
+            Compressed::Disk(file, positions) if id < positions.len() => {
+                Some(signals(&file.read(positions[id]).expect(DISK_ERROR)))
+            }
+            _ => None,
+        }
+    }
+
+    // Changes with update the constraints that have a signal for which affected holds.
+    // On disk the constraints are written to a new file, so the file does not grow.
+    pub fn apply(&mut self, affected: &dyn Fn(S) -> bool, update: &mut dyn FnMut(&mut C)) {
+        let is_affected = |(a, b, c): &CompressedConstraint| a.iter().chain(b).chain(c).any(|(_, signal)| affected(*signal));
+        match &mut self.constraints {
+            Compressed::Memory(constraints) => {
+                for c in constraints.iter_mut().filter(|c| is_affected(c)) {
+                    let mut decoded = logic::decode_constraint(c, &self.field_tracker);
+                    update(&mut decoded);
+                    *c = logic::code_constraint(decoded, &mut self.field_tracker);
+                }
+            }
+            Compressed::Disk(file, positions) => {
+                let new_file = ConstraintFile::new().expect(DISK_ERROR);
+                for position in positions.iter_mut() {
+                    let mut compressed = file.read(*position).expect(DISK_ERROR);
+                    if is_affected(&compressed) {
+                        let mut decoded = logic::decode_constraint(&compressed, &self.field_tracker);
+                        update(&mut decoded);
+                        compressed = logic::code_constraint(decoded, &mut self.field_tracker);
+                    }
+                    *position = new_file.push(&compressed).expect(DISK_ERROR);
+                }
+                *file = new_file;
+            }
+        }
+    }
+
+    // Removes from the file the old versions of the replaced constraints
+    pub fn compact(&mut self) {
+        if let Compressed::Disk(file, positions) = &mut self.constraints {
+            let new_file = ConstraintFile::new().expect(DISK_ERROR);
+            for position in positions.iter_mut() {
+                *position = new_file.push(&file.read(*position).expect(DISK_ERROR)).expect(DISK_ERROR);
+            }
+            *file = new_file;
+        }
+    }
+
+    pub fn append(&mut self, other: &ConstraintStorage) {
+        for id in other.get_ids() {
+            self.add_constraint(other.read_constraint(id).unwrap());
         }
     }
 
     pub fn extract_with(&mut self, filter: &dyn Fn(&C) -> bool) -> LinkedList<C> {
-        let old = std::mem::take(&mut self.constraints);
         let mut removed = LinkedList::new();
-        for c in old {
-            let decoded = logic::decode_constraint(&c, &self.field_tracker);
-            if filter(&decoded) {
-                removed.push_back(decoded);
-            } else {
-                self.constraints.push(c);
+        match &mut self.constraints {
+            Compressed::Memory(constraints) => {
+                let old = std::mem::take(constraints);
+                for c in old {
+                    let decoded = logic::decode_constraint(&c, &self.field_tracker);
+                    if filter(&decoded) {
+                        removed.push_back(decoded);
+                    } else {
+                        constraints.push(c);
+                    }
+                }
+            }
+            Compressed::Disk(file, positions) => {
+                let old = std::mem::take(positions);
+                for position in old {
+                    let c = file.read(position).expect(DISK_ERROR);
+                    let decoded = logic::decode_constraint(&c, &self.field_tracker);
+                    if filter(&decoded) {
+                        removed.push_back(decoded);
+                    } else {
+                        positions.push(position);
+                    }
+                }
             }
         }
         removed
     }
 
     pub fn get_ids(&self) -> Vec<ConstraintID> {
-        (0..self.constraints.len()).collect()
+        (0..self.len()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.constraints {
+            Compressed::Memory(constraints) => constraints.len(),
+            Compressed::Disk(_, positions) => positions.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
     pub fn no_constants(&self) -> CID {
         self.field_tracker.next_id()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+    use std::collections::HashMap;
+
+    fn constraint(values: &[(usize, i64)]) -> C {
+        // 2^64 - 59, the largest prime that fits in one limb
+        let field = BigInt::from(u64::MAX - 58);
+        let lc: HashMap<usize, FieldElement> =
+            values.iter().map(|(s, v)| (*s, FieldElement::new(&BigInt::from(*v), &field))).collect();
+        C::new(lc.clone(), HashMap::new(), lc)
+    }
+
+    #[test]
+    fn disk_storage_reads_what_memory_storage_reads() {
+        let mut memory = ConstraintStorage::new();
+        let mut disk = ConstraintStorage::new_on_disk().unwrap();
+        for i in 0..300 {
+            let c = constraint(&[(0, i), (i as usize + 1, -1), (1 << 20, 1 << 40)]);
+            assert_eq!(memory.add_constraint(c.clone()), disk.add_constraint(c));
+        }
+        memory.replace(7, constraint(&[(3, 3)]));
+        disk.replace(7, constraint(&[(3, 3)]));
+        let short = |c: &C| c.c().len() == 1;
+        assert_eq!(memory.extract_with(&short).len(), disk.extract_with(&short).len());
+        assert_eq!(memory.get_ids(), disk.get_ids());
+        for id in disk.get_ids().into_iter().rev() {
+            let (expected, read) = (memory.read_constraint(id).unwrap(), disk.read_constraint(id).unwrap());
+            assert!(expected.a() == read.a() && expected.b() == read.b() && expected.c() == read.c());
+        }
+    }
+
+    #[test]
+    fn disk_storage_does_not_grow_after_compacting() {
+        let size = |storage: &ConstraintStorage| match &storage.constraints {
+            Compressed::Disk(file, _) => file.size(),
+            Compressed::Memory(_) => 0,
+        };
+        let mut disk = ConstraintStorage::new_on_disk().unwrap();
+        for i in 0..100 {
+            disk.add_constraint(constraint(&[(0, i), (i as usize + 1, 1)]));
+        }
+        let written = size(&disk);
+        for _ in 0..10 {
+            for id in disk.get_ids() {
+                let c = disk.read_constraint(id).unwrap();
+                disk.replace(id, c);
+            }
+        }
+        assert_eq!(size(&disk), 11 * written);
+        disk.compact();
+        assert_eq!(size(&disk), written);
+        // Only the constraints with the signal 5 change, and the file is rewritten
+        disk.apply(&|signal| signal == 5, &mut |c| *c = constraint(&[(0, 7), (5, 2)]));
+        assert_eq!(size(&disk), written);
+        assert_eq!(disk.read_constraint(4).unwrap().c()[&5], FieldElement::from(2));
+        assert_eq!(disk.read_constraint(5).unwrap().c()[&6], FieldElement::one());
+        assert_eq!(disk.read_signals(4).unwrap().len(), 4);
+    }
+}
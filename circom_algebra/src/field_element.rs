@@ -0,0 +1,435 @@
+use num_bigint::{BigInt, BigUint, ModInverse, Sign};
+use num_traits::{One, Zero};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+// Every prime supported by the compiler except bls12381 fits in four 64-bit limbs
+pub const LIMBS: usize = 4;
+type Limbs = [u64; LIMBS];
+
+// An element of a prime field, always reduced to [0, p). The elements below 2^256,
+// which are all the elements of every supported prime but bls12381, are kept in
+// fixed-size limbs, little endian, so they are cloned, compared and hashed without
+// allocating. Only the elements above 2^256 of wider primes are kept as a BigInt. The
+// values are in canonical form, so two elements can be compared without knowing the
+// field, and the Montgomery form is only used inside the multiplication.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FieldElement {
+    value: Value,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum Value {
+    Limbs(Limbs),
+    Wide(Box<BigInt>),
+}
+
+impl FieldElement {
+    pub fn zero() -> FieldElement {
+        FieldElement { value: Value::Limbs([0; LIMBS]) }
+    }
+
+    pub fn one() -> FieldElement {
+        FieldElement::from(1)
+    }
+
+    // The element of any integer, including the negative ones
+    pub fn new(value: &BigInt, field: &BigInt) -> FieldElement {
+        prime_field(field).element(value)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        matches!(self.value, Value::Limbs(limbs) if limbs == [0; LIMBS])
+    }
+
+    pub fn is_one(&self) -> bool {
+        matches!(self.value, Value::Limbs(limbs) if limbs == [1, 0, 0, 0])
+    }
+
+    pub fn to_bigint(&self) -> BigInt {
+        match &self.value {
+            Value::Limbs(limbs) => BigInt::from_biguint(Sign::Plus, BigUint::from_slice_native(limbs)),
+            Value::Wide(value) => value.as_ref().clone(),
+        }
+    }
+
+    // Little endian bytes of the value, without the leading zeros
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        match &self.value {
+            Value::Limbs(limbs) => {
+                let mut bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+                while bytes.len() > 1 && bytes[bytes.len() - 1] == 0 {
+                    bytes.pop();
+                }
+                bytes
+            }
+            Value::Wide(value) => value.to_bytes_le().1,
+        }
+    }
+
+    // Any non-negative integer below 2^256 is kept in limbs
+    pub(crate) fn from_reduced(value: BigInt) -> FieldElement {
+        debug_assert!(value.sign() != Sign::Minus);
+        match limbs_of(&value) {
+            Option::Some(limbs) => FieldElement { value: Value::Limbs(limbs) },
+            Option::None => FieldElement { value: Value::Wide(Box::new(value)) },
+        }
+    }
+}
+
+// Small constants, which must be lower than the prime
+impl From<u64> for FieldElement {
+    fn from(value: u64) -> FieldElement {
+        FieldElement { value: Value::Limbs([value, 0, 0, 0]) }
+    }
+}
+
+impl Display for FieldElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_bigint())
+    }
+}
+
+// The order of the integers in [0, p)
+impl Ord for FieldElement {
+    fn cmp(&self, other: &FieldElement) -> Ordering {
+        match (&self.value, &other.value) {
+            (Value::Limbs(left), Value::Limbs(right)) => left.iter().rev().cmp(right.iter().rev()),
+            (Value::Limbs(_), Value::Wide(_)) => Ordering::Less,
+            (Value::Wide(_), Value::Limbs(_)) => Ordering::Greater,
+            (Value::Wide(left), Value::Wide(right)) => left.cmp(right),
+        }
+    }
+}
+
+impl PartialOrd for FieldElement {
+    fn partial_cmp(&self, other: &FieldElement) -> Option<Ordering> {
+        Option::Some(self.cmp(other))
+    }
+}
+
+// Multiplication, addition and subtraction modulo an odd p < 2^256 without
+// allocating, with the multiplication done in Montgomery form with R = 2^256
+struct MontgomeryField {
+    modulus: Limbs,
+    // -p^(-1) mod 2^64
+    inv: u64,
+    // R^2 mod p, to multiply by R the result of a Montgomery multiplication
+    r2: Limbs,
+}
+
+impl MontgomeryField {
+    fn new(prime: &BigInt) -> Option<MontgomeryField> {
+        if prime.sign() != Sign::Plus || (prime % 2u32).is_zero() {
+            return Option::None;
+        }
+        let modulus = limbs_of(prime)?;
+        // Newton iteration: each step doubles the number of correct bits of p0^(-1)
+        let mut inverse: u64 = 1;
+        for _ in 0..6 {
+            inverse = inverse.wrapping_mul(2u64.wrapping_sub(modulus[0].wrapping_mul(inverse)));
+        }
+        let r2 = limbs_of(&((BigInt::one() << (128 * LIMBS)) % prime))?;
+        Option::Some(MontgomeryField { modulus, inv: inverse.wrapping_neg(), r2 })
+    }
+
+    fn add(&self, left: &Limbs, right: &Limbs) -> Limbs {
+        let (mut sum, carry) = add_limbs(left, right);
+        if carry || !less_than(&sum, &self.modulus) {
+            sum = sub_limbs(&sum, &self.modulus).0;
+        }
+        sum
+    }
+
+    fn sub(&self, left: &Limbs, right: &Limbs) -> Limbs {
+        let (mut difference, borrow) = sub_limbs(left, right);
+        if borrow {
+            difference = add_limbs(&difference, &self.modulus).0;
+        }
+        difference
+    }
+
+    fn mul(&self, left: &Limbs, right: &Limbs) -> Limbs {
+        // (a * b * R^-1) * R^2 * R^-1 = a * b
+        let reduced = self.montgomery_mul(left, right);
+        self.montgomery_mul(&reduced, &self.r2)
+    }
+
+    // a * b * R^-1 mod p for a, b < p, following the CIOS method. The two extra
+    // words of the accumulator allow moduli that use the top bit of the last limb.
+    fn montgomery_mul(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let p = &self.modulus;
+        let mut t = [0u64; LIMBS + 2];
+        for b_i in b.iter() {
+            let mut carry = 0;
+            for j in 0..LIMBS {
+                let (low, high) = mac(t[j], a[j], *b_i, carry);
+                t[j] = low;
+                carry = high;
+            }
+            let (sum, overflow) = t[LIMBS].overflowing_add(carry);
+            t[LIMBS] = sum;
+            t[LIMBS + 1] = overflow as u64;
+
+            let m = t[0].wrapping_mul(self.inv);
+            let (_, mut carry) = mac(t[0], m, p[0], 0);
+            for j in 1..LIMBS {
+                let (low, high) = mac(t[j], m, p[j], carry);
+                t[j - 1] = low;
+                carry = high;
+            }
+            let (sum, overflow) = t[LIMBS].overflowing_add(carry);
+            t[LIMBS - 1] = sum;
+            t[LIMBS] = t[LIMBS + 1] + overflow as u64;
+        }
+        let mut result = [0; LIMBS];
+        result.copy_from_slice(&t[..LIMBS]);
+        if t[LIMBS] != 0 || !less_than(&result, p) {
+            result = sub_limbs(&result, p).0;
+        }
+        result
+    }
+}
+
+// The arithmetic of the field of the circuit: Montgomery multiplication in fixed-size
+// limbs for the odd primes that fit in them, BigInt for any other modulus
+pub struct PrimeField {
+    prime: BigInt,
+    montgomery: Option<MontgomeryField>,
+}
+
+impl PrimeField {
+    pub fn new(prime: &BigInt) -> PrimeField {
+        PrimeField { prime: prime.clone(), montgomery: MontgomeryField::new(prime) }
+    }
+
+    pub fn prime(&self) -> &BigInt {
+        &self.prime
+    }
+
+    pub fn uses_limbs(&self) -> bool {
+        self.montgomery.is_some()
+    }
+
+    // The element of any integer, including the negative ones
+    pub fn element(&self, value: &BigInt) -> FieldElement {
+        match limbs_of(value) {
+            Option::Some(limbs) if self.montgomery.is_some() && less_than(&limbs, &self.montgomery.as_ref().unwrap().modulus) => {
+                FieldElement { value: Value::Limbs(limbs) }
+            }
+            _ => FieldElement::from_reduced(crate::modular_arithmetic::modulus(value, &self.prime)),
+        }
+    }
+
+    pub fn add(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        match (&self.montgomery, &left.value, &right.value) {
+            (Option::Some(field), Value::Limbs(left), Value::Limbs(right)) => {
+                FieldElement { value: Value::Limbs(field.add(left, right)) }
+            }
+            _ => self.element(&(left.to_bigint() + right.to_bigint())),
+        }
+    }
+
+    pub fn sub(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        match (&self.montgomery, &left.value, &right.value) {
+            (Option::Some(field), Value::Limbs(left), Value::Limbs(right)) => {
+                FieldElement { value: Value::Limbs(field.sub(left, right)) }
+            }
+            _ => self.element(&(left.to_bigint() - right.to_bigint())),
+        }
+    }
+
+    pub fn neg(&self, element: &FieldElement) -> FieldElement {
+        self.sub(&FieldElement::zero(), element)
+    }
+
+    pub fn mul(&self, left: &FieldElement, right: &FieldElement) -> FieldElement {
+        match (&self.montgomery, &left.value, &right.value) {
+            (Option::Some(field), Value::Limbs(left), Value::Limbs(right)) => {
+                FieldElement { value: Value::Limbs(field.mul(left, right)) }
+            }
+            _ => self.element(&(left.to_bigint() * right.to_bigint())),
+        }
+    }
+
+    // None for the elements without inverse, which is only zero when p is prime
+    pub fn inverse(&self, element: &FieldElement) -> Option<FieldElement> {
+        let inverse = element.to_bigint().mod_inverse(&self.prime)?;
+        Option::Some(self.element(&inverse))
+    }
+
+    // The inverses of all the values with a single inversion, as multi_inv does
+    pub fn multi_inv(&self, values: &[FieldElement]) -> Vec<FieldElement> {
+        let mut partials = Vec::with_capacity(values.len() + 1);
+        partials.push(FieldElement::one());
+        for value in values {
+            partials.push(self.mul(partials.last().unwrap(), value));
+        }
+        let mut inverse = self.inverse(partials.last().unwrap()).unwrap();
+        let mut outputs = vec![FieldElement::zero(); values.len()];
+        for i in (0..values.len()).rev() {
+            outputs[i] = self.mul(&partials[i], &inverse);
+            inverse = self.mul(&inverse, &values[i]);
+        }
+        outputs
+    }
+}
+
+thread_local! {
+    static CURRENT_FIELD: RefCell<Option<Rc<PrimeField>>> = const { RefCell::new(Option::None) };
+}
+
+// The operations receive the prime as a BigInt, so the parameters of the last field
+// used in each thread are kept to avoid computing them again for every operation
+pub fn prime_field(prime: &BigInt) -> Rc<PrimeField> {
+    CURRENT_FIELD.with(|current| {
+        let mut current = current.borrow_mut();
+        match &*current {
+            Option::Some(field) if field.prime() == prime => Rc::clone(field),
+            _ => {
+                let field = Rc::new(PrimeField::new(prime));
+                *current = Option::Some(Rc::clone(&field));
+                field
+            }
+        }
+    })
+}
+
+// The limbs of a non-negative integer of at most 256 bits
+fn limbs_of(value: &BigInt) -> Option<Limbs> {
+    if value.sign() == Sign::Minus || value.bits() > 64 * LIMBS {
+        return Option::None;
+    }
+    let mut limbs = [0; LIMBS];
+    if let Option::Some(magnitude) = value.to_biguint() {
+        for (i, limb) in limbs.iter_mut().enumerate().take(magnitude.bits().div_ceil(64)) {
+            *limb = magnitude.get_limb(i);
+        }
+    }
+    Option::Some(limbs)
+}
+
+// low and high words of a + b * c + carry, which always fits in 128 bits
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let result = a as u128 + (b as u128) * (c as u128) + carry as u128;
+    (result as u64, (result >> 64) as u64)
+}
+
+fn add_limbs(a: &Limbs, b: &Limbs) -> (Limbs, bool) {
+    let mut result = [0; LIMBS];
+    let mut carry = false;
+    for i in 0..LIMBS {
+        let (sum, overflow_0) = a[i].overflowing_add(b[i]);
+        let (sum, overflow_1) = sum.overflowing_add(carry as u64);
+        result[i] = sum;
+        carry = overflow_0 || overflow_1;
+    }
+    (result, carry)
+}
+
+fn sub_limbs(a: &Limbs, b: &Limbs) -> (Limbs, bool) {
+    let mut result = [0; LIMBS];
+    let mut borrow = false;
+    for i in 0..LIMBS {
+        let (difference, overflow_0) = a[i].overflowing_sub(b[i]);
+        let (difference, overflow_1) = difference.overflowing_sub(borrow as u64);
+        result[i] = difference;
+        borrow = overflow_0 || overflow_1;
+    }
+    (result, borrow)
+}
+
+fn less_than(a: &Limbs, b: &Limbs) -> bool {
+    for i in (0..LIMBS).rev() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modular_arithmetic::modulus;
+
+    const PRIMES: [&str; 4] = [
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+        "115792089210356248762697446949407573530086143415290314195533631308867097853951",
+        "18446744069414584321",
+        "257",
+    ];
+
+    fn big(value: &str) -> BigInt {
+        BigInt::parse_bytes(value.as_bytes(), 10).unwrap()
+    }
+
+    // Values around 0, p/2, p and 2^256 for each prime, some of them negative
+    fn samples(prime: &BigInt) -> Vec<BigInt> {
+        let half = prime / 2;
+        let top: BigInt = (BigInt::one() << 256usize) - 1;
+        let mut samples = vec![top.clone(), -top, big("-1"), big("123456789123456789123456789")];
+        for value in [BigInt::zero(), half, prime.clone()].iter() {
+            for offset in -2..3 {
+                samples.push(value + offset);
+            }
+        }
+        samples
+    }
+
+    fn check_operations(field: &PrimeField) {
+        let prime = field.prime();
+        for left in samples(prime) {
+            let l = field.element(&left);
+            assert_eq!(l.to_bigint(), modulus(&left, prime));
+            for right in samples(prime) {
+                let r = field.element(&right);
+                assert_eq!(field.add(&l, &r).to_bigint(), modulus(&(&left + &right), prime));
+                assert_eq!(field.sub(&l, &r).to_bigint(), modulus(&(&left - &right), prime));
+                assert_eq!(field.mul(&l, &r).to_bigint(), modulus(&(&left * &right), prime));
+                assert_eq!(l.cmp(&r), modulus(&left, prime).cmp(&modulus(&right, prime)));
+            }
+        }
+    }
+
+    #[test]
+    fn operations_match_the_bigint_ones() {
+        for prime in PRIMES.iter().map(|p| big(p)) {
+            let field = PrimeField::new(&prime);
+            assert!(field.uses_limbs());
+            check_operations(&field);
+        }
+    }
+
+    #[test]
+    fn moduli_that_do_not_fit_use_bigint() {
+        let wide: BigInt = (BigInt::one() << 300usize) + 157;
+        for prime in [wide, big("1000")].iter() {
+            let field = PrimeField::new(prime);
+            assert!(!field.uses_limbs());
+            check_operations(&field);
+            let value = field.element(&(prime - 3));
+            assert_eq!(field.mul(&value, &value), FieldElement::from(9));
+            assert_eq!(field.add(&value, &FieldElement::from(5)), FieldElement::from(2));
+        }
+    }
+
+    #[test]
+    fn elements_are_printed_and_written_as_their_integer() {
+        let prime = big(PRIMES[0]);
+        let field = PrimeField::new(&prime);
+        let minus_one = field.element(&big("-1"));
+        assert_eq!(format!("{}", minus_one), format!("{}", &prime - 1));
+        assert_eq!(minus_one.to_bytes_le(), (&prime - BigInt::one()).to_bytes_le().1);
+        assert_eq!(field.inverse(&minus_one), Option::Some(minus_one.clone()));
+        assert_eq!(field.inverse(&FieldElement::zero()), Option::None);
+        assert!(field.sub(&minus_one, &minus_one).is_zero());
+        let values: Vec<_> = samples(&prime).iter().map(|v| field.element(v)).filter(|v| !v.is_zero()).collect();
+        for (value, inverse) in values.iter().zip(field.multi_inv(&values)) {
+            assert!(field.mul(value, &inverse).is_one());
+        }
+        assert_eq!(FieldElement::zero().to_bytes_le(), BigInt::zero().to_bytes_le().1);
+    }
+}
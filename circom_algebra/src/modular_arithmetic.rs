@@ -6,7 +6,7 @@ pub enum ArithmeticError {
     BitOverFlowInShift,
 }
 
-fn modulus(a: &BigInt, b: &BigInt) -> BigInt {
+pub(crate) fn modulus(a: &BigInt, b: &BigInt) -> BigInt {
     ((a % b) + b) % b
 }
 // The maximum number of bits a BigInt can have is 18_446_744_073_709_551_615
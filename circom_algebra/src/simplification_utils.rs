@@ -1,6 +1,6 @@
 use super::num_bigint::BigInt;
+use crate::field_element::{prime_field, FieldElement};
 use crate::num_traits::ToPrimitive;
-use crate::modular_arithmetic;
 use std::collections::{BTreeMap, HashMap, HashSet, LinkedList};
 use std::mem::replace;
 
@@ -8,7 +8,7 @@ type C = crate::algebra::Constraint<usize>;
 type S = crate::algebra::Substitution<usize>;
 type A = crate::algebra::ArithmeticExpression<usize>;
 type SH = BTreeMap<usize, S>;
-type SHNotNormalized = BTreeMap<usize, (BigInt, S)>;
+type SHNotNormalized = BTreeMap<usize, (FieldElement, S)>;
 
 #[allow(dead_code)]
 fn debug_check_keys_in_order(tree: &BTreeMap<usize, S>) -> bool {
@@ -246,8 +246,8 @@ fn treat_constraint_2(
         let (in_conflict_coef, in_conflict_subs) = in_conflict.unwrap();
         let right = S::decompose(in_conflict_subs).1;
         let left = S::decompose(substitution).1;
-        let exp_coef_right = A::Number {value : in_conflict_coef};
-        let exp_coef_left = A::Number {value : coefficient};
+        let exp_coef_right = A::Number {value : in_conflict_coef.to_bigint()};
+        let exp_coef_left = A::Number {value : coefficient.to_bigint()};
         let new_left  = A::mul(&exp_coef_right,&left,field);
         let new_right  = A::mul(&exp_coef_left,&right,field);
         let merge = A::sub(&new_left, &new_right, field);
@@ -283,8 +283,8 @@ fn treat_constraint_3(
         let (in_conflict_coef, in_conflict_subs) = in_conflict.unwrap();
         let right = S::decompose(in_conflict_subs).1;
         let left = S::decompose(substitution).1;
-        let exp_coef_right = A::Number {value : in_conflict_coef};
-        let exp_coef_left = A::Number {value : coefficient};
+        let exp_coef_right = A::Number {value : in_conflict_coef.to_bigint()};
+        let exp_coef_left = A::Number {value : coefficient.to_bigint()};
         let new_left  = A::mul(&exp_coef_right,&left,field);
         let new_right  = A::mul(&exp_coef_left,&right,field);
         let merge = A::sub(&new_left, &new_right, field);
@@ -338,8 +338,8 @@ fn treat_constraint_4(
         let (in_conflict_coef, in_conflict_subs) = in_conflict.unwrap();
         let right = S::decompose(in_conflict_subs).1;
         let left = S::decompose(substitution).1;
-        let exp_coef_right = A::Number {value : in_conflict_coef};
-        let exp_coef_left = A::Number {value : coefficient};
+        let exp_coef_right = A::Number {value : in_conflict_coef.to_bigint()};
+        let exp_coef_left = A::Number {value : coefficient.to_bigint()};
         let new_left  = A::mul(&exp_coef_right,&left,field);
         let new_right  = A::mul(&exp_coef_left,&right,field);
         let merge = A::sub(&new_left, &new_right, field);
@@ -376,16 +376,18 @@ fn take_signal_3(signals: &SignalDefinition, constraint: &C) -> Option<usize> {
     ret
 }
 
+// The keys of a constraint are not ordered, so among the deleted signals the
+// biggest one is taken to make the result independent of the iteration order
 fn take_signal_4(signals: &SignalDefinition4, info_ocurrences: &SignalsInformation, constraint: &C) -> Option<usize> {
     let mut ret = Option::None;
     let mut ocurrences_ret: Option<usize> = Option::None;
+    let mut deleted: Option<usize> = Option::None;
     for k in constraint.c().keys() {
         if signals.can_be_taken(*k) {
             if signals.is_deleted(*k) {
-                ret = Some(*k);
-                break;
+                deleted = Some(deleted.map_or(*k, |v| std::cmp::max(*k, v)));
             }
-            else {
+            else if deleted.is_none() {
                 let new_ocurrences = info_ocurrences.signal_to_ocurrences.get(k).unwrap();
                 match ocurrences_ret{
                     Some(val_ant) => {
@@ -407,31 +409,25 @@ fn take_signal_4(signals: &SignalDefinition4, info_ocurrences: &SignalsInformati
             }
         }
     }
-    ret
+    deleted.or(ret)
 }
 
 
 fn normalize_substitutions(substitutions: SHNotNormalized, field: &BigInt) -> SH{
-    let mut coeffs : Vec<BigInt> = Vec::new();
+    let field = prime_field(field);
+    let mut coeffs : Vec<FieldElement> = Vec::new();
 
     for (_signal, (coeff, _sub)) in &substitutions{
         coeffs.push(coeff.clone());
     }
     
-    let inverses = modular_arithmetic::multi_inv(&coeffs, field);
+    let inverses = field.multi_inv(&coeffs);
     let mut tree : BTreeMap<usize,S> = BTreeMap::new();
-    let mut i = 0;
-    for (signal, (_coeff, sub)) in substitutions{
-        let inv = inverses.get(i).unwrap();
-        let arith_sub = A::hashmap_into_arith(sub.to().clone());
-        let mult_by_inverse = A::mul(
-            &arith_sub, 
-            &A::Number {value : inv.clone()}, 
-            field
-        );
-        let new_sub = S::new(signal.clone(), mult_by_inverse).unwrap(); 
-        tree.insert(signal, new_sub);
-        i = i + 1;
+    for ((signal, (_coeff, mut sub)), inv) in substitutions.into_iter().zip(inverses){
+        for value in sub.to.values_mut() {
+            *value = field.mul(value, &inv);
+        }
+        tree.insert(signal, sub);
     }
     tree
 }
@@ -570,6 +566,9 @@ where
         normalized_holder = normalize_substitutions(holder, &field);
         non_overlapping = create_nonoverlapping_substitutions(normalized_holder, &field);
     }
+    // The substitutions are returned ordered by the signal they remove
+    let mut non_overlapping: Vec<_> = non_overlapping.into_iter().collect();
+    non_overlapping.sort_by_key(|(s, _)| *s);
     let mut substitutions = LinkedList::new();
     let mut removed = LinkedList::new();
     for (s, v) in non_overlapping {
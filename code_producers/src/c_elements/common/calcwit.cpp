@@ -1,6 +1,7 @@
 #include <iomanip>
 #include <sstream>
 #include <assert.h>
+#include <cstring>
 #include "calcwit.hpp"
 
 extern void run(Circom_CalcWit* ctx);
@@ -23,6 +24,43 @@ u64 fnv1a(std::string s) {
   return hash;
 }
 
+// Formats the decimal representation of a field element used in a log with a format
+// string: 0 decimal, 1 hexadecimal, 2 signed (centered around p/2). Takes ownership of
+// the given string.
+char* format_log_value(char* decimal, int format, const char* prime) {
+  if (format == 0) {
+    return decimal;
+  }
+  mpz_t value;
+  mpz_t p;
+  mpz_init_set_str(value, decimal, 10);
+  mpz_init_set_str(p, prime, 10);
+  delete [] decimal;
+  std::string result;
+  if (format == 1) {
+    char* hex = mpz_get_str(0, 16, value);
+    result = hex;
+    free(hex);
+  } else {
+    mpz_t half;
+    mpz_init(half);
+    mpz_fdiv_q_2exp(half, p, 1);
+    if (mpz_cmp(value, half) > 0) {
+      mpz_sub(value, p, value);
+      result = "-";
+    }
+    char* dec = mpz_get_str(0, 10, value);
+    result += dec;
+    free(dec);
+    mpz_clear(half);
+  }
+  mpz_clear(value);
+  mpz_clear(p);
+  char* formatted = new char[result.length() + 1];
+  strcpy(formatted, result.c_str());
+  return formatted;
+}
+
 Circom_CalcWit::Circom_CalcWit (Circom_Circuit *aCircuit, uint maxTh) {
   circuit = aCircuit;
   inputSignalAssignedCounter = get_main_input_signal_no();
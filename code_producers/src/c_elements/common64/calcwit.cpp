@@ -1,6 +1,8 @@
 #include <iomanip>
 #include <sstream>
 #include <assert.h>
+#include <cstdlib>
+#include <cstring>
 #include "calcwit.hpp"
 
 extern void run(Circom_CalcWit* ctx);
@@ -23,6 +25,30 @@ u64 fnv1a(std::string s) {
   return hash;
 }
 
+// Formats the decimal representation of a field element used in a log with a format
+// string: 0 decimal, 1 hexadecimal, 2 signed (centered around p/2). Takes ownership of
+// the given string.
+char* format_log_value(char* decimal, int format, const char* prime) {
+  if (format == 0) {
+    return decimal;
+  }
+  u64 value = strtoull(decimal, NULL, 10);
+  u64 p = strtoull(prime, NULL, 10);
+  delete [] decimal;
+  std::stringstream stream;
+  if (format == 1) {
+    stream << std::hex << value;
+  } else if (value > p / 2) {
+    stream << "-" << p - value;
+  } else {
+    stream << value;
+  }
+  std::string result = stream.str();
+  char* formatted = new char[result.length() + 1];
+  strcpy(formatted, result.c_str());
+  return formatted;
+}
+
 Circom_CalcWit::Circom_CalcWit (Circom_Circuit *aCircuit, uint maxTh) {
   circuit = aCircuit;
   inputSignalAssignedCounter = get_main_input_signal_no();
@@ -14,6 +14,8 @@
 
 u64 fnv1a(std::string s);
 
+char* format_log_value(char* decimal, int format, const char* prime);
+
 class Circom_CalcWit {
 
   bool *inputSignalAssigned;
@@ -58,7 +58,14 @@ module.exports = async function builder(code, options) {
 	    },
 	    showSharedRWMemory : function() {
 		printSharedRWMemory ();
-            }
+            },
+	    writeFormattedBufferMessage : function() {
+		// The pieces of a log with a format string are not separated
+		msgStr += getMessage();
+	    },
+	    showFormattedSharedRWMemory : function(format) {
+		printFormattedSharedRWMemory (format);
+	    }
 
         }
     });
@@ -103,6 +110,29 @@ module.exports = async function builder(code, options) {
 	msgStr += (fromArray32(arr).toString());
 	}
 
+    function readSharedRWMemoryValue () {
+	const shared_rw_memory_size = instance.exports.getFieldNumLen32();
+	const arr = new Uint32Array(shared_rw_memory_size);
+	for (let j=0; j<shared_rw_memory_size; j++) {
+	    arr[shared_rw_memory_size-1-j] = instance.exports.readSharedRWMemory(j);
+	}
+	return fromArray32(arr);
+    }
+
+    // format: 0 decimal, 1 hexadecimal, 2 signed (centered around p/2)
+    function printFormattedSharedRWMemory (format) {
+	const value = readSharedRWMemoryValue();
+	if (format == 1) {
+	    msgStr += value.toString(16);
+	} else if (format == 2) {
+	    instance.exports.getRawPrime();
+	    const prime = readSharedRWMemoryValue();
+	    msgStr += (value > prime / 2n) ? "-" + (prime - value).toString() : value.toString();
+	} else {
+	    msgStr += value.toString();
+	}
+    }
+
 };
 
 class WitnessCalculator {
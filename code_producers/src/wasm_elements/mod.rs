@@ -29,6 +29,8 @@ pub struct WASMProducer {
     pub major_version: usize,
     pub minor_version: usize,
     pub patch_version: usize,
+    // the runtime must provide the imports used by logs with format strings
+    pub uses_log_format: bool,
     stack_free_pos: usize,
     local_info_size_u32: usize,
     size_of_message_buffer_in_bytes: usize,
@@ -112,6 +114,7 @@ impl Default for WASMProducer {
             major_version: 0,
             minor_version: 0,
             patch_version: 0,
+            uses_log_format: false,
             stack_free_pos: 0,
             local_info_size_u32: 0, // in the future we can add some info like pointer to run father or text father
             size_of_message_buffer_in_bytes: 256,
@@ -633,6 +633,23 @@ pub fn generate_imports_list() -> Vec<WasmInstruction> {
     imports
 }
 
+// Only declared when the circuit uses logs with format strings, so that the
+// rest of circuits can still be run by runtimes that do not provide them
+pub fn generate_log_format_imports_list(producer: &WASMProducer) -> Vec<WasmInstruction> {
+    let mut imports = vec![];
+    if producer.uses_log_format {
+        imports.push(
+            "(import \"runtime\" \"writeFormattedBufferMessage\" (func $writeFormattedBufferMessage (type $_t_void)))"
+                .to_string(),
+        );
+        imports.push(
+            "(import \"runtime\" \"showFormattedSharedRWMemory\" (func $showFormattedSharedRWMemory (type $_t_i32)))"
+                .to_string(),
+        );
+    }
+    imports
+}
+
 pub fn generate_memory_def_list(producer: &WASMProducer) -> Vec<WasmInstruction> {
     let mut wmemory = vec![];
     wmemory.push(format!("(memory {})", get_initial_size_of_memory(&producer)));
@@ -136,6 +136,7 @@ fn build_template_instances(
         template_info.var_stack_depth = out.stack_depth;
         template_info.signal_stack_depth = out.signal_depth;
         string_table = out.string_table;
+        circuit.wasm_producer.uses_log_format |= out.uses_log_format;
         cmp_id = out.next_cmp_id;
         circuit.add_template_code(template_info);
         tmp_id += 1;
@@ -188,6 +189,7 @@ fn build_function_instances(
         let code = instance.body;
         let out = translate::translate_code(code, code_info);
         string_table = out.string_table;
+        circuit.wasm_producer.uses_log_format |= out.uses_log_format;
         field_tracker = out.constant_tracker;
         function_info.body = out.code;
         function_info.max_number_of_ops_in_expression = out.expression_depth;
@@ -39,6 +39,8 @@ impl WriteWasm for Circuit {
         code.push("(module".to_string());
         let mut code_aux = generate_imports_list();
         code.append(&mut code_aux);
+        code_aux = generate_log_format_imports_list(&producer);
+        code.append(&mut code_aux);
         code_aux = generate_memory_def_list(&producer);
         code.append(&mut code_aux);
 
@@ -144,6 +146,10 @@ impl WriteWasm for Circuit {
         writer.write_all(code.as_bytes()).map_err(|_| {})?;
         //writer.flush().map_err(|_| {})?;
 
+        code_aux = generate_log_format_imports_list(&producer);
+        code = merge_code(code_aux);
+        writer.write_all(code.as_bytes()).map_err(|_| {})?;
+
         code_aux = generate_memory_def_list(&producer);
         code = merge_code(code_aux);
         writer.write_all(code.as_bytes()).map_err(|_| {})?;
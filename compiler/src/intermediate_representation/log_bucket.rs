@@ -2,6 +2,7 @@ use super::ir_interface::*;
 use crate::translating_traits::*;
 use code_producers::c_elements::*;
 use code_producers::wasm_elements::*;
+use program_structure::ast::LogFormat;
 
 
 #[derive(Clone)]
@@ -24,6 +25,9 @@ pub struct LogBucket {
     pub line: usize,
     pub message_id: usize,
     pub argsprint: Vec<LogBucketArg>,
+    // Format of each value when the log uses a format string, in that case
+    // the arguments are printed without separators
+    pub formats: Option<Vec<LogFormat>>,
 }
 
 impl IntoInstruction for LogBucket {
@@ -70,20 +74,30 @@ impl WriteWasm for LogBucket {
         if producer.needs_comments() {
             instructions.push(";; log bucket".to_string());
 	    }
+        let mut formats = self.formats.iter().flatten();
         for logarg in self.argsprint.clone() {
 	    match &logarg {
                 LogBucketArg::LogExp(exp) => {
                     let mut instructions_print = exp.produce_wasm(producer);
                     instructions.append(&mut instructions_print);
                     instructions.push(call("$copyFr2SharedRWMemory"));
-                    instructions.push(call("$showSharedRWMemory"));
+                    if let Option::Some(format) = formats.next() {
+                        instructions.push(set_constant(&log_format_code(*format).to_string()));
+                        instructions.push(call("$showFormattedSharedRWMemory"));
+                    } else {
+                        instructions.push(call("$showSharedRWMemory"));
+                    }
 	        }
 		LogBucketArg::LogStr(stringid) => {
                     let pos = producer.get_string_list_start() +
                               stringid * producer.get_size_of_message_in_bytes();
                     instructions.push(set_constant(&pos.to_string()));
                     instructions.push(call("$buildLogMessage"));
-                    instructions.push(call("$writeBufferMessage"));                    
+                    if self.formats.is_some() {
+                        instructions.push(call("$writeFormattedBufferMessage"));
+                    } else {
+                        instructions.push(call("$writeBufferMessage"));
+                    }
 	        }
             }
 	}
@@ -107,10 +121,21 @@ impl WriteC for LogBucket {
         use c_code_generator::*;
         let mut log_c = Vec::new();
         let mut index = 0;
+        let mut formats = self.formats.iter().flatten();
         for logarg in &self.argsprint {
             if let LogBucketArg::LogExp(exp) = logarg {
                 let (mut argument_code, argument_result) = exp.produce_c(producer, parallel);
-                let to_string_call = build_call("Fr_element2str".to_string(), vec![argument_result]);
+                let mut to_string_call = build_call("Fr_element2str".to_string(), vec![argument_result]);
+                if let Option::Some(format) = formats.next() {
+                    to_string_call = build_call(
+                        "format_log_value".to_string(),
+                        vec![
+                            to_string_call,
+                            log_format_code(*format).to_string(),
+                            format!("\"{}\"", producer.get_prime()),
+                        ],
+                    );
+                }
                 let temp_var = "temp".to_string();
                 let into_temp = format!("char* temp = {}", to_string_call);
                 let print_c =
@@ -138,7 +163,7 @@ impl WriteC for LogBucket {
             else{
                 unreachable!();
             }
-            if index != self.argsprint.len() - 1 { 
+            if self.formats.is_none() && index != self.argsprint.len() - 1 { 
                 let print_c =
                     build_call(
                         "printf".to_string(), 
@@ -160,3 +185,13 @@ impl WriteC for LogBucket {
         (log_c, "".to_string())
     }
 }
+
+// Encoding of the formats shared with the runtimes (format_log_value in C and
+// showFormattedSharedRWMemory in JavaScript)
+fn log_format_code(format: LogFormat) -> usize {
+    match format {
+        LogFormat::Decimal => 0,
+        LogFormat::Hexadecimal => 1,
+        LogFormat::Signed => 2,
+    }
+}
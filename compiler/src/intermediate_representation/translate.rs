@@ -5,6 +5,7 @@ use crate::intermediate_representation::types::SizeOption;
 use constant_tracking::ConstantTracker;
 use num_bigint_dig::BigInt;
 use program_structure::ast::*;
+use program_structure::statement_builders::split_string;
 use program_structure::file_definition::FileLibrary;
 use program_structure::utils::environment::VarEnvironment;
 use std::collections::{HashMap, BTreeMap, HashSet};
@@ -143,6 +144,7 @@ struct State {
     code: InstructionList,
     // string_table
     string_table: HashMap<String, usize>,
+    uses_log_format: bool,
 }
 
 impl State {
@@ -168,6 +170,7 @@ impl State {
             max_stack_depth: 0,
             code: vec![],
             string_table : HashMap::new(),
+            uses_log_format: false,
         }
     }
     fn reserve(fresh: &mut usize, size: usize) -> usize {
@@ -754,6 +757,33 @@ fn translate_log(stmt: Statement, state: &mut State, context: &Context) {
     if let LogCall { meta, args, .. } = stmt {
         let line = context.files.get_line(meta.start, meta.get_file_id()).unwrap();
         let mut logbucket_args = Vec::new();
+        let mut formats = Option::None;
+        let mut args = args.into_iter();
+        if let Option::Some(LogArgument::LogFormat(format)) = args.as_slice().first() {
+            let pieces = parse_log_format(format).unwrap();
+            args.next();
+            let mut value_formats = Vec::new();
+            for piece in pieces {
+                match piece {
+                    LogFormatPiece::Text(text) => {
+                        for chunk in split_string(text) {
+                            if let LogArgument::LogStr(chunk) = chunk {
+                                logbucket_args.push(LogBucketArg::LogStr(register_log_string(chunk, state)));
+                            }
+                        }
+                    }
+                    LogFormatPiece::Value(format) => {
+                        if let Option::Some(LogArgument::LogExp(arg)) = args.next() {
+                            let code = translate_expression(arg, state, context);
+                            logbucket_args.push(LogBucketArg::LogExp(code));
+                            value_formats.push(format);
+                        }
+                    }
+                }
+            }
+            state.uses_log_format = true;
+            formats = Option::Some(value_formats);
+        }
         for arglog in args {
             match arglog {
                 LogArgument::LogExp(arg) => {
@@ -761,15 +791,9 @@ fn translate_log(stmt: Statement, state: &mut State, context: &Context) {
                     logbucket_args.push(LogBucketArg::LogExp(code));
                 }
                 LogArgument::LogStr(exp) => {
-                    match state.string_table.get(&exp) {
-                        Some( idx) => {logbucket_args.push(LogBucketArg::LogStr(*idx));},
-                        None => {
-                            logbucket_args.push(LogBucketArg::LogStr(state.string_table.len()));
-                            state.string_table.insert(exp, state.string_table.len());
-                        },
-                    }
-                    
+                    logbucket_args.push(LogBucketArg::LogStr(register_log_string(exp, state)));
                 }
+                LogArgument::LogFormat(_) => unreachable!("Only the first argument of a log can be a format string"),
             }
         }
         
@@ -777,11 +801,23 @@ fn translate_log(stmt: Statement, state: &mut State, context: &Context) {
             line,
             message_id: state.message_id,
             argsprint: logbucket_args,
+            formats,
         }.allocate();
         state.code.push(log);
     }
 }
 
+fn register_log_string(string: String, state: &mut State) -> usize {
+    match state.string_table.get(&string) {
+        Some(idx) => *idx,
+        None => {
+            let idx = state.string_table.len();
+            state.string_table.insert(string, idx);
+            idx
+        }
+    }
+}
+
 fn translate_return(stmt: Statement, state: &mut State, context: &Context) {
     use Statement::Return;
     if let Return { meta, value, .. } = stmt {
@@ -1931,6 +1967,7 @@ pub struct CodeOutput {
     pub code: InstructionList,
     pub constant_tracker: FieldTracker,
     pub string_table: HashMap<String, usize>,
+    pub uses_log_format: bool,
 }
 
 pub fn translate_code(body: Statement, code_info: CodeInfo) -> CodeOutput {
@@ -1973,7 +2010,8 @@ pub fn translate_code(body: Statement, code_info: CodeInfo) -> CodeOutput {
         stack_depth: state.max_stack_depth,
         signal_depth: state.signal_stack,
         constant_tracker: state.field_tracker,
-        string_table : state.string_table
+        string_table : state.string_table,
+        uses_log_format: state.uses_log_format,
     }
 }
 
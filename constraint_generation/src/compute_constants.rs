@@ -4,10 +4,12 @@ use circom_algebra::algebra::ArithmeticExpression;
 use compiler::hir::very_concrete_program::{Argument, TemplateInstance};
 use num_bigint::BigInt;
 use num_traits::ToPrimitive;
-use program_structure::ast::{Expression, Meta, Statement};
+use program_structure::ast::{Access, Expression, LogArgument, Meta, Statement};
+use program_structure::constant_data::constant_dependencies;
 use program_structure::error_definition::ReportCollection;
 use program_structure::program_archive::ProgramArchive;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use crate::execute::execute_constant_expression;
 use crate::FlagsExecution;
 
 type CCResult = Result<(), ReportCollection>;
@@ -39,6 +41,149 @@ pub fn manage_functions(program_archive: &mut ProgramArchive, flags: FlagsExecut
     }
 }
 
+// Evaluates the global constants in dependency order and replaces every use of
+// them by its value, so the rest of the pipeline only sees numbers.
+pub fn compute_global_constants(program_archive: &mut ProgramArchive, flags: FlagsExecution, prime: &String) -> CCResult {
+    let mut pending = BTreeMap::new();
+    for (name, data) in program_archive.get_constants() {
+        let dependencies =
+            constant_dependencies(data.get_value(), program_archive.get_constants(), program_archive.get_functions());
+        pending.insert(name.clone(), dependencies);
+    }
+    let mut values = HashMap::new();
+    while let Option::Some(name) = pending
+        .iter()
+        .find(|(_, dependencies)| dependencies.iter().all(|d| values.contains_key(d)))
+        .map(|(name, _)| name.clone())
+    {
+        pending.remove(&name);
+        let value = program_archive.get_constant_data(&name).get_value();
+        let value = execute_constant_expression(value, program_archive, EE::new(), flags, prime)?;
+        let mut solved = HashMap::new();
+        solved.insert(name.clone(), value.clone());
+        for data in program_archive.get_mut_functions().values_mut() {
+            replace_constants_in_statement(data.get_mut_body(), &solved);
+        }
+        for data in program_archive.get_mut_constants().values_mut() {
+            replace_constants_in_expression(data.get_mut_value(), &solved);
+        }
+        values.insert(name, value);
+    }
+    debug_assert!(pending.is_empty(), "Cyclic global constants should have been rejected");
+    for data in program_archive.get_mut_templates().values_mut() {
+        replace_constants_in_statement(data.get_mut_body(), &values);
+        for clause in data.get_mut_requires() {
+            replace_constants_in_expression(clause, &values);
+        }
+    }
+    for data in program_archive.get_mut_buses().values_mut() {
+        replace_constants_in_statement(data.get_mut_body(), &values);
+    }
+    replace_constants_in_expression(program_archive.get_mut_main_expression(), &values);
+    Result::Ok(())
+}
+
+fn replace_constants_in_statement(stmt: &mut Statement, values: &HashMap<String, BigInt>) {
+    use Statement::*;
+    match stmt {
+        IfThenElse { cond, if_case, else_case, .. } => {
+            replace_constants_in_expression(cond, values);
+            replace_constants_in_statement(if_case, values);
+            if let Option::Some(else_stmt) = else_case {
+                replace_constants_in_statement(else_stmt, values);
+            }
+        }
+        While { cond, stmt, .. } => {
+            replace_constants_in_expression(cond, values);
+            replace_constants_in_statement(stmt, values);
+        }
+        Return { value, .. } => replace_constants_in_expression(value, values),
+        InitializationBlock { initializations, .. } => {
+            for init in initializations {
+                replace_constants_in_statement(init, values);
+            }
+        }
+        Declaration { dimensions, .. } => {
+            for dim in dimensions {
+                replace_constants_in_expression(dim, values);
+            }
+        }
+        Substitution { access, rhe, .. } => {
+            replace_constants_in_access(access, values);
+            replace_constants_in_expression(rhe, values);
+        }
+        MultSubstitution { lhe, rhe, .. } | ConstraintEquality { lhe, rhe, .. } => {
+            replace_constants_in_expression(lhe, values);
+            replace_constants_in_expression(rhe, values);
+        }
+        UnderscoreSubstitution { rhe, .. } => replace_constants_in_expression(rhe, values),
+        LogCall { args, .. } => {
+            for arg in args {
+                if let LogArgument::LogExp(expr) = arg {
+                    replace_constants_in_expression(expr, values);
+                }
+            }
+        }
+        Block { stmts, .. } => {
+            for s in stmts {
+                replace_constants_in_statement(s, values);
+            }
+        }
+        Assert { arg, .. } | StaticAssert { arg, .. } => replace_constants_in_expression(arg, values),
+    }
+}
+
+fn replace_constants_in_access(access: &mut [Access], values: &HashMap<String, BigInt>) {
+    for acc in access {
+        if let Access::ArrayAccess(index) = acc {
+            replace_constants_in_expression(index, values);
+        }
+    }
+}
+
+fn replace_constants_in_expression(expr: &mut Expression, values: &HashMap<String, BigInt>) {
+    use Expression::*;
+    match expr {
+        InfixOp { lhe, rhe, .. } => {
+            replace_constants_in_expression(lhe, values);
+            replace_constants_in_expression(rhe, values);
+        }
+        PrefixOp { rhe, .. } | ParallelOp { rhe, .. } => replace_constants_in_expression(rhe, values),
+        InlineSwitchOp { cond, if_true, if_false, .. } => {
+            replace_constants_in_expression(cond, values);
+            replace_constants_in_expression(if_true, values);
+            replace_constants_in_expression(if_false, values);
+        }
+        Variable { meta, name, access } => {
+            if let (true, Option::Some(value)) = (access.is_empty(), values.get(name)) {
+                *expr = Number(meta.clone(), value.clone());
+            } else {
+                replace_constants_in_access(access, values);
+            }
+        }
+        Number(..) => {}
+        Call { args, .. } | BusCall { args, .. } => {
+            for arg in args {
+                replace_constants_in_expression(arg, values);
+            }
+        }
+        AnonymousComp { params, signals, .. } => {
+            for e in params.iter_mut().chain(signals.iter_mut()) {
+                replace_constants_in_expression(e, values);
+            }
+        }
+        ArrayInLine { values: elements, .. } | Tuple { values: elements, .. } => {
+            for e in elements {
+                replace_constants_in_expression(e, values);
+            }
+        }
+        UniformArray { value, dimension, .. } => {
+            replace_constants_in_expression(value, values);
+            replace_constants_in_expression(dimension, values);
+        }
+    }
+}
+
 pub fn compute_vct(
     instances: &mut Vec<TemplateInstance>,
     program_archive: &ProgramArchive,
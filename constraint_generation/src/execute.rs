@@ -30,8 +30,10 @@ use super::execution_data::type_definitions::{AccessingInformationBus, Accessing
 use super::{
     ast::*, ArithmeticError, FileID, ProgramArchive, Report, ReportCode, ReportCollection
 };
+use circom_algebra::algebra::Constraint;
+use circom_algebra::constraint_storage::ConstraintStorage;
 use circom_algebra::num_bigint::BigInt;
-use std::collections::{HashMap, BTreeMap};
+use std::collections::{HashMap, HashSet, BTreeMap};
 use crate::FlagsExecution;
 type AExpr = ArithmeticExpressionGen<String>;
 type AnonymousComponentsInfo = BTreeMap<String, (Meta, Vec<Expression>)>;
@@ -49,12 +51,18 @@ struct RuntimeInformation {
     pub analysis: Analysis,
     pub public_inputs: Vec<String>,
     pub constants: UsefulConstants,
-    pub call_trace: Vec<String>,
+    pub call_trace: Vec<CallFrame>,
+    pub component_path: Vec<String>,
+    pub recursion_depth: usize,
+    pub executed_steps: usize,
     pub current_file: FileID,
     pub runtime_errors: ReportCollection,
     pub environment: ExecutionEnvironment,
     pub exec_program: ExecutedProgram,
     pub anonymous_components: AnonymousComponentsInfo,
+    // statements, with the instance that executed them, already reported as
+    // constraints without signals
+    pub constant_constraints: HashSet<(FileID, usize, String)>,
 }
 impl RuntimeInformation {
     pub fn new(current_file: FileID, id_max: usize, prime: &String) -> RuntimeInformation {
@@ -65,12 +73,16 @@ impl RuntimeInformation {
             public_inputs: vec![],
             constants: UsefulConstants::new(prime),
             call_trace: Vec::new(),
+            component_path: Vec::new(),
+            recursion_depth: 0,
+            executed_steps: 0,
             runtime_errors: ReportCollection::new(),
             environment: ExecutionEnvironment::new(),
             exec_program: ExecutedProgram::new(prime),
             anonymous_components: AnonymousComponentsInfo::new(),
             conditions_state: Vec::new(),
             unknown_counter: 0,
+            constant_constraints: HashSet::new(),
         }
     }
 }
@@ -152,12 +164,19 @@ enum ExecutionError {
     UnknownTemplate,
     NonValidTagAssignment,
     FalseAssert,
-    ArraySizeTooBig
+    FalseStaticAssert(String),
+    UnknownStaticAssert,
+    ArraySizeTooBig,
+    LoopIterationsExceeded(usize),
+    RecursionDepthExceeded(usize),
+    ExecutionStepsExceeded(usize),
+    UnsatisfiableConstraint,
 }
 
 enum ExecutionWarning {
     CanBeQuadraticConstraintSingle(),
     CanBeQuadraticConstraintMultiple(Vec<String>),
+    TriviallyTrueConstraint,
 }
 
 
@@ -169,6 +188,10 @@ pub fn constraint_execution(
 ) -> Result<(ExecutedProgram, ReportCollection), ReportCollection> {    
     let main_file_id = program_archive.get_file_id_main();
     let mut runtime_information = RuntimeInformation::new(*main_file_id, program_archive.id_max, prime);
+    if flags.low_memory {
+        let storage = ConstraintStorage::new_on_disk().expect("Could not create the temporary file of the constraints");
+        runtime_information.exec_program.constraint_storage = Option::Some(storage);
+    }
     use Expression::Call;
 
     runtime_information.public_inputs = program_archive.get_public_inputs_main_component().clone();
@@ -185,6 +208,8 @@ pub fn constraint_execution(
                 id,
                 arg_values,
                 HashMap::new(),
+                "main",
+                program_archive.get_main_expression().get_meta(),
                 program_archive,
                 &mut runtime_information,
                 flags,
@@ -240,6 +265,15 @@ fn execute_statement(
     use Statement::*;
     let id = stmt.get_meta().elem_id;
     Analysis::reached(&mut runtime.analysis, id);
+    runtime.executed_steps += 1;
+    if exceeds_limit(runtime.executed_steps, flags.limits.max_steps) {
+        treat_result_with_execution_error(
+            Result::Err(ExecutionError::ExecutionStepsExceeded(flags.limits.max_steps)),
+            stmt.get_meta(),
+            &mut runtime.runtime_errors,
+            &runtime.call_trace,
+        )?;
+    }
     let mut can_be_simplified = true;
     let res = match stmt {
         MultSubstitution { .. } => unreachable!(),
@@ -461,6 +495,7 @@ fn execute_statement(
                                 let symbol = signal_left;
                                 let expr = AExpr::sub(&symbol, &value_right, &p);
                                 let ctr = AExpr::transform_expression_to_constraint_form(expr, &p).unwrap();
+                                check_constant_constraint(&ctr, meta, runtime)?;
                                 node.add_constraint(ctr);
                             }
                         } else if let AssignOp::AssignSignal = op {// needs fix, check case arrays
@@ -653,6 +688,7 @@ fn execute_statement(
                     runtime.constants.get_p(),
                 )
                 .unwrap();
+                check_constant_constraint(&constraint_expression, meta, runtime)?;
                 if let Option::Some(node) = actual_node {
                     node.add_constraint(constraint_expression);
                 }    
@@ -683,10 +719,11 @@ fn execute_statement(
             can_be_simplified = can_simplify;
             possible_return
         }
-        While { cond, stmt, .. } => {
+        While { meta, cond, stmt, .. } => {
             // We update the conditions state of the execution
             runtime.conditions_state.push((runtime.unknown_counter, true));
             runtime.unknown_counter+=1;
+            let mut iterations = 0;
             loop {
 
                 let (returned, can_simplify, condition_result) = execute_conditional_statement(
@@ -715,6 +752,15 @@ fn execute_statement(
                 } else if !condition_result.unwrap() {
                     break returned;
                 }
+                iterations += 1;
+                if exceeds_limit(iterations, flags.limits.max_loop_iterations) {
+                    treat_result_with_execution_error(
+                        Result::Err(ExecutionError::LoopIterationsExceeded(flags.limits.max_loop_iterations)),
+                        meta,
+                        &mut runtime.runtime_errors,
+                        &runtime.call_trace,
+                    )?;
+                }
                 // We remove the last conditions_state added
                 runtime.conditions_state.pop();
             }
@@ -729,7 +775,9 @@ fn execute_statement(
         }
         LogCall { args, .. } => {
             can_be_simplified = false;
-            if flags.verbose{
+            if flags.verbose && matches!(args.first(), Option::Some(LogArgument::LogFormat(_))) {
+                execute_formatted_log(args, program_archive, runtime, flags)?;
+            } else if flags.verbose{
                 let mut index = 0;
                 for arglog in args {
                     if let LogArgument::LogExp(arg) = arglog{
@@ -780,6 +828,22 @@ fn execute_statement(
                 &runtime.call_trace,
             )?
         }
+        StaticAssert { arg, meta, message } => {
+            let f_result = execute_expression(arg, program_archive, runtime, flags)?;
+            let arith = safe_unwrap_to_single_arithmetic_expression(f_result, line!());
+            let possible_bool = AExpr::get_boolean_equivalence(&arith, runtime.constants.get_p());
+            let result = match possible_bool {
+                Some(true) => Ok(None),
+                Some(false) => Err(ExecutionError::FalseStaticAssert(message.clone())),
+                None => Err(ExecutionError::UnknownStaticAssert),
+            };
+            treat_result_with_execution_error(
+                result,
+                meta,
+                &mut runtime.runtime_errors,
+                &runtime.call_trace,
+            )?
+        }
         UnderscoreSubstitution{ meta, rhe, op} =>{
             let f_result = execute_expression(rhe, program_archive, runtime, flags)?;
             if FoldedValue::valid_arithmetic_slice(&f_result){
@@ -1310,8 +1374,9 @@ fn execute_call(
         let new_file_id = program_archive.get_function_data(id).get_file_id();
         let previous_id = std::mem::replace(&mut runtime.current_file, new_file_id);
 
-        runtime.call_trace.push(id.clone());
-        let folded_result = execute_function_call(id, program_archive, runtime, flags)?;
+        let params = program_archive.get_function_data(id).get_name_of_params();
+        runtime.call_trace.push(CallFrame::new(id, params, &arg_values, Option::None));
+        let folded_result = execute_function_call(id, meta, program_archive, runtime, flags)?;
 
         runtime.environment = previous_environment;
         runtime.current_file = previous_id;
@@ -1329,11 +1394,17 @@ fn execute_template_call_complete(
     id: &String,
     arg_values: Vec<AExpressionSlice>,
     tags: HashMap<String, TagWire>,
+    component_name: &str,
+    call_site: &Meta,
     program_archive: &ProgramArchive,
     runtime: &mut RuntimeInformation,
     flags: FlagsExecution,
 ) -> Result<FoldedValue, ()> {
     if program_archive.contains_template(id){ // in this case we execute
+        // The instance is described before the arguments are moved into its environment
+        runtime.component_path.push(component_name.to_string());
+        let params = program_archive.get_template_data(id).get_name_of_params();
+        let instance = CallFrame::new(id, params, &arg_values, Option::Some(component_name));
         let new_environment = prepare_environment_for_call(id, &arg_values, program_archive);
         let previous_environment = std::mem::replace(&mut runtime.environment, new_environment);
         let previous_block_type = std::mem::replace(&mut runtime.block_type, BlockType::Known);
@@ -1342,7 +1413,8 @@ fn execute_template_call_complete(
         let new_file_id = program_archive.get_template_data(id).get_file_id();
         let previous_id = std::mem::replace(&mut runtime.current_file, new_file_id);
 
-        runtime.call_trace.push(id.clone());
+        runtime.call_trace.push(instance);
+        check_template_contracts(id, &tags, call_site, program_archive, runtime, flags)?;
         let folded_result = execute_template_call(id, arg_values, tags, program_archive, runtime, flags)?;
 
         runtime.environment = previous_environment;
@@ -1350,12 +1422,140 @@ fn execute_template_call_complete(
         runtime.block_type = previous_block_type;
         runtime.anonymous_components = previous_anonymous_components;
         runtime.call_trace.pop();
+        runtime.component_path.pop();
         Ok(folded_result)
     } else { 
        unreachable!();
     }
 }
 
+// The contracts of a template are checked before its body is executed, using the
+// values of its parameters and the values of the tags received by its inputs
+fn check_template_contracts(
+    id: &str,
+    tags: &HashMap<String, TagWire>,
+    call_site: &Meta,
+    program_archive: &ProgramArchive,
+    runtime: &mut RuntimeInformation,
+    flags: FlagsExecution,
+) -> Result<(), ()> {
+    for clause in program_archive.get_template_data(id).get_requires() {
+        let mut clause = clause.clone();
+        if let Err((input, tag)) = replace_tag_values_in_contract(&mut clause, tags) {
+            let mut report = Report::error(
+                format!("The contract of template {} can not be checked: the tag {} of the input {} has no value", id, tag, input),
+                ReportCode::TemplateContractViolated,
+            );
+            report.add_secondary(call_site.location.clone(), call_site.get_file_id(), Option::Some("Instantiated here".to_string()));
+            add_report_to_runtime(report, clause.get_meta(), &mut runtime.runtime_errors, &runtime.call_trace);
+            return Result::Err(());
+        }
+        let f_result = execute_expression(&clause, program_archive, runtime, flags)?;
+        let arith = safe_unwrap_to_single_arithmetic_expression(f_result, line!());
+        let message = match AExpr::get_boolean_equivalence(&arith, runtime.constants.get_p()) {
+            Some(true) => continue,
+            Some(false) => format!("The contract of template {} is violated", id),
+            None => format!("The contract of template {} must be known during the constraint generation phase", id),
+        };
+        let mut report = Report::error(message, ReportCode::TemplateContractViolated);
+        report.add_secondary(call_site.location.clone(), call_site.get_file_id(), Option::Some("Instantiated here".to_string()));
+        add_report_to_runtime(report, clause.get_meta(), &mut runtime.runtime_errors, &runtime.call_trace);
+        return Result::Err(());
+    }
+    Result::Ok(())
+}
+
+// Replaces every access to the tag of an input by the value received by the instance,
+// returns the input and tag whose value is missing otherwise
+fn replace_tag_values_in_contract(
+    expr: &mut Expression,
+    tags: &HashMap<String, TagWire>,
+) -> Result<(), (String, String)> {
+    use Expression::*;
+    match expr {
+        InfixOp { lhe, rhe, .. } => {
+            replace_tag_values_in_contract(lhe, tags)?;
+            replace_tag_values_in_contract(rhe, tags)
+        }
+        PrefixOp { rhe, .. } => replace_tag_values_in_contract(rhe, tags),
+        InlineSwitchOp { cond, if_true, if_false, .. } => {
+            replace_tag_values_in_contract(cond, tags)?;
+            replace_tag_values_in_contract(if_true, tags)?;
+            replace_tag_values_in_contract(if_false, tags)
+        }
+        Call { args, .. } => {
+            for arg in args {
+                replace_tag_values_in_contract(arg, tags)?;
+            }
+            Result::Ok(())
+        }
+        Variable { meta, name, access } => {
+            if let [Access::ComponentAccess(tag)] = access.as_slice() {
+                let value = tags.get(name).and_then(|wire| wire.tags.get(tag)).cloned().flatten();
+                match value {
+                    Some(value) => *expr = Number(meta.clone(), value),
+                    None => return Result::Err((name.clone(), tag.clone())),
+                }
+            } else {
+                for acc in access {
+                    if let Access::ArrayAccess(index) = acc {
+                        replace_tag_values_in_contract(index, tags)?;
+                    }
+                }
+            }
+            Result::Ok(())
+        }
+        _ => Result::Ok(()),
+    }
+}
+
+// Prints a log whose first argument is a format string, its placeholders are
+// replaced by the values of the rest of arguments
+fn execute_formatted_log(
+    args: &[LogArgument],
+    program_archive: &ProgramArchive,
+    runtime: &mut RuntimeInformation,
+    flags: FlagsExecution,
+) -> Result<(), ()> {
+    let pieces = if let LogArgument::LogFormat(format) = &args[0] {
+        parse_log_format(format).unwrap()
+    } else {
+        unreachable!()
+    };
+    let mut values = args[1..].iter();
+    let mut line = String::new();
+    for piece in pieces {
+        match piece {
+            LogFormatPiece::Text(text) => line.push_str(&text),
+            LogFormatPiece::Value(format) => {
+                let arg = if let Option::Some(LogArgument::LogExp(arg)) = values.next() {
+                    arg
+                } else {
+                    unreachable!("Format strings always match the number of values")
+                };
+                let f_result = execute_expression(arg, program_archive, runtime, flags)?;
+                let arith = safe_unwrap_to_single_arithmetic_expression(f_result, line!());
+                if let AExpr::Number { value } = arith {
+                    line.push_str(&format_log_value(&value, format, runtime.constants.get_p()));
+                } else {
+                    line.push_str("Unknown");
+                }
+            }
+        }
+    }
+    println!("{}", line);
+    Result::Ok(())
+}
+
+fn format_log_value(value: &BigInt, format: LogFormat, prime: &BigInt) -> String {
+    match format {
+        LogFormat::Decimal => value.to_str_radix(10),
+        LogFormat::Hexadecimal => value.to_str_radix(16),
+        LogFormat::Signed if *value > prime / 2 => format!("-{}", (prime - value).to_str_radix(10)),
+        LogFormat::Signed => value.to_str_radix(10),
+    }
+}
+
 fn execute_component_declaration(
     component_name: &str,
     dimensions: &[SliceCapacity],
@@ -1405,7 +1605,8 @@ fn execute_bus_call_complete(
         let new_file_id = program_archive.get_bus_data(id).get_file_id();
         let previous_id = std::mem::replace(&mut runtime.current_file, new_file_id);
 
-        runtime.call_trace.push(id.clone());
+        let params = program_archive.get_bus_data(id).get_name_of_params();
+        runtime.call_trace.push(CallFrame::new(id, params, &arg_values, Option::None));
         let folded_result = execute_bus_call(id, arg_values, program_archive, runtime, flags)?;
 
         runtime.environment = previous_environment;
@@ -1821,10 +2022,17 @@ fn perform_assign(
                 // calls to execute and initialize the component              
                 let pretemplate_info = runtime.exec_program.get_prenode_value(prenode_pointer).unwrap();
                 let inputs_tags = component.inputs_tags.clone();
+                let component_name = if is_anonymous_component {
+                    symbol.to_string()
+                } else {
+                    create_component_symbol(symbol, &accessing_information.array_access)
+                };
                 let result = execute_template_call_complete(
                     pretemplate_info.template_name(),
                     pretemplate_info.parameter_instances().clone(),
                     inputs_tags,
+                    &component_name,
+                    meta,
                     program_archive,
                     runtime,
                     flags,
@@ -2061,11 +2269,19 @@ fn perform_assign(
                         component.node_pointer.unwrap()
                     ).unwrap();
                     let inputs_tags = component.inputs_tags.clone();
+                    let call_site = component.meta.clone().unwrap();
+                    let component_name = if is_anonymous_component {
+                        symbol.to_string()
+                    } else {
+                        create_component_symbol(symbol, &accessing_information.array_access)
+                    };
     
                     let folded_result = execute_template_call_complete(
                         pretemplate_info.template_name(),
                         pretemplate_info.parameter_instances().clone(),
                         inputs_tags,
+                        &component_name,
+                        &call_site,
                         program_archive,
                         runtime,
                         flags,
@@ -3356,17 +3572,28 @@ fn prepare_environment_for_call(
 
 fn execute_function_call(
     id: &str,
+    call_site: &Meta,
     program_archive: &ProgramArchive,
     runtime: &mut RuntimeInformation,
     flags: FlagsExecution
 ) -> Result<(FoldedValue, bool), ()> {
     use std::mem;
+    runtime.recursion_depth += 1;
+    if exceeds_limit(runtime.recursion_depth, flags.limits.max_recursion_depth) {
+        treat_result_with_execution_error(
+            Result::Err(ExecutionError::RecursionDepthExceeded(flags.limits.max_recursion_depth)),
+            call_site,
+            &mut runtime.runtime_errors,
+            &runtime.call_trace,
+        )?;
+    }
     let previous_block = runtime.block_type;
     let previous_conditions = mem::replace(&mut runtime.conditions_state, vec![]);
     runtime.block_type = BlockType::Known;
     let function_body = program_archive.get_function_data(id).get_body_as_vec();
     let (function_result, can_be_simplified) =
         execute_sequence_of_statements(function_body, program_archive, runtime, &mut Option::None, flags, true)?;
+    runtime.recursion_depth -= 1;
     runtime.block_type = previous_block;
     runtime.conditions_state = previous_conditions;
     let return_value = function_result.unwrap();
@@ -3917,7 +4144,7 @@ fn treat_result_with_arithmetic_error<C>(
     arithmetic_error: Result<C, ArithmeticError>,
     meta: &Meta,
     runtime_errors: &mut ReportCollection,
-    call_trace: &[String],
+    call_trace: &[CallFrame],
 ) -> Result<C, ()> {
     use ReportCode::RuntimeError;
     match arithmetic_error {
@@ -3941,7 +4168,7 @@ fn treat_result_with_memory_error_void(
     memory_error: Result<(), MemoryError>,
     meta: &Meta,
     runtime_errors: &mut ReportCollection,
-    call_trace: &[String],
+    call_trace: &[CallFrame],
 ) -> Result<(), ()> {
     use ReportCode::RuntimeError;
     match memory_error {
@@ -4101,7 +4328,7 @@ pub fn treat_result_with_memory_error<C>(
     memory_error: Result<C, MemoryError>,
     meta: &Meta,
     runtime_errors: &mut ReportCollection,
-    call_trace: &[String],
+    call_trace: &[CallFrame],
 ) -> Result<C, ()> {
     use ReportCode::RuntimeError;
     match memory_error {
@@ -4244,7 +4471,7 @@ fn treat_result_with_environment_error<C>(
     environment_error: Result<C, ExecutionEnvironmentError>,
     meta: &Meta,
     runtime_errors: &mut ReportCollection,
-    call_trace: &[String],
+    call_trace: &[CallFrame],
 ) -> Result<C, ()> {
     use ReportCode::*;
     match environment_error {
@@ -4265,7 +4492,7 @@ fn treat_result_with_execution_error<C>(
     execution_error: Result<C, ExecutionError>,
     meta: &Meta,
     runtime_errors: &mut ReportCollection,
-    call_trace: &[String],
+    call_trace: &[CallFrame],
 ) -> Result<C, ()> {
     use ExecutionError::*;
     match execution_error {
@@ -4287,6 +4514,18 @@ fn treat_result_with_execution_error<C>(
                 FalseAssert => {
                     Report::error("False assert reached".to_string(), ReportCode::RuntimeError)
                 }
+                UnsatisfiableConstraint => Report::error(
+                    "This constraint does not depend on any signal and never holds, so the circuit can not be satisfied".to_string(),
+                    ReportCode::UnsatisfiableConstraint,
+                ),
+                FalseStaticAssert(message) => Report::error(
+                    format!("Static assertion failed: {}", message),
+                    ReportCode::StaticAssertFailed,
+                ),
+                UnknownStaticAssert => Report::error(
+                    "The condition of a static assertion must be known during the constraint generation phase".to_string(),
+                    ReportCode::StaticAssertFailed,
+                ),
                 ArraySizeTooBig => Report::error(
                     "The size of the array is expected to be a usize".to_string(),
                     ReportCode::RuntimeError,
@@ -4302,7 +4541,31 @@ fn treat_result_with_execution_error<C>(
                 TagAssignmentInUnknown => Report::error(
                     "There are tag assignments depending on the value of a condition that can be unknown during the constraint generation phase".to_string(),
                     ReportCode::RuntimeError,
-                )
+                ),
+                LoopIterationsExceeded(iterations) => {
+                    let mut report = Report::error(
+                        format!("The loop did not finish after {} iterations", iterations),
+                        ReportCode::ExecutionLimitExceeded,
+                    );
+                    report.add_note("The limit can be changed with --max_loop_iterations".to_string());
+                    report
+                }
+                RecursionDepthExceeded(depth) => {
+                    let mut report = Report::error(
+                        format!("The depth of nested function calls exceeds {}", depth),
+                        ReportCode::ExecutionLimitExceeded,
+                    );
+                    report.add_note("The limit can be changed with --max_recursion_depth".to_string());
+                    report
+                }
+                ExecutionStepsExceeded(steps) => {
+                    let mut report = Report::error(
+                        format!("The constraint generation executed more than {} statements", steps),
+                        ReportCode::ExecutionLimitExceeded,
+                    );
+                    report.add_note("The limit can be changed with --max_steps".to_string());
+                    report
+                }
             };
             add_report_to_runtime(report, meta, runtime_errors, call_trace);
             Result::Err(())
@@ -4314,7 +4577,7 @@ fn treat_result_with_execution_warning<C>(
     execution_error: Result<C, ExecutionWarning>,
     meta: &Meta,
     runtime_errors: &mut ReportCollection,
-    call_trace: &[String],
+    call_trace: &[CallFrame],
 ) -> Result<(), ()> {
     use ExecutionWarning::*;
     match execution_error {
@@ -4345,8 +4608,10 @@ fn treat_result_with_execution_warning<C>(
                         ReportCode::RuntimeWarning,
                     )
                 }
-
-
+                TriviallyTrueConstraint => Report::warning(
+                    "This constraint does not depend on any signal and always holds, so it does not constrain the circuit".to_string(),
+                    ReportCode::TriviallyTrueConstraint,
+                ),
             };
             add_report_to_runtime(report, meta, runtime_errors, call_trace);
             Result::Ok(())
@@ -4354,19 +4619,145 @@ fn treat_result_with_execution_warning<C>(
     }
 }
 
+// A constraint without signals, such as 0 === 1 from a constant branch or 0 === 0 from
+// x - x, either can never be satisfied or does not constrain anything. A statement
+// executed in a loop is reported once for each instance of the template.
+fn check_constant_constraint(
+    constraint: &Constraint<String>,
+    meta: &Meta,
+    runtime: &mut RuntimeInformation,
+) -> Result<(), ()> {
+    use circom_algebra::field_element::{prime_field, FieldElement};
+    let field = prime_field(runtime.constants.get_p());
+    let constant = Constraint::<String>::constant_coefficient();
+    let has_signals = [constraint.a(), constraint.b(), constraint.c()]
+        .iter()
+        .any(|lc| lc.iter().any(|(signal, value)| *signal != constant && !value.is_zero()));
+    if has_signals {
+        return Result::Ok(());
+    }
+    let key = (meta.get_file_id(), meta.location.start, runtime.component_path.join("."));
+    if !runtime.constant_constraints.insert(key) {
+        return Result::Ok(());
+    }
+    let value =
+        |lc: &HashMap<String, FieldElement>| lc.get(&constant).cloned().unwrap_or_else(FieldElement::zero);
+    let product = field.mul(&value(constraint.a()), &value(constraint.b()));
+    if field.sub(&product, &value(constraint.c())).is_zero() {
+        treat_result_with_execution_warning(
+            Result::<(), _>::Err(ExecutionWarning::TriviallyTrueConstraint),
+            meta,
+            &mut runtime.runtime_errors,
+            &runtime.call_trace,
+        )
+    } else {
+        treat_result_with_execution_error(
+            Result::<(), _>::Err(ExecutionError::UnsatisfiableConstraint),
+            meta,
+            &mut runtime.runtime_errors,
+            &runtime.call_trace,
+        )
+    }
+}
+
+fn exceeds_limit(count: usize, limit: usize) -> bool {
+    limit != 0 && count > limit
+}
+
+// A call of the trace, e.g. Poseidon(nInputs = 2), so that the different instances of a
+// template can be told apart. Only the first values of each argument are kept, and they
+// are formatted when a report shows the trace.
+pub struct CallFrame {
+    id: String,
+    // The name of the component, for the calls of templates
+    component: Option<String>,
+    args: Vec<ShownArgument>,
+}
+
+struct ShownArgument {
+    param: String,
+    is_array: bool,
+    values: Vec<Option<BigInt>>,
+    truncated: bool,
+}
+
+impl CallFrame {
+    const MAX_SHOWN_VALUES: usize = 8;
+
+    fn new(id: &str, params: &[String], arg_values: &[AExpressionSlice], component: Option<&str>) -> CallFrame {
+        let mut args = Vec::new();
+        for (param, value) in params.iter().zip(arg_values) {
+            let values = AExpressionSlice::get_reference_values(value);
+            args.push(ShownArgument {
+                param: param.clone(),
+                is_array: !value.is_single(),
+                values: values
+                    .iter()
+                    .take(CallFrame::MAX_SHOWN_VALUES)
+                    .map(|value| match value {
+                        AExpr::Number { value } => Option::Some(value.clone()),
+                        _ => Option::None,
+                    })
+                    .collect(),
+                truncated: values.len() > CallFrame::MAX_SHOWN_VALUES,
+            });
+        }
+        CallFrame { id: id.to_string(), component: component.map(|name| name.to_string()), args }
+    }
+}
+
+impl std::fmt::Display for CallFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}(", self.id)?;
+        for (index, arg) in self.args.iter().enumerate() {
+            let values: Vec<String> = arg
+                .values
+                .iter()
+                .map(|value| value.as_ref().map_or("unknown".to_string(), |value| value.to_string()))
+                .collect();
+            let separator = if index == 0 { "" } else { ", " };
+            if !arg.is_array {
+                write!(f, "{}{} = {}", separator, arg.param, values.join(", "))?;
+            } else if arg.truncated {
+                write!(f, "{}{} = [{}, ...]", separator, arg.param, values.join(", "))?;
+            } else {
+                write!(f, "{}{} = [{}]", separator, arg.param, values.join(", "))?;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
 fn add_report_to_runtime(
     report: Report,
     meta: &Meta,
     runtime_errors: &mut ReportCollection,
-    call_trace: &[String],
+    call_trace: &[CallFrame],
 ) {
     let mut report = report;
     report.add_primary(meta.location.clone(), meta.get_file_id(), "found here".to_string());
 
+    // Deep recursions are shortened to their outermost and innermost calls
+    const SHOWN_CALLS: usize = 10;
     let mut trace = "call trace:\n".to_string();
     let mut spacing = "".to_string();
-    for call in call_trace.iter() {
-        let msg = format!("{}->{}\n", spacing, call);
+    // The calls of templates are shown with the path of their component, e.g. main.c[2]
+    let mut component_path = Vec::new();
+    for (index, call) in call_trace.iter().enumerate() {
+        if let Option::Some(component) = &call.component {
+            component_path.push(component.as_str());
+        }
+        let hidden = call_trace.len().saturating_sub(2 * SHOWN_CALLS);
+        if hidden > 0 && index >= SHOWN_CALLS && index < SHOWN_CALLS + hidden {
+            if index == SHOWN_CALLS {
+                trace.push_str(&format!("{}... {} more calls\n", spacing, hidden));
+            }
+            continue;
+        }
+        let msg = match &call.component {
+            Option::Some(_) => format!("{}->{} = {}\n", spacing, component_path.join("."), call),
+            Option::None => format!("{}->{}\n", spacing, call),
+        };
         trace.push_str(msg.as_str());
         spacing.push_str(" ");
     }
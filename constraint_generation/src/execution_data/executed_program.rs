@@ -4,6 +4,7 @@ use super::executed_template::{ExecutedTemplate, PreExecutedTemplate};
 use super::executed_bus::ExecutedBus;
 
 use super::type_definitions::*;
+use circom_algebra::constraint_storage::ConstraintStorage;
 use compiler::hir::very_concrete_program::{Stats, VCPConfig, VCP};
 use dag::DAG;
 use program_structure::program_archive::ProgramArchive;
@@ -20,6 +21,8 @@ pub struct ExecutedProgram {
     pub template_to_nodes: HashMap<String, Vec<NodePointer>>,
     pub bus_to_nodes: HashMap<String, Vec<NodePointer>>,
     pub prime: String,
+    // temporary file for the constraints of the instances with --low_memory
+    pub constraint_storage: Option<ConstraintStorage>,
 }
 
 impl ExecutedProgram {
@@ -31,6 +34,7 @@ impl ExecutedProgram {
             model_pretemplates: Vec::new(),
             model_buses: Vec::new(),
             bus_to_nodes: HashMap::new(),
+            constraint_storage: Option::None,
         }
     }
 
@@ -122,6 +126,9 @@ impl ExecutedProgram {
         self.template_to_nodes.entry(node.template_name().clone()).or_insert_with(|| vec![]);
         let nodes_for_template = self.template_to_nodes.get_mut(node.template_name()).unwrap();
         let node_index = self.model.len();
+        if let Option::Some(storage) = &mut self.constraint_storage {
+            node.store_constraints(storage);
+        }
         self.model.push(node);
         nodes_for_template.push(node_index);
         node_index
@@ -167,6 +174,9 @@ impl ExecutedProgram {
 
         let mut warnings = vec![];
         let mut dag = DAG::new(&self.prime);
+        if flags.low_memory {
+            dag.set_constraint_storage(ConstraintStorage::new_on_disk().expect("Could not create the temporary file of the constraints"));
+        }
         let mut temp_instances = Vec::with_capacity(self.model.len());
         let mut mixed_instances = vec![false; self.model.len()];
 
@@ -176,8 +186,9 @@ impl ExecutedProgram {
         }
 
         for exe in &mut self.model {
-            exe.insert_in_dag(&mut dag, &self.model_buses);
+            exe.insert_in_dag(&mut dag, &self.model_buses, self.constraint_storage.as_ref());
         }
+        self.constraint_storage = Option::None;
 
         let mut wrapped_buses_table = vec![None; self.model_buses.len()];
         let mut index = 0;
@@ -2,6 +2,7 @@ use super::executed_bus::BusConnexion;
 use super::type_definitions::*;
 use super::ExecutedBus;
 use circom_algebra::algebra::ArithmeticExpression;
+use circom_algebra::constraint_storage::{ConstraintID, ConstraintStorage};
 use compiler::hir::very_concrete_program::*;
 use dag::DAG;
 use num_bigint::BigInt;
@@ -61,6 +62,13 @@ impl PreExecutedTemplate {
 
 
 
+// Constraints of an instance written to the temporary file of --low_memory. Their
+// signals are numbered from 1 following the order of the names.
+pub struct StoredConstraints {
+    pub ids: std::ops::Range<ConstraintID>,
+    pub signals: Vec<String>,
+}
+
 pub struct ExecutedTemplate {
     pub code: Statement,
     pub template_name: String,
@@ -70,6 +78,7 @@ pub struct ExecutedTemplate {
     pub intermediates: WireCollector,
     pub ordered_signals: WireCollector,
     pub constraints: Vec<Constraint>,
+    pub stored_constraints: Option<StoredConstraints>,
     pub components: ComponentCollector,
     pub number_of_components: usize,
     pub public_inputs: HashSet<String>,
@@ -118,6 +127,7 @@ impl ExecutedTemplate {
             intermediates: WireCollector::new(),
             ordered_signals: WireCollector::new(),
             constraints: Vec::new(),
+            stored_constraints: Option::None,
             components: ComponentCollector::new(),
             number_of_components: 0,
             connexions: Vec::new(),
@@ -215,6 +225,24 @@ impl ExecutedTemplate {
         self.constraints.push(constraint);
     }
 
+    // Moves the constraints of the complete instance to the storage
+    pub fn store_constraints(&mut self, storage: &mut ConstraintStorage) {
+        let constant: String = Constraint::constant_coefficient();
+        let mut numbering = HashMap::new();
+        let mut signals = Vec::new();
+        let start = storage.len();
+        for constraint in std::mem::take(&mut self.constraints) {
+            for signal in constraint.take_cloned_signals() {
+                if signal != constant && !numbering.contains_key(&signal) {
+                    signals.push(signal.clone());
+                    numbering.insert(signal, signals.len());
+                }
+            }
+            storage.add_constraint(Constraint::apply_correspondence_and_drop(constraint, &numbering));
+        }
+        self.stored_constraints = Option::Some(StoredConstraints { ids: start..storage.len(), signals });
+    }
+
     pub fn add_underscored_signal(&mut self, signal: &str) {
         self.underscored_signals.push(signal.to_string());
     }
@@ -243,7 +271,7 @@ impl ExecutedTemplate {
         &self.intermediates
     }
 
-    pub fn insert_in_dag(&mut self, dag: &mut DAG, buses_info : &Vec<ExecutedBus>) {
+    pub fn insert_in_dag(&mut self, dag: &mut DAG, buses_info : &Vec<ExecutedBus>, storage: Option<&ConstraintStorage>) {
         let parameters = {
             let mut parameters = vec![];
             for (_, data) in self.parameter_instances.clone() {
@@ -264,7 +292,8 @@ impl ExecutedTemplate {
         self.build_wires(dag, buses_info);
         self.build_ordered_signals(dag, buses_info);
         self.build_connexions(dag);
-        self.build_constraints(dag);
+        self.build_constraints(dag, storage);
+        self.build_tags(dag);
     }
 
     fn build_wires(&self, dag: &mut DAG, buses_info : &Vec<ExecutedBus>) {
@@ -346,8 +375,18 @@ impl ExecutedTemplate {
         self.has_parallel_sub_cmp = dag.nodes[dag.main_id()].has_parallel_sub_cmp();
         dag.set_number_of_subcomponents_indexes(self.number_of_components);
     }
-    fn build_constraints(&self, dag: &mut DAG) {
-        
+    fn build_constraints(&self, dag: &mut DAG, storage: Option<&ConstraintStorage>) {
+        if let (Option::Some(stored), Option::Some(storage)) = (&self.stored_constraints, storage) {
+            let correspondence = dag.get_main().unwrap().correspondence();
+            let mut numbering = HashMap::new();
+            for (index, signal) in stored.signals.iter().enumerate() {
+                numbering.insert(index + 1, *correspondence.get(signal).unwrap());
+            }
+            for id in stored.ids.clone() {
+                let c = storage.read_constraint(id).unwrap();
+                dag.add_constraint(circom_algebra::algebra::Constraint::apply_correspondence_and_drop(c, &numbering));
+            }
+        }
         for c in &self.constraints {
             let correspondence = dag.get_main().unwrap().correspondence();
             let cc = Constraint::apply_correspondence(c, correspondence);
@@ -360,6 +399,22 @@ impl ExecutedTemplate {
         }
     }
 
+    // The {maxbit} tags of the io signals are used by the range analysis
+    fn build_tags(&self, dag: &mut DAG) {
+        for input in self.inputs() {
+            let tags = self.tag_instances.get(&input.name).filter(|wire| wire.fields.is_none());
+            if let Some(Some(bits)) = tags.and_then(|wire| wire.tags.get("maxbit")) {
+                dag.add_maxbit_tag(&input.name, bits.clone(), true);
+            }
+        }
+        for output in self.outputs() {
+            let key = vec![output.name.clone(), "maxbit".to_string()];
+            if let (false, Some(bits)) = (output.is_bus, self.signal_to_tags.get(&key)) {
+                dag.add_maxbit_tag(&output.name, bits.clone(), false);
+            }
+        }
+    }
+
     pub fn export_to_circuit(self, instances: &mut [TemplateInstance], buses_info : &Vec<BusInstance>) -> TemplateInstance {
         use SignalType::*;
         fn build_triggers(
@@ -43,6 +43,11 @@ fn clean_dead_code(stmt: &mut Statement, analysis: &Analysis, prime: &String) ->
             }
             stmts.is_empty()
         }
+        StaticAssert { meta, .. } => {
+            // Static assertions are only checked while the template is instantiated
+            *stmt = Block { meta: meta.clone(), stmts: vec![] };
+            true
+        }
         _ => false,
     }
 }
@@ -104,6 +109,7 @@ pub fn apply_computed(stmt: &mut Statement, analysis: &Analysis) {
             *rhe = computed_or_original(analysis, rhe);
             apply_computed_expr(rhe, analysis);
         },
+        StaticAssert { .. } => {}
     }
 }
 
@@ -12,14 +12,16 @@ use circom_algebra::algebra::{ArithmeticError, ArithmeticExpression};
 use compiler::hir::very_concrete_program::VCP;
 use constraint_list::ConstraintList;
 use constraint_writers::ConstraintExporter;
-use dag::DAG;
+use dag::{CircuitSize, OriginalCircuit, SignalBounds, DAG};
 use execution_data::executed_program::ExportResult;
 use execution_data::ExecutedProgram;
 use program_structure::ast::{self};
 use program_structure::error_code::ReportCode;
 use program_structure::error_definition::{Report, ReportCollection};
-use program_structure::file_definition::FileID;
+use program_structure::file_definition::{FileID, FileLibrary};
+use program_structure::lint_config::LintConfig;
 use program_structure::program_archive::ProgramArchive;
+use program_structure::template_budget::TemplateBudget;
 use std::rc::Rc;
 
 pub struct BuildConfig {
@@ -31,35 +33,124 @@ pub struct BuildConfig {
     pub flag_p: bool,
     pub flag_verbose: bool,
     pub flag_old_heuristics: bool,
-    pub inspect_constraints: bool,
+    pub flag_extended_sym: bool,
+    pub extended_sym: String,
+    pub keep_signals: Vec<String>,
+    pub flag_verify_simplification: bool,
+    pub low_memory: bool,
     pub prime: String,
+    pub limits: ExecutionLimits,
+    pub budgets: CircuitBudgets,
+    pub lint_config: LintConfig,
+}
+
+// Bounds on the execution of the program during the constraint generation, so that a
+// loop or recursion that does not terminate is reported instead of running forever.
+// A limit equal to 0 is not checked.
+#[derive(Debug, Copy, Clone)]
+pub struct ExecutionLimits {
+    pub max_loop_iterations: usize,
+    pub max_recursion_depth: usize,
+    pub max_steps: usize,
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        ExecutionLimits { max_loop_iterations: 1_000_000, max_recursion_depth: 1_000, max_steps: 0 }
+    }
+}
+
+// Limits on the size of the circuit, checked after its generation. The limits of the
+// whole circuit equal to 0 are not checked. The budgets of the templates are added to
+// the ones declared in the source.
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBudgets {
+    pub max_constraints: usize,
+    pub max_wires: usize,
+    pub templates: Vec<TemplateBudget>,
+}
+
+impl CircuitBudgets {
+    fn is_empty(&self) -> bool {
+        self.max_constraints == 0 && self.max_wires == 0 && self.templates.is_empty()
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
 pub struct FlagsExecution{
     pub verbose: bool,
     pub inspect: bool,
+    pub limits: ExecutionLimits,
+    pub low_memory: bool,
 }
 
 pub type ConstraintWriter = Box<dyn ConstraintExporter>;
 type BuildResponse = Result<(ConstraintWriter, VCP), ()>;
-pub fn build_circuit(program: ProgramArchive, config: BuildConfig) -> BuildResponse {
+pub fn build_circuit(mut program: ProgramArchive, mut config: BuildConfig) -> BuildResponse {
     let files = program.file_library.clone();
+    for budget in &config.budgets.templates {
+        if !program.contains_template(&budget.template) {
+            let msg = format!("warning: the template {} of --template_budget does not exist", budget.template);
+            println!("{}", Colour::Yellow.paint(msg));
+        }
+    }
+    config.budgets.templates.extend(program.template_budgets.iter().cloned());
+    let lints = &config.lint_config;
+    let inspect_constraints = lints.requires_inspection();
     let flags = FlagsExecution{
         verbose: config.flag_verbose,
-        inspect: config.inspect_constraints,
+        inspect: inspect_constraints,
+        limits: config.limits,
+        low_memory: config.low_memory,
     };
+    if config.low_memory {
+        check_temporary_files()?;
+    }
+    compute_constants::compute_global_constants(&mut program, flags, &config.prime).map_err(|r| {
+        Report::print_reports(&r, &files, lints);
+    })?;
     let (exe, warnings) = instantiation(&program, flags, &config.prime).map_err(|r| {
-        Report::print_reports(&r, &files);
+        Report::print_reports(&r, &files, lints);
     })?;
-    Report::print_reports(&warnings, &files);
+    if Report::print_reports(&warnings, &files, lints) {
+        return Result::Err(());
+    }
     let (mut dag, mut vcp, warnings) = export(exe, program, flags).map_err(|r| {
-        Report::print_reports(&r, &files);
+        Report::print_reports(&r, &files, lints);
     })?;
-    if config.inspect_constraints {
-        Report::print_reports(&warnings, &files);
+    if Report::print_reports(&warnings, &files, lints) {
+        return Result::Err(());
     }
-    if config.flag_f {
+    // The bounds of the signals are needed by the extended sym file and by the
+    // range warnings of --inspect
+    let bounds = if inspect_constraints || config.flag_extended_sym {
+        let (bounds, warnings) = dag.range_analysis();
+        if inspect_constraints && Report::print_reports(&warnings, &files, lints) {
+            return Result::Err(());
+        }
+        Option::Some(bounds)
+    } else {
+        Option::None
+    };
+    let unmatched: ReportCollection = dag.keep_signals(&config.keep_signals).iter().map(|pattern| {
+        Report::warning(
+            format!("No signal matches the name {} of --keep_signals", pattern),
+            ReportCode::UnmatchedSignalPattern,
+        )
+    }).collect();
+    if Report::print_reports(&unmatched, &files, lints) {
+        return Result::Err(());
+    }
+    let size = if config.budgets.is_empty() {
+        Option::None
+    } else {
+        let size = dag.measure_size();
+        if Report::print_reports(&size.check_templates(&config.budgets.templates), &files, lints) {
+            return Result::Err(());
+        }
+        Option::Some(size)
+    };
+    let exporter: ConstraintWriter = if config.flag_f {
         sync_dag_and_vcp(&mut vcp, &mut dag);
         if config.flag_json_sub { 
             use constraint_writers::json_writer::SubstitutionJSON;
@@ -68,13 +159,96 @@ pub fn build_circuit(program: ProgramArchive, config: BuildConfig) -> BuildRespo
             println!("{} {}", Colour::Green.paint("Written successfully:"), config.json_substitutions);
         };
 
-        Result::Ok((Box::new(dag), vcp))
+        if let Option::Some(size) = &size {
+            check_circuit_budgets(size, &config.budgets, size.constraints(), size.wires(), &files, lints)?;
+        }
+        Box::new(dag)
     } else {
+        let original = if config.flag_verify_simplification { Option::Some(dag.original_circuit()) } else { Option::None };
         let list = simplification_process(&mut vcp, dag, &config);
         if config.flag_json_sub { 
             println!("{} {}", Colour::Green.paint("Written successfully:"), config.json_substitutions);
         };
-        Result::Ok((Box::new(list), vcp))
+        if let Option::Some(original) = original {
+            verify_simplification(&original, &config.json_substitutions, &list)?;
+        }
+        let unsatisfiable = list.unsatisfiable_constraints();
+        if unsatisfiable > 0 {
+            let mut report = Report::error(
+                format!("{} of the simplified constraints do not depend on any signal and never hold, so the circuit can not be satisfied", unsatisfiable),
+                ReportCode::UnsatisfiableConstraint,
+            );
+            report.add_note("They come from constraints that contradict each other, as x === 1 and x === 2. The substitutions that lead to them can be seen with --simplification_substitution.".to_string());
+            Report::print_reports(&[report], &files, lints);
+            return Result::Err(());
+        }
+        if let Option::Some(size) = &size {
+            let constraints = list.constraints.get_ids().len();
+            check_circuit_budgets(size, &config.budgets, constraints, list.no_wires(), &files, lints)?;
+        }
+        Box::new(list)
+    };
+    if let (true, Option::Some(bounds)) = (config.flag_extended_sym, bounds) {
+        generate_output_extended_sym(&config.extended_sym, exporter.as_ref(), &bounds)?;
+    }
+    Result::Ok((exporter, vcp))
+}
+
+// With --low_memory the constraints are written to temporary files, which are created
+// when they are needed. One is created in advance to report the error instead of failing
+// in the middle of the compilation.
+fn check_temporary_files() -> Result<(), ()> {
+    use circom_algebra::constraint_storage::ConstraintStorage;
+    match ConstraintStorage::new_on_disk() {
+        Result::Ok(_) => Result::Ok(()),
+        Result::Err(error) => {
+            let msg = format!("Could not create the temporary file of the constraints in {}: {}", std::env::temp_dir().display(), error);
+            eprintln!("{}", Colour::Red.paint(msg));
+            Result::Err(())
+        }
+    }
+}
+
+fn check_circuit_budgets(
+    size: &CircuitSize,
+    budgets: &CircuitBudgets,
+    constraints: usize,
+    wires: usize,
+    files: &FileLibrary,
+    lints: &LintConfig,
+) -> Result<(), ()> {
+    let reports = size.check_circuit((constraints, budgets.max_constraints), (wires, budgets.max_wires));
+    if Report::print_reports(&reports, files, lints) {
+        Result::Err(())
+    } else {
+        Result::Ok(())
+    }
+}
+
+fn verify_simplification(original: &OriginalCircuit, substitutions: &str, list: &ConstraintList) -> Result<(), ()> {
+    match DAG::check_simplification(original, substitutions, list) {
+        Result::Ok(checked) => {
+            let msg = format!("{} constraints and {} substitutions", checked.constraints, checked.substitutions);
+            println!("{} {}", Colour::Green.paint("Simplification verified:"), msg);
+            Result::Ok(())
+        }
+        Result::Err(failures) => {
+            for failure in failures {
+                eprintln!("{}", Colour::Red.paint(failure));
+            }
+            eprintln!("{}", Colour::Red.paint("The simplification could not be verified"));
+            Result::Err(())
+        }
+    }
+}
+
+fn generate_output_extended_sym(file: &str, exporter: &dyn ConstraintExporter, bounds: &SignalBounds) -> Result<(), ()> {
+    if let Result::Ok(()) = exporter.extended_sym(file, bounds.as_map()) {
+        println!("{} {}", Colour::Green.paint("Written successfully:"), file);
+        Result::Ok(())
+    } else {
+        eprintln!("{}", Colour::Red.paint("Could not write the output in the given path"));
+        Result::Err(())
     }
 }
 
@@ -112,6 +286,7 @@ fn simplification_process(vcp: &mut VCP, dag: DAG, config: &BuildConfig) -> Cons
         json_substitutions: config.json_substitutions.clone(),
         no_rounds: config.no_rounds,
         flag_old_heuristics: config.flag_old_heuristics,
+        low_memory: config.low_memory,
         prime : config.prime.clone(),
     };
     let list = DAG::map_to_list(dag, flags);
@@ -1,5 +1,5 @@
 // Uncomment lines 163, 165, 336 and 338 to print cluster information
-use super::{ConstraintStorage, EncodingIterator, SEncoded, Simplifier, A, C, S};
+use super::{ConstraintID, ConstraintStorage, EncodingIterator, SEncoded, Simplifier, A, C, S};
 use crate::SignalMap;
 use circom_algebra::num_bigint::BigInt;
 use constraint_writers::json_writer::SubstitutionJSON;
@@ -16,13 +16,14 @@ fn log_substitutions(substitutions: &LinkedList<S>, writer: &mut Option<Substitu
     }
 }
 
+// The ids of the constraints of a cluster in the storage of the linear constraints
 #[derive(Default, Clone)]
 struct Cluster {
-    constraints: LinkedList<C>,
+    constraints: LinkedList<ConstraintID>,
     num_signals: usize
 }
 impl Cluster {
-    pub fn new(constraint: C, num_signals: usize) -> Cluster {
+    pub fn new(constraint: ConstraintID, num_signals: usize) -> Cluster {
         let mut new = Cluster::default();
         LinkedList::push_back(&mut new.constraints, constraint);
         new.num_signals = num_signals;
@@ -42,7 +43,7 @@ impl Cluster {
     }
 }
 
-fn build_clusters(linear: LinkedList<C>, no_vars: usize) -> Vec<Cluster> {
+fn build_clusters(linear: &ConstraintStorage, no_vars: usize) -> Vec<Cluster> {
     type ClusterArena = Vec<Option<Cluster>>;
     type ClusterPath = Vec<usize>;
     fn shrink_jumps_and_find(c_to_c: &mut ClusterPath, org: usize) -> usize {
@@ -68,15 +69,19 @@ fn build_clusters(linear: LinkedList<C>, no_vars: usize) -> Vec<Cluster> {
         c_to_c[current_src] = current_dest;
     }
 
-    let no_linear = LinkedList::len(&linear);
+    let no_linear = linear.len();
     let mut arena = ClusterArena::with_capacity(no_linear);
     let mut cluster_to_current = ClusterPath::with_capacity(no_linear);
     let mut signal_to_cluster = vec![no_linear; no_vars];
-    for constraint in linear {
-        if !constraint.is_empty(){
-            let signals = C::take_cloned_signals(&constraint);
+    let constant = C::constant_coefficient();
+    for c_id in linear.get_ids() {
+        let mut signals = linear.read_signals(c_id).unwrap();
+        if !signals.is_empty(){
+            signals.sort_unstable();
+            signals.dedup();
+            signals.retain(|signal| *signal != constant);
             let dest = ClusterArena::len(&arena);
-            ClusterArena::push(&mut arena, Some(Cluster::new(constraint, signals.len())));
+            ClusterArena::push(&mut arena, Some(Cluster::new(c_id, signals.len())));
             Vec::push(&mut cluster_to_current, dest);
             for signal in signals {
                 let prev = signal_to_cluster[signal];
@@ -98,11 +103,65 @@ fn build_clusters(linear: LinkedList<C>, no_vars: usize) -> Vec<Cluster> {
     clusters
 }
 
+// Maximum number of constraints of the clusters that are read from the storage at the
+// same time. With --low_memory only the clusters of a chunk are in memory, so the
+// memory used by the simplification of the linear constraints is bounded by the size
+// of the chunks and of the biggest cluster.
+const CHUNK_SIZE: usize = 1 << 16;
+
+// Simplifies the clusters in chunks in the threads of the pool. The results are passed
+// to consume in the order of the clusters, not in the order in which the threads
+// finish, so that the output does not change between runs.
+fn simplify_clusters<R, F>(storage: &ConstraintStorage, clusters: Vec<Cluster>, simplify: Arc<F>, consume: &mut dyn FnMut(R))
+where
+    R: Send + 'static,
+    F: Fn(LinkedList<C>, usize) -> R + Send + Sync + 'static,
+{
+    use std::sync::mpsc;
+    use threadpool::ThreadPool;
+    let pool = ThreadPool::new(num_cpus::get());
+    let mut clusters = clusters.into_iter().peekable();
+    while clusters.peek().is_some() {
+        let mut chunk = Vec::new();
+        let mut chunk_size = 0;
+        while let Some(cluster) = clusters.next_if(|c| chunk_size == 0 || chunk_size + c.size() <= CHUNK_SIZE) {
+            chunk_size += cluster.size();
+            chunk.push(cluster);
+        }
+        let (cluster_tx, simplified_rx) = mpsc::channel();
+        let mut results: Vec<Option<R>> = (0..chunk.len()).map(|_| None).collect();
+        let mut in_pool = 0;
+        for (id, cluster) in chunk.into_iter().enumerate() {
+            let constraints: LinkedList<C> =
+                cluster.constraints.iter().map(|c_id| storage.read_constraint(*c_id).unwrap()).collect();
+            if Cluster::size(&cluster) == 1 {
+                results[id] = Some(simplify(constraints, cluster.num_signals));
+            } else {
+                let cluster_tx = cluster_tx.clone();
+                let simplify = Arc::clone(&simplify);
+                let job = move || {
+                    let result = simplify(constraints, cluster.num_signals);
+                    cluster_tx.send((id, result)).unwrap();
+                };
+                ThreadPool::execute(&pool, job);
+                in_pool += 1;
+            }
+        }
+        for _ in 0..in_pool {
+            let (id, result) = simplified_rx.recv().unwrap();
+            results[id] = Some(result);
+        }
+        for result in results {
+            consume(result.unwrap());
+        }
+    }
+}
+
 fn rebuild_witness(
     max_signal: usize, 
     deleted: &mut HashSet<usize>, 
     forbidden: &HashSet<usize>, 
-    non_linear_map: SignalToConstraints, 
+    used: &HashSet<usize>,
     remove_unused: bool,
 ) -> SignalMap {
     let mut map = SignalMap::with_capacity(max_signal);
@@ -110,7 +169,7 @@ fn rebuild_witness(
     for signal in 0..max_signal {
         if deleted.contains(&signal) {
             free.push_back(signal);
-        } else if remove_unused && !forbidden.contains(&signal) && !non_linear_map.contains_key(&signal){
+        } else if remove_unused && !forbidden.contains(&signal) && !used.contains(&signal){
             deleted.insert(signal);
             free.push_back(signal);
         } else if let Some(new_pos) = free.pop_front() {
@@ -124,14 +183,14 @@ fn rebuild_witness(
 }
 
 fn eq_cluster_simplification(
-    mut cluster: Cluster,
+    mut cluster: LinkedList<C>,
     forbidden: &HashSet<usize>,
     field: &BigInt,
 ) -> (LinkedList<S>, LinkedList<C>) {
-    if Cluster::size(&cluster) == 1 {
+    if LinkedList::len(&cluster) == 1 {
         let mut substitutions = LinkedList::new();
         let mut constraints = LinkedList::new();
-        let constraint = LinkedList::pop_back(&mut cluster.constraints).unwrap();
+        let constraint = LinkedList::pop_back(&mut cluster).unwrap();
         let signals: Vec<_> = C::take_cloned_signals_ordered(&constraint).iter().cloned().collect();
         let s_0 = signals[0];
         let s_1 = signals[1];
@@ -156,15 +215,15 @@ fn eq_cluster_simplification(
         let mut cons = LinkedList::new();
         let mut subs = LinkedList::new();
         let (mut remains, mut min_remains) = (BTreeSet::new(), None);
-        let (mut remove, mut min_remove) = (HashSet::new(), None);
-        for c in cluster.constraints {
+        let (mut remove, mut min_remove) = (BTreeSet::new(), None);
+        for c in cluster {
             for signal in C::take_cloned_signals_ordered(&c) {
                 if HashSet::contains(&forbidden, &signal) {
                     BTreeSet::insert(&mut remains, signal);
                     min_remains = Some(min_remains.map_or(signal, |s| std::cmp::min(s, signal)));
                 } else {
                     min_remove = Some(min_remove.map_or(signal, |s| std::cmp::min(s, signal)));
-                    HashSet::insert(&mut remove, signal);
+                    BTreeSet::insert(&mut remove, signal);
                 }
             }
         }
@@ -174,7 +233,7 @@ fn eq_cluster_simplification(
             signal
         } else {
             let signal = min_remove.unwrap();
-            HashSet::remove(&mut remove, &signal);
+            BTreeSet::remove(&mut remove, &signal);
             signal
         };
 
@@ -196,75 +255,52 @@ fn eq_cluster_simplification(
 }
 
 fn eq_simplification(
-    equalities: LinkedList<C>,
+    equalities: ConstraintStorage,
     forbidden: Arc<HashSet<usize>>,
     no_vars: usize,
     field: &BigInt,
     substitution_log: &mut Option<SubstitutionJSON>,
 ) -> (LinkedList<S>, LinkedList<C>) {
-    use std::sync::mpsc;
-    use threadpool::ThreadPool;
     let field = Arc::new(field.clone());
     let mut constraints = LinkedList::new();
     let mut substitutions = LinkedList::new();
-    let clusters = build_clusters(equalities, no_vars);
-    let (cluster_tx, simplified_rx) = mpsc::channel();
-    let pool = ThreadPool::new(num_cpus::get());
-    let no_clusters = Vec::len(&clusters);
-    // println!("Clusters: {}", no_clusters);
-    let mut single_clusters = 0;
-    let mut id = 0;
-    let mut aux_constraints = vec![LinkedList::new(); clusters.len()];
-    for cluster in clusters {
-        if Cluster::size(&cluster) == 1 {
-            let (mut subs, cons) = eq_cluster_simplification(cluster, &forbidden, &field);
-            aux_constraints[id] = cons;
-            LinkedList::append(&mut substitutions, &mut subs);
-            single_clusters += 1;
-        } else {
-            let cluster_tx = cluster_tx.clone();
-            let forbidden = Arc::clone(&forbidden);
-            let field = Arc::clone(&field);
-            let job = move || {
-                //println!("Cluster: {}", id);
-                let result = eq_cluster_simplification(cluster, &forbidden, &field);
-                //println!("End of cluster: {}", id);
-                cluster_tx.send((id, result)).unwrap();
-            };
-            ThreadPool::execute(&pool, job);
-        }
-        let _ = id;
-        id += 1;
-    }
-    // println!("{} clusters were of size 1", single_clusters);
-    ThreadPool::join(&pool);
-    for _ in 0..(no_clusters - single_clusters) {
-        let (id, (mut subs, cons)) = simplified_rx.recv().unwrap();
-        aux_constraints[id] = cons;
+    let clusters = build_clusters(&equalities, no_vars);
+    // println!("Clusters: {}", clusters.len());
+    let simplify = move |cluster: LinkedList<C>, _: usize| eq_cluster_simplification(cluster, &forbidden, &field);
+    simplify_clusters(&equalities, clusters, Arc::new(simplify), &mut |(mut subs, mut cons)| {
+        LinkedList::append(&mut constraints, &mut cons);
         LinkedList::append(&mut substitutions, &mut subs);
-    }
-    for id in 0..no_clusters {
-        LinkedList::append(&mut constraints, &mut aux_constraints[id]);
-    }
+    });
     log_substitutions(&substitutions, substitution_log);
     (substitutions, constraints)
 }
 
 fn constant_eq_simplification(
-    c_eq: LinkedList<C>,
+    c_eq: ConstraintStorage,
     forbidden: &HashSet<usize>,
     field: &BigInt,
     substitution_log: &mut Option<SubstitutionJSON>,
 ) -> (LinkedList<S>, LinkedList<C>) {
     let mut cons = LinkedList::new();
     let mut subs = LinkedList::new();
-    for constraint in c_eq {
+    // A signal equal to several constants is only substituted once, and the rest of
+    // its constraints become constant ones, which are false if the values differ
+    let mut substituted: HashMap<usize, S> = HashMap::new();
+    for c_id in c_eq.get_ids() {
+        let mut constraint = c_eq.read_constraint(c_id).unwrap();
         let mut signals: Vec<_> = C::take_cloned_signals_ordered(&constraint).iter().cloned().collect();
         let signal = signals.pop().unwrap();
         if HashSet::contains(&forbidden, &signal) {
             LinkedList::push_back(&mut cons, constraint);
+        } else if let Some(previous) = substituted.get(&signal) {
+            C::apply_substitution(&mut constraint, previous, field);
+            C::fix_constraint(&mut constraint, field);
+            if !C::is_empty(&constraint) {
+                LinkedList::push_back(&mut cons, constraint);
+            }
         } else {
             let sub = C::clear_signal_from_linear(constraint, &signal, field);
+            substituted.insert(signal, sub.clone());
             LinkedList::push_back(&mut subs, sub);
         }
     }
@@ -272,56 +308,37 @@ fn constant_eq_simplification(
     (subs, cons)
 }
 
+// Passes the substitutions and the constraints that remain of every cluster to consume
 fn linear_simplification(
     log: &mut Option<SubstitutionJSON>,
-    linear: LinkedList<C>,
+    linear: &ConstraintStorage,
     forbidden: Arc<HashSet<usize>>,
     no_labels: usize,
     field: &BigInt,
     use_old_heuristics: bool,
-) -> (LinkedList<S>, LinkedList<C>) {
+    consume: &mut dyn FnMut(LinkedList<S>, LinkedList<C>),
+) {
     use circom_algebra::simplification_utils::full_simplification;
     use circom_algebra::simplification_utils::Config;
-    use std::sync::mpsc;
-    use threadpool::ThreadPool;
 
     // println!("Cluster simplification");
-    let mut cons = LinkedList::new();
-    let mut substitutions = LinkedList::new();
     let clusters = build_clusters(linear, no_labels);
-    let (cluster_tx, simplified_rx) = mpsc::channel();
-    let pool = ThreadPool::new(num_cpus::get());
-    let no_clusters = Vec::len(&clusters);
-    // println!("Clusters: {}", no_clusters);
-    let mut id = 0;
-    for cluster in clusters {
-        let cluster_tx = cluster_tx.clone();
+    // println!("Clusters: {}", clusters.len());
+    let field = field.clone();
+    let simplify = move |constraints: LinkedList<C>, num_signals: usize| {
         let config = Config {
             field: field.clone(),
-            constraints: cluster.constraints,
+            constraints,
             forbidden: Arc::clone(&forbidden),
-            num_signals: cluster.num_signals,
+            num_signals,
             use_old_heuristics,
         };
-        let job = move || {
-            // println!("cluster: {}", id);
-            let result = full_simplification(config);
-            // println!("End of cluster: {}", id);
-            cluster_tx.send(result).unwrap();
-        };
-        ThreadPool::execute(&pool, job);
-        let _ = id;
-        id += 1;
-    }
-    ThreadPool::join(&pool);
-
-    for _ in 0..no_clusters {
-        let mut result = simplified_rx.recv().unwrap();
+        full_simplification(config)
+    };
+    simplify_clusters(linear, clusters, Arc::new(simplify), &mut |result| {
         log_substitutions(&result.substitutions, log);
-        LinkedList::append(&mut cons, &mut result.constraints);
-        LinkedList::append(&mut substitutions, &mut result.substitutions);
-    }
-    (substitutions, cons)
+        consume(result.substitutions, result.constraints);
+    });
 }
 
 type SignalToConstraints = HashMap<usize, LinkedList<usize>>;
@@ -347,7 +364,7 @@ fn apply_substitution_to_map(
     map: &mut SignalToConstraints,
     substitutions: &LinkedList<S>,
     field: &BigInt,
-) -> LinkedList<C> {
+) -> ConstraintStorage {
     fn constraint_processing(
         storage: &mut ConstraintStorage,
         map: &mut SignalToConstraints,
@@ -386,10 +403,10 @@ fn apply_substitution_to_map(
             linear_id.append(&mut new_linear);
         }
     }
-    let mut linear = LinkedList::new();
+    let mut linear = storage.new_like();
     for c_id in linear_id {
         let constraint = storage.read_constraint(c_id).unwrap();
-        linear.push_back(constraint);
+        linear.add_constraint(constraint);
         storage.replace(c_id, C::empty());
     }
     linear
@@ -456,12 +473,12 @@ pub fn simplification(smp: &mut Simplifier) -> (ConstraintStorage, SignalMap, us
     let field = smp.field.clone();
     let forbidden = Arc::new(std::mem::replace(&mut smp.forbidden, HashSet::with_capacity(0)));
     let no_labels = Simplifier::no_labels(smp);
-    let equalities = std::mem::replace(&mut smp.equalities, LinkedList::new());
+    let equalities = std::mem::replace(&mut smp.equalities, ConstraintStorage::new());
     let max_signal = smp.max_signal;
-    let mut cons_equalities = std::mem::replace(&mut smp.cons_equalities, LinkedList::new());
-    let mut linear = std::mem::replace(&mut smp.linear, LinkedList::new());
+    let mut cons_equalities = std::mem::replace(&mut smp.cons_equalities, ConstraintStorage::new());
+    let mut linear = std::mem::replace(&mut smp.linear, ConstraintStorage::new());
     let mut deleted = HashSet::new();
-    let mut lconst = LinkedList::new();
+    let mut lconst = linear.new_like();
     let mut no_rounds = smp.no_rounds;
     let remove_unused = true;
 
@@ -481,7 +498,7 @@ pub fn simplification(smp: &mut Simplifier) -> (ConstraintStorage, SignalMap, us
     let single_substitutions = {
         // println!("Start of single assignment simplification");
         let now = SystemTime::now();
-        let (subs, mut cons) = eq_simplification(
+        let (subs, cons) = eq_simplification(
             equalities,
             Arc::clone(&forbidden),
             no_labels,
@@ -489,18 +506,18 @@ pub fn simplification(smp: &mut Simplifier) -> (ConstraintStorage, SignalMap, us
             &mut substitution_log,
         );
 
-        LinkedList::append(&mut lconst, &mut cons);
-        let mut substitutions = build_encoded_fast_substitutions(subs);
-        for constraint in &mut linear {
-            if fast_encoded_constraint_substitution(constraint, &substitutions, &field){
-                C::fix_constraint(constraint, &field);
-            }
+        for constraint in cons {
+            lconst.add_constraint(constraint);
         }
-        for constraint in &mut cons_equalities {
-            if fast_encoded_constraint_substitution(constraint, &substitutions, &field){
+        let mut substitutions = build_encoded_fast_substitutions(subs);
+        let substituted = |signal| substitutions.contains_key(&signal);
+        let mut substitute = |constraint: &mut C| {
+            if fast_encoded_constraint_substitution(constraint, &substitutions, &field) {
                 C::fix_constraint(constraint, &field);
             }
-        }
+        };
+        linear.apply(&substituted, &mut substitute);
+        cons_equalities.apply(&substituted, &mut substitute);
         for signal in substitutions.keys().cloned() {
             deleted.insert(signal);
         }
@@ -513,15 +530,17 @@ pub fn simplification(smp: &mut Simplifier) -> (ConstraintStorage, SignalMap, us
     let cons_substitutions = {
         // println!("Start of constant assignment simplification");
         let now = SystemTime::now();
-        let (subs, mut cons) =
+        let (subs, cons) =
             constant_eq_simplification(cons_equalities, &forbidden, &field, &mut substitution_log);
-        LinkedList::append(&mut lconst, &mut cons);
+        for constraint in cons {
+            lconst.add_constraint(constraint);
+        }
         let substitutions = build_encoded_fast_substitutions(subs);
-        for constraint in &mut linear {
-            if fast_encoded_constraint_substitution(constraint, &substitutions, &field){
+        linear.apply(&|signal| substitutions.contains_key(&signal), &mut |constraint| {
+            if fast_encoded_constraint_substitution(constraint, &substitutions, &field) {
                 C::fix_constraint(constraint, &field);
             }
-        }
+        });
         for signal in substitutions.keys().cloned() {
             deleted.insert(signal);
         }
@@ -543,39 +562,45 @@ pub fn simplification(smp: &mut Simplifier) -> (ConstraintStorage, SignalMap, us
 
     let linear_substitutions = if apply_linear {
         let now = SystemTime::now();
-        let (subs, mut cons) = linear_simplification(
+        // Only the substitutions of the signals of the non linear constraints are kept
+        let mut only_relevant = LinkedList::new();
+        linear_simplification(
             &mut substitution_log,
-            linear,
+            &linear,
             Arc::clone(&forbidden),
             no_labels,
             &field,
             use_old_heuristics,
+            &mut |subs, cons| {
+                for substitution in subs {
+                    deleted.insert(*substitution.from());
+                    if relevant_signals.contains(substitution.from()) {
+                        only_relevant.push_back(substitution);
+                    }
+                }
+                for constraint in cons {
+                    lconst.add_constraint(constraint);
+                }
+            },
         );
         // println!("Building substitution map");
         let now0 = SystemTime::now();
-        let mut only_relevant = LinkedList::new();
-        for substitution in subs {
-            deleted.insert(*substitution.from());
-            if relevant_signals.contains(substitution.from()) {
-                only_relevant.push_back(substitution);
-            }
-        }
         let substitutions = build_encoded_fast_substitutions(only_relevant);
         let _dur0 = now0.elapsed().unwrap().as_millis();
         // println!("End of substitution map: {} ms", dur0);
         let _dur = now.elapsed().unwrap().as_millis();
         // println!("End of cluster simplification: {} ms", dur);
-        LinkedList::append(&mut lconst, &mut cons);
-        for constraint in &mut lconst {
-            if fast_encoded_constraint_substitution(constraint, &substitutions, &field){
+        lconst.apply(&|signal| substitutions.contains_key(&signal), &mut |constraint| {
+            if fast_encoded_constraint_substitution(constraint, &substitutions, &field) {
                 C::fix_constraint(constraint, &field);
             }
-        }
+        });
         substitutions
     } else {
-        LinkedList::append(&mut lconst, &mut linear);
+        lconst.append(&linear);
         HashMap::with_capacity(0)
     };
+    drop(linear);
 
     let (with_linear, mut constraint_storage) = {
         // println!("Building constraint storage");
@@ -585,8 +610,13 @@ pub fn simplification(smp: &mut Simplifier) -> (ConstraintStorage, SignalMap, us
         LinkedList::push_back(&mut frames, cons_substitutions);
         LinkedList::push_back(&mut frames, linear_substitutions);
         let iter = EncodingIterator::new(&smp.dag_encoding);
-        let mut storage = ConstraintStorage::new();
-        let with_linear = obtain_and_simplify_non_linear(iter, &mut storage, &frames, &field);
+        let mut storage = if smp.low_memory {
+            ConstraintStorage::new_on_disk().expect("Could not create the temporary file of the constraints")
+        } else {
+            ConstraintStorage::new()
+        };
+        let mut with_linear = storage.new_like();
+        obtain_and_simplify_non_linear(iter, &mut storage, &mut with_linear, &frames, &field);
         crate::state_utils::empty_encoding_constraints(&mut smp.dag_encoding);
         let _dur = now.elapsed().unwrap().as_millis();
         // println!("Storages built in {} ms", dur);
@@ -613,31 +643,48 @@ pub fn simplification(smp: &mut Simplifier) -> (ConstraintStorage, SignalMap, us
     while apply_round {
         let now = SystemTime::now();
         // println!("Number of linear constraints: {}", linear.len());
-        let (substitutions, mut constants) = linear_simplification(
+        // The substitutions are only kept if their signal may appear in the non linear
+        // constraints or in lconst, where they are applied in order. A substitution may
+        // add the signals of its expression, which are tracked from then on.
+        let mut tracked: HashSet<usize> = non_linear_map.keys().cloned().collect();
+        for c_id in lconst.get_ids() {
+            tracked.extend(C::take_cloned_signals(&lconst.read_constraint(c_id).unwrap()));
+        }
+        let mut substitutions = LinkedList::new();
+        linear_simplification(
             &mut substitution_log,
-            linear,
+            &linear,
             Arc::clone(&forbidden),
             no_labels,
             &field,
             use_old_heuristics,
+            &mut |subs, cons| {
+                for constraint in cons {
+                    tracked.extend(C::take_cloned_signals(&constraint));
+                    lconst.add_constraint(constraint);
+                }
+                for substitution in subs {
+                    deleted.insert(*substitution.from());
+                    if tracked.contains(substitution.from()) {
+                        tracked.extend(substitution.to().keys().cloned());
+                        substitutions.push_back(substitution);
+                    }
+                }
+            },
         );
-
-        for sub in &substitutions {
-            deleted.insert(*sub.from());
-        }
-        lconst.append(&mut constants);
-        for constraint in &mut lconst {
+        lconst.apply(&|_| true, &mut |constraint| {
             for substitution in &substitutions {
                 C::apply_substitution(constraint, substitution, &field);
             }
             C::fix_constraint(constraint, &field);
-        }
+        });
         linear = apply_substitution_to_map(
             &mut constraint_storage,
             &mut non_linear_map,
             &substitutions,
             &field,
         );
+        constraint_storage.compact();
         round_id += 1;
         no_rounds -= 1;
         apply_round = !linear.is_empty() && no_rounds > 0;
@@ -645,44 +692,25 @@ pub fn simplification(smp: &mut Simplifier) -> (ConstraintStorage, SignalMap, us
         // println!("Iteration no {} took {} ms", round_id, dur);
     }
 
-    for constraint in linear {
+    // Only the signals of the constraints are needed to rebuild the witness
+    let mut used: HashSet<usize> = non_linear_map.into_keys().collect();
+    for c_id in linear.get_ids() {
+        let constraint = linear.read_constraint(c_id).unwrap();
         if remove_unused {
-            let signals =  C::take_cloned_signals(&constraint);
-            let c_id = constraint_storage.add_constraint(constraint);
-            for signal in signals {
-                if let Some(list) = non_linear_map.get_mut(&signal) {
-                    list.push_back(c_id);
-                } else {
-                    let mut new = LinkedList::new();
-                    new.push_back(c_id);
-                    non_linear_map.insert(signal, new);
-                }
-            }
-        }
-        else{
-            constraint_storage.add_constraint(constraint);
+            used.extend(C::take_cloned_signals(&constraint));
         }
+        constraint_storage.add_constraint(constraint);
     }
-    for mut constraint in lconst {
-        if remove_unused{
-            C::fix_constraint(&mut constraint, &field);
-            let signals =  C::take_cloned_signals(&constraint);
-            let c_id = constraint_storage.add_constraint(constraint);
-            for signal in signals {
-                if let Some(list) = non_linear_map.get_mut(&signal) {
-                    list.push_back(c_id);
-                } else {
-                    let mut new = LinkedList::new();
-                    new.push_back(c_id);
-                    non_linear_map.insert(signal, new);
-                }
-            }
-        }
-        else{
-            C::fix_constraint(&mut constraint, &field);
-            constraint_storage.add_constraint(constraint);
+    drop(linear);
+    for c_id in lconst.get_ids() {
+        let mut constraint = lconst.read_constraint(c_id).unwrap();
+        C::fix_constraint(&mut constraint, &field);
+        if remove_unused {
+            used.extend(C::take_cloned_signals(&constraint));
         }
+        constraint_storage.add_constraint(constraint);
     }
+    drop(lconst);
 
     let erased = crate::non_linear_simplification::simplify(
         &mut constraint_storage,
@@ -695,7 +723,7 @@ pub fn simplification(smp: &mut Simplifier) -> (ConstraintStorage, SignalMap, us
     }
 
     let _trash = constraint_storage.extract_with(&|c| C::is_empty(c));
-
+    constraint_storage.compact();
 
     let signal_map = {
         // println!("Rebuild witness");
@@ -704,7 +732,7 @@ pub fn simplification(smp: &mut Simplifier) -> (ConstraintStorage, SignalMap, us
             max_signal, 
             &mut deleted, 
             &forbidden, 
-            non_linear_map, 
+            &used, 
             remove_unused
         );
         let _dur = now.elapsed().unwrap().as_millis();
@@ -1,6 +1,6 @@
 use super::{ConstraintStorage, C, S};
 use crate::SignalMap;
-use circom_algebra::num_bigint::BigInt;
+use circom_algebra::field_element::FieldElement;
 use constraint_writers::debug_writer::DebugWriter;
 use json::JsonValue;
 use std::collections::HashMap;
@@ -13,13 +13,13 @@ pub fn transform_constraint_to_json(constraint: &C) -> JsonValue {
     ])
 }
 
-fn hashmap_as_json(values: &HashMap<usize, BigInt>) -> JsonValue {
+fn hashmap_as_json(values: &HashMap<usize, FieldElement>) -> JsonValue {
     let mut order: Vec<&usize> = values.keys().collect();
     order.sort();
     let mut correspondence = json::object! {};
     for i in order {
         let (key, value) = values.get_key_value(i).unwrap();
-        let value = value.to_str_radix(10);
+        let value = value.to_string();
         correspondence[format!("{}", key)] = value.as_str().into();
     }
     correspondence
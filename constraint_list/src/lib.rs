@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet, LinkedList};
 
-use circom_algebra::constraint_storage::ConstraintStorage;
+use circom_algebra::constraint_storage::{ConstraintID, ConstraintStorage};
 use circom_algebra::num_bigint::BigInt;
 use constraint_writers::debug_writer::DebugWriter;
 use constraint_writers::ConstraintExporter;
@@ -31,6 +31,8 @@ pub struct EncodingNode {
     pub signals: Vec<SignalInfo>,
     pub ordered_signals: Vec<usize>,
     pub non_linear: LinkedList<C>,
+    // ids in the constraint storage of the encoding of the rest of non linear constraints
+    pub stored_non_linear: Vec<usize>,
     pub is_custom_gate: bool,
 }
 
@@ -45,6 +47,8 @@ pub struct DAGEncoding {
     pub no_constraints: usize,
     pub nodes: Vec<EncodingNode>,
     pub adjacency: Vec<Vec<EncodingEdge>>,
+    // temporary file with the constraints of the nodes when compiling with --low_memory
+    pub constraint_storage: Option<ConstraintStorage>,
 }
 
 pub struct IteratorSignal {
@@ -114,9 +118,10 @@ pub struct Simplifier {
     pub no_public_outputs: usize,
     pub no_private_inputs: usize,
     pub forbidden: HashSet<usize>,
-    pub cons_equalities: LinkedList<C>,
-    pub equalities: LinkedList<C>,
-    pub linear: LinkedList<C>,
+    // the linear constraints, which are written to temporary files with --low_memory
+    pub cons_equalities: ConstraintStorage,
+    pub equalities: ConstraintStorage,
+    pub linear: ConstraintStorage,
     //  Signals in [witness_len, Vec::len(&signal_map)) are the ones deleted
     pub max_signal: usize,
     // Flags
@@ -124,6 +129,7 @@ pub struct Simplifier {
     pub parallel_flag: bool,
     pub flag_s: bool,
     pub flag_old_heuristics: bool,
+    pub low_memory: bool,
     pub port_substitution: bool,
     pub json_substitutions: String,
 }
@@ -177,6 +183,10 @@ impl ConstraintExporter for ConstraintList {
     fn sym(&self, out: &str) -> Result<(), ()> {
         sym_porting::port_sym(self, out)
     }
+
+    fn extended_sym(&self, out: &str, bounds: &HashMap<usize, BigInt>) -> Result<(), ()> {
+        sym_porting::port_extended_sym(self, out, bounds)
+    }
 }
 
 impl ConstraintList {
@@ -199,4 +209,25 @@ impl ConstraintList {
     pub fn no_wires(&self) -> usize {
         self.signal_map.len()
     }
+
+    // Constraints without signals that do not hold. The simplification produces them
+    // from constraints that contradict each other, as x === 1 and x === 2.
+    pub fn unsatisfiable_constraints(&self) -> usize {
+        use circom_algebra::field_element::{prime_field, FieldElement};
+        let field = prime_field(&self.field);
+        let constant = C::constant_coefficient();
+        let value = |lc: &HashMap<usize, FieldElement>| lc.get(&constant).cloned().unwrap_or_else(FieldElement::zero);
+        let mut unsatisfiable = 0;
+        for id in self.constraints.get_ids() {
+            let constraint = self.constraints.read_constraint(id).unwrap();
+            let has_signals = [constraint.a(), constraint.b(), constraint.c()]
+                .iter()
+                .any(|lc| lc.iter().any(|(signal, value)| *signal != constant && !value.is_zero()));
+            let product = field.mul(&value(constraint.a()), &value(constraint.b()));
+            if !has_signals && !field.sub(&product, &value(constraint.c())).is_zero() {
+                unsatisfiable += 1;
+            }
+        }
+        unsatisfiable
+    }
 }
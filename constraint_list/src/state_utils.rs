@@ -3,7 +3,9 @@ use super::*;
 pub fn empty_encoding_constraints(encoding: &mut DAGEncoding) {
     for node in &mut encoding.nodes {
         node.non_linear.clear();
+        node.stored_non_linear = Vec::with_capacity(0);
     }
+    encoding.constraint_storage = Option::None;
 }
 
 pub fn clear_encoding_iterator(iterator: &mut EncodingIterator) {
@@ -28,6 +30,13 @@ pub fn build_encoding_iterator(mut iterator: EncodingIterator) -> EncodingIterat
         let constraint = C::apply_offset(constraint, offset);
         LinkedList::push_back(&mut non_linear, constraint);
     }
+    if let Option::Some(storage) = &encoding.constraint_storage {
+        for id in &encoding.nodes[node_id].stored_non_linear {
+            let constraint = storage.read_constraint(*id).unwrap();
+            let constraint = C::apply_offset(&constraint, offset);
+            LinkedList::push_back(&mut non_linear, constraint);
+        }
+    }
     iterator.path = path;
     iterator.non_linear = non_linear;
     iterator.signals = signals;
@@ -1,11 +1,21 @@
 use super::{ConstraintList, EncodingIterator, IteratorSignal, SignalMap};
+use circom_algebra::num_bigint::BigInt;
 use circom_algebra::num_traits::AsPrimitive;
 use constraint_writers::sym_writer::*;
+use std::collections::HashMap;
 
 pub fn port_sym(list: &ConstraintList, file_name: &str) -> Result<(), ()> {
+    port(list, file_name, None)
+}
+
+pub fn port_extended_sym(list: &ConstraintList, file_name: &str, bounds: &HashMap<usize, BigInt>) -> Result<(), ()> {
+    port(list, file_name, Some(bounds))
+}
+
+fn port(list: &ConstraintList, file_name: &str, bounds: Option<&HashMap<usize, BigInt>>) -> Result<(), ()> {
     let iter = EncodingIterator::new(&list.dag_encoding);
     let mut dot_sym = SymFile::new(file_name)?;
-    signal_iteration(iter, &list.signal_map, &mut dot_sym)?;
+    signal_iteration(iter, &list.signal_map, bounds, &mut dot_sym)?;
     SymFile::finish_writing(dot_sym)?;
     //SymFile::close(dot_sym);
     Ok(())
@@ -14,6 +24,7 @@ pub fn port_sym(list: &ConstraintList, file_name: &str) -> Result<(), ()> {
 pub fn signal_iteration(
     mut iter: EncodingIterator,
     map: &SignalMap,
+    bounds: Option<&HashMap<usize, BigInt>>,
     dot_sym: &mut SymFile,
 ) -> Result<(), ()> {
     let (signals, _) = EncodingIterator::take(&mut iter);
@@ -26,12 +37,16 @@ pub fn signal_iteration(
             node_id: iter.node_id.as_(),
             symbol: signal.name.clone(),
         };
-        SymFile::write_sym_elem(dot_sym, sym_elem)?;
+        if let Some(bounds) = bounds {
+            SymFile::write_extended_sym_elem(dot_sym, sym_elem, bounds.get(&signal.original))?;
+        } else {
+            SymFile::write_sym_elem(dot_sym, sym_elem)?;
+        }
     }
 
     for edge in EncodingIterator::edges(&iter) {
         let next = EncodingIterator::next(&iter, edge);
-        signal_iteration(next, map, dot_sym)?;
+        signal_iteration(next, map, bounds, dot_sym)?;
     }
     Ok(())
 }
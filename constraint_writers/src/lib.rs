@@ -4,9 +4,15 @@ pub mod log_writer;
 pub mod r1cs_writer;
 pub mod sym_writer;
 pub mod r1cs_reader;
+pub mod sym_reader;
+
+use circom_algebra::num_bigint::BigInt;
+use std::collections::HashMap;
 
 pub trait ConstraintExporter {
     fn r1cs(&self, out: &str, custom_gates: bool) -> Result<(), ()>;
     fn json_constraints(&self, writer: &debug_writer::DebugWriter) -> Result<(), ()>;
     fn sym(&self, out: &str) -> Result<(), ()>;
+    // The sym file with the upper bound of each signal, indexed by its original id
+    fn extended_sym(&self, out: &str, bounds: &HashMap<usize, BigInt>) -> Result<(), ()>;
 }
@@ -447,6 +447,19 @@ impl R1CSData {
             custom_gates_applied_data: None,
         }
     }
+
+    pub fn header(&self) -> &HeaderData {
+        &self.header_data
+    }
+
+    pub fn constraints(&self) -> &ConstraintList {
+        &self.constraints
+    }
+
+    // The label, that is, the id in the sym file, of every wire
+    pub fn wire_labels(&self) -> &SignalList {
+        &self.signals
+    }
 }
 
 
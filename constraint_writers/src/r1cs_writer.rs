@@ -1,3 +1,4 @@
+use circom_algebra::field_element::FieldElement;
 use circom_algebra::num_bigint::BigInt;
 use std::collections::HashMap;
 use std::fs::File;
@@ -47,7 +48,7 @@ fn end_section(writer: &mut BufWriter<File>, go_back: u64, size: usize) -> Resul
 }
 
 fn obtain_linear_combination_block<T>(
-    linear_combination: &HashMap<T, BigInt>,
+    linear_combination: &HashMap<T, FieldElement>,
     field_size: usize,
 ) -> (Vec<u8>, usize) where T: AsRef<[u8]> + std::cmp::Ord + std::hash::Hash {
     let mut block = Vec::new();
@@ -64,7 +65,7 @@ fn obtain_linear_combination_block<T>(
         size += bytes;
         block.extend_from_slice(&stream);
 
-        let (stream, bytes) = bigint_as_bytes(factor, field_size);
+        let (stream, bytes) = into_format(&factor.to_bytes_le(), field_size);
         size += bytes;
         block.extend_from_slice(&stream);
     }
@@ -73,9 +74,9 @@ fn obtain_linear_combination_block<T>(
 
 fn write_constraint<T>(
     file: &mut BufWriter<File>,
-    a: &HashMap<T, BigInt>,
-    b: &HashMap<T, BigInt>,
-    c: &HashMap<T, BigInt>,
+    a: &HashMap<T, FieldElement>,
+    b: &HashMap<T, FieldElement>,
+    c: &HashMap<T, FieldElement>,
     field_size: usize,
 ) -> Result<usize, ()> where T: AsRef<[u8]> + std::cmp::Ord + std::hash::Hash {
     let (block_a, size_a) = obtain_linear_combination_block(a, field_size);
@@ -277,7 +278,7 @@ impl HeaderSection {
     }
 }
 
-type Constraint = HashMap<usize, BigInt>;
+type Constraint = HashMap<usize, FieldElement>;
 impl ConstraintSection {
     pub fn write_constraint_usize(
         &mut self,
@@ -0,0 +1,29 @@
+use super::sym_writer::SymElem;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+fn invalid_line(line: usize) -> std::io::Error {
+    std::io::Error::other(format!("Invalid sym line {}", line))
+}
+
+// Reads the lines original,witness,node_id,name of a sym file. The lines of the
+// extended format carry two more fields after the name, that are ignored
+pub fn read_sym(input: &str) -> Result<Vec<SymElem>, std::io::Error> {
+    let reader = File::open(input).map(BufReader::new)?;
+    let mut elems = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.splitn(4, ',');
+        let mut number = || -> Result<i64, std::io::Error> {
+            fields.next().and_then(|f| f.parse().ok()).ok_or_else(|| invalid_line(index + 1))
+        };
+        let (original, witness, node_id) = (number()?, number()?, number()?);
+        let rest = fields.next().ok_or_else(|| invalid_line(index + 1))?;
+        let symbol = rest.split(',').next().unwrap().to_string();
+        elems.push(SymElem { original, witness, node_id, symbol });
+    }
+    Ok(elems)
+}
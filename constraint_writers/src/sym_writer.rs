@@ -1,3 +1,4 @@
+use circom_algebra::num_bigint::BigInt;
 use std::fs::File;
 use std::io::{BufWriter, Write};
 
@@ -30,6 +31,17 @@ impl SymFile {
         //sym.writer.flush().map_err(|_err| {})
     }
     
+    // Extended format: the sym line followed by the number of bits and the maximum value
+    // proven for the signal, both empty when the signal is not bounded
+    pub fn write_extended_sym_elem(sym: &mut SymFile, elem: SymElem, max: Option<&BigInt>) -> Result<(), ()> {
+        let bound = match max {
+            Some(max) => format!("{},{}", max.bits(), max),
+            None => ",".to_string(),
+        };
+        let line = format!("{},{}\n", elem.to_string(), bound);
+        sym.writer.write_all(line.as_bytes()).map_err(|_err| {})
+    }
+
     pub fn finish_writing(mut sym: SymFile) -> Result<(), ()> {
 	sym.writer.flush().map_err(|_err| {})
     }
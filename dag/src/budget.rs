@@ -0,0 +1,184 @@
+use super::DAG;
+use program_structure::error_code::ReportCode;
+use program_structure::error_definition::{Report, ReportCollection};
+use program_structure::template_budget::TemplateBudget;
+use std::collections::HashMap;
+
+// Components listed when a budget is exceeded
+const MAX_CONTRIBUTORS: usize = 5;
+
+#[derive(Copy, Clone)]
+enum Measure {
+    Constraints,
+    Signals,
+}
+
+impl Measure {
+    fn name(&self) -> &'static str {
+        match self {
+            Measure::Constraints => "constraints",
+            Measure::Signals => "signals",
+        }
+    }
+}
+
+// Size of an instance of every node of the DAG, counting the constraints and
+// signals of its subcomponents, before the simplification
+pub struct CircuitSize {
+    // name of every node and of its template
+    templates: Vec<String>,
+    template_names: Vec<String>,
+    // path of the first instance of every node
+    paths: Vec<String>,
+    local_constraints: Vec<usize>,
+    local_signals: Vec<usize>,
+    constraints: Vec<usize>,
+    signals: Vec<usize>,
+    // label and node of the subcomponents of every node
+    children: Vec<Vec<(String, usize)>>,
+    main: usize,
+}
+
+pub fn measure(dag: &DAG) -> CircuitSize {
+    let number_of_nodes = dag.number_of_nodes();
+    let mut size = CircuitSize {
+        templates: Vec::with_capacity(number_of_nodes),
+        template_names: Vec::with_capacity(number_of_nodes),
+        paths: vec![String::new(); number_of_nodes],
+        local_constraints: Vec::with_capacity(number_of_nodes),
+        local_signals: Vec::with_capacity(number_of_nodes),
+        constraints: Vec::with_capacity(number_of_nodes),
+        signals: Vec::with_capacity(number_of_nodes),
+        children: Vec::with_capacity(number_of_nodes),
+        main: dag.main_id(),
+    };
+    // The subcomponents of a node are created before it
+    for (index, node) in dag.nodes.iter().enumerate() {
+        // the constraints stored on disk are never empty
+        let local_constraints =
+            node.constraints.iter().filter(|c| !c.is_empty()).count() + node.stored_constraints.len();
+        let mut constraints = local_constraints;
+        for edge in &dag.adjacency[index] {
+            constraints += size.constraints[edge.goes_to];
+        }
+        size.templates.push(node.template_name.clone());
+        size.template_names.push(node.template_name().to_string());
+        size.local_constraints.push(local_constraints);
+        size.local_signals.push(node.inputs_length + node.outputs_length + node.intermediates_length);
+        size.constraints.push(constraints);
+        size.signals.push(node.number_of_signals);
+        size.children.push(dag.adjacency[index].iter().map(|edge| (edge.label.clone(), edge.goes_to)).collect());
+    }
+    let mut pending = vec![(size.main, dag.get_entry().unwrap().label.clone())];
+    let mut visited = vec![false; number_of_nodes];
+    while let Some((node, path)) = pending.pop() {
+        if visited[node] {
+            continue;
+        }
+        visited[node] = true;
+        for (label, child) in size.children[node].iter().rev() {
+            pending.push((*child, format!("{}.{}", path, label)));
+        }
+        size.paths[node] = path;
+    }
+    size
+}
+
+impl CircuitSize {
+    pub fn constraints(&self) -> usize {
+        self.constraints[self.main]
+    }
+
+    // The signal 0 is the constant 1
+    pub fn wires(&self) -> usize {
+        self.signals[self.main] + 1
+    }
+
+    fn value(&self, node: usize, measure: Measure) -> usize {
+        match measure {
+            Measure::Constraints => self.constraints[node],
+            Measure::Signals => self.signals[node],
+        }
+    }
+
+    // The subcomponents of the node, and the node itself, that contribute the most
+    fn contributors(&self, node: usize, measure: Measure) -> Vec<String> {
+        let local = match measure {
+            Measure::Constraints => self.local_constraints[node],
+            Measure::Signals => self.local_signals[node],
+        };
+        let mut contributors = vec![(local, format!("{} (own {})", self.paths[node], measure.name()))];
+        for (label, child) in &self.children[node] {
+            let name = format!("{}.{} ({})", self.paths[node], label, self.templates[*child]);
+            contributors.push((self.value(*child, measure), name));
+        }
+        contributors.sort_by_key(|(value, _)| std::cmp::Reverse(*value));
+        contributors
+            .into_iter()
+            .filter(|(value, _)| *value > 0)
+            .take(MAX_CONTRIBUTORS)
+            .map(|(value, name)| format!("{}: {} {}", name, value, measure.name()))
+            .collect()
+    }
+
+    fn exceeded(&self, node: usize, measure: Measure, message: String) -> Report {
+        let mut report = Report::error(message, ReportCode::BudgetExceeded);
+        let contributors = self.contributors(node, measure);
+        if !contributors.is_empty() {
+            report.add_note(format!("The biggest contributors before the simplification are:\n{}", contributors.join("\n")));
+        }
+        report
+    }
+
+    // Every instance of a template with a budget is checked, once for each value of
+    // its parameters
+    pub fn check_templates(&self, budgets: &[TemplateBudget]) -> ReportCollection {
+        let mut by_template: HashMap<&str, Vec<&TemplateBudget>> = HashMap::new();
+        for budget in budgets {
+            by_template.entry(&budget.template).or_default().push(budget);
+        }
+        let mut reports = Vec::new();
+        for node in 0..self.templates.len() {
+            let template = self.template_names[node].as_str();
+            for budget in by_template.get(template).into_iter().flatten() {
+                for (measure, limit) in [(Measure::Constraints, budget.constraints), (Measure::Signals, budget.signals)] {
+                    let value = self.value(node, measure);
+                    if let Option::Some(limit) = limit.filter(|limit| value > *limit) {
+                        let message = format!(
+                            "The instance {} of template \"{}\" has {} {}, over its budget of {}",
+                            self.paths[node],
+                            self.templates[node],
+                            value,
+                            measure.name(),
+                            limit
+                        );
+                        let mut report = self.exceeded(node, measure, message);
+                        report.set_template(template.to_string());
+                        if let Option::Some((file_id, location)) = &budget.declaration {
+                            report.add_primary(location.clone(), *file_id, "budget declared here".to_string());
+                        }
+                        reports.push(report);
+                    }
+                }
+            }
+        }
+        reports
+    }
+
+    // The budgets of the whole circuit are checked against the sizes of its final
+    // version, while the contributors are measured before the simplification
+    pub fn check_circuit(&self, constraints: (usize, usize), wires: (usize, usize)) -> ReportCollection {
+        let mut reports = Vec::new();
+        for (measure, (value, limit), option) in [
+            (Measure::Constraints, constraints, "--max_constraints"),
+            (Measure::Signals, wires, "--max_wires"),
+        ] {
+            if limit != 0 && value > limit {
+                let kind = if let Measure::Constraints = measure { "constraints" } else { "wires" };
+                let message = format!("The circuit has {} {}, over the limit of {} of {}", value, kind, limit, option);
+                reports.push(self.exceeded(self.main, measure, message));
+            }
+        }
+        reports
+    }
+}
@@ -1,4 +1,5 @@
-use super::Node;
+use super::{uniqueness_analysis, Node, DAG};
+use circom_algebra::num_bigint::BigInt;
 use circom_algebra::algebra::Constraint;
 use program_structure::error_code::ReportCode;
 use program_structure::error_definition::{Report, ReportCollection};
@@ -12,15 +13,17 @@ const UNCONSTRAINED_IOSIGNAL_CODE: ReportCode = ReportCode::UnconstrainedIOSigna
 
 struct UnconstrainedSignal;
 impl UnconstrainedSignal {
-    pub fn new(signal: &str, template: &str, examples: &Vec<String>) -> Report {
+    pub fn new(signal: &str, template: &str, template_name: &str, examples: &Vec<String>) -> Report {
         
         if examples.len() == 1{
             let msg = format!("In template \"{}\": Local signal {} does not appear in any constraint", template, examples[0]);
-            let report = Report::warning(msg, UNCONSTRAINED_SIGNAL_CODE);
+            let mut report = Report::warning(msg, UNCONSTRAINED_SIGNAL_CODE);
+            report.set_template(template_name.to_string());
             report
         } else{
             let msg = format!("In template \"{}\": Array of local signals {} contains a total of {} signals that do not appear in any constraint", template, signal, examples.len());
             let mut report = Report::warning(msg, UNCONSTRAINED_SIGNAL_CODE);
+            report.set_template(template_name.to_string());
             let ex = format!("For example: {}, {}.", examples[0], examples[1]);
             report.add_note(ex);
             report
@@ -30,15 +33,17 @@ impl UnconstrainedSignal {
 
 struct UnconstrainedIOSignal;
 impl UnconstrainedIOSignal {
-    pub fn new(signal: &str, template: &str, examples: &Vec<String>) -> Report {
+    pub fn new(signal: &str, template: &str, template_name: &str, examples: &Vec<String>) -> Report {
         
         if examples.len() == 1{
             let msg = format!("In template \"{}\": Subcomponent input/output signal {} does not appear in any constraint of the father component", template, examples[0]);
-            let report = Report::warning(msg, UNCONSTRAINED_IOSIGNAL_CODE);
+            let mut report = Report::warning(msg, UNCONSTRAINED_IOSIGNAL_CODE);
+            report.set_template(template_name.to_string());
             report
         } else{
             let msg = format!("In template \"{}\": Array of subcomponent input/output signals {} contains a total of {} signals that do not appear in any constraint of the father component", template, signal, examples.len());
             let mut report = Report::warning(msg, UNCONSTRAINED_IOSIGNAL_CODE);
+            report.set_template(template_name.to_string());
             let ex = format!("For example: {}, {}.", examples[0], examples[1]);
             report.add_note(ex);
             report
@@ -52,6 +57,8 @@ enum SignalType {
     IOSubcomponent,
 }
 struct Analysis {
+    // the name of the node and the name of its template
+    instance_name: String,
     template_name: String,
     // signal name, type and number of appearances
     signal_stats: Vec<(String, SignalType, usize)>,
@@ -71,7 +78,8 @@ fn split_signal_name_index(name: &String)-> String{
 }
 
 fn analysis_interpretation(analysis: Analysis, result: &mut AnalysisResult) {
-    let tmp_name = analysis.template_name;
+    let tmp_name = analysis.instance_name;
+    let template_name = analysis.template_name;
     let stats = analysis.signal_stats;
 
     let mut signal2unconstrainedex: HashMap<String, (SignalType, Vec<String>)> = HashMap::new();
@@ -92,14 +100,14 @@ fn analysis_interpretation(analysis: Analysis, result: &mut AnalysisResult) {
     }
     for (name, (xtype, examples)) in signal2unconstrainedex{
         if xtype == SignalType::Local{
-            result.warnings.push(UnconstrainedSignal::new(&name, &tmp_name, &examples));
+            result.warnings.push(UnconstrainedSignal::new(&name, &tmp_name, &template_name, &examples));
         } else{
-            result.warnings.push(UnconstrainedIOSignal::new(&name, &tmp_name, &examples));
+            result.warnings.push(UnconstrainedIOSignal::new(&name, &tmp_name, &template_name, &examples));
         }
     }
 }
 
-fn visit_node(node: &Node) -> Analysis {
+fn visit_node(node: &Node, constraints: &[C]) -> Analysis {
 
     let mut constraint_counter = HashMap::new();
     let mut rev_correspondence = HashMap::new();
@@ -109,7 +117,7 @@ fn visit_node(node: &Node) -> Analysis {
             constraint_counter.insert(*id, 0);
         }
     }
-    for constraint in &node.constraints {
+    for constraint in constraints {
         let signals = constraint.take_cloned_signals();
         for signal in signals {
             let prev = constraint_counter.remove(&signal).unwrap();
@@ -134,7 +142,8 @@ fn visit_node(node: &Node) -> Analysis {
     }
     signal_stats.sort_by(|a, b| a.0.cmp(&b.0));
     Analysis {
-        template_name: node.template_name.clone(),
+        instance_name: node.template_name.clone(),
+        template_name: node.template_name().to_string(),
         signal_stats,
     }
 }
@@ -156,16 +165,19 @@ pub fn clean_constraints(nodes: &mut [Node]){
     }
 }
 
-pub fn analyse(nodes: &[Node]) -> AnalysisResult {
+pub fn analyse(dag: &DAG, field: &BigInt) -> AnalysisResult {
     let mut result = AnalysisResult { errors: vec![], warnings: vec![] };
     let mut visited : HashSet<String> = HashSet::new();
-    for node in nodes {
+    let nodes = &dag.nodes;
+    for (index, node) in nodes.iter().enumerate() {
         if !node.is_custom_gate() && !visited.contains(&node.template_name.clone()){
-            let analysis = visit_node(node);
+            let constraints = dag.constraints_of(index);
+            let analysis = visit_node(node, &constraints);
             let mut result2 = AnalysisResult { errors: vec![], warnings: vec![] };
             analysis_interpretation(analysis, &mut result2);    
             result.errors.append(&mut result2.errors);
             result.warnings.append(&mut result2.warnings);
+            result.warnings.append(&mut uniqueness_analysis::analyse(node, &constraints, &dag.adjacency[index], nodes, field));
             visited.insert(node.template_name.clone());
         }
     }
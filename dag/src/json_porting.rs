@@ -1,6 +1,6 @@
 use super::{Tree, DAG};
 use circom_algebra::algebra::Constraint;
-use circom_algebra::num_bigint::BigInt;
+use circom_algebra::field_element::FieldElement;
 use constraint_writers::debug_writer::DebugWriter;
 use constraint_writers::json_writer::ConstraintJSON;
 use json::JsonValue;
@@ -15,13 +15,13 @@ fn transform_constraint_to_json(constraint: &C) -> JsonValue {
         hashmap_as_json(constraint.c()),
     ])
 }
-fn hashmap_as_json(values: &HashMap<usize, BigInt>) -> JsonValue {
+fn hashmap_as_json(values: &HashMap<usize, FieldElement>) -> JsonValue {
     let mut order: Vec<&usize> = values.keys().collect();
     order.sort();
     let mut correspondence = json::object! {};
     for i in order {
         let (key, value) = values.get_key_value(i).unwrap();
-        let value = value.to_str_radix(10);
+        let value = value.to_string();
         correspondence[format!("{}", key)] = value.as_str().into();
     }
     correspondence
@@ -1,16 +1,26 @@
+mod budget;
 mod constraint_correctness_analysis;
 mod json_porting;
 mod map_to_constraint_list;
 mod r1cs_porting;
+mod range_analysis;
+mod simplification_check;
+mod uniqueness_analysis;
 mod sym_porting;
 mod witness_producer;
+use circom_algebra::constraint_storage::ConstraintStorage;
 use circom_algebra::num_bigint::BigInt;
 use constraint_list::ConstraintList;
 use constraint_writers::debug_writer::DebugWriter;
 use constraint_writers::ConstraintExporter;
 use program_structure::constants::UsefulConstants;
 use program_structure::error_definition::ReportCollection;
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+
+pub use budget::CircuitSize;
+pub use range_analysis::{coefficient, is_boolean, SignalBounds};
+pub use simplification_check::{CheckedSimplification, OriginalCircuit};
 type Signal = usize;
 type Constraint = circom_algebra::algebra::Constraint<usize>;
 type Substitution = circom_algebra::algebra::Substitution<usize>;
@@ -38,7 +48,7 @@ impl<'a> Tree<'a> {
         let node_id = dag.main_id();
         let offset = dag.get_entry().unwrap().in_number;
         let path = dag.get_entry().unwrap().label.clone();
-        let constraints = root.constraints.clone();
+        let constraints = dag.constraints_of(node_id).into_owned();
         let mut id_to_name = HashMap::new();
         let mut signals: Vec<_> = Vec::new();
         let forbidden: HashSet<_> =
@@ -70,8 +80,8 @@ impl<'a> Tree<'a> {
             }
         }
         signals.sort();
-        let constraints: Vec<_> = node
-            .constraints
+        let constraints: Vec<_> = dag
+            .constraints_of(node_id)
             .iter()
             .filter(|c| !c.is_empty())
             .map(|c| Constraint::apply_offset(c, offset))
@@ -149,14 +159,25 @@ pub struct Node {
     forbidden_if_main: HashSet<usize>,
     io_signals: Vec<usize>,
     constraints: Vec<Constraint>,
+    // ids in the storage of the DAG of the constraints written to disk with --low_memory
+    stored_constraints: Range,
     underscored_signals: Vec<usize>,
     is_parallel: bool,
     has_parallel_sub_cmp: bool,
     is_custom_gate: bool,
     number_of_subcomponents_indexes: usize,
+    // {maxbit} tags of the io signals: the ones of the inputs are required from the
+    // signals connected to them, the ones of the outputs are guaranteed by the template
+    maxbit_inputs: HashMap<usize, BigInt>,
+    maxbit_outputs: HashMap<usize, BigInt>,
 }
 
 impl Node {
+    // The name of a node includes the arguments of its template, e.g. Num2Bits(8)
+    pub fn template_name(&self) -> &str {
+        self.template_name.split('(').next().unwrap()
+    }
+
     fn new(
         id: usize,
         template_name: String,
@@ -229,6 +250,20 @@ impl Node {
         self.number_of_subcomponents_indexes = number_scmp
     }
 
+    fn add_maxbit_tag(&mut self, name: &str, bits: BigInt, is_input: bool) {
+        let array_prefix = format!("{}[", name);
+        let mut tagged = Vec::new();
+        for (signal, id) in &self.signal_correspondence {
+            if (signal == name || signal.starts_with(&array_prefix)) && self.io_signals.contains(id) {
+                tagged.push(*id);
+            }
+        }
+        let tags = if is_input { &mut self.maxbit_inputs } else { &mut self.maxbit_outputs };
+        for id in tagged {
+            tags.insert(id, bits.clone());
+        }
+    }
+
     pub fn parameters(&self) -> &Vec<BigInt> {
         &self.parameters
     }
@@ -249,8 +284,8 @@ impl Node {
         &self.signal_correspondence
     }
 
-    pub fn constraints(&self) -> &[Constraint] {
-        &self.constraints
+    pub fn number_of_constraints(&self) -> usize {
+        self.constraints.len() + self.stored_constraints.len()
     }
 
     pub fn io_signals(&self) -> &Vec<usize> {
@@ -299,6 +334,10 @@ pub struct DAG {
     pub nodes: Vec<Node>,
     pub adjacency: Vec<Vec<Edge>>,
     pub prime: String,
+    // signals that are not removed by the simplification, as the public ones
+    pub kept_signals: HashSet<usize>,
+    // temporary file with the constraints of the nodes when compiling with --low_memory
+    constraint_storage: Option<ConstraintStorage>,
 }
 
 impl ConstraintExporter for DAG {
@@ -313,6 +352,10 @@ impl ConstraintExporter for DAG {
     fn sym(&self, out: &str) -> Result<(), ()> {
         DAG::generate_sym_output(self, out)
     }
+
+    fn extended_sym(&self, out: &str, bounds: &HashMap<usize, BigInt>) -> Result<(), ()> {
+        sym_porting::write(self, out, Some(bounds))
+    }
 }
 
 impl DAG {
@@ -322,6 +365,24 @@ impl DAG {
             one_signal: 0,
             nodes: Vec::new(),
             adjacency: Vec::new(),
+            kept_signals: HashSet::new(),
+            constraint_storage: Option::None,
+        }
+    }
+
+    // The constraints added from now on are written to the storage instead of being
+    // kept in memory
+    pub fn set_constraint_storage(&mut self, storage: ConstraintStorage) {
+        self.constraint_storage = Option::Some(storage);
+    }
+
+    pub fn constraints_of(&self, node: usize) -> Cow<'_, [Constraint]> {
+        let node = &self.nodes[node];
+        match &self.constraint_storage {
+            Option::Some(storage) if !node.stored_constraints.is_empty() => Cow::Owned(
+                node.stored_constraints.clone().map(|id| storage.read_constraint(id).unwrap()).collect(),
+            ),
+            _ => Cow::Borrowed(&node.constraints),
         }
     }
 
@@ -409,8 +470,20 @@ impl DAG {
         }
     }
 
-    pub fn add_constraint(&mut self, constraint: Constraint) {
-        if let Option::Some(node) = self.get_mut_main() {
+    pub fn add_constraint(&mut self, mut constraint: Constraint) {
+        if let Option::Some(storage) = &mut self.constraint_storage {
+            // the constraints of the nodes are consecutive in the storage, and they are
+            // cleaned before as clean_constraints does with the ones in memory
+            Constraint::remove_zero_value_coefficients(&mut constraint);
+            if !constraint.is_empty() {
+                let id = storage.add_constraint(constraint);
+                let node = self.nodes.last_mut().unwrap();
+                if node.stored_constraints.is_empty() {
+                    node.stored_constraints = id..id;
+                }
+                node.stored_constraints.end = id + 1;
+            }
+        } else if let Option::Some(node) = self.get_mut_main() {
             node.add_constraint(constraint);
         }
     }
@@ -427,6 +500,12 @@ impl DAG {
         }
     }
 
+    pub fn add_maxbit_tag(&mut self, name: &str, bits: BigInt, is_input: bool) {
+        if let Option::Some(node) = self.get_mut_main() {
+            node.add_maxbit_tag(name, bits, is_input);
+        }
+    }
+
     pub fn get_node(&self, id: usize) -> Option<&Node> {
         if id < self.nodes.len() {
             Some(&self.nodes[id])
@@ -466,7 +545,8 @@ impl DAG {
     }
 
     pub fn constraint_analysis(&mut self) -> Result<ReportCollection, ReportCollection> {
-        let reports = constraint_correctness_analysis::analyse(&self.nodes);
+        let field = UsefulConstants::new(&self.prime).get_p().clone();
+        let reports = constraint_correctness_analysis::analyse(self, &field);
         if reports.errors.is_empty() {
             Ok(reports.warnings)
         } else {
@@ -474,6 +554,28 @@ impl DAG {
         }
     }
 
+    pub fn range_analysis(&self) -> (SignalBounds, ReportCollection) {
+        range_analysis::analyse(self)
+    }
+
+    // Size of every instance, used to check the budgets of the circuit and its templates
+    pub fn measure_size(&self) -> CircuitSize {
+        budget::measure(self)
+    }
+
+    // Marks the signals whose qualified name (main.c.out) matches one of the patterns
+    // to be kept by the simplification. Returns the patterns that match no signal
+    pub fn keep_signals(&mut self, patterns: &[String]) -> Vec<String> {
+        if patterns.is_empty() {
+            return Vec::new();
+        }
+        let mut matched = vec![false; patterns.len()];
+        let mut kept = HashSet::new();
+        find_signals(&Tree::new(self), patterns, &mut matched, &mut kept);
+        self.kept_signals.extend(kept);
+        patterns.iter().zip(matched).filter(|(_, m)| !m).map(|(p, _)| p.clone()).collect()
+    }
+
     pub fn clean_constraints(&mut self) {
         constraint_correctness_analysis::clean_constraints(&mut self.nodes);
     }
@@ -483,7 +585,7 @@ impl DAG {
     }
 
     pub fn generate_sym_output(&self, output_file: &str) -> Result<(), ()> {
-        sym_porting::write(self, output_file)
+        sym_porting::write(self, output_file, None)
     }
 
     pub fn generate_json_constraints(&self, debug: &DebugWriter) -> Result<(), ()> {
@@ -538,11 +640,56 @@ impl DAG {
         }
     }
 
+    pub fn original_circuit(&self) -> OriginalCircuit {
+        simplification_check::collect(self)
+    }
+
+    // Checks that the simplified constraints and the substitutions written in the
+    // given file are equivalent to the constraints of the original circuit
+    pub fn check_simplification(
+        original: &OriginalCircuit,
+        substitutions: &str,
+        list: &ConstraintList,
+    ) -> Result<CheckedSimplification, Vec<String>> {
+        simplification_check::check(original, substitutions, list)
+    }
+
     pub fn map_to_list(self, flags: SimplificationFlags) -> ConstraintList {
         map_to_constraint_list::map(self, flags)
     }
 }
 
+fn find_signals(tree: &Tree, patterns: &[String], matched: &mut [bool], kept: &mut HashSet<usize>) {
+    for signal in &tree.signals {
+        let name = format!("{}.{}", tree.path, tree.id_to_name[signal]);
+        for (index, pattern) in patterns.iter().enumerate() {
+            if matches_signal_pattern(pattern, &name) {
+                matched[index] = true;
+                kept.insert(*signal);
+            }
+        }
+    }
+    for edge in Tree::get_edges(tree) {
+        let subtree = Tree::go_to_subtree(tree, edge);
+        find_signals(&subtree, patterns, matched, kept);
+    }
+}
+
+// A pattern matches the signal with that name, the positions of the array with that
+// name and the signals of the component with that name. A * matches any sequence of
+// characters.
+fn matches_signal_pattern(pattern: &str, name: &str) -> bool {
+    fn glob(pattern: &[u8], name: &[u8]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some((b'*', rest)) => (0..=name.len()).any(|skip| glob(rest, &name[skip..])),
+            Some((c, rest)) => name.first() == Some(c) && glob(rest, &name[1..]),
+        }
+    }
+    let mut prefixes = name.char_indices().filter(|(_, c)| *c == '[' || *c == '.').map(|(i, _)| &name[..i]);
+    glob(pattern.as_bytes(), name.as_bytes()) || prefixes.any(|p| glob(pattern.as_bytes(), p.as_bytes()))
+}
+
 pub struct SimplificationFlags {
     pub no_rounds: usize,
     pub flag_s: bool,
@@ -550,5 +697,6 @@ pub struct SimplificationFlags {
     pub port_substitution: bool,
     pub json_substitutions: String,
     pub flag_old_heuristics: bool,
+    pub low_memory: bool,
     pub prime : String,
 }
@@ -1,12 +1,28 @@
 use super::{Constraint, Edge, Node, SimplificationFlags, Tree, DAG};
+use circom_algebra::constraint_storage::ConstraintStorage;
 use constraint_list::{ConstraintList, DAGEncoding, EncodingEdge, EncodingNode, SignalInfo, Simplifier};
 use program_structure::utils::constants::UsefulConstants;
 use std::collections::{HashSet, LinkedList};
-#[derive(Default)]
+
+// The linear constraints of the circuit, which are written to temporary files one
+// instance at a time with --low_memory
 struct CHolder {
-    linear: LinkedList<Constraint>,
-    equalities: LinkedList<Constraint>,
-    constant_equalities: LinkedList<Constraint>,
+    linear: ConstraintStorage,
+    equalities: ConstraintStorage,
+    constant_equalities: ConstraintStorage,
+}
+
+impl CHolder {
+    fn new(low_memory: bool) -> CHolder {
+        let storage = || {
+            if low_memory {
+                ConstraintStorage::new_on_disk().expect("Could not create the temporary file of the constraints")
+            } else {
+                ConstraintStorage::new()
+            }
+        };
+        CHolder { linear: storage(), equalities: storage(), constant_equalities: storage() }
+    }
 }
 
 fn map_tree(
@@ -26,11 +42,11 @@ fn map_tree(
 
     for constraint in &tree.constraints {
         if Constraint::is_constant_equality(constraint) {
-            LinkedList::push_back(&mut c_holder.constant_equalities, constraint.clone());
+            c_holder.constant_equalities.add_constraint(constraint.clone());
         } else if Constraint::is_equality(constraint, &tree.field) {
-            LinkedList::push_back(&mut c_holder.equalities, constraint.clone());
+            c_holder.equalities.add_constraint(constraint.clone());
         } else if Constraint::is_linear(constraint) {
-            LinkedList::push_back(&mut c_holder.linear, constraint.clone());
+            c_holder.linear.add_constraint(constraint.clone());
         } else {
             no_constraints += 1;
         }
@@ -48,12 +64,13 @@ fn produce_encoding(
     init: usize,
     dag_nodes: Vec<Node>,
     dag_edges: Vec<Vec<Edge>>,
+    constraint_storage: Option<ConstraintStorage>,
 ) -> DAGEncoding {
     let mut adjacency = Vec::new();
     let mut nodes = Vec::new();
     let mut id = 0;
     for node in dag_nodes {
-        let encoded = map_node_to_encoding(id, node);
+        let encoded = map_node_to_encoding(id, node, constraint_storage.as_ref());
         Vec::push(&mut nodes, encoded);
         id += 1;
     }
@@ -65,10 +82,10 @@ fn produce_encoding(
         }
         Vec::push(&mut adjacency, encoded);
     }
-    DAGEncoding { init, no_constraints, nodes, adjacency }
+    DAGEncoding { init, no_constraints, nodes, adjacency, constraint_storage }
 }
 
-fn map_node_to_encoding(id: usize, node: Node) -> EncodingNode {
+fn map_node_to_encoding(id: usize, node: Node, storage: Option<&ConstraintStorage>) -> EncodingNode {
     let mut signals = Vec::new();
     let mut ordered_signals = Vec::new();
     let locals = node.locals;
@@ -78,6 +95,14 @@ fn map_node_to_encoding(id: usize, node: Node) -> EncodingNode {
             LinkedList::push_back(&mut non_linear, c);
         }
     }
+    let mut stored_non_linear = Vec::new();
+    if let Option::Some(storage) = storage {
+        for c_id in node.stored_constraints {
+            if !Constraint::is_linear(&storage.read_constraint(c_id).unwrap()) {
+                stored_non_linear.push(c_id);
+            }
+        }
+    }
 
     for signal in node.ordered_signals {
         let signal_numbering = node.signal_correspondence.get(&signal).unwrap();
@@ -99,6 +124,7 @@ fn map_node_to_encoding(id: usize, node: Node) -> EncodingNode {
         signals,
         ordered_signals,
         non_linear,
+        stored_non_linear,
         is_custom_gate: node.is_custom_gate,
     }
 }
@@ -107,7 +133,7 @@ fn map_edge_to_encoding(edge: Edge) -> EncodingEdge {
     EncodingEdge { goes_to: edge.goes_to, path: edge.label, offset: edge.in_number }
 }
 
-pub fn map(dag: DAG, flags: SimplificationFlags) -> ConstraintList {
+pub fn map(mut dag: DAG, flags: SimplificationFlags) -> ConstraintList {
     use std::time::SystemTime;
     // println!("Start of dag to list mapping");
     let now = SystemTime::now();
@@ -118,11 +144,12 @@ pub fn map(dag: DAG, flags: SimplificationFlags) -> ConstraintList {
     let no_public_outputs = dag.public_outputs();
     let no_private_inputs = dag.private_inputs();
     let mut forbidden = dag.get_main().unwrap().forbidden_if_main.clone();
-    let mut c_holder = CHolder::default();
+    forbidden.extend(dag.kept_signals.iter().cloned());
+    let mut c_holder = CHolder::new(flags.low_memory);
     let mut signal_map = vec![0];
     let no_constraints = map_tree(&Tree::new(&dag), &mut signal_map, &mut c_holder, &mut forbidden);
     let max_signal = Vec::len(&signal_map);
-    let name_encoding = produce_encoding(no_constraints, init_id, dag.nodes, dag.adjacency);
+    let name_encoding = produce_encoding(no_constraints, init_id, dag.nodes, dag.adjacency, dag.constraint_storage.take());
     let _dur = now.elapsed().unwrap().as_millis();
     // println!("End of dag to list mapping: {} ms", dur);
     Simplifier {
@@ -140,8 +167,89 @@ pub fn map(dag: DAG, flags: SimplificationFlags) -> ConstraintList {
         flag_s: flags.flag_s,
         parallel_flag: flags.parallel_flag,
         flag_old_heuristics: flags.flag_old_heuristics,
+        low_memory: flags.low_memory,
         port_substitution: flags.port_substitution,
         json_substitutions: flags.json_substitutions,
     }
     .simplify_constraints()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use circom_algebra::field_element::FieldElement;
+    use circom_algebra::num_bigint::BigInt;
+    use std::collections::HashMap;
+
+    fn constraint(a: &[(usize, i64)], b: &[(usize, i64)], c: &[(usize, i64)]) -> Constraint {
+        let field = UsefulConstants::new(&"bn128".to_string()).get_p().clone();
+        let lc = |terms: &[(usize, i64)]| -> HashMap<usize, FieldElement> {
+            terms.iter().map(|(signal, value)| (*signal, FieldElement::new(&BigInt::from(*value), &field))).collect()
+        };
+        Constraint::new(lc(a), lc(b), lc(c))
+    }
+
+    // A main component with two instances of Square, where every template has a
+    // non linear constraint, the output of main has a linear one and the inputs of
+    // the instances are equal to the one of main
+    fn circuit(low_memory: bool) -> DAG {
+        let mut dag = DAG::new(&"bn128".to_string());
+        if low_memory {
+            dag.set_constraint_storage(ConstraintStorage::new_on_disk().unwrap());
+        }
+        dag.add_node("Square()".to_string(), Vec::new(), false, false);
+        dag.add_output("out".to_string());
+        dag.add_input("in".to_string(), false);
+        dag.add_constraint(constraint(&[(2, 1)], &[(2, 1)], &[(1, 1)]));
+        dag.add_node("Main()".to_string(), Vec::new(), false, false);
+        dag.add_output("out".to_string());
+        dag.add_input("in".to_string(), false);
+        dag.add_intermediate("product".to_string());
+        dag.add_edge(0, "a", false);
+        dag.add_edge(0, "b", false);
+        // in, out and product are 1 to 3, a.out and a.in 4 and 5, b.out and b.in 6 and 7
+        dag.add_constraint(constraint(&[], &[], &[(5, 1), (2, -1)]));
+        dag.add_constraint(constraint(&[], &[], &[(7, 1), (2, -1)]));
+        dag.add_constraint(constraint(&[(4, 1)], &[(6, 1)], &[(3, 1)]));
+        dag.add_constraint(constraint(&[], &[], &[(1, 1), (3, -2)]));
+        dag.clean_constraints();
+        dag
+    }
+
+    fn flags(low_memory: bool) -> SimplificationFlags {
+        SimplificationFlags {
+            no_rounds: usize::MAX,
+            flag_s: false,
+            parallel_flag: false,
+            port_substitution: false,
+            json_substitutions: String::new(),
+            flag_old_heuristics: false,
+            low_memory,
+            prime: "bn128".to_string(),
+        }
+    }
+
+    #[test]
+    fn low_memory_keeps_no_constraint_in_memory() {
+        let mut dag = circuit(true);
+        assert!(dag.nodes.iter().all(|node| node.constraints.is_empty()));
+        assert_eq!(dag.nodes.iter().map(Node::number_of_constraints).sum::<usize>(), 5);
+        let mut c_holder = CHolder::new(true);
+        let mut forbidden = HashSet::new();
+        let no_constraints = map_tree(&Tree::new(&dag), &mut vec![0], &mut c_holder, &mut forbidden);
+        assert!(c_holder.linear.is_on_disk() && c_holder.equalities.is_on_disk());
+        assert_eq!(c_holder.equalities.len() + c_holder.linear.len(), 3);
+        // The non linear constraints of every template are kept on disk, and only their
+        // ids are kept in the encoding
+        let storage = dag.constraint_storage.take();
+        let encoding = produce_encoding(no_constraints, dag.main_id(), dag.nodes, dag.adjacency, storage);
+        assert!(encoding.nodes.iter().all(|node| node.non_linear.is_empty()));
+        assert_eq!(encoding.nodes.iter().map(|node| node.stored_non_linear.len()).collect::<Vec<_>>(), vec![1, 1]);
+
+        let on_disk = circuit(true).map_to_list(flags(true));
+        let in_memory = circuit(false).map_to_list(flags(false));
+        assert!(on_disk.constraints.is_on_disk() && !in_memory.constraints.is_on_disk());
+        assert_eq!(on_disk.constraints.len(), 3);
+        assert_eq!(on_disk.constraints.len(), in_memory.constraints.len());
+    }
+}
@@ -0,0 +1,377 @@
+use super::{Tree, DAG};
+use circom_algebra::algebra::Constraint;
+use circom_algebra::field_element::{prime_field, FieldElement, PrimeField};
+use circom_algebra::num_bigint::BigInt;
+use circom_algebra::num_traits::{One, Zero};
+use program_structure::error_code::ReportCode;
+use program_structure::error_definition::{Report, ReportCollection};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+type C = Constraint<usize>;
+type LinearCombination = HashMap<usize, FieldElement>;
+const INSUFFICIENT_RANGE_CHECK_CODE: ReportCode = ReportCode::InsufficientRangeCheck;
+// Each constraint is revisited at most this number of times when the bound of one
+// of its signals improves, so that the analysis ends on cyclic dependencies
+const MAX_VISITS: usize = 8;
+// Constraints with more signals are not used to bound their signals
+const MAX_SIGNALS_PER_CONSTRAINT: usize = 512;
+
+// Upper bounds on the values of the signals of the circuit, indexed by the
+// identifier of the signal before the simplification
+pub struct SignalBounds {
+    bounds: HashMap<usize, BigInt>,
+}
+
+impl SignalBounds {
+    pub fn get(&self, signal: usize) -> Option<&BigInt> {
+        self.bounds.get(&signal)
+    }
+
+    pub fn as_map(&self) -> &HashMap<usize, BigInt> {
+        &self.bounds
+    }
+}
+
+// An input of a subcomponent with a {maxbit} tag
+struct Requirement {
+    signal: usize,
+    bits: BigInt,
+    parent: String,
+    name: String,
+    template: String,
+}
+
+#[derive(Default)]
+struct Problem {
+    constraints: Vec<C>,
+    sources: HashMap<usize, BigInt>,
+    requirements: Vec<Requirement>,
+}
+
+fn max_value_of_bits(bits: &BigInt, field: &BigInt) -> Option<BigInt> {
+    if bits >= &BigInt::from(field.bits()) {
+        None
+    } else {
+        let bits: usize = bits.to_string().parse().ok()?;
+        Some((BigInt::one() << bits) - BigInt::one())
+    }
+}
+
+// The constraints of every instance are moved from its tree to the problem
+fn collect(mut tree: Tree, problem: &mut Problem) {
+    let node = &tree.dag.nodes[tree.node_id];
+    problem.constraints.extend(std::mem::take(&mut tree.constraints).into_iter().filter(|c| !c.is_empty()));
+    for (signal, bits) in &node.maxbit_outputs {
+        if let Some(max) = max_value_of_bits(bits, &tree.field) {
+            let entry = problem.sources.entry(signal + tree.offset).or_insert_with(|| max.clone());
+            if max < *entry {
+                *entry = max;
+            }
+        }
+    }
+    for edge in Tree::get_edges(&tree) {
+        let subtree = Tree::go_to_subtree(&tree, edge);
+        let child = &tree.dag.nodes[edge.get_goes_to()];
+        for (name, signal) in child.correspondence() {
+            if let Some(bits) = child.maxbit_inputs.get(signal) {
+                problem.requirements.push(Requirement {
+                    signal: signal + subtree.offset,
+                    bits: bits.clone(),
+                    parent: node.template_name().to_string(),
+                    name: format!("{}.{}", edge.get_label(), name),
+                    template: child.template_name().to_string(),
+                });
+            }
+        }
+        collect(subtree, problem);
+    }
+}
+
+pub fn coefficient(lc: &LinearCombination, signal: usize) -> FieldElement {
+    lc.get(&signal).cloned().unwrap_or_else(FieldElement::zero)
+}
+
+pub(crate) fn is_constant(lc: &LinearCombination) -> bool {
+    lc.iter().all(|(signal, value)| *signal == 0 || value.is_zero())
+}
+
+fn scale(lc: &LinearCombination, factor: &FieldElement, field: &PrimeField) -> LinearCombination {
+    lc.iter().map(|(s, v)| (*s, field.mul(v, factor))).collect()
+}
+
+// Upper bound of the value of the linear combination when all its coefficients,
+// taken as numbers between 0 and p - 1, give a sum smaller than p
+fn max_of_linear(
+    lc: &LinearCombination,
+    except: Option<usize>,
+    bounds: &HashMap<usize, BigInt>,
+    field: &BigInt,
+) -> Option<BigInt> {
+    let mut total = BigInt::zero();
+    for (signal, value) in lc {
+        if Some(*signal) == except || value.is_zero() {
+            continue;
+        }
+        total += value.to_bigint() * bounds.get(signal)?;
+        if &total >= field {
+            return None;
+        }
+    }
+    Some(total)
+}
+
+// Constraints (a*x + b) * (c*x + d) = e*x + f whose only solutions are 0 and 1
+pub fn is_boolean(constraint: &C, signal: usize, field: &BigInt) -> bool {
+    let field = prime_field(field);
+    let (a, b) = (coefficient(constraint.a(), signal), coefficient(constraint.a(), 0));
+    let (c, d) = (coefficient(constraint.b(), signal), coefficient(constraint.b(), 0));
+    let (e, f) = (coefficient(constraint.c(), signal), coefficient(constraint.c(), 0));
+    let at_zero = field.sub(&field.mul(&b, &d), &f);
+    let at_one = field.sub(&field.mul(&field.add(&a, &b), &field.add(&c, &d)), &field.add(&e, &f));
+    let leading = field.mul(&a, &c);
+    at_zero.is_zero() && at_one.is_zero() && !leading.is_zero()
+}
+
+// Bounds of the signals of a constraint A * B = C that follow from the bounds of
+// the rest of its signals
+fn derive(constraint: &C, bounds: &HashMap<usize, BigInt>, field: &BigInt) -> Vec<(usize, BigInt)> {
+    let mut signals = HashSet::new();
+    for lc in [constraint.a(), constraint.b(), constraint.c()] {
+        signals.extend(lc.iter().filter(|(s, v)| **s != 0 && !v.is_zero()).map(|(s, _)| *s));
+    }
+    if signals.len() > MAX_SIGNALS_PER_CONSTRAINT {
+        return Vec::new();
+    }
+    if signals.len() == 1 {
+        let signal = *signals.iter().next().unwrap();
+        if is_boolean(constraint, signal, field) {
+            return vec![(signal, BigInt::one())];
+        }
+    }
+
+    // The constraint is seen as product + linear = 0
+    let prime = &*prime_field(field);
+    let minus_one = prime.neg(&FieldElement::one());
+    let (product, mut linear) = if is_constant(constraint.a()) {
+        (None, scale(constraint.b(), &coefficient(constraint.a(), 0), prime))
+    } else if is_constant(constraint.b()) {
+        (None, scale(constraint.a(), &coefficient(constraint.b(), 0), prime))
+    } else {
+        (Some((constraint.a(), constraint.b())), LinearCombination::new())
+    };
+    for (signal, value) in constraint.c() {
+        let sum = prime.sub(&coefficient(&linear, *signal), value);
+        linear.insert(*signal, sum);
+    }
+
+    let mut derived = Vec::new();
+    for (signal, value) in &linear {
+        if *signal == 0 || value.is_zero() {
+            continue;
+        }
+        if let Some((a, b)) = product {
+            if a.contains_key(signal) || b.contains_key(signal) {
+                continue;
+            }
+        }
+        // signal = factor * (product + rest of the linear part)
+        let factor = match prime.inverse(value) {
+            Some(inverse) => prime.mul(&minus_one, &inverse),
+            None => continue,
+        };
+        let rest = match max_of_linear(&scale(&linear, &factor, prime), Some(*signal), bounds, field) {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let product_bound = match product {
+            None => Some(BigInt::zero()),
+            Some((a, b)) => {
+                let opposite = prime.neg(&factor);
+                let same_sign = max_of_linear(&scale(a, &factor, prime), None, bounds, field)
+                    .zip(max_of_linear(b, None, bounds, field));
+                let both_negated = max_of_linear(&scale(a, &opposite, prime), None, bounds, field)
+                    .zip(max_of_linear(&scale(b, &minus_one, prime), None, bounds, field));
+                same_sign.into_iter().chain(both_negated).map(|(l, r)| l * r).min()
+            }
+        };
+        if let Some(product_bound) = product_bound {
+            let total = rest + product_bound;
+            if &total < field {
+                derived.push((*signal, total));
+            }
+        }
+    }
+    derived
+}
+
+fn compute_bounds(problem: &Problem, field: &BigInt) -> HashMap<usize, BigInt> {
+    let mut bounds = problem.sources.clone();
+    bounds.insert(0, BigInt::one());
+    let mut appearances: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (index, constraint) in problem.constraints.iter().enumerate() {
+        let mut signals: HashSet<usize> = constraint.a().keys().cloned().collect();
+        signals.extend(constraint.b().keys());
+        signals.extend(constraint.c().keys());
+        for signal in signals {
+            appearances.entry(signal).or_default().push(index);
+        }
+    }
+
+    let mut visits = vec![0; problem.constraints.len()];
+    let mut queued = vec![true; problem.constraints.len()];
+    let mut pending: VecDeque<usize> = (0..problem.constraints.len()).collect();
+    while let Some(index) = pending.pop_front() {
+        queued[index] = false;
+        visits[index] += 1;
+        for (signal, max) in derive(&problem.constraints[index], &bounds, field) {
+            if let Some(current) = bounds.get(&signal) {
+                if max >= *current {
+                    continue;
+                }
+            }
+            bounds.insert(signal, max);
+            for next in appearances.get(&signal).into_iter().flatten() {
+                if !queued[*next] && visits[*next] < MAX_VISITS {
+                    queued[*next] = true;
+                    pending.push_back(*next);
+                }
+            }
+        }
+    }
+    bounds
+}
+
+fn split_signal_name_index(name: &str) -> String {
+    match name.rfind('.') {
+        Some(dot) => {
+            let (component, signal) = name.split_at(dot + 1);
+            format!("{}{}", component, signal.split('[').next().unwrap())
+        }
+        None => name.split('[').next().unwrap().to_string(),
+    }
+}
+
+struct InsufficientRangeCheck;
+impl InsufficientRangeCheck {
+    pub fn new(requirement: &Requirement, proven: Option<&BigInt>, total: usize) -> Report {
+        let msg = format!(
+            "In template \"{}\": Signal {} requires {{maxbit}} = {} by template \"{}\", but its value is {}",
+            requirement.parent,
+            requirement.name,
+            requirement.bits,
+            requirement.template,
+            match proven {
+                Some(max) => format!("only proven to fit in {} bits", max.bits()),
+                None => "not proven to be bounded by the constraints".to_string(),
+            }
+        );
+        let mut report = Report::warning(msg, INSUFFICIENT_RANGE_CHECK_CODE);
+        report.set_template(requirement.parent.clone());
+        if total > 1 {
+            report.add_note(format!(
+                "The array {} contains a total of {} signals without the required bound.",
+                split_signal_name_index(&requirement.name),
+                total
+            ));
+        }
+        report.add_note(
+            "Constrain the signal, for instance with Num2Bits, before giving it the tag.".to_string(),
+        );
+        report
+    }
+}
+
+pub fn analyse(dag: &DAG) -> (SignalBounds, ReportCollection) {
+    let tree = Tree::new(dag);
+    let field = tree.field.clone();
+    let mut problem = Problem::default();
+    collect(tree, &mut problem);
+    let bounds = compute_bounds(&problem, &field);
+
+    // The failures are grouped by template and signal, as every instance of the
+    // template connects the same signal
+    let mut failures: Vec<(String, String)> = Vec::new();
+    let mut grouped: HashMap<(String, String), (usize, HashSet<&str>)> = HashMap::new();
+    for (index, requirement) in problem.requirements.iter().enumerate() {
+        let required = max_value_of_bits(&requirement.bits, &field);
+        let proven = bounds.get(&requirement.signal);
+        let satisfied = match (required, proven) {
+            (None, _) => true,
+            (Some(required), Some(proven)) => *proven <= required,
+            (Some(_), None) => false,
+        };
+        if !satisfied {
+            let key = (requirement.parent.clone(), split_signal_name_index(&requirement.name));
+            if !grouped.contains_key(&key) {
+                failures.push(key.clone());
+            }
+            grouped.entry(key).or_insert_with(|| (index, HashSet::new())).1.insert(&requirement.name);
+        }
+    }
+    let mut reports = Vec::new();
+    for key in failures {
+        let (index, names) = &grouped[&key];
+        let requirement = &problem.requirements[*index];
+        reports.push(InsufficientRangeCheck::new(requirement, bounds.get(&requirement.signal), names.len()));
+    }
+    (SignalBounds { bounds }, reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const FIELD: u64 = 257;
+
+    fn field() -> BigInt {
+        BigInt::from(FIELD)
+    }
+
+    fn lc(terms: &[(usize, i64)]) -> LinearCombination {
+        terms.iter().map(|(signal, value)| (*signal, FieldElement::new(&BigInt::from(*value), &field()))).collect()
+    }
+
+    fn bounds(values: &[(usize, u64)]) -> HashMap<usize, BigInt> {
+        values.iter().map(|(signal, max)| (*signal, BigInt::from(*max))).collect()
+    }
+
+    #[test]
+    fn is_boolean_check() {
+        let boolean = C::new(lc(&[(1, 1)]), lc(&[(1, 1), (0, -1)]), lc(&[]));
+        assert!(is_boolean(&boolean, 1, &field()));
+        let square = C::new(lc(&[(1, 1)]), lc(&[(1, 1)]), lc(&[]));
+        assert!(!is_boolean(&square, 1, &field()));
+        let zero_or_two = C::new(lc(&[(1, 1)]), lc(&[(1, 1), (0, -2)]), lc(&[]));
+        assert!(!is_boolean(&zero_or_two, 1, &field()));
+    }
+
+    #[test]
+    fn max_of_linear_check() {
+        let known = bounds(&[(1, 10), (2, 5)]);
+        assert_eq!(max_of_linear(&lc(&[(1, 2), (2, 3)]), None, &known, &field()), Some(BigInt::from(35)));
+        assert_eq!(max_of_linear(&lc(&[(1, 2), (2, 3)]), Some(2), &known, &field()), Some(BigInt::from(20)));
+        assert_eq!(max_of_linear(&lc(&[(1, 2), (3, 1)]), None, &known, &field()), None);
+        // -1 is taken as p - 1, so it only fits when the rest is 0
+        let bits = bounds(&[(1, 1), (2, 1)]);
+        assert_eq!(max_of_linear(&lc(&[(1, -1)]), None, &bits, &field()), Some(BigInt::from(FIELD - 1)));
+        assert_eq!(max_of_linear(&lc(&[(1, -1), (2, 1)]), None, &bits, &field()), None);
+        // The bound can not reach p
+        assert_eq!(max_of_linear(&lc(&[(1, 200), (2, 56)]), None, &bits, &field()), Some(BigInt::from(256)));
+        assert_eq!(max_of_linear(&lc(&[(1, 200), (2, 57)]), None, &bits, &field()), None);
+    }
+
+    #[test]
+    fn derive_check() {
+        let boolean = C::new(lc(&[(1, 1)]), lc(&[(1, 1), (0, -1)]), lc(&[]));
+        assert_eq!(derive(&boolean, &HashMap::new(), &field()), vec![(1, BigInt::one())]);
+        // in === b1 + 2 * b2 with boolean b1 and b2
+        let bits = bounds(&[(2, 1), (3, 1)]);
+        let sum = C::new(lc(&[]), lc(&[]), lc(&[(1, 1), (2, -1), (3, -2)]));
+        assert_eq!(derive(&sum, &bits, &field()), vec![(1, BigInt::from(3))]);
+        // out === a * b with a <= 3 and b <= 4
+        let product = C::new(lc(&[(2, 1)]), lc(&[(3, 1)]), lc(&[(1, 1)]));
+        assert_eq!(derive(&product, &bounds(&[(2, 3), (3, 4)]), &field()), vec![(1, BigInt::from(12))]);
+        // in === b1 + 128 * b2 + 128 * b3 may wrap around p = 257, so nothing is derived
+        let wrapping = C::new(lc(&[]), lc(&[]), lc(&[(1, 1), (2, -1), (3, -128), (4, -128)]));
+        assert_eq!(derive(&wrapping, &bounds(&[(2, 1), (3, 1), (4, 1)]), &field()), vec![]);
+    }
+}
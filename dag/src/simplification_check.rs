@@ -0,0 +1,517 @@
+use super::{Constraint, Tree, DAG};
+use circom_algebra::field_element::{prime_field, FieldElement, PrimeField};
+use circom_algebra::num_bigint::BigInt;
+use constraint_list::ConstraintList;
+use std::collections::{HashMap, HashSet};
+
+type LinearCombination = HashMap<usize, FieldElement>;
+// A quadratic polynomial over the signals, where the pair (i, j) with i <= j is the
+// product of the signals i and j. As the signal 0 is the constant 1, the pairs (0, j)
+// are the linear terms.
+type Polynomial = HashMap<(usize, usize), FieldElement>;
+type QuadraticTerms = Vec<((usize, usize), FieldElement)>;
+// Failures shown in detail, the rest are only counted
+const MAX_SHOWN_FAILURES: usize = 10;
+
+// The constraints of the circuit before the simplification, with the path of the
+// component that generated them
+pub struct OriginalCircuit {
+    field: BigInt,
+    paths: Vec<String>,
+    constraints: Vec<(usize, Constraint)>,
+    names: HashMap<usize, String>,
+}
+
+pub struct CheckedSimplification {
+    pub constraints: usize,
+    pub substitutions: usize,
+}
+
+pub fn collect(dag: &DAG) -> OriginalCircuit {
+    let tree = Tree::new(dag);
+    let mut circuit = OriginalCircuit {
+        field: tree.field.clone(),
+        paths: Vec::new(),
+        constraints: Vec::new(),
+        names: HashMap::new(),
+    };
+    visit_tree(tree, &mut circuit);
+    circuit
+}
+
+// The constraints of every instance are moved from its tree to the circuit
+fn visit_tree(mut tree: Tree, circuit: &mut OriginalCircuit) {
+    let path = circuit.paths.len();
+    circuit.paths.push(tree.path.clone());
+    for signal in &tree.signals {
+        let name = format!("{}.{}", tree.path, tree.id_to_name[signal]);
+        circuit.names.insert(*signal, name);
+    }
+    for constraint in std::mem::take(&mut tree.constraints) {
+        if !constraint.is_empty() {
+            circuit.constraints.push((path, constraint));
+        }
+    }
+    for edge in Tree::get_edges(&tree) {
+        let subtree = Tree::go_to_subtree(&tree, edge);
+        visit_tree(subtree, circuit);
+    }
+}
+
+fn read_substitutions(file: &str, field: &BigInt) -> Result<HashMap<usize, LinearCombination>, String> {
+    let content = std::fs::read_to_string(file)
+        .map_err(|_| format!("Could not read the substitutions of {}", file))?;
+    let log = json::parse(&content).map_err(|_| format!("The substitutions of {} are not valid json", file))?;
+    let invalid = || format!("The substitutions of {} do not have the format of --simplification_substitution", file);
+    let mut substitutions = HashMap::new();
+    for (from, to) in log.entries() {
+        let from: usize = from.parse().map_err(|_| invalid())?;
+        let mut expression = LinearCombination::new();
+        for (signal, value) in to.entries() {
+            let signal: usize = signal.parse().map_err(|_| invalid())?;
+            let value = value.as_str().and_then(|v| BigInt::parse_bytes(v.as_bytes(), 10)).ok_or_else(invalid)?;
+            expression.insert(signal, FieldElement::new(&value, field));
+        }
+        substitutions.insert(from, expression);
+    }
+    Ok(substitutions)
+}
+
+fn add_scaled(target: &mut LinearCombination, lc: &LinearCombination, factor: &FieldElement, field: &PrimeField) {
+    for (signal, value) in lc {
+        let term = field.mul(value, factor);
+        let sum = field.add(target.get(signal).unwrap_or(&FieldElement::zero()), &term);
+        if sum.is_zero() {
+            target.remove(signal);
+        } else {
+            target.insert(*signal, sum);
+        }
+    }
+}
+
+// Expresses every substituted signal in terms of signals that are not substituted.
+// Returns a signal of a cycle of substitutions if there is one.
+fn resolve_substitutions(
+    substitutions: &HashMap<usize, LinearCombination>,
+    field: &PrimeField,
+) -> Result<HashMap<usize, LinearCombination>, usize> {
+    let mut resolved: HashMap<usize, LinearCombination> = HashMap::new();
+    let mut in_progress = HashSet::new();
+    for root in substitutions.keys() {
+        let mut stack = vec![*root];
+        while let Some(signal) = stack.last().cloned() {
+            if resolved.contains_key(&signal) {
+                stack.pop();
+                continue;
+            }
+            let expression = &substitutions[&signal];
+            let pending: Vec<usize> = expression
+                .keys()
+                .filter(|s| substitutions.contains_key(s) && !resolved.contains_key(s))
+                .cloned()
+                .collect();
+            if pending.is_empty() {
+                let mut value = LinearCombination::new();
+                for (s, coefficient) in expression {
+                    match resolved.get(s) {
+                        Some(r) => add_scaled(&mut value, r, coefficient, field),
+                        None => add_scaled(&mut value, &HashMap::from([(*s, FieldElement::one())]), coefficient, field),
+                    }
+                }
+                resolved.insert(signal, value);
+                in_progress.remove(&signal);
+                stack.pop();
+            } else {
+                in_progress.insert(signal);
+                for next in pending {
+                    if in_progress.contains(&next) {
+                        return Err(next);
+                    }
+                    stack.push(next);
+                }
+            }
+        }
+    }
+    Ok(resolved)
+}
+
+fn substitute(lc: &LinearCombination, resolved: &HashMap<usize, LinearCombination>, field: &PrimeField) -> LinearCombination {
+    let mut result = LinearCombination::new();
+    for (signal, coefficient) in lc {
+        match resolved.get(signal) {
+            Some(value) => add_scaled(&mut result, value, coefficient, field),
+            None => add_scaled(&mut result, &HashMap::from([(*signal, FieldElement::one())]), coefficient, field),
+        }
+    }
+    result
+}
+
+// A * B - C after applying the substitutions
+fn polynomial(constraint: &Constraint, resolved: &HashMap<usize, LinearCombination>, field: &PrimeField) -> Polynomial {
+    let a = substitute(constraint.a(), resolved, field);
+    let b = substitute(constraint.b(), resolved, field);
+    let c = substitute(constraint.c(), resolved, field);
+    let mut result = Polynomial::new();
+    let mut add = |key: (usize, usize), value: FieldElement| {
+        let sum = field.add(result.get(&key).unwrap_or(&FieldElement::zero()), &value);
+        if sum.is_zero() {
+            result.remove(&key);
+        } else {
+            result.insert(key, sum);
+        }
+    };
+    for (i, x) in &a {
+        for (j, y) in &b {
+            add((*i.min(j), *i.max(j)), field.mul(x, y));
+        }
+    }
+    for (k, z) in &c {
+        add((0, *k), field.neg(z));
+    }
+    result
+}
+
+fn linear_part(polynomial: &Polynomial) -> LinearCombination {
+    polynomial.iter().filter(|((i, _), _)| *i == 0).map(|((_, j), v)| (*j, v.clone())).collect()
+}
+
+// The quadratic terms scaled so that the first one is 1, and the scale
+fn normalized_quadratic_part(polynomial: &Polynomial, field: &PrimeField) -> Option<(QuadraticTerms, FieldElement)> {
+    let mut terms: Vec<_> = polynomial.iter().filter(|((i, _), _)| *i != 0).collect();
+    if terms.is_empty() {
+        return None;
+    }
+    terms.sort_by_key(|(key, _)| **key);
+    let scale = terms[0].1.clone();
+    let inverse = field.inverse(&scale).unwrap();
+    let normalized = terms
+        .into_iter()
+        .map(|(key, value)| (*key, field.mul(value, &inverse)))
+        .collect();
+    Some((normalized, scale))
+}
+
+// Linear combinations in echelon form: the pivot of every row is its largest
+// signal, with coefficient 1
+#[derive(Default)]
+struct LinearBasis {
+    rows: HashMap<usize, LinearCombination>,
+}
+
+impl LinearBasis {
+    fn reduce(&self, mut lc: LinearCombination, field: &PrimeField) -> LinearCombination {
+        while let Some(top) = lc.keys().max().cloned() {
+            match self.rows.get(&top) {
+                Some(row) => {
+                    let factor = field.neg(&lc[&top]);
+                    add_scaled(&mut lc, row, &factor, field);
+                }
+                None => break,
+            }
+        }
+        lc
+    }
+
+    fn insert(&mut self, lc: LinearCombination, field: &PrimeField) {
+        let reduced = self.reduce(lc, field);
+        if let Some(top) = reduced.keys().max().cloned() {
+            let inverse = field.inverse(&reduced[&top]).unwrap();
+            let mut row = LinearCombination::new();
+            add_scaled(&mut row, &reduced, &inverse, field);
+            self.rows.insert(top, row);
+        }
+    }
+
+    fn contains(&self, lc: LinearCombination, field: &PrimeField) -> bool {
+        self.reduce(lc, field).is_empty()
+    }
+}
+
+// A system of constraints: the linear ones and the non linear ones indexed by their
+// normalized quadratic part
+#[derive(Default)]
+struct ConstraintSystem {
+    linear: LinearBasis,
+    non_linear: HashMap<QuadraticTerms, Vec<(FieldElement, LinearCombination)>>,
+}
+
+impl ConstraintSystem {
+    fn insert(&mut self, polynomial: &Polynomial, field: &PrimeField) {
+        match normalized_quadratic_part(polynomial, field) {
+            None => self.linear.insert(linear_part(polynomial), field),
+            Some((key, scale)) => self.non_linear.entry(key).or_default().push((scale, linear_part(polynomial))),
+        }
+    }
+
+    // The polynomial is a multiple of a constraint of the system plus a linear
+    // combination of the linear constraints of the system
+    fn implies(&self, polynomial: &Polynomial, field: &PrimeField) -> bool {
+        let linear = linear_part(polynomial);
+        match normalized_quadratic_part(polynomial, field) {
+            None => self.linear.contains(linear, field),
+            Some((key, scale)) => self.non_linear.get(&key).into_iter().flatten().any(|(other_scale, other)| {
+                let factor = field.mul(&scale, &field.inverse(other_scale).unwrap());
+                let mut rest = linear.clone();
+                add_scaled(&mut rest, other, &field.neg(&factor), field);
+                self.linear.contains(rest, field)
+            }),
+        }
+    }
+}
+
+fn signal_names(constraint: &Constraint, name: &dyn Fn(&usize) -> String) -> String {
+    let signals: HashSet<&usize> = constraint.a().keys().chain(constraint.b().keys()).chain(constraint.c().keys()).collect();
+    let mut signals: Vec<String> = signals.into_iter().filter(|s| **s != 0).map(name).collect();
+    signals.sort();
+    signals.join(", ")
+}
+
+// Keeps the first failures and counts the rest
+#[derive(Default)]
+struct Failures {
+    shown: Vec<String>,
+    hidden: usize,
+}
+
+impl Failures {
+    fn push(&mut self, failure: impl FnOnce() -> String) {
+        if self.shown.len() < MAX_SHOWN_FAILURES {
+            self.shown.push(failure());
+        } else {
+            self.hidden += 1;
+        }
+    }
+
+    fn into_result(mut self) -> Result<(), Vec<String>> {
+        if self.hidden > 0 {
+            self.shown.push(format!("{} more failures are not shown", self.hidden));
+        }
+        if self.shown.is_empty() {
+            Ok(())
+        } else {
+            Err(self.shown)
+        }
+    }
+}
+
+// The substitutions that are not linear combinations of the linear original constraints.
+// A non linear constraint becomes linear once some signals are substituted, as
+// b === a * x after a === 3, so the substitutions are verified in rounds: the original
+// constraints that become linear after the verified substitutions are used to verify
+// the next ones.
+fn unimplied_substitutions(original: &OriginalCircuit, substitutions: &HashMap<usize, LinearCombination>) -> Vec<usize> {
+    let field = &*prime_field(&original.field);
+    let mut basis = LinearBasis::default();
+    let mut non_linear = Vec::new();
+    for (_, constraint) in &original.constraints {
+        let polynomial = polynomial(constraint, &HashMap::new(), field);
+        match normalized_quadratic_part(&polynomial, field) {
+            None => basis.insert(linear_part(&polynomial), field),
+            Some(_) => non_linear.push(constraint),
+        }
+    }
+    let mut pending: Vec<usize> = substitutions.keys().cloned().collect();
+    pending.sort();
+    let mut verified = HashMap::new();
+    loop {
+        let before = pending.len();
+        pending.retain(|signal| {
+            let mut difference = HashMap::from([(*signal, FieldElement::one())]);
+            add_scaled(&mut difference, &substitutions[signal], &field.neg(&FieldElement::one()), field);
+            let implied = basis.contains(difference, field);
+            if implied {
+                verified.insert(*signal, substitutions[signal].clone());
+            }
+            !implied
+        });
+        if pending.len() == before || pending.is_empty() {
+            return pending;
+        }
+        // the verified substitutions are a subset of substitutions without cycles
+        let resolved = resolve_substitutions(&verified, field).ok().unwrap();
+        non_linear.retain(|constraint| {
+            let polynomial = polynomial(constraint, &resolved, field);
+            let still_non_linear = normalized_quadratic_part(&polynomial, field).is_some();
+            if !still_non_linear {
+                basis.insert(linear_part(&polynomial), field);
+            }
+            still_non_linear
+        });
+    }
+}
+
+pub fn check(
+    original: &OriginalCircuit,
+    substitutions_file: &str,
+    list: &ConstraintList,
+) -> Result<CheckedSimplification, Vec<String>> {
+    let substitutions = read_substitutions(substitutions_file, &original.field).map_err(|e| vec![e])?;
+    let simplified = || list.constraints.get_ids().into_iter().map(|id| list.constraints.read_constraint(id).unwrap());
+    let witness = list.get_witness();
+    verify(original, &substitutions, simplified, &|signal| witness.contains_key(signal))
+}
+
+// Checks that the simplified constraints and the substitutions are equivalent to the
+// original constraints: every substitution and every simplified constraint is implied
+// by the original constraints, and every original constraint is implied by the
+// simplified ones after the substitutions. The simplified constraints are read twice, so they are given
+// by a function that iterates over them.
+fn verify<I: Iterator<Item = Constraint>>(
+    original: &OriginalCircuit,
+    substitutions: &HashMap<usize, LinearCombination>,
+    simplified: impl Fn() -> I,
+    in_witness: &dyn Fn(&usize) -> bool,
+) -> Result<CheckedSimplification, Vec<String>> {
+    let field = &*prime_field(&original.field);
+    let name = |signal: &usize| original.names.get(signal).cloned().unwrap_or_else(|| format!("signal {}", signal));
+    let resolved = resolve_substitutions(substitutions, field)
+        .map_err(|s| vec![format!("The substitutions of {} form a cycle", name(&s))])?;
+    let mut failures = Failures::default();
+
+    for signal in unimplied_substitutions(original, substitutions) {
+        failures.push(|| format!("The substitution of {} is not implied by the original constraints", name(&signal)));
+    }
+
+    // The original constraints are implied by the simplified ones
+    let mut simplified_system = ConstraintSystem::default();
+    for constraint in simplified() {
+        simplified_system.insert(&polynomial(&constraint, &resolved, field), field);
+    }
+    for (path, constraint) in &original.constraints {
+        if !simplified_system.implies(&polynomial(constraint, &resolved, field), field) {
+            failures.push(|| {
+                format!(
+                    "A constraint of {} over {} is not implied by the simplified constraints",
+                    original.paths[*path],
+                    signal_names(constraint, &name)
+                )
+            });
+        }
+    }
+    drop(simplified_system);
+
+    // The simplified constraints are implied by the original ones after the substitutions
+    let mut original_system = ConstraintSystem::default();
+    for (_, constraint) in &original.constraints {
+        original_system.insert(&polynomial(constraint, &resolved, field), field);
+    }
+    for constraint in simplified() {
+        if !original_system.implies(&polynomial(&constraint, &resolved, field), field) {
+            failures.push(|| {
+                format!("A simplified constraint over {} is not implied by the original constraints", signal_names(&constraint, &name))
+            });
+        }
+    }
+
+    // A removed signal is recomputed from its substitution, which can only use
+    // signals of the witness
+    let mut not_recomputable: Vec<&usize> = resolved
+        .iter()
+        .filter(|(_, value)| value.keys().any(|s| *s != 0 && !in_witness(s)))
+        .map(|(signal, _)| signal)
+        .collect();
+    not_recomputable.sort();
+    for signal in not_recomputable {
+        failures.push(|| format!("{} can not be recomputed from the witness, as its substitution depends on signals that are not in it", name(signal)));
+    }
+
+    failures.into_result()?;
+    Ok(CheckedSimplification { constraints: original.constraints.len(), substitutions: substitutions.len() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field() -> BigInt {
+        BigInt::from(257)
+    }
+
+    fn lc(terms: &[(usize, i64)]) -> LinearCombination {
+        terms.iter().map(|(signal, value)| (*signal, FieldElement::new(&BigInt::from(*value), &field()))).collect()
+    }
+
+    // main.out <== main.a * main.b; main.c <== main.out; main.d <== main.c + 1
+    fn circuit() -> OriginalCircuit {
+        OriginalCircuit {
+            field: field(),
+            paths: vec!["main".to_string()],
+            constraints: vec![
+                (0, Constraint::new(lc(&[(1, 1)]), lc(&[(2, 1)]), lc(&[(3, 1)]))),
+                (0, Constraint::new(lc(&[]), lc(&[]), lc(&[(4, 1), (3, -1)]))),
+                (0, Constraint::new(lc(&[]), lc(&[]), lc(&[(5, 1), (4, -1), (0, -1)]))),
+            ],
+            names: ["a", "b", "out", "c", "d"].iter().enumerate().map(|(i, n)| (i + 1, format!("main.{}", n))).collect(),
+        }
+    }
+
+    // c and d are removed, and out <== a * b is the only simplified constraint
+    fn substitutions(c: &[(usize, i64)]) -> HashMap<usize, LinearCombination> {
+        HashMap::from([(4, lc(c)), (5, lc(&[(4, 1), (0, 1)]))])
+    }
+
+    fn run(substitutions: &HashMap<usize, LinearCombination>, simplified: &[Constraint]) -> Result<CheckedSimplification, Vec<String>> {
+        verify(&circuit(), substitutions, || simplified.iter().cloned(), &|signal| *signal <= 3)
+    }
+
+    fn product() -> Constraint {
+        Constraint::new(lc(&[(1, 1)]), lc(&[(2, 1)]), lc(&[(3, 1)]))
+    }
+
+    #[test]
+    fn correct_simplification_is_verified() {
+        let checked = run(&substitutions(&[(3, 1)]), &[product()]).ok().unwrap();
+        assert_eq!((checked.constraints, checked.substitutions), (3, 2));
+    }
+
+    #[test]
+    fn tampered_substitution_is_rejected() {
+        // the substitution of main.c, written by the simplification as {"3":"1"}
+        let file = std::env::temp_dir().join(format!("tampered_substitutions_{}.json", std::process::id()));
+        std::fs::write(&file, r#"{"4":{"3":"2"},"5":{"4":"1","0":"1"}}"#).unwrap();
+        let tampered = read_substitutions(file.to_str().unwrap(), &field());
+        std::fs::remove_file(&file).unwrap();
+        let failures = run(&tampered.unwrap(), &[product()]).err().unwrap();
+        assert!(failures.iter().any(|f| f.contains("over main.c, main.out is not implied by the simplified constraints")));
+        assert!(failures.iter().any(|f| f == "The substitution of main.c is not implied by the original constraints"));
+    }
+
+    #[test]
+    fn substitution_of_unconstrained_signal_is_rejected() {
+        // main.out === 0 is implied by the substitution, but not by the original circuit
+        let mut tampered = substitutions(&[(3, 1)]);
+        tampered.insert(6, lc(&[(3, 1)]));
+        let failures = run(&tampered, &[product()]).err().unwrap();
+        assert_eq!(failures, vec!["The substitution of signal 6 is not implied by the original constraints".to_string()]);
+    }
+
+    #[test]
+    fn substitutions_found_after_other_substitutions_are_verified() {
+        // main.a === 3; main.b === main.a * main.x, so main.b is removed as 3 * main.x
+        let original = OriginalCircuit {
+            field: field(),
+            paths: vec!["main".to_string()],
+            constraints: vec![
+                (0, Constraint::new(lc(&[]), lc(&[]), lc(&[(1, 1), (0, -3)]))),
+                (0, Constraint::new(lc(&[(1, 1)]), lc(&[(2, 1)]), lc(&[(3, 1)]))),
+            ],
+            names: HashMap::new(),
+        };
+        let substitutions = HashMap::from([(1, lc(&[(0, 3)])), (3, lc(&[(2, 3)]))]);
+        assert!(verify(&original, &substitutions, || Vec::new().into_iter(), &|signal| *signal == 2).is_ok());
+    }
+
+    #[test]
+    fn stronger_simplified_constraints_are_rejected() {
+        let extra = Constraint::new(lc(&[]), lc(&[]), lc(&[(1, 1), (2, -1)]));
+        let failures = run(&substitutions(&[(3, 1)]), &[product(), extra]).err().unwrap();
+        assert_eq!(failures, vec!["A simplified constraint over main.a, main.b is not implied by the original constraints".to_string()]);
+    }
+
+    #[test]
+    fn substitutions_outside_the_witness_are_rejected() {
+        // main.c is expressed in terms of main.d, which is not in the witness
+        let failures = verify(&circuit(), &substitutions(&[(3, 1)]), || vec![product()].into_iter(), &|signal| *signal <= 2).err().unwrap();
+        assert!(failures.iter().any(|f| f.starts_with("main.c can not be recomputed from the witness")));
+    }
+}
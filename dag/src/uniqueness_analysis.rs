@@ -0,0 +1,290 @@
+use super::range_analysis::{coefficient, is_boolean, is_constant};
+use super::{Edge, Node};
+use circom_algebra::algebra::Constraint;
+use circom_algebra::field_element::{prime_field, FieldElement};
+use circom_algebra::num_bigint::BigInt;
+use circom_algebra::num_traits::{One, Zero};
+use program_structure::error_code::ReportCode;
+use program_structure::error_definition::Report;
+use std::collections::{HashMap, HashSet};
+
+type C = Constraint<usize>;
+type LinearCombination = HashMap<usize, FieldElement>;
+const UNDERCONSTRAINED_OUTPUT_CODE: ReportCode = ReportCode::UnderconstrainedOutput;
+// Constraints shown in the warning for each output
+const MAX_SHOWN_CONSTRAINTS: usize = 3;
+// Terms shown for each linear combination of those constraints
+const MAX_SHOWN_TERMS: usize = 6;
+
+fn split_signal_name_index(name: &str) -> String {
+    name.split('[').next().unwrap().to_string()
+}
+
+// The signals of a template instance that are uniquely determined by its inputs. The
+// subcomponents are assumed to determine their outputs from their inputs, as they
+// are analysed on their own.
+struct Propagation<'a> {
+    node: &'a Node,
+    constraints: &'a [C],
+    field: &'a BigInt,
+    determined: Vec<bool>,
+    boolean: Vec<bool>,
+    // constraints in which each signal appears
+    appearances: Vec<Vec<usize>>,
+    // subcomponent of each input of a subcomponent, and inputs of each subcomponent
+    // that are not determined yet
+    subcomponent_of: HashMap<usize, usize>,
+    pending_inputs: Vec<usize>,
+    subcomponent_outputs: Vec<Vec<usize>>,
+    queue: Vec<usize>,
+}
+
+impl<'a> Propagation<'a> {
+    fn new(node: &'a Node, constraints: &'a [C], edges: &[Edge], nodes: &[Node], field: &'a BigInt) -> Propagation<'a> {
+        let size = node.number_of_signals + 1;
+        let mut propagation = Propagation {
+            node,
+            constraints,
+            field,
+            determined: vec![false; size],
+            boolean: vec![false; size],
+            appearances: vec![Vec::new(); size],
+            subcomponent_of: HashMap::new(),
+            pending_inputs: Vec::new(),
+            subcomponent_outputs: Vec::new(),
+            queue: Vec::new(),
+        };
+        for (index, constraint) in constraints.iter().enumerate() {
+            let signals = constraint.take_cloned_signals();
+            if signals.len() == 1 {
+                let signal = *signals.iter().next().unwrap();
+                propagation.boolean[signal] |= is_boolean(constraint, signal, field);
+            }
+            for signal in signals {
+                propagation.appearances[signal].push(index);
+            }
+        }
+        for (index, edge) in edges.iter().enumerate() {
+            let child = &nodes[edge.goes_to];
+            let outputs = (1..=child.outputs_length).map(|s| s + edge.in_number).collect();
+            let inputs = (child.outputs_length + 1)..=(child.outputs_length + child.inputs_length);
+            for input in inputs {
+                propagation.subcomponent_of.insert(input + edge.in_number, index);
+            }
+            propagation.pending_inputs.push(child.inputs_length);
+            propagation.subcomponent_outputs.push(outputs);
+        }
+        propagation
+    }
+
+    fn mark(&mut self, signal: usize) {
+        let mut newly = vec![signal];
+        while let Some(signal) = newly.pop() {
+            if self.determined[signal] {
+                continue;
+            }
+            self.determined[signal] = true;
+            self.queue.extend(self.appearances[signal].iter().cloned());
+            if let Some(subcomponent) = self.subcomponent_of.get(&signal) {
+                self.pending_inputs[*subcomponent] -= 1;
+                if self.pending_inputs[*subcomponent] == 0 {
+                    newly.extend(self.subcomponent_outputs[*subcomponent].iter().cloned());
+                }
+            }
+        }
+    }
+
+    fn has_undetermined(&self, lc: &LinearCombination) -> bool {
+        lc.iter().any(|(signal, value)| !self.determined[*signal] && !value.is_zero())
+    }
+
+    // Coefficients of the signals that are not determined yet when they only appear
+    // linearly, with a coefficient known at compile time
+    fn linear_coefficients(&self, constraint: &C) -> Option<LinearCombination> {
+        let field = prime_field(self.field);
+        let mut undetermined: HashSet<usize> = HashSet::new();
+        for lc in [constraint.a(), constraint.b(), constraint.c()] {
+            undetermined.extend(lc.keys().filter(|s| !self.determined[**s]));
+        }
+        let product_is_known = !self.has_undetermined(constraint.a()) && !self.has_undetermined(constraint.b());
+        let mut coefficients = LinearCombination::new();
+        for signal in undetermined {
+            let from_product = if product_is_known {
+                FieldElement::zero()
+            } else if is_constant(constraint.a()) {
+                field.mul(&coefficient(constraint.a(), 0), &coefficient(constraint.b(), signal))
+            } else if is_constant(constraint.b()) {
+                field.mul(&coefficient(constraint.a(), signal), &coefficient(constraint.b(), 0))
+            } else {
+                return None;
+            };
+            let value = field.sub(&from_product, &coefficient(constraint.c(), signal));
+            if !value.is_zero() {
+                coefficients.insert(signal, value);
+            }
+        }
+        Some(coefficients)
+    }
+
+    // Boolean signals whose coefficients, all with the same sign, are superincreasing
+    // and add up to less than p: as in a binary decomposition, only one assignment of
+    // the signals gives each value
+    fn is_unique_decomposition(&self, coefficients: &LinearCombination) -> bool {
+        if !coefficients.keys().all(|s| self.boolean[*s]) {
+            return false;
+        }
+        let field = prime_field(self.field);
+        let positive: Vec<BigInt> = coefficients.values().map(|v| v.to_bigint()).collect();
+        let negative = coefficients.values().map(|v| field.neg(v).to_bigint()).collect();
+        vec![positive, negative].into_iter().any(|mut values| {
+            values.sort();
+            let mut sum = BigInt::zero();
+            for value in values {
+                if value <= sum {
+                    return false;
+                }
+                sum += value;
+            }
+            &sum < self.field
+        })
+    }
+
+    fn run(&mut self) {
+        self.mark(0);
+        let inputs = (self.node.outputs_length + 1)..=(self.node.outputs_length + self.node.inputs_length);
+        for input in inputs {
+            self.mark(input);
+        }
+        for subcomponent in 0..self.pending_inputs.len() {
+            if self.pending_inputs[subcomponent] == 0 {
+                let outputs = self.subcomponent_outputs[subcomponent].clone();
+                for output in outputs {
+                    self.mark(output);
+                }
+            }
+        }
+        self.queue.extend(0..self.constraints.len());
+        while let Some(index) = self.queue.pop() {
+            let constraint = &self.constraints[index];
+            let coefficients = match self.linear_coefficients(constraint) {
+                Some(coefficients) if !coefficients.is_empty() => coefficients,
+                _ => continue,
+            };
+            if coefficients.len() == 1 || self.is_unique_decomposition(&coefficients) {
+                for signal in coefficients.keys() {
+                    self.mark(*signal);
+                }
+            }
+        }
+    }
+}
+
+fn format_linear(lc: &LinearCombination, names: &HashMap<usize, String>, field: &BigInt) -> String {
+    let half = field / BigInt::from(2);
+    let mut terms: Vec<(usize, String)> = Vec::new();
+    for (signal, value) in lc {
+        let value = value.to_bigint();
+        if value.is_zero() {
+            continue;
+        }
+        let (negative, magnitude) = if value > half { (true, field - &value) } else { (false, value) };
+        let term = match (*signal, magnitude.is_one()) {
+            (0, _) => magnitude.to_string(),
+            (s, true) => names[&s].clone(),
+            (s, false) => format!("{}*{}", magnitude, names[&s]),
+        };
+        terms.push((*signal, if negative { format!("-{}", term) } else { term }));
+    }
+    if terms.is_empty() {
+        return "0".to_string();
+    }
+    terms.sort();
+    let mut text = terms[0].1.clone();
+    for (_, term) in terms.iter().take(MAX_SHOWN_TERMS).skip(1) {
+        match term.strip_prefix('-') {
+            Some(term) => text.push_str(&format!(" - {}", term)),
+            None => text.push_str(&format!(" + {}", term)),
+        }
+    }
+    if terms.len() > MAX_SHOWN_TERMS {
+        text.push_str(&format!(" + ... ({} more terms)", terms.len() - MAX_SHOWN_TERMS));
+    }
+    text
+}
+
+fn format_constraint(constraint: &C, names: &HashMap<usize, String>, field: &BigInt) -> String {
+    let c = format_linear(constraint.c(), names, field);
+    if constraint.a().is_empty() || constraint.b().is_empty() {
+        format!("0 === {}", c)
+    } else {
+        let a = format_linear(constraint.a(), names, field);
+        let b = format_linear(constraint.b(), names, field);
+        format!("({}) * ({}) === {}", a, b, c)
+    }
+}
+
+struct UnderconstrainedOutput;
+impl UnderconstrainedOutput {
+    pub fn new(signal: &str, node: &Node, examples: &[String], constraints: &[String]) -> Report {
+        let template = &node.template_name;
+        let msg = if examples.len() == 1 {
+            format!(
+                "In template \"{}\": Output signal {} is not proven to be uniquely determined by the inputs",
+                template, examples[0]
+            )
+        } else {
+            format!(
+                "In template \"{}\": Array of output signals {} contains a total of {} signals that are not proven to be uniquely determined by the inputs",
+                template,
+                signal,
+                examples.len()
+            )
+        };
+        let mut report = Report::warning(msg, UNDERCONSTRAINED_OUTPUT_CODE);
+        report.set_template(node.template_name().to_string());
+        if examples.len() > 1 {
+            report.add_note(format!("For example: {}, {}.", examples[0], examples[1]));
+        }
+        report.add_note(format!("Constraints involved:\n{}", constraints.join("\n")));
+        report.add_note(
+            "Only linear constraints, binary decompositions and the outputs of subcomponents are used to prove that a signal is determined."
+                .to_string(),
+        );
+        report
+    }
+}
+
+pub fn analyse(node: &Node, constraints: &[C], edges: &[Edge], nodes: &[Node], field: &BigInt) -> Vec<Report> {
+    let mut propagation = Propagation::new(node, constraints, edges, nodes, field);
+    propagation.run();
+    let mut names = HashMap::new();
+    for (name, id) in &node.signal_correspondence {
+        names.insert(*id, name.clone());
+    }
+
+    // The outputs without constraints are already reported as unconstrained signals
+    let mut undetermined: Vec<(String, usize)> = (1..=node.outputs_length)
+        .filter(|s| !propagation.determined[*s] && !propagation.appearances[*s].is_empty())
+        .map(|s| (names[&s].clone(), s))
+        .collect();
+    undetermined.sort();
+    let mut grouped: Vec<(String, Vec<String>, Vec<String>)> = Vec::new();
+    for (name, signal) in undetermined {
+        let base = split_signal_name_index(&name);
+        if grouped.last().map(|(last, _, _)| last) != Some(&base) {
+            let constraints = propagation.appearances[signal]
+                .iter()
+                .take(MAX_SHOWN_CONSTRAINTS)
+                .map(|c| format_constraint(&constraints[*c], &names, field))
+                .collect();
+            grouped.push((base, Vec::new(), constraints));
+        }
+        grouped.last_mut().unwrap().1.push(name);
+    }
+    grouped
+        .into_iter()
+        .map(|(base, examples, constraints)| {
+            UnderconstrainedOutput::new(&base, node, &examples, &constraints)
+        })
+        .collect()
+}
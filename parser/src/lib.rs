@@ -10,17 +10,24 @@ lalrpop_mod!(pub lang);
 mod include_logic;
 mod parser_logic;
 mod syntax_sugar_remover;
+mod template_specializer;
 
 use include_logic::{FileStack, IncludesGraph};
-use num_bigint::BigInt;
-use program_structure::ast::{produce_compiler_version_report, produce_report, produce_report_with_message, produce_version_warning_report, Expression};
+use program_structure::ast::{build_constant, produce_compiler_version_report, produce_report, produce_report_with_message, produce_version_warning_report, Definition, Expression, MainComponent, Meta};
+use program_structure::constants::UsefulConstants;
+use program_structure::expression_builders::build_number;
 use program_structure::error_code::ReportCode;
 use program_structure::error_definition::ReportCollection;
 use program_structure::error_definition::Report;
-use program_structure::file_definition::{FileLibrary};
+use program_structure::file_definition::{FileID, FileLibrary};
+use program_structure::lint_config::LintScope;
 use program_structure::program_archive::ProgramArchive;
+use program_structure::template_budget::TemplateBudget;
+use num_bigint::BigInt;
+use std::collections::HashSet;
 use std::path::{PathBuf, Path};
 use syntax_sugar_remover::{apply_syntactic_sugar};
+use template_specializer::specialize_templates;
 
 use std::str::FromStr;
 
@@ -57,22 +64,46 @@ pub fn find_file(
     (found, path, src, crr_str_file, reports)
 }
 
-pub fn run_parser(
+// The input file and the files it includes, read and parsed
+struct ParsedFiles {
+    file_library: FileLibrary,
+    definitions: Vec<(FileID, Vec<Definition>)>,
+    main_components: Vec<(FileID, MainComponent, bool)>,
+    includes_graph: IncludesGraph,
+    warnings: ReportCollection,
+    lint_scopes: Vec<LintScope>,
+    template_budgets: Vec<TemplateBudget>,
+    library_files: HashSet<FileID>,
+}
+
+fn parse_files(
     file: String,
     version: &str,
-    link_libraries: Vec<PathBuf>,
-    field: &BigInt,     
-    flag_no_init: bool
-) -> Result<(ProgramArchive, ReportCollection), (FileLibrary, ReportCollection)> {
-    let mut file_library = FileLibrary::new();
-    let mut definitions = Vec::new();
-    let mut main_components = Vec::new();
-    let mut file_stack = FileStack::new(PathBuf::from(file));
-    let mut includes_graph = IncludesGraph::new();
-    let mut warnings = Vec::new();
-    let mut link_libraries2 = link_libraries.clone();
+    link_libraries: &[PathBuf],
+    field: &BigInt,
+    flag_no_init: bool,
+) -> Result<ParsedFiles, (FileLibrary, ReportCollection)> {
+    let mut parsed = ParsedFiles {
+        file_library: FileLibrary::new(),
+        definitions: Vec::new(),
+        main_components: Vec::new(),
+        includes_graph: IncludesGraph::new(),
+        warnings: Vec::new(),
+        lint_scopes: Vec::new(),
+        template_budgets: Vec::new(),
+        library_files: HashSet::new(),
+    };
+    let file_library = &mut parsed.file_library;
+    let mut file_stack = FileStack::new(PathBuf::from(&file));
     let mut ext_link_libraries = vec![Path::new("").to_path_buf()];
-    ext_link_libraries.append(&mut link_libraries2);
+    ext_link_libraries.extend(link_libraries.iter().cloned());
+    // A directory of -l that contains the main file, as in -l ., holds user files
+    let main_path = std::fs::canonicalize(&file).unwrap_or_default();
+    let library_folders: Vec<PathBuf> = link_libraries
+        .iter()
+        .filter_map(|folder| std::fs::canonicalize(folder).ok())
+        .filter(|folder| !main_path.starts_with(folder))
+        .collect();
     while let Some(crr_file) = FileStack::take_next(&mut file_stack) {
         let (found, path, src, crr_str_file, reports) =
             find_file(crr_file, ext_link_libraries.clone());
@@ -80,21 +111,26 @@ pub fn run_parser(
             return Result::Err((file_library.clone(), reports));
         }
         let file_id = file_library.add_file(path.clone(), src.clone());
-        let program =
+        if is_library_path(&crr_str_file, &library_folders) {
+            parsed.library_files.insert(file_id);
+        }
+        let mut program =
             parser_logic::parse_file(&src, file_id, field, flag_no_init).map_err(|e| (file_library.clone(), e))?;
+        parsed.lint_scopes.append(&mut program.lint_scopes);
+        parsed.template_budgets.append(&mut program.template_budgets);
         if let Some(main) = program.main_component {
-            main_components.push((file_id, main, program.custom_gates));
+            parsed.main_components.push((file_id, main, program.custom_gates));
         }
-        includes_graph.add_node(crr_str_file, program.custom_gates, program.custom_gates_declared);
+        parsed.includes_graph.add_node(crr_str_file, program.custom_gates, program.custom_gates_declared);
         let includes = program.includes;
-        definitions.push((file_id, program.definitions));
+        parsed.definitions.push((file_id, program.definitions));
         for include in includes {
             let path_include =
-                FileStack::add_include(&mut file_stack, include.clone(), &link_libraries.clone())
+                FileStack::add_include(&mut file_stack, include.clone(), &link_libraries.to_vec())
                     .map_err(|e| (file_library.clone(), vec![e]))?;
-            includes_graph.add_edge(path_include).map_err(|e| (file_library.clone(), vec![e]))?;
+            parsed.includes_graph.add_edge(path_include).map_err(|e| (file_library.clone(), vec![e]))?;
         }
-        warnings.append(
+        parsed.warnings.append(
             &mut check_number_version(
                 path.clone(),
                 program.compiler_version,
@@ -111,6 +147,26 @@ pub fn run_parser(
             .map_err(|e| (file_library.clone(), vec![e]))?
         }
     }
+    Result::Ok(parsed)
+}
+
+pub fn run_parser(
+    file: String,
+    version: &str,
+    link_libraries: Vec<PathBuf>,
+    constants: &UsefulConstants,
+    flag_no_init: bool
+) -> Result<(ProgramArchive, ReportCollection), (FileLibrary, ReportCollection)> {
+    let ParsedFiles {
+        mut file_library,
+        mut definitions,
+        mut main_components,
+        includes_graph,
+        mut warnings,
+        lint_scopes,
+        template_budgets,
+        library_files,
+    } = parse_files(file, version, &link_libraries, constants.get_p(), flag_no_init)?;
 
     if main_components.len() == 0 {
         let report = produce_report(ReportCode::NoMainFoundInProject,0..0, 0);
@@ -135,7 +191,15 @@ pub fn run_parser(
             warnings.append(& mut errors);
             Err((file_library, warnings))
         } else {
-            let (main_id, main_component, custom_gates) = main_components.pop().unwrap();
+            let (main_id, mut main_component, custom_gates) = main_components.pop().unwrap();
+            definitions.insert(0, field_constant_definitions(constants, &mut file_library));
+            let specializations = match specialize_templates(&mut definitions, main_id, &mut main_component.1) {
+                Ok(specializations) => specializations,
+                Err(mut reports) => {
+                    warnings.append(&mut reports);
+                    return Err((file_library, warnings));
+                }
+            };
             let result_program_archive = ProgramArchive::new(
                 file_library,
                 main_id,
@@ -149,6 +213,11 @@ pub fn run_parser(
                     Err((lib, warnings))
                 }
                 Ok(mut program_archive) => {
+                    program_archive.lint_scopes = lint_scopes;
+                    program_archive.template_budgets = template_budgets;
+                    program_archive.link_libraries = link_libraries;
+                    program_archive.specializations = specializations;
+                    program_archive.library_files = library_files;
                     let lib = program_archive.get_file_library().clone();
                     let program_archive_result = apply_syntactic_sugar( &mut program_archive);
                     match program_archive_result {
@@ -163,6 +232,65 @@ pub fn run_parser(
     }
 }
 
+// Reads the definitions of a library of templates, buses and functions, which does not
+// need a main component. A main component, if there is one, is ignored, so the generic
+// templates are kept as they are written and not specialized.
+pub fn run_library_parser(
+    file: String,
+    version: &str,
+    link_libraries: Vec<PathBuf>,
+    constants: &UsefulConstants,
+) -> Result<(ProgramArchive, ReportCollection), (FileLibrary, ReportCollection)> {
+    let ParsedFiles { mut file_library, mut definitions, mut warnings, library_files, .. } =
+        parse_files(file, version, &link_libraries, constants.get_p(), false)?;
+    let main_id = definitions[0].0;
+    definitions.insert(0, field_constant_definitions(constants, &mut file_library));
+    match ProgramArchive::new_library(file_library, main_id, definitions) {
+        Err((lib, mut rep)) => {
+            warnings.append(&mut rep);
+            Err((lib, warnings))
+        }
+        Ok(mut program_archive) => {
+            program_archive.link_libraries = link_libraries;
+            program_archive.library_files = library_files;
+            let lib = program_archive.get_file_library().clone();
+            match apply_syntactic_sugar(&mut program_archive) {
+                Result::Err(v) => {
+                    warnings.push(v);
+                    Result::Err((lib, warnings))
+                }
+                Result::Ok(_) => Ok((program_archive, warnings)),
+            }
+        }
+    }
+}
+
+const BUILT_IN_FILE: &str = "<built-in constants>";
+
+// The built-in field constants are global constants defined in a file of their own, whose
+// source is generated here so that the reports that refer to them have a location to show
+fn field_constant_definitions(constants: &UsefulConstants, file_library: &mut FileLibrary) -> (FileID, Vec<Definition>) {
+    let mut source = String::new();
+    let mut locations = Vec::new();
+    for (name, value) in constants.get_field_constants() {
+        let start = source.len();
+        source.push_str(&format!("const {} = {}", name, value));
+        let value_start = source.len() - value.to_string().len();
+        locations.push((name, value, start..source.len(), value_start..source.len()));
+        source.push_str(";\n");
+    }
+    let file_id = file_library.add_file(BUILT_IN_FILE.to_string(), source);
+    let mut definitions = Vec::new();
+    for (name, value, location, value_location) in locations {
+        let mut meta = Meta::new(location.start, location.end);
+        meta.set_file_id(file_id);
+        let mut value_meta = Meta::new(value_location.start, value_location.end);
+        value_meta.set_file_id(file_id);
+        definitions.push(build_constant(meta, name, build_number(value_meta, value, constants.get_p())));
+    }
+    (file_id, definitions)
+}
+
 fn produce_report_with_main_components(main_components: Vec<(usize, (Vec<String>, Expression), bool)>) -> Report {
     let mut j = 0;
     let mut r = produce_report(ReportCode::MultipleMain, 0..0, 0);
@@ -178,6 +306,18 @@ fn produce_report_with_main_components(main_components: Vec<(usize, (Vec<String>
     r
 }
 
+// A file is part of a library if it is in one of the directories given with -l or in a
+// node_modules directory, as the files of circomlib usually are
+fn is_library_path(path: &Path, library_folders: &[PathBuf]) -> bool {
+    match std::fs::canonicalize(path) {
+        Result::Ok(path) => {
+            library_folders.iter().any(|folder| path.starts_with(folder))
+                || path.components().any(|component| component.as_os_str() == "node_modules")
+        }
+        Result::Err(_) => false,
+    }
+}
+
 fn open_file(path: PathBuf) -> Result<(String, String), Report> /* path, src */ {
     use std::fs::read_to_string;
     let path_str = format!("{:?}", path);
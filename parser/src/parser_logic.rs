@@ -1,10 +1,13 @@
 use super::lang;
 use num_bigint::BigInt;
-use program_structure::ast::{AST};
+use program_structure::ast::{Definition, DocComment, Statement, VariableType, AST};
 use program_structure::ast::produce_report;
 use program_structure::error_code::ReportCode;
 use program_structure::error_definition::{ReportCollection, Report};
-use program_structure::file_definition::FileID;
+use program_structure::file_definition::{FileID, FileLocation};
+use program_structure::lint_config::{LintLevel, LintScope};
+use program_structure::template_budget::TemplateBudget;
+use std::collections::{BTreeMap, HashSet};
 
 pub fn preprocess(expr: &str, file_id: FileID) -> Result<String, ReportCollection> {
     let mut pp = String::new();
@@ -84,13 +87,312 @@ pub fn preprocess(expr: &str, file_id: FileID) -> Result<String, ReportCollectio
     }
 }
 
+// Collects the blocks of '///' comments of a file. Each block is indexed by the
+// position of the first token that follows it, which is where the definition or
+// declaration it documents starts.
+fn collect_doc_comments(src: &str) -> BTreeMap<usize, String> {
+    let mut docs = BTreeMap::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut offset = 0;
+    for line in src.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("///") && !trimmed.starts_with("////") {
+            let text = trimmed[3..].trim_end();
+            current.push(text.strip_prefix(' ').unwrap_or(text));
+        } else if !current.is_empty() && !trimmed.is_empty() {
+            let start = offset + (line.len() - trimmed.len());
+            docs.insert(start, current.join("\n"));
+            current.clear();
+        }
+        offset += line.len();
+    }
+    docs
+}
+
+fn attach_doc_comments(ast: &mut AST, docs: &BTreeMap<usize, String>) {
+    if docs.is_empty() {
+        return;
+    }
+    for definition in ast.definitions.iter_mut() {
+        match definition {
+            Definition::Template { meta, body, docs: doc_comment, .. }
+            | Definition::Bus { meta, body, docs: doc_comment, .. } => {
+                doc_comment.text = docs.get(&meta.start).cloned();
+                collect_wire_docs(body, docs, doc_comment, &mut HashSet::new());
+            }
+            Definition::Function { meta, docs: doc_comment, .. } => {
+                doc_comment.text = docs.get(&meta.start).cloned();
+            }
+            Definition::Constant { .. } => {}
+        }
+    }
+}
+
+// The declarations of a statement such as 'signal input a[n], b;' start at the same
+// position, and its doc comment only documents the first declared name
+fn collect_wire_docs(
+    stmt: &Statement,
+    docs: &BTreeMap<usize, String>,
+    doc_comment: &mut DocComment,
+    documented: &mut HashSet<usize>,
+) {
+    use Statement::*;
+    match stmt {
+        IfThenElse { if_case, else_case, .. } => {
+            collect_wire_docs(if_case, docs, doc_comment, documented);
+            if let Option::Some(else_stmt) = else_case {
+                collect_wire_docs(else_stmt, docs, doc_comment, documented);
+            }
+        }
+        While { stmt, .. } => collect_wire_docs(stmt, docs, doc_comment, documented),
+        Block { stmts, .. } => {
+            for s in stmts {
+                collect_wire_docs(s, docs, doc_comment, documented);
+            }
+        }
+        InitializationBlock { initializations, .. } => {
+            for init in initializations {
+                collect_wire_docs(init, docs, doc_comment, documented);
+            }
+        }
+        Declaration { meta, xtype: VariableType::Signal(..), name, .. }
+        | Declaration { meta, xtype: VariableType::Bus(..), name, .. } => {
+            match docs.get(&meta.start) {
+                Option::Some(text) if documented.insert(meta.start) => {
+                    doc_comment.wires.insert(name.clone(), text.clone());
+                }
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+// A comment '// lint: allow(Lint, ...)', or with the levels warn and deny, changes the level
+// of the given lints inside the template, function or statement that follows it.
+struct LintComment {
+    location: FileLocation,
+    level: LintLevel,
+    lints: Vec<String>,
+}
+
+const LINT_COMMENT_PREFIX: &str = "lint:";
+
+// Lint comments are indexed by the position of the first token that follows them
+fn collect_lint_comments(
+    src: &str,
+    file_id: FileID,
+    reports: &mut ReportCollection,
+) -> BTreeMap<usize, Vec<LintComment>> {
+    let mut comments: BTreeMap<usize, Vec<LintComment>> = BTreeMap::new();
+    let mut pending = Vec::new();
+    let mut offset = 0;
+    for line in src.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let start = offset + (line.len() - trimmed.len());
+        if let Option::Some(comment) = trimmed.strip_prefix("//") {
+            if let Option::Some(contents) = comment.trim_start().strip_prefix(LINT_COMMENT_PREFIX) {
+                let location = start..start + trimmed.trim_end().len();
+                match parse_lint_comment(contents.trim()) {
+                    Option::Some((level, lints)) => pending.push(LintComment { location, level, lints }),
+                    Option::None => reports.push(lint_comment_error(
+                        "Lint comments must have the form // lint: allow(Lint, ...), with the levels allow, warn or deny",
+                        location,
+                        file_id,
+                    )),
+                }
+            }
+        } else if !trimmed.trim_end().is_empty() && !pending.is_empty() {
+            comments.entry(start).or_default().append(&mut pending);
+        }
+        offset += line.len();
+    }
+    for comment in pending {
+        reports.push(lint_comment_error(
+            "Lint comments must precede a template, function or statement",
+            comment.location,
+            file_id,
+        ));
+    }
+    comments
+}
+
+fn parse_lint_comment(contents: &str) -> Option<(LintLevel, Vec<String>)> {
+    let (level, lints) = contents.split_once('(')?;
+    let level = LintLevel::from_name(level.trim())?;
+    let lints: Vec<String> =
+        lints.trim_end().strip_suffix(')')?.split(',').map(|lint| lint.trim().to_string()).collect();
+    if lints.iter().any(|lint| lint.is_empty()) {
+        Option::None
+    } else {
+        Option::Some((level, lints))
+    }
+}
+
+fn lint_comment_error(message: &str, location: FileLocation, file_id: FileID) -> Report {
+    let mut report = Report::error("Invalid lint comment".to_string(), ReportCode::InvalidLintComment);
+    report.add_primary(location, file_id, message.to_string());
+    report
+}
+
+// Positions where a lint comment can be placed: the templates, functions and statements,
+// together with their location and the template they belong to
+fn collect_lint_targets(ast: &AST) -> BTreeMap<usize, (FileLocation, Option<String>)> {
+    let mut targets = BTreeMap::new();
+    for definition in &ast.definitions {
+        match definition {
+            Definition::Template { meta, name, body, .. } => {
+                targets.entry(meta.start).or_insert((meta.start..meta.end, Option::Some(name.clone())));
+                collect_statement_lint_targets(body, &mut targets);
+            }
+            Definition::Function { meta, body, .. } => {
+                targets.entry(meta.start).or_insert((meta.start..meta.end, Option::None));
+                collect_statement_lint_targets(body, &mut targets);
+            }
+            Definition::Bus { .. } | Definition::Constant { .. } => {}
+        }
+    }
+    targets
+}
+
+fn collect_statement_lint_targets(
+    stmt: &Statement,
+    targets: &mut BTreeMap<usize, (FileLocation, Option<String>)>,
+) {
+    use Statement::*;
+    let meta = stmt.get_meta();
+    targets.entry(meta.start).or_insert((meta.start..meta.end, Option::None));
+    match stmt {
+        IfThenElse { if_case, else_case, .. } => {
+            collect_statement_lint_targets(if_case, targets);
+            if let Option::Some(else_stmt) = else_case {
+                collect_statement_lint_targets(else_stmt, targets);
+            }
+        }
+        While { stmt, .. } => collect_statement_lint_targets(stmt, targets),
+        Block { stmts, .. } => {
+            for s in stmts {
+                collect_statement_lint_targets(s, targets);
+            }
+        }
+        InitializationBlock { initializations, .. } => {
+            for init in initializations {
+                collect_statement_lint_targets(init, targets);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn attach_lint_comments(ast: &mut AST, src: &str, file_id: FileID) -> Result<(), ReportCollection> {
+    let mut reports = Vec::new();
+    let comments = collect_lint_comments(src, file_id, &mut reports);
+    if comments.is_empty() && reports.is_empty() {
+        return Ok(());
+    }
+    let targets = collect_lint_targets(ast);
+    for (position, comments) in comments {
+        for comment in comments {
+            let target = targets.get(&position);
+            if target.is_none() {
+                reports.push(lint_comment_error(
+                    "Lint comments must precede a template, function or statement",
+                    comment.location.clone(),
+                    file_id,
+                ));
+                continue;
+            }
+            let (location, template) = target.unwrap();
+            for lint in comment.lints {
+                if ReportCode::from_lint_name(&lint).is_none() {
+                    reports.push(lint_comment_error(
+                        &format!("Unknown lint {}", lint),
+                        comment.location.clone(),
+                        file_id,
+                    ));
+                    continue;
+                }
+                ast.lint_scopes.push(LintScope {
+                    file_id,
+                    location: location.clone(),
+                    template: template.clone(),
+                    lint,
+                    level: comment.level,
+                });
+            }
+        }
+    }
+    if reports.is_empty() {
+        Ok(())
+    } else {
+        Err(reports)
+    }
+}
+
+// A comment '// budget: constraints = N, signals = M' before a template limits the size
+// of its instances
+const BUDGET_COMMENT_PREFIX: &str = "budget:";
+
+fn attach_budget_comments(ast: &mut AST, src: &str, file_id: FileID) -> Result<(), ReportCollection> {
+    let mut reports = Vec::new();
+    let mut pending: Vec<(FileLocation, &str)> = Vec::new();
+    let mut templates = BTreeMap::new();
+    for definition in &ast.definitions {
+        if let Definition::Template { meta, name, .. } = definition {
+            templates.insert(meta.start, name.clone());
+        }
+    }
+    let mut offset = 0;
+    for line in src.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let start = offset + (line.len() - trimmed.len());
+        if let Option::Some(comment) = trimmed.strip_prefix("//") {
+            if let Option::Some(contents) = comment.trim_start().strip_prefix(BUDGET_COMMENT_PREFIX) {
+                pending.push((start..start + trimmed.trim_end().len(), contents));
+            }
+        } else if !trimmed.trim_end().is_empty() {
+            for (location, contents) in pending.drain(..) {
+                let budget = templates
+                    .get(&start)
+                    .and_then(|template| TemplateBudget::parse_limits(template, contents));
+                match budget {
+                    Option::Some(mut budget) => {
+                        budget.declaration = Option::Some((file_id, location));
+                        ast.template_budgets.push(budget);
+                    }
+                    Option::None => reports.push(budget_comment_error(location, file_id)),
+                }
+            }
+        }
+        offset += line.len();
+    }
+    for (location, _) in pending {
+        reports.push(budget_comment_error(location, file_id));
+    }
+    if reports.is_empty() {
+        Ok(())
+    } else {
+        Err(reports)
+    }
+}
+
+fn budget_comment_error(location: FileLocation, file_id: FileID) -> Report {
+    let mut report = Report::error("Invalid budget comment".to_string(), ReportCode::InvalidBudgetComment);
+    report.add_primary(
+        location,
+        file_id,
+        "Budget comments must precede a template and have the form // budget: constraints = N, signals = M".to_string(),
+    );
+    report
+}
+
 pub fn parse_file(src: &str, file_id: FileID, field: &BigInt, flag_no_init: bool) -> Result<AST, ReportCollection> {
     use lalrpop_util::ParseError::*;
 
     let mut errors = Vec::new();
     let preprocess = preprocess(src, file_id)?;
 
-    let ast = lang::ParseAstParser::new()
+    let mut ast = lang::ParseAstParser::new()
         .parse(file_id, &mut errors, field, flag_no_init, &preprocess)
         // TODO: is this always fatal?
         .map_err(|parse_error| match parse_error {
@@ -119,6 +421,10 @@ pub fn parse_file(src: &str, file_id: FileID, field: &BigInt, flag_no_init: bool
         return Err(errors.into_iter().collect());
     }
 
+    attach_doc_comments(&mut ast, &collect_doc_comments(src));
+    attach_lint_comments(&mut ast, src, file_id)?;
+    attach_budget_comments(&mut ast, src, file_id)?;
+
     Ok(ast)
 }
 
@@ -107,6 +107,13 @@ fn check_anonymous_components_statement(
                 Result::Ok(())
             }
         }
+        Statement::StaticAssert { meta, arg, .. } => {
+            if arg.contains_anonymous_comp() {
+                Result::Err(anonymous_general_error(meta.clone(), "An anonymous component cannot be used inside a static_assert".to_string()))
+            } else{
+                Result::Ok(())
+            }
+        }
         Statement::Return {  meta, value: arg}=> {
             if arg.contains_anonymous_comp(){
                 Result::Err(anonymous_general_error(meta.clone(), "An anonymous component cannot be used inside a function ".to_string()))
@@ -648,7 +655,7 @@ fn check_tuples_statement(stm: &Statement)-> Result<(), Report>{
         Statement::LogCall {args, .. } => {
             for arg in args {
                 match arg {
-                    LogArgument::LogStr(_) => {},
+                    LogArgument::LogStr(_) | LogArgument::LogFormat(_) => {},
                     LogArgument::LogExp(exp) => {
                         check_tuples_expression(&exp)?;
                     },
@@ -664,6 +671,14 @@ fn check_tuples_statement(stm: &Statement)-> Result<(), Report>{
                 Result::Ok(())
             }
         }
+        Statement::StaticAssert { meta, arg, .. } => {
+            if arg.contains_tuple(){
+                Result::Err(tuple_general_error(meta.clone(),"A tuple cannot be used inside a static_assert".to_string()))
+            }
+            else{
+                Result::Ok(())
+            }
+        }
         Statement::Return {  meta, value: arg}=> {
             if arg.contains_tuple(){
                 Result::Err(tuple_general_error(meta.clone(),"A tuple cannot be used inside a function ".to_string()))     
@@ -872,12 +887,19 @@ fn remove_tuples_from_statement(stm: Statement) -> Result<Statement, Report> {
             Result::Ok(Statement::While { meta : meta, cond : cond, stmt : b_while})
         }     
         Statement::LogCall {meta, args } => {
+            let is_formatted = matches!(args.first(), Option::Some(LogArgument::LogFormat(_)));
             let mut newargs = Vec::new();
             for arg in args {
                 match arg {
                     LogArgument::LogStr(str) => {
                         newargs.push(LogArgument::LogStr(str));
                     },
+                    LogArgument::LogFormat(format) => {
+                        newargs.push(LogArgument::LogFormat(format));
+                    },
+                    LogArgument::LogExp(exp) if is_formatted && exp.is_tuple() => {
+                        return Result::Err(tuple_general_error(meta.clone(),"A tuple cannot be used as a value of a format string".to_string()));
+                    },
                     LogArgument::LogExp(exp) => {
                         let mut args2 = separate_tuple_for_logcall(vec![exp]);
                         newargs.append(&mut args2);
@@ -0,0 +1,563 @@
+// Specialization of the templates that take templates as parameters.
+//
+// Template arguments are resolved here, right after parsing, and not while typing or
+// instantiating the program. The rest of the compiler identifies a template by its name
+// and a template instance by the field values of its parameters: TemplateData, the
+// executor, the merger of very_concrete_program and the code producers have no way to
+// hold a template as the value of a parameter. Rewriting every generic template into one
+// ordinary template per combination of template arguments keeps all of them unchanged,
+// and gives every instance a distinct name (Apply(Sum2) becomes Apply_Sum2). The checks
+// the language requires still happen in the same places as for ordinary templates:
+// - the template arguments are checked against the declared interfaces while
+//   specializing, which is the only place where the argument of every call is known,
+// - the bodies of the generic templates are typed by type_check against their interfaces
+//   (type_generic_templates), also when they are never used,
+// - the specializations are typed, instantiated and merged as any other template, so
+//   the numeric parameters of a template argument are evaluated in execute_template_call.
+use program_structure::ast::*;
+use program_structure::error_code::ReportCode;
+use program_structure::error_definition::{Report, ReportCollection};
+use std::collections::{HashMap, HashSet};
+
+// Bound on the number of specializations, it prevents generic templates that call
+// themselves with ever growing template arguments from looping forever
+const MAX_SPECIALIZATIONS: usize = 10000;
+
+// A template argument: the template it refers to and the arguments already applied to it
+#[derive(Clone)]
+struct TemplateRef {
+    name: String,
+    bound: Vec<Expression>,
+}
+
+type Environment = HashMap<String, TemplateRef>;
+
+// Inputs and outputs of a template: name, kind, number of dimensions and bus type
+type Wire = (String, SignalType, usize, Option<String>);
+
+// What a template argument must provide to be given to a template parameter
+struct Signature {
+    params: usize,
+    wires: Vec<Wire>,
+}
+
+// Templates with template parameters are not instantiated directly. For every combination
+// of template arguments they are called with, a new template is generated where the
+// template parameters are replaced by the referenced templates and the arguments partially
+// applied to them become new parameters, so the rest of the compiler only instantiates
+// ordinary templates, each one with its own name. The generic templates are kept with
+// their template parameters replaced by their interfaces, which allows type checking
+// their bodies even if they are never used. Returns the generic template of every
+// specialization.
+pub fn specialize_templates(
+    definitions: &mut [(usize, Vec<Definition>)],
+    main_id: usize,
+    main: &mut Expression,
+) -> Result<HashMap<String, String>, ReportCollection> {
+    let mut specializer = Specializer::new(definitions);
+    if specializer.generics.is_empty() {
+        return Result::Ok(HashMap::new());
+    }
+    for (file_id, file_definitions) in definitions.iter_mut() {
+        for definition in file_definitions.iter_mut() {
+            if is_generic(definition) {
+                let environment = specializer.interface_environment(*file_id, definition);
+                specializer.specialize_definition(*file_id, definition, &environment);
+            } else if let Definition::Template { .. } = definition {
+                specializer.specialize_definition(*file_id, definition, &Environment::new());
+            }
+        }
+    }
+    specializer.file_id = main_id;
+    specializer.rewrite_expression(main, &Environment::new());
+    let mut specialized: HashMap<usize, Vec<Definition>> = HashMap::new();
+    while let Option::Some((file_id, mut definition, environment)) = specializer.pending.pop() {
+        specializer.specialize_definition(file_id, &mut definition, &environment);
+        specialized.entry(file_id).or_default().push(definition);
+    }
+    for (file_id, file_definitions) in definitions.iter_mut() {
+        if let Option::Some(mut new_definitions) = specialized.remove(file_id) {
+            file_definitions.append(&mut new_definitions);
+        }
+    }
+    if specializer.reports.is_empty() {
+        Result::Ok(specializer.origins)
+    } else {
+        Result::Err(specializer.reports)
+    }
+}
+
+fn is_generic(definition: &Definition) -> bool {
+    matches!(definition, Definition::Template { template_params, .. } if !template_params.is_empty())
+}
+
+fn interface_of<'a>(template_params: &'a [(String, String)], param: &str) -> Option<&'a String> {
+    template_params.iter().find(|(name, _)| name == param).map(|(_, interface)| interface)
+}
+
+fn signature(args: &[String], template_params: &[(String, String)], body: &Statement) -> Signature {
+    let mut wires = Vec::new();
+    collect_wires(body, &mut wires);
+    Signature { params: args.len() - template_params.len(), wires }
+}
+
+fn collect_wires(stmt: &Statement, wires: &mut Vec<Wire>) {
+    use Statement::*;
+    match stmt {
+        Declaration { xtype, name, dimensions, .. } => {
+            let (kind, bus) = match xtype {
+                VariableType::Signal(kind, _) => (*kind, Option::None),
+                VariableType::Bus(bus, kind, _) => (*kind, Option::Some(bus.clone())),
+                _ => return,
+            };
+            if kind != SignalType::Intermediate {
+                wires.push((name.clone(), kind, dimensions.len(), bus));
+            }
+        }
+        InitializationBlock { initializations: stmts, .. } | Block { stmts, .. } => {
+            for stmt in stmts {
+                collect_wires(stmt, wires);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Differences between the inputs and outputs of a template argument and the ones of the
+// interface: it must have the same inputs and at least the outputs of the interface
+fn wire_mismatch(actual: &[Wire], expected: &[Wire]) -> Option<String> {
+    let kind = |kind: SignalType| if kind == SignalType::Input { "input" } else { "output" };
+    for (name, expected_kind, dimensions, bus) in expected {
+        match actual.iter().find(|wire| wire.0 == *name) {
+            Option::None => return Option::Some(format!("the {} {} is missing", kind(*expected_kind), name)),
+            Option::Some((_, actual_kind, _, _)) if actual_kind != expected_kind => {
+                return Option::Some(format!("{} is not an {}", name, kind(*expected_kind)))
+            }
+            Option::Some((_, _, actual_dimensions, _)) if actual_dimensions != dimensions => {
+                return Option::Some(format!(
+                    "{} has {} dimensions instead of {}",
+                    name, actual_dimensions, dimensions
+                ))
+            }
+            Option::Some((_, _, _, actual_bus)) if actual_bus != bus => {
+                return Option::Some(format!("{} has a different bus type", name))
+            }
+            _ => {}
+        }
+    }
+    let extra = actual.iter().find(|(name, kind, _, _)| {
+        *kind == SignalType::Input && !expected.iter().any(|wire| wire.0 == *name)
+    });
+    extra.map(|(name, _, _, _)| format!("the input {} is not in the interface", name))
+}
+
+struct Specializer {
+    generics: HashMap<String, (usize, Definition)>,
+    templates: HashSet<String>,
+    signatures: HashMap<String, Signature>,
+    used_names: HashSet<String>,
+    specializations: HashMap<String, String>,
+    origins: HashMap<String, String>,
+    pending: Vec<(usize, Definition, Environment)>,
+    file_id: usize,
+    reported: HashSet<(usize, usize)>,
+    reports: ReportCollection,
+}
+
+impl Specializer {
+    fn new(definitions: &[(usize, Vec<Definition>)]) -> Specializer {
+        let mut generics = HashMap::new();
+        let mut templates = HashSet::new();
+        let mut signatures = HashMap::new();
+        let mut used_names = HashSet::new();
+        for (file_id, file_definitions) in definitions {
+            for definition in file_definitions {
+                let name = match definition {
+                    Definition::Template { name, args, template_params, body, .. } => {
+                        if is_generic(definition) {
+                            generics.insert(name.clone(), (*file_id, definition.clone()));
+                        } else {
+                            templates.insert(name.clone());
+                            signatures.insert(name.clone(), signature(args, template_params, body));
+                        }
+                        name
+                    }
+                    Definition::Function { name, .. }
+                    | Definition::Bus { name, .. }
+                    | Definition::Constant { name, .. } => name,
+                };
+                used_names.insert(name.clone());
+            }
+        }
+        Specializer {
+            generics,
+            templates,
+            signatures,
+            used_names,
+            specializations: HashMap::new(),
+            origins: HashMap::new(),
+            pending: Vec::new(),
+            file_id: 0,
+            reported: HashSet::new(),
+            reports: ReportCollection::new(),
+        }
+    }
+
+    // The template parameters of a generic template are replaced by their interfaces,
+    // which must be ordinary templates
+    fn interface_environment(&mut self, file_id: usize, definition: &Definition) -> Environment {
+        let mut environment = Environment::new();
+        if let Definition::Template { name, arg_location, template_params, .. } = definition {
+            self.file_id = file_id;
+            for (param, interface) in template_params {
+                if self.templates.contains(interface) {
+                    environment.insert(param.clone(), TemplateRef { name: interface.clone(), bound: Vec::new() });
+                } else {
+                    let message = format!(
+                        "The interface {} of the parameter {} of {} is not a template without template parameters",
+                        interface, param, name
+                    );
+                    self.add_error(&Meta::new(arg_location.start, arg_location.end), message);
+                }
+            }
+        }
+        environment
+    }
+
+    fn specialize_definition(&mut self, file_id: usize, definition: &mut Definition, environment: &Environment) {
+        self.file_id = file_id;
+        if let Definition::Template { body, requires, .. } = definition {
+            self.rewrite_statement(body, environment);
+            for clause in requires {
+                self.rewrite_expression(clause, environment);
+            }
+        }
+    }
+
+    fn rewrite_statement(&mut self, stmt: &mut Statement, environment: &Environment) {
+        use Statement::*;
+        match stmt {
+            IfThenElse { cond, if_case, else_case, .. } => {
+                self.rewrite_expression(cond, environment);
+                self.rewrite_statement(if_case, environment);
+                if let Option::Some(else_case) = else_case {
+                    self.rewrite_statement(else_case, environment);
+                }
+            }
+            While { cond, stmt, .. } => {
+                self.rewrite_expression(cond, environment);
+                self.rewrite_statement(stmt, environment);
+            }
+            InitializationBlock { initializations, .. } => {
+                for init in initializations {
+                    self.rewrite_statement(init, environment);
+                }
+            }
+            Block { stmts, .. } => {
+                for stmt in stmts {
+                    self.rewrite_statement(stmt, environment);
+                }
+            }
+            Declaration { dimensions, .. } => {
+                for dimension in dimensions {
+                    self.rewrite_expression(dimension, environment);
+                }
+            }
+            Substitution { access, rhe, .. } => {
+                self.rewrite_access(access, environment);
+                self.rewrite_expression(rhe, environment);
+            }
+            MultSubstitution { lhe, rhe, .. } | ConstraintEquality { lhe, rhe, .. } => {
+                self.rewrite_expression(lhe, environment);
+                self.rewrite_expression(rhe, environment);
+            }
+            UnderscoreSubstitution { rhe, .. } => self.rewrite_expression(rhe, environment),
+            Return { value, .. } => self.rewrite_expression(value, environment),
+            Assert { arg, .. } | StaticAssert { arg, .. } => self.rewrite_expression(arg, environment),
+            LogCall { args, .. } => {
+                for arg in args {
+                    if let LogArgument::LogExp(exp) = arg {
+                        self.rewrite_expression(exp, environment);
+                    }
+                }
+            }
+        }
+    }
+
+    fn rewrite_access(&mut self, access: &mut [Access], environment: &Environment) {
+        for acc in access {
+            if let Access::ArrayAccess(index) = acc {
+                self.rewrite_expression(index, environment);
+            }
+        }
+    }
+
+    fn rewrite_expressions(&mut self, exprs: &mut [Expression], environment: &Environment) {
+        for expr in exprs {
+            self.rewrite_expression(expr, environment);
+        }
+    }
+
+    fn rewrite_expression(&mut self, expr: &mut Expression, environment: &Environment) {
+        use Expression::*;
+        match expr {
+            InfixOp { lhe, rhe, .. } => {
+                self.rewrite_expression(lhe, environment);
+                self.rewrite_expression(rhe, environment);
+            }
+            PrefixOp { rhe, .. } | ParallelOp { rhe, .. } => self.rewrite_expression(rhe, environment),
+            InlineSwitchOp { cond, if_true, if_false, .. } => {
+                self.rewrite_expression(cond, environment);
+                self.rewrite_expression(if_true, environment);
+                self.rewrite_expression(if_false, environment);
+            }
+            Variable { meta, name, access } => {
+                if environment.contains_key(name) {
+                    let message = format!(
+                        "The template parameter {} can only be called or passed as argument of another template",
+                        name
+                    );
+                    self.add_error(meta, message);
+                }
+                self.rewrite_access(access, environment);
+            }
+            Number(..) => {}
+            Call { meta, id, args } => self.rewrite_call(meta, id, args, environment),
+            AnonymousComp { meta, id, params, signals, .. } => {
+                self.rewrite_call(meta, id, params, environment);
+                self.rewrite_expressions(signals, environment);
+            }
+            BusCall { args, .. } | ArrayInLine { values: args, .. } | Tuple { values: args, .. } => {
+                self.rewrite_expressions(args, environment)
+            }
+            UniformArray { value, dimension, .. } => {
+                self.rewrite_expression(value, environment);
+                self.rewrite_expression(dimension, environment);
+            }
+        }
+    }
+
+    fn rewrite_call(&mut self, meta: &Meta, id: &mut String, args: &mut Vec<Expression>, environment: &Environment) {
+        if let Option::Some(reference) = environment.get(id) {
+            self.rewrite_expressions(args, environment);
+            let mut new_args = reference.bound.clone();
+            new_args.append(args);
+            *id = reference.name.clone();
+            *args = new_args;
+        } else if self.generics.contains_key(id) {
+            let n_params = self.generic_params(id).0.len();
+            if args.len() != n_params {
+                let message = format!("The template {} expects {} arguments, found {}", id, n_params, args.len());
+                self.add_error(meta, message);
+                return;
+            }
+            if let Option::Some(reference) = self.instantiate(meta, id, std::mem::take(args), environment) {
+                *id = reference.name;
+                *args = reference.bound;
+            }
+        } else {
+            self.rewrite_expressions(args, environment);
+        }
+    }
+
+    fn generic_params(&self, id: &str) -> (Vec<String>, Vec<(String, String)>) {
+        if let Definition::Template { args, template_params, .. } = &self.generics.get(id).unwrap().1 {
+            (args.clone(), template_params.clone())
+        } else {
+            unreachable!()
+        }
+    }
+
+    // Resolves the template arguments of a call to a generic template. The result refers
+    // to the specialization of the template together with the arguments it receives,
+    // which may be fewer than its parameters when the template is partially applied.
+    fn instantiate(
+        &mut self,
+        meta: &Meta,
+        id: &str,
+        args: Vec<Expression>,
+        environment: &Environment,
+    ) -> Option<TemplateRef> {
+        let (params, template_params) = self.generic_params(id);
+        if args.len() > params.len() {
+            let message = format!("The template {} expects {} arguments, found {}", id, params.len(), args.len());
+            self.add_error(meta, message);
+            return Option::None;
+        }
+        if let Option::Some(missing) = params[args.len()..].iter().find(|p| interface_of(&template_params, p).is_some()) {
+            let message = format!("Missing template argument for the parameter {} of {}", missing, id);
+            self.add_error(meta, message);
+            return Option::None;
+        }
+        let mut references = Vec::new();
+        let mut new_args = Vec::new();
+        let mut valid = true;
+        for (param, mut arg) in params.iter().zip(args) {
+            if let Option::Some(interface) = interface_of(&template_params, param) {
+                let arg_meta = arg.get_meta().clone();
+                match self.resolve_template_argument(&mut arg, environment) {
+                    Option::Some(reference) if self.matches_interface(&arg_meta, &reference, param, interface) => {
+                        new_args.extend(reference.bound.iter().cloned());
+                        references.push(reference);
+                    }
+                    _ => valid = false,
+                }
+            } else {
+                self.rewrite_expression(&mut arg, environment);
+                new_args.push(arg);
+            }
+        }
+        if !valid {
+            return Option::None;
+        }
+        let name = self.specialization_name(meta, id, &references)?;
+        Option::Some(TemplateRef { name, bound: new_args })
+    }
+
+    fn matches_interface(&mut self, meta: &Meta, reference: &TemplateRef, param: &str, interface: &str) -> bool {
+        let (actual, expected) = match (self.signatures.get(&reference.name), self.signatures.get(interface)) {
+            (Option::Some(actual), Option::Some(expected)) => (actual, expected),
+            // An invalid interface is reported in the definition of the generic template
+            _ => return false,
+        };
+        let mismatch = if actual.params < reference.bound.len() + expected.params {
+            Option::Some(format!(
+                "it has {} parameters but {} are given and {} are expected by the interface",
+                actual.params,
+                reference.bound.len(),
+                expected.params
+            ))
+        } else if actual.params > reference.bound.len() + expected.params {
+            Option::Some(format!(
+                "{} of its {} parameters are not given and the interface only expects {}",
+                actual.params - reference.bound.len(),
+                actual.params,
+                expected.params
+            ))
+        } else {
+            wire_mismatch(&actual.wires, &expected.wires)
+        };
+        if let Option::Some(mismatch) = mismatch {
+            let message = format!(
+                "{} does not match the interface {} of the parameter {}: {}",
+                reference.name, interface, param, mismatch
+            );
+            self.add_error(meta, message);
+            false
+        } else {
+            true
+        }
+    }
+
+    fn resolve_template_argument(&mut self, arg: &mut Expression, environment: &Environment) -> Option<TemplateRef> {
+        match arg {
+            Expression::Variable { meta, name, access } if access.is_empty() => {
+                if let Option::Some(reference) = environment.get(name) {
+                    Option::Some(reference.clone())
+                } else if self.templates.contains(name) {
+                    Option::Some(TemplateRef { name: name.clone(), bound: Vec::new() })
+                } else if self.generics.contains_key(name) {
+                    let message = format!("The template {} has template parameters that must be given", name);
+                    let meta = meta.clone();
+                    self.add_error(&meta, message);
+                    Option::None
+                } else {
+                    self.expected_template_error(arg)
+                }
+            }
+            Expression::Call { meta, id, args } => {
+                if let Option::Some(reference) = environment.get(id) {
+                    let mut reference = reference.clone();
+                    self.rewrite_expressions(args, environment);
+                    reference.bound.append(args);
+                    Option::Some(reference)
+                } else if self.templates.contains(id) {
+                    self.rewrite_expressions(args, environment);
+                    Option::Some(TemplateRef { name: id.clone(), bound: std::mem::take(args) })
+                } else if self.generics.contains_key(id) {
+                    let meta = meta.clone();
+                    let id = id.clone();
+                    self.instantiate(&meta, &id, std::mem::take(args), environment)
+                } else {
+                    self.expected_template_error(arg)
+                }
+            }
+            _ => self.expected_template_error(arg),
+        }
+    }
+
+    fn specialization_name(&mut self, meta: &Meta, id: &str, references: &[TemplateRef]) -> Option<String> {
+        let key = format!(
+            "{}({})",
+            id,
+            references.iter().map(|r| format!("{}/{}", r.name, r.bound.len())).collect::<Vec<_>>().join(",")
+        );
+        if let Option::Some(name) = self.specializations.get(&key) {
+            return Option::Some(name.clone());
+        }
+        if self.specializations.len() >= MAX_SPECIALIZATIONS {
+            let message = format!("Too many specializations of generic templates generated from {}", id);
+            self.add_error(meta, message);
+            return Option::None;
+        }
+        let base = format!(
+            "{}_{}",
+            id,
+            references.iter().map(|r| r.name.as_str()).collect::<Vec<_>>().join("_")
+        );
+        let mut name = base.clone();
+        let mut counter = 1;
+        while self.used_names.contains(&name) {
+            name = format!("{}_{}", base, counter);
+            counter += 1;
+        }
+        self.used_names.insert(name.clone());
+        self.specializations.insert(key, name.clone());
+
+        let (file_id, mut definition) = self.generics.get(id).unwrap().clone();
+        let mut environment = Environment::new();
+        if let Definition::Template { name: new_name, args, template_params, body, .. } = &mut definition {
+            let mut new_params = Vec::new();
+            let mut references = references.iter();
+            for param in args.iter() {
+                if interface_of(template_params, param).is_some() {
+                    let reference = references.next().unwrap();
+                    let hidden: Vec<String> =
+                        (0..reference.bound.len()).map(|i| format!("{}${}", param, i)).collect();
+                    let bound = hidden
+                        .iter()
+                        .map(|h| Expression::Variable { meta: meta.clone(), name: h.clone(), access: Vec::new() })
+                        .collect();
+                    environment.insert(param.clone(), TemplateRef { name: reference.name.clone(), bound });
+                    new_params.extend(hidden);
+                } else {
+                    new_params.push(param.clone());
+                }
+            }
+            *new_name = name.clone();
+            *args = new_params;
+            template_params.clear();
+            self.signatures.insert(name.clone(), signature(args, template_params, body));
+        }
+        self.origins.insert(name.clone(), id.to_string());
+        self.pending.push((file_id, definition, environment));
+        Option::Some(name)
+    }
+
+    fn expected_template_error(&mut self, arg: &Expression) -> Option<TemplateRef> {
+        let meta = arg.get_meta().clone();
+        self.add_error(&meta, "Expected a template, possibly applied to some of its arguments".to_string());
+        Option::None
+    }
+
+    // The body of a generic template is traversed once per specialization, its errors are
+    // only reported the first time
+    fn add_error(&mut self, meta: &Meta, message: String) {
+        if !self.reported.insert((self.file_id, meta.start)) {
+            return;
+        }
+        let mut report = Report::error("Invalid template argument".to_string(), ReportCode::InvalidTemplateArgument);
+        report.add_primary(meta.file_location(), self.file_id, message);
+        self.reports.push(report);
+    }
+}
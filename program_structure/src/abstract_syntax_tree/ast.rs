@@ -1,6 +1,9 @@
 use crate::{file_definition::{FileLocation, FileID}, error_definition::Report, error_code::{ReportCode}};
+use crate::lint_config::LintScope;
+use crate::template_budget::TemplateBudget;
 use num_bigint::BigInt;
 use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Clone)]
 pub enum Pragma {
@@ -90,6 +93,8 @@ pub struct AST {
     pub includes: Vec<String>,
     pub definitions: Vec<Definition>,
     pub main_component: Option<MainComponent>,
+    pub lint_scopes: Vec<LintScope>,
+    pub template_budgets: Vec<TemplateBudget>,
 }
 
 impl AST {
@@ -133,68 +138,127 @@ impl AST {
             includes,
             definitions,
             main_component,
+            lint_scopes: Vec::new(),
+            template_budgets: Vec::new(),
         }, reports)
     }
 }
 
+/// Documentation written with `///` comments before a definition and
+/// before the signal and bus declarations of its body.
+#[derive(Clone, Default)]
+pub struct DocComment {
+    pub text: Option<String>,
+    pub wires: BTreeMap<String, String>,
+}
+
+impl DocComment {
+    pub fn get_text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+    pub fn get_wire_text(&self, name: &str) -> Option<&str> {
+        self.wires.get(name).map(|text| text.as_str())
+    }
+}
+
 #[derive(Clone)]
 pub enum Definition {
     Template {
         meta: Meta,
         name: String,
+        name_location: FileLocation,
         args: Vec<String>,
         arg_location: FileLocation,
         body: Statement,
         parallel: bool,
         is_custom_gate: bool,
-        is_extern_c: bool
+        is_extern_c: bool,
+        requires: Vec<Expression>,
+        // Parameters whose value is a template, with the template that declares the
+        // interface (inputs, outputs and remaining parameters) their values must have
+        template_params: Vec<(String, String)>,
+        docs: DocComment,
     },
     Function {
         meta: Meta,
         name: String,
+        name_location: FileLocation,
         args: Vec<String>,
         arg_location: FileLocation,
         body: Statement,
+        docs: DocComment,
     },
     Bus {
         meta: Meta,
         name: String,
+        name_location: FileLocation,
         args: Vec<String>,
         arg_location: FileLocation,
         body: Statement,
+        docs: DocComment,
+    },
+    Constant {
+        meta: Meta,
+        name: String,
+        value: Expression,
     },
 }
 pub fn build_template(
     meta: Meta,
     name: String,
+    name_location: FileLocation,
     args: Vec<String>,
     arg_location: FileLocation,
     body: Statement,
     parallel: bool,
     is_custom_gate: bool,
-    is_extern_c: bool
+    is_extern_c: bool,
+    requires: Vec<Expression>,
 ) -> Definition {
-    Definition::Template { meta, name, args, arg_location, body, parallel, is_custom_gate, is_extern_c }
+    Definition::Template {
+        meta,
+        name,
+        name_location,
+        args,
+        arg_location,
+        body,
+        parallel,
+        is_custom_gate,
+        is_extern_c,
+        requires,
+        template_params: Vec::new(),
+        docs: DocComment::default(),
+    }
 }
 
 pub fn build_function(
     meta: Meta,
     name: String,
+    name_location: FileLocation,
     args: Vec<String>,
     arg_location: FileLocation,
     body: Statement,
 ) -> Definition {
-    Definition::Function { meta, name, args, arg_location, body }
+    Definition::Function { meta, name, name_location, args, arg_location, body, docs: DocComment::default() }
 }
 
 pub fn build_bus(
     meta: Meta,
     name: String,
+    name_location: FileLocation,
     args: Vec<String>,
     arg_location: FileLocation,
     body: Statement,
 ) -> Definition {
-    Definition::Bus { meta, name, args, arg_location, body }
+    Definition::Bus { meta, name, name_location, args, arg_location, body, docs: DocComment::default() }
+}
+
+pub fn build_constant(
+    meta: Meta,
+    name: String,
+    value: Expression,
+) -> Definition {
+    Definition::Constant { meta, name, value }
 }
 
 #[derive(Clone)]
@@ -262,6 +326,11 @@ pub enum Statement {
         meta: Meta,
         arg: Expression,
     },
+    StaticAssert {
+        meta: Meta,
+        arg: Expression,
+        message: String,
+    },
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -410,6 +479,8 @@ pub enum TypeReduction {
 pub enum LogArgument {
     LogStr(String),
     LogExp(Expression),
+    // Format string of the log, the expressions that follow it are its values
+    LogFormat(String),
 }
 pub fn build_log_string(acc: String) -> LogArgument {
     LogArgument::LogStr(acc)
@@ -418,6 +489,91 @@ pub fn build_log_expression(expr: Expression) -> LogArgument {
     LogArgument::LogExp(expr)
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LogFormat {
+    Decimal,
+    Hexadecimal,
+    // Values greater than p/2 are printed as negative numbers
+    Signed,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LogFormatPiece {
+    Text(String),
+    Value(LogFormat),
+}
+
+// Number of placeholders of a string that is a valid format string with at least one
+// placeholder. Any other string is printed literally, as in the logs without format.
+pub fn log_format_placeholders(string: &str) -> Option<usize> {
+    let pieces = parse_log_format(string).ok()?;
+    let placeholders = pieces.iter().filter(|piece| matches!(piece, LogFormatPiece::Value(_))).count();
+    if placeholders > 0 {
+        Option::Some(placeholders)
+    } else {
+        Option::None
+    }
+}
+
+// The first string of a log is a format string when its placeholders are valid and
+// they match the values that follow it
+pub fn is_log_format(string: &str, values: &[LogArgument]) -> bool {
+    let only_values = values.iter().all(|value| matches!(value, LogArgument::LogExp(_)));
+    only_values && log_format_placeholders(string) == Option::Some(values.len())
+}
+
+// Splits a format string such as "x[{}] = {:x}" into its text and its placeholders.
+// The supported placeholders are {} (decimal), {:x} (hexadecimal) and {:s} (signed),
+// {{ and }} are used to print braces.
+pub fn parse_log_format(format: &str) -> Result<Vec<LogFormatPiece>, String> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut chars = format.chars().peekable();
+    while let Option::Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Option::Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Option::Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut spec = String::new();
+                loop {
+                    match chars.next() {
+                        Option::Some('}') => break,
+                        Option::Some(c) => spec.push(c),
+                        Option::None => return Result::Err("Unclosed placeholder '{'".to_string()),
+                    }
+                }
+                let value_format = match spec.as_str() {
+                    "" => LogFormat::Decimal,
+                    ":x" => LogFormat::Hexadecimal,
+                    ":s" => LogFormat::Signed,
+                    _ => {
+                        return Result::Err(format!(
+                            "Unknown placeholder {{{}}}, expected {{}}, {{:x}} or {{:s}}",
+                            spec
+                        ))
+                    }
+                };
+                if !text.is_empty() {
+                    pieces.push(LogFormatPiece::Text(std::mem::take(&mut text)));
+                }
+                pieces.push(LogFormatPiece::Value(value_format));
+            }
+            '}' => return Result::Err("Unmatched '}', use '}}' to print it".to_string()),
+            _ => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        pieces.push(LogFormatPiece::Text(text));
+    }
+    Result::Ok(pieces)
+}
+
 
 #[derive(Default, Clone)]
 pub struct TypeKnowledge {
@@ -622,6 +778,16 @@ pub fn produce_compiler_version_report(path : String, required_version : Version
     report
 }
 
+pub fn reserved_name_error(name: &str, location: FileLocation, file_id: FileID) -> Report {
+    let mut report = Report::error(
+        format!("{} is the name of a built-in constant", name),
+        ReportCode::ReservedName,
+    );
+    report.add_primary(location, file_id, format!("{} can not be declared", name));
+    report.add_note("Rename the symbol, the names of the built-in constants are reserved".to_string());
+    report
+}
+
 pub fn anonymous_inside_condition_error(meta : Meta) -> Report {
     let msg = "An anonymous component cannot be used inside a condition ".to_string();
                 let mut report = Report::error(
@@ -56,16 +56,23 @@ pub fn build_constraint_equality(meta: Meta, lhe: Expression, rhe: Expression) -
 
 pub fn build_log_call(meta: Meta, args: Vec<LogArgument>) -> Statement {
     let mut new_args = Vec::new();
-    for arg in args {
+    let is_formatted = match args.split_first() {
+        Option::Some((LogArgument::LogStr(str), values)) => is_log_format(str, values),
+        _ => false,
+    };
+    for (index, arg) in args.into_iter().enumerate() {
         match arg {
-            LogArgument::LogExp(..) => { new_args.push(arg);}
+            LogArgument::LogExp(..) | LogArgument::LogFormat(..) => { new_args.push(arg);}
+            LogArgument::LogStr(str) if index == 0 && is_formatted => {
+                new_args.push(LogArgument::LogFormat(str));
+            }
             LogArgument::LogStr(str) => { new_args.append(&mut split_string(str));}
         }
     }
     LogCall { meta, args: new_args }
 }
 
-fn split_string(str: String) -> Vec<LogArgument> {
+pub fn split_string(str: String) -> Vec<LogArgument> {
     let mut v = vec![];
     let sub_len = 230;
     let mut cur = str;
@@ -81,6 +88,10 @@ pub fn build_assert(meta: Meta, arg: Expression) -> Statement {
     Assert { meta, arg }
 }
 
+pub fn build_static_assert(meta: Meta, arg: Expression, message: String) -> Statement {
+    StaticAssert { meta, arg, message }
+}
+
 pub fn build_mult_substitution(meta: Meta, lhe: Expression, op : AssignOp, rhe: Expression) -> Statement {
     MultSubstitution { meta: meta.clone(), lhe, op, rhe }
 }
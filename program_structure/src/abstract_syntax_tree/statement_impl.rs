@@ -12,6 +12,7 @@ impl Statement {
             | LogCall { meta, .. }
             | Block { meta, .. }
             | Assert { meta, .. }
+            | StaticAssert { meta, .. }
             | ConstraintEquality { meta, .. }
             | InitializationBlock { meta, .. }
             | MultSubstitution { meta, ..}
@@ -29,6 +30,7 @@ impl Statement {
             | LogCall { meta, .. }
             | Block { meta, .. }
             | Assert { meta, .. }
+            | StaticAssert { meta, .. }
             | ConstraintEquality { meta, .. }
             | InitializationBlock { meta, .. }
             | MultSubstitution { meta, ..}
@@ -125,6 +127,14 @@ impl Statement {
             false
         }
     }
+    pub fn is_static_assert(&self) -> bool {
+        use Statement::StaticAssert;
+        if let StaticAssert { .. } = self {
+            true
+        } else {
+            false
+        }
+    }
 
     pub fn contains_anonymous_comp(&self) -> bool {
         use Statement::*;
@@ -178,7 +188,7 @@ impl Statement {
                 use crate::abstract_syntax_tree::statement_impl::LogArgument::*;
                 for arg in args{
                     match arg {
-                        LogStr(_) => {},
+                        LogStr(_) | LogFormat(_) => {},
                         LogExp(exp) => if exp.contains_anonymous_comp() {return true;},
                     }
                 }
@@ -192,7 +202,7 @@ impl Statement {
                 }
                 false
             }
-            Assert {  arg, .. } => {
+            Assert {  arg, .. } | StaticAssert { arg, .. } => {
                 arg.contains_anonymous_comp() 
             }
             UnderscoreSubstitution { rhe, .. } => {
@@ -232,7 +242,9 @@ impl FillMeta for Statement {
             }
             LogCall { meta, args, .. } => fill_log_call(meta, args, file_id, element_id),
             Block { meta, stmts, .. } => fill_block(meta, stmts, file_id, element_id),
-            Assert { meta, arg, .. } => fill_assert(meta, arg, file_id, element_id),
+            Assert { meta, arg, .. } | StaticAssert { meta, arg, .. } => {
+                fill_assert(meta, arg, file_id, element_id)
+            }
             UnderscoreSubstitution { meta, rhe, .. } => {
                 fill_underscore_substitution(meta, rhe, file_id, element_id);
             },
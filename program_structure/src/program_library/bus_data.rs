@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use super::ast::{FillMeta, Statement, VariableType, SignalType};
+use super::ast::{DocComment, FillMeta, Statement, VariableType, SignalType};
 use super::file_definition::{FileID, FileLocation};
 use super::wire_data::*;
 
@@ -16,6 +16,9 @@ pub struct BusData {
     fields: WireInfo,
     /* Only used to know the order in which fields are declared.*/
     field_declarations: WireDeclarationOrder,
+    docs: DocComment,
+    // The location of the name in the definition
+    name_location: FileLocation,
 }
 
 impl BusData {
@@ -40,7 +43,9 @@ impl BusData {
              name_of_params,
              param_location,
              fields,
-             field_declarations
+             field_declarations,
+             name_location: FileLocation::default(),
+             docs: DocComment::default()
         }
     }
     pub fn copy(
@@ -61,7 +66,9 @@ impl BusData {
             name_of_params,
             param_location,
             fields,
-            field_declarations
+            field_declarations,
+            name_location: FileLocation::default(),
+            docs: DocComment::default()
         }
     }
     pub fn get_file_id(&self) -> FileID {
@@ -103,6 +110,18 @@ impl BusData {
     pub fn get_name(&self) -> &str {
         &self.name
     }
+    pub fn get_docs(&self) -> &DocComment {
+        &self.docs
+    }
+    pub fn set_docs(&mut self, docs: DocComment) {
+        self.docs = docs;
+    }
+    pub fn get_name_location(&self) -> FileLocation {
+        self.name_location.clone()
+    }
+    pub fn set_name_location(&mut self, name_location: FileLocation) {
+        self.name_location = name_location;
+    }
     pub fn get_field_info(&self, name: &str) -> Option<&WireData> {
         self.fields.get(name)
     }
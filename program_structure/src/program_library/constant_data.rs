@@ -0,0 +1,183 @@
+use super::ast::{Access, Expression, FillMeta, LogArgument, Statement};
+use super::file_definition::{FileID, FileLocation};
+use super::function_data::FunctionInfo;
+use std::collections::{HashMap, HashSet};
+
+pub type ConstantInfo = HashMap<String, ConstantData>;
+
+#[derive(Clone)]
+pub struct ConstantData {
+    name: String,
+    file_id: FileID,
+    location: FileLocation,
+    value: Expression,
+}
+
+impl ConstantData {
+    pub fn new(
+        name: String,
+        file_id: FileID,
+        location: FileLocation,
+        mut value: Expression,
+        elem_id: &mut usize,
+    ) -> ConstantData {
+        value.fill(file_id, elem_id);
+        ConstantData { name, file_id, location, value }
+    }
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn get_file_id(&self) -> FileID {
+        self.file_id
+    }
+    pub fn get_location(&self) -> FileLocation {
+        self.location.clone()
+    }
+    pub fn get_value(&self) -> &Expression {
+        &self.value
+    }
+    pub fn get_mut_value(&mut self) -> &mut Expression {
+        &mut self.value
+    }
+}
+
+// Returns the global constants that have to be known in order to evaluate
+// the given expression, including the ones used inside the called functions.
+pub fn constant_dependencies(
+    value: &Expression,
+    constants: &ConstantInfo,
+    functions: &FunctionInfo,
+) -> HashSet<String> {
+    let mut dependencies = HashSet::new();
+    let mut visited_functions = HashSet::new();
+    dependencies_in_expression(value, constants, functions, &mut visited_functions, &mut dependencies);
+    dependencies
+}
+
+fn dependencies_in_statement(
+    stmt: &Statement,
+    constants: &ConstantInfo,
+    functions: &FunctionInfo,
+    visited_functions: &mut HashSet<String>,
+    dependencies: &mut HashSet<String>,
+) {
+    use Statement::*;
+    let visit = |expr: &Expression, visited_functions: &mut HashSet<String>, dependencies: &mut HashSet<String>| {
+        dependencies_in_expression(expr, constants, functions, visited_functions, dependencies)
+    };
+    match stmt {
+        IfThenElse { cond, if_case, else_case, .. } => {
+            visit(cond, visited_functions, dependencies);
+            dependencies_in_statement(if_case, constants, functions, visited_functions, dependencies);
+            if let Option::Some(else_stmt) = else_case {
+                dependencies_in_statement(else_stmt, constants, functions, visited_functions, dependencies);
+            }
+        }
+        While { cond, stmt, .. } => {
+            visit(cond, visited_functions, dependencies);
+            dependencies_in_statement(stmt, constants, functions, visited_functions, dependencies);
+        }
+        Return { value, .. } => visit(value, visited_functions, dependencies),
+        InitializationBlock { initializations, .. } => {
+            for init in initializations {
+                dependencies_in_statement(init, constants, functions, visited_functions, dependencies);
+            }
+        }
+        Declaration { dimensions, .. } => {
+            for dim in dimensions {
+                visit(dim, visited_functions, dependencies);
+            }
+        }
+        Substitution { access, rhe, .. } => {
+            for acc in access {
+                if let Access::ArrayAccess(index) = acc {
+                    visit(index, visited_functions, dependencies);
+                }
+            }
+            visit(rhe, visited_functions, dependencies);
+        }
+        MultSubstitution { lhe, rhe, .. } | ConstraintEquality { lhe, rhe, .. } => {
+            visit(lhe, visited_functions, dependencies);
+            visit(rhe, visited_functions, dependencies);
+        }
+        UnderscoreSubstitution { rhe, .. } => visit(rhe, visited_functions, dependencies),
+        LogCall { args, .. } => {
+            for arg in args {
+                if let LogArgument::LogExp(expr) = arg {
+                    visit(expr, visited_functions, dependencies);
+                }
+            }
+        }
+        Block { stmts, .. } => {
+            for s in stmts {
+                dependencies_in_statement(s, constants, functions, visited_functions, dependencies);
+            }
+        }
+        Assert { arg, .. } | StaticAssert { arg, .. } => visit(arg, visited_functions, dependencies),
+    }
+}
+
+fn dependencies_in_expression(
+    expr: &Expression,
+    constants: &ConstantInfo,
+    functions: &FunctionInfo,
+    visited_functions: &mut HashSet<String>,
+    dependencies: &mut HashSet<String>,
+) {
+    use Expression::*;
+    let visit = |expr: &Expression, visited_functions: &mut HashSet<String>, dependencies: &mut HashSet<String>| {
+        dependencies_in_expression(expr, constants, functions, visited_functions, dependencies)
+    };
+    match expr {
+        InfixOp { lhe, rhe, .. } => {
+            visit(lhe, visited_functions, dependencies);
+            visit(rhe, visited_functions, dependencies);
+        }
+        PrefixOp { rhe, .. } | ParallelOp { rhe, .. } => visit(rhe, visited_functions, dependencies),
+        InlineSwitchOp { cond, if_true, if_false, .. } => {
+            visit(cond, visited_functions, dependencies);
+            visit(if_true, visited_functions, dependencies);
+            visit(if_false, visited_functions, dependencies);
+        }
+        Variable { name, access, .. } => {
+            if constants.contains_key(name) {
+                dependencies.insert(name.clone());
+            }
+            for acc in access {
+                if let Access::ArrayAccess(index) = acc {
+                    visit(index, visited_functions, dependencies);
+                }
+            }
+        }
+        Number(..) => {}
+        Call { id, args, .. } => {
+            for arg in args {
+                visit(arg, visited_functions, dependencies);
+            }
+            if let Option::Some(function) = functions.get(id) {
+                if visited_functions.insert(id.clone()) {
+                    dependencies_in_statement(function.get_body(), constants, functions, visited_functions, dependencies);
+                }
+            }
+        }
+        BusCall { args, .. } => {
+            for arg in args {
+                visit(arg, visited_functions, dependencies);
+            }
+        }
+        AnonymousComp { params, signals, .. } => {
+            for e in params.iter().chain(signals.iter()) {
+                visit(e, visited_functions, dependencies);
+            }
+        }
+        ArrayInLine { values, .. } | Tuple { values, .. } => {
+            for value in values {
+                visit(value, visited_functions, dependencies);
+            }
+        }
+        UniformArray { value, dimension, .. } => {
+            visit(value, visited_functions, dependencies);
+            visit(dimension, visited_functions, dependencies);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use core::fmt;
 use std::fmt::Formatter;
 
-#[derive(Copy,Clone)]
+#[derive(Copy,Clone,Debug)]
 pub enum ReportCode {
     //Parse Errors
     UnclosedComment,
@@ -13,6 +13,8 @@ pub enum ReportCode {
     UnrecognizedVersion,
     UnrecognizedPragma,
     ExpectedIdentifier,
+    InvalidLintComment,
+    InvalidBudgetComment,
     IncludeNotFound,
     IllegalExpression,
     MultiplePragma,
@@ -107,11 +109,34 @@ pub enum ReportCode {
     NonValidTagAssignment,
     NonConstantArrayLength,
     NonComputableExpression,
+    GlobalConstantAssigned,
+    InvalidGlobalConstant,
+    StaticAssertFailed,
+    NonConstantStaticAssert,
+    InvalidTemplateContract,
+    TemplateContractViolated,
+    ExecutionLimitExceeded,
+    BudgetExceeded,
+    UnsatisfiableConstraint,
+    TriviallyTrueConstraint,
+    UnmatchedSignalPattern,
+    InvalidLogFormat,
+    InvalidTemplateArgument,
+    ReservedName,
     // Constraint analysis codes
     UnconstrainedSignal,
     UnconstrainedIOSignal,
     UnusedInput,
     UnusedOutput,
+    SignalAssignmentNotConstrained,
+    InsufficientRangeCheck,
+    UnderconstrainedOutput,
+    // Dead code analysis codes
+    UnusedDefinition,
+    UnusedVariable,
+    UnassignedComponent,
+    UnreadSignal,
+    UnreachableCode,
 
     ErrorWat2Wasm,
     CustomGateIntermediateSignalWarning,
@@ -130,6 +155,199 @@ pub enum ReportCode {
     MustBeBus,
 }
 
+impl ReportCode {
+    // Warnings whose level can be configured with -A, -W and -D
+    pub fn lints() -> Vec<ReportCode> {
+        use self::ReportCode::*;
+        vec![
+            NoCompilerVersionWarning,
+            RuntimeWarning,
+            TriviallyTrueConstraint,
+            UnmatchedSignalPattern,
+            UnconstrainedSignal,
+            UnconstrainedIOSignal,
+            UnusedInput,
+            UnusedOutput,
+            SignalAssignmentNotConstrained,
+            InsufficientRangeCheck,
+            UnderconstrainedOutput,
+            UnusedDefinition,
+            UnusedVariable,
+            UnassignedComponent,
+            UnreadSignal,
+            UnreachableCode,
+            CustomGateIntermediateSignalWarning,
+            UnderscoreWithNoSignalWarning,
+        ]
+    }
+
+    // A lint is named either by its variant (UnconstrainedSignal) or by its code (CA01)
+    pub fn from_lint_name(name: &str) -> Option<ReportCode> {
+        ReportCode::lints()
+            .into_iter()
+            .find(|code| format!("{:?}", code) == name || code.to_string() == name)
+    }
+
+    // Lints that are only checked when inspecting the constraints of the circuit
+    pub fn is_inspect_lint(&self) -> bool {
+        use self::ReportCode::*;
+        matches!(self, RuntimeWarning | UnconstrainedSignal | UnconstrainedIOSignal | UnusedInput | UnusedOutput | InsufficientRangeCheck | UnderconstrainedOutput)
+    }
+
+    // Every code, with arbitrary values for the ones that carry information
+    pub fn all() -> Vec<ReportCode> {
+        use self::ReportCode::*;
+        vec![
+            UnclosedComment,
+            FileOs,
+            NoMainFoundInProject,
+            MultipleMain,
+            MissingSemicolon,
+            UnrecognizedInclude,
+            UnrecognizedVersion,
+            UnrecognizedPragma,
+            ExpectedIdentifier,
+            InvalidLintComment,
+            InvalidBudgetComment,
+            IncludeNotFound,
+            IllegalExpression,
+            MultiplePragma,
+            NoCompilerVersionWarning,
+            CompilerVersionError,
+            WrongTypesInAssignOperationOperatorSignal,
+            WrongTypesInAssignOperationOperatorNoSignal,
+            WrongTypesInAssignOperationTemplate,
+            WrongTypesInAssignOperationBus,
+            WrongTypesInAssignOperationDifferentBus,
+            WrongTypesInAssignOperationExpression,
+            WrongTypesInAssignOperationArrayTemplates,
+            WrongTypesInAssignOperationArrayBuses,
+            WrongTypesInAssignOperationDims(0, 0),
+            WrongNumberOfArguments(0, 0),
+            UndefinedFunction,
+            UndefinedTemplate,
+            UndefinedBus,
+            UninitializedSymbolInExpression,
+            UnableToTypeFunction,
+            UnreachableConstraints,
+            UnreachableTags,
+            UnreachableSignals,
+            UnknownIndex,
+            UnknownDimension,
+            UnknownTemplateAssignment,
+            SameFunctionDeclaredTwice,
+            SameTemplateDeclaredTwice,
+            SameSymbolDeclaredTwice,
+            StaticInfoWasOverwritten,
+            SignalInLineInitialization,
+            SignalOutsideOriginalScope,
+            FunctionWrongNumberOfArguments,
+            FunctionInconsistentTyping,
+            FunctionPathWithoutReturn,
+            FunctionReturnError,
+            ForbiddenDeclarationInFunction,
+            NonHomogeneousArray(0, 0),
+            NonBooleanCondition,
+            NonCompatibleBranchTypes,
+            NonEqualTypesInExpression,
+            NonExistentSymbol,
+            MainComponentWithTags,
+            IllegalMainExpression,
+            TemplateCallAsArgument,
+            TemplateWrongNumberOfArguments,
+            TemplateWithReturnStatement,
+            TypeCantBeUseAsCondition,
+            EmptyArrayInlineDeclaration,
+            PrefixOperatorWithWrongTypes,
+            ParallelOperatorWithWrongTypes,
+            InfixOperatorWithWrongTypes,
+            InvalidArgumentInCall,
+            InvalidArgumentInCallBus,
+            InvalidArgumentInBusInstantiationT,
+            InvalidArgumentInBusInstantiationB,
+            InconsistentReturnTypesInBlock,
+            InconsistentStaticInformation,
+            InvalidArrayAccess(0, 0),
+            InvalidSignalAccess,
+            InvalidTagAccess,
+            InvalidTagAccessAfterArray,
+            InvalidArraySize(0),
+            InvalidArraySizeT,
+            InvalidArraySizeB,
+            InvalidArrayType,
+            InvalidArrayTypeB,
+            InvalidArrayTypeBandSignal,
+            InvalidArrayTypeDifferentBuses,
+            ForStatementIllConstructed,
+            BadArrayAccess,
+            AssigningAComponentTwice,
+            AssigningASignalTwice,
+            NotAllowedOperation,
+            ConstraintGeneratorInFunction,
+            WrongSignalTags,
+            InvalidPartialArray,
+            MustBeSingleArithmetic(0),
+            MustBeSingleArithmeticT,
+            MustBeSingleArithmeticB,
+            MustBeArithmetic,
+            OutputTagCannotBeModifiedOutside,
+            InputTagCannotBeAccessedOutside,
+            InputTagCannotBeModifiedOutside,
+            MustBeSameDimension(0, 0),
+            ExpectedDimDiffGotDim(0, 0),
+            RuntimeError,
+            RuntimeWarning,
+            UnknownTemplate,
+            UnknownBus,
+            NonQuadratic,
+            NonValidTagAssignment,
+            NonConstantArrayLength,
+            NonComputableExpression,
+            GlobalConstantAssigned,
+            InvalidGlobalConstant,
+            StaticAssertFailed,
+            NonConstantStaticAssert,
+            InvalidTemplateContract,
+            TemplateContractViolated,
+            ExecutionLimitExceeded,
+            BudgetExceeded,
+            UnsatisfiableConstraint,
+            TriviallyTrueConstraint,
+            UnmatchedSignalPattern,
+            InvalidLogFormat,
+            InvalidTemplateArgument,
+            ReservedName,
+            UnconstrainedSignal,
+            UnconstrainedIOSignal,
+            UnusedInput,
+            UnusedOutput,
+            SignalAssignmentNotConstrained,
+            InsufficientRangeCheck,
+            UnderconstrainedOutput,
+            UnusedDefinition,
+            UnusedVariable,
+            UnassignedComponent,
+            UnreadSignal,
+            UnreachableCode,
+            ErrorWat2Wasm,
+            CustomGateIntermediateSignalWarning,
+            CustomGateConstraintError,
+            CustomGateSubComponentError,
+            CustomGatesPragmaError,
+            CustomGatesVersionError,
+            AnonymousCompError,
+            UnderscoreWithNoSignalWarning,
+            TupleError,
+            InvalidSignalTagAccess,
+            UninitializedComponent,
+            BusWrongNumberOfArguments,
+            InvalidSignalAccessInBus,
+            MustBeSameBus,
+            MustBeBus,
+        ]
+    }
+}
+
 impl fmt::Display for ReportCode {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         use self::ReportCode::*;
@@ -148,6 +366,8 @@ impl fmt::Display for ReportCode {
             MultiplePragma => "P1013",
             IncludeNotFound => "P1014",
             ExpectedIdentifier => "P1015",
+            InvalidLintComment => "P1016",
+            InvalidBudgetComment => "P1017",
             UndefinedFunction => "T2001",
             UndefinedTemplate => "T2002",
             UninitializedSymbolInExpression => "T2003",
@@ -226,8 +446,22 @@ impl fmt::Display for ReportCode {
             WrongTypesInAssignOperationDims(..) => "T2061",
             NonValidTagAssignment => "T2062",
             IllegalMainExpression => "T2063",
+            GlobalConstantAssigned => "T2064",
+            InvalidGlobalConstant => "T2065",
+            NonConstantStaticAssert => "T2066",
+            InvalidTemplateContract => "T2067",
+            InvalidLogFormat => "T2068",
+            InvalidTemplateArgument => "T2069",
+            ReservedName => "T2070",
             RuntimeError => "T3001",
             RuntimeWarning => "T3002",
+            StaticAssertFailed => "T3003",
+            TemplateContractViolated => "T3004",
+            ExecutionLimitExceeded => "T3005",
+            BudgetExceeded => "T3006",
+            UnsatisfiableConstraint => "T3007",
+            TriviallyTrueConstraint => "T3008",
+            UnmatchedSignalPattern => "T3009",
             UnknownDimension => "T20460",
             UnknownTemplate => "T20461",
             UnknownTemplateAssignment => "T2O461-A",
@@ -242,6 +476,14 @@ impl fmt::Display for ReportCode {
             UnconstrainedIOSignal => "CA02",
             UnusedInput => "CA03",
             UnusedOutput => "CA04",
+            SignalAssignmentNotConstrained => "CA05",
+            InsufficientRangeCheck => "CA06",
+            UnderconstrainedOutput => "CA07",
+            UnusedDefinition => "DC01",
+            UnusedVariable => "DC02",
+            UnassignedComponent => "DC03",
+            UnreadSignal => "DC04",
+            UnreachableCode => "DC05",
             ErrorWat2Wasm => "W01",
             CustomGateIntermediateSignalWarning => "CG01",
             CustomGateConstraintError => "CG02",
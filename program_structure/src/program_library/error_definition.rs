@@ -1,5 +1,6 @@
 use super::error_code::ReportCode;
 use super::file_definition::{FileID, FileLibrary, FileLocation};
+use super::lint_config::{LintConfig, LintLevel};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::term;
 
@@ -38,6 +39,7 @@ pub struct Report {
     primary: Vec<ReportLabel>,
     secondary: Vec<ReportLabel>,
     notes: Vec<ReportNote>,
+    template: Option<String>,
 }
 impl Report {
     fn new(category: MessageCategory, error_message: String, error_code: ReportCode) -> Report {
@@ -48,16 +50,32 @@ impl Report {
             primary: Vec::new(),
             secondary: Vec::new(),
             notes: Vec::new(),
+            template: Option::None,
         }
     }
-    pub fn print_reports(reports: &[Report], file_library: &FileLibrary) {
+    // Prints the reports according to the lint configuration: allowed warnings are
+    // skipped and denied ones are printed as errors. Returns whether any error was printed.
+    pub fn print_reports(reports: &[Report], file_library: &FileLibrary, lints: &LintConfig) -> bool {
         use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
         let writer = StandardStream::stderr(ColorChoice::Always);
         let config = term::Config::default();
         let mut diagnostics = Vec::new();
+        let mut found_errors = false;
         let files = file_library.to_storage();
         for report in reports.iter() {
-            diagnostics.push(report.to_diagnostic());
+            match lints.level_of(report) {
+                LintLevel::Allow => {}
+                LintLevel::Warn => diagnostics.push(report.to_diagnostic()),
+                LintLevel::Deny => {
+                    found_errors = true;
+                    let mut denied = report.clone();
+                    if denied.is_warning() {
+                        denied.category = MessageCategory::Error;
+                        denied.add_note(format!("{} is denied by the lint configuration", report.get_code()));
+                    }
+                    diagnostics.push(denied.to_diagnostic());
+                }
+            }
         }
         for diagnostic in diagnostics.iter() {
             let print_result = term::emit(&mut writer.lock(), &config, files, &diagnostic);
@@ -65,6 +83,7 @@ impl Report {
                 panic!("Error printing reports")
             }
         }
+        found_errors
     }
     pub fn error_code_to_diagnostic_code(error_code: &ReportCode) -> DiagnosticCode {
         error_code.to_string()
@@ -102,17 +121,30 @@ impl Report {
         self.get_mut_notes().push(note);
         self
     }
+    // Template where the report was found, used to apply the lint levels of the
+    // template to reports that do not point to any location
+    pub fn set_template(&mut self, template: String) -> &mut Self {
+        self.template = Option::Some(template);
+        self
+    }
 
     pub fn to_diagnostic(&self) -> Diagnostic<FileID> {
         let mut labels = self.get_primary().clone();
         let mut secondary = self.get_secondary().clone();
         labels.append(&mut secondary);
+        let mut notes = self.get_notes().clone();
+        let kind = if self.is_warning() { "warning" } else { "error" };
+        notes.push(format!(
+            "For more information about this {}, try `circom --explain {}`",
+            kind,
+            self.get_code()
+        ));
 
         if self.is_warning() { Diagnostic::warning() } else { Diagnostic::error() }
             .with_message(self.get_message())
             .with_code(Report::error_code_to_diagnostic_code(self.get_code()))
             .with_labels(labels)
-            .with_notes(self.get_notes().clone())
+            .with_notes(notes)
     }
 
     pub fn is_error(&self) -> bool {
@@ -142,6 +174,9 @@ impl Report {
     fn get_mut_secondary(&mut self) -> &mut Vec<ReportLabel> {
         &mut self.secondary
     }
+    pub fn get_template(&self) -> Option<&String> {
+        self.template.as_ref()
+    }
     pub fn get_notes(&self) -> &Vec<ReportNote> {
         &self.notes
     }
@@ -0,0 +1,2286 @@
+use super::error_code::ReportCode;
+
+// Explanation of the codes that are kept for compatibility but are not produced by
+// the current version of the compiler
+const NOT_EMITTED: &str = "\
+This code is not emitted by the current version of the compiler. It is kept so that
+the numbering of the remaining codes does not change.
+";
+
+// Long-form explanations shown by 'circom --explain <CODE>'. Several variants share
+// the same code, so looking up a code returns the explanations of all of them.
+pub fn explanations_of(code: &str) -> Vec<&'static str> {
+    let mut explanations = Vec::new();
+    for report_code in ReportCode::all() {
+        let explanation = report_code.explanation();
+        if report_code.to_string().eq_ignore_ascii_case(code.trim()) && !explanations.contains(&explanation) {
+            explanations.push(explanation);
+        }
+    }
+    explanations
+}
+
+impl ReportCode {
+    pub fn explanation(&self) -> &'static str {
+        use self::ReportCode::*;
+        match self {
+            UnclosedComment => "\
+A block comment was opened with /* but never closed with */, so the rest of the
+file is part of the comment.
+
+Erroneous code example:
+
+    /* Computes the square of the input
+    template Square() { signal input in; signal output out; out <== in * in; }
+
+Fixed example:
+
+    /* Computes the square of the input */
+    template Square() { signal input in; signal output out; out <== in * in; }
+",
+            FileOs => "\
+A file of the project could not be read from the disk: it does not exist, it is a
+directory or the compiler does not have permission to read it.
+
+Erroneous code example:
+
+    circom missing.circom
+
+Fixed example:
+
+    circom circuit.circom
+",
+            NoMainFoundInProject => "\
+None of the files of the project declares the main component, which is the
+template instance the circuit is generated from.
+
+Erroneous code example:
+
+    template Square() { signal input in; signal output out; out <== in * in; }
+
+Fixed example:
+
+    template Square() { signal input in; signal output out; out <== in * in; }
+    component main = Square();
+",
+            MultipleMain => "\
+More than one file of the project (the compiled file or one of its includes)
+declares a main component. A circuit has a single main component.
+
+Erroneous code example:
+
+    // lib.circom
+    template Square() { signal input in; signal output out; out <== in * in; }
+    component main = Square();
+
+    // circuit.circom
+    include \"lib.circom\";
+    component main = Square();
+
+Fixed example:
+
+    // lib.circom
+    template Square() { signal input in; signal output out; out <== in * in; }
+
+    // circuit.circom
+    include \"lib.circom\";
+    component main = Square();
+",
+            MissingSemicolon => "\
+A statement, declaration or directive is not terminated with a semicolon.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        signal output out
+        out <== in;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        out <== in;
+    }
+",
+            UnrecognizedInclude => "\
+The argument of an include directive must be a string with the path of a file.
+
+Erroneous code example:
+
+    include comparators.circom;
+
+Fixed example:
+
+    include \"comparators.circom\";
+",
+            UnrecognizedVersion => "\
+The version given in the pragma circom directive does not have the form
+major.minor.patch.
+
+Erroneous code example:
+
+    pragma circom 2.1;
+
+Fixed example:
+
+    pragma circom 2.1.0;
+",
+            UnrecognizedPragma => "\
+The pragma directive is not one of the pragmas known by the compiler, which are
+'pragma circom <version>' and 'pragma custom_templates'.
+
+Erroneous code example:
+
+    pragma circuit 2.1.0;
+
+Fixed example:
+
+    pragma circom 2.1.0;
+",
+            ExpectedIdentifier => "\
+The parser expected the name of a signal, variable, component, template, function
+or bus, but found another token, usually a reserved keyword.
+
+Erroneous code example:
+
+    template A() {
+        signal input signal;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input value;
+    }
+",
+            InvalidLintComment => "\
+A comment of the form '// lint: level(lints)' is not valid: the level is not one
+of allow, warn or deny, or some of the lints is not the name or the code of a
+warning.
+
+Erroneous code example:
+
+    // lint: ignore(UnconstrainedSignal)
+    template A() { signal input in; }
+
+Fixed example:
+
+    // lint: allow(UnconstrainedSignal)
+    template A() { signal input in; }
+",
+            InvalidBudgetComment => "\
+A comment of the form '// budget: constraints = N, signals = M' is not valid: it
+does not precede a template, it gives a limit other than constraints or signals,
+or the limit is not a number.
+
+Erroneous code example:
+
+    // budget: constraints < 1000
+    template A() { signal input in; }
+
+Fixed example:
+
+    // budget: constraints = 1000
+    template A() { signal input in; }
+",
+            IncludeNotFound => "\
+The file of an include directive was not found, neither relative to the file that
+includes it nor in any of the library paths given with -l.
+
+Erroneous code example:
+
+    include \"comparators.circom\";    // compiled without -l
+
+Fixed example:
+
+    include \"comparators.circom\";    // compiled with -l node_modules/circomlib/circuits
+",
+            IllegalExpression => "\
+The parser found a token that can not appear at that position of the program, for
+instance an operator that does not exist in circom or an unbalanced parenthesis.
+
+Erroneous code example:
+
+    function f(x) {
+        var y = 0;
+        ++y;
+        return x + y;
+    }
+
+Fixed example:
+
+    function f(x) {
+        var y = 0;
+        y++;
+        return x + y;
+    }
+",
+            MultiplePragma => "\
+A file contains the same pragma directive more than once.
+
+Erroneous code example:
+
+    pragma circom 2.1.0;
+    pragma circom 2.1.5;
+
+Fixed example:
+
+    pragma circom 2.1.5;
+",
+            NoCompilerVersionWarning => "\
+The file does not declare the version of the compiler it was written for, so the
+version of the running compiler is assumed.
+
+Erroneous code example:
+
+    template A() { signal input in; }
+
+Fixed example:
+
+    pragma circom 2.1.0;
+    template A() { signal input in; }
+",
+            CompilerVersionError => "\
+The file requires, with its pragma circom directive, a version of the compiler
+newer than the one being used.
+
+Erroneous code example:
+
+    pragma circom 9.0.0;
+
+Fixed example:
+
+    pragma circom 2.1.0;
+",
+            WrongTypesInAssignOperationOperatorSignal => "\
+A signal is assigned with the operator =, which only assigns variables and
+components. Signals are assigned with <== (assignment and constraint) or <--
+(assignment only).
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        out = in * in;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        out <== in * in;
+    }
+",
+            WrongTypesInAssignOperationOperatorNoSignal => "\
+A variable or component is assigned with <== or <--, which only assign signals.
+Variables and components are assigned with =.
+
+Erroneous code example:
+
+    template A() {
+        var x;
+        x <== 2;
+    }
+
+Fixed example:
+
+    template A() {
+        var x;
+        x = 2;
+    }
+",
+            WrongTypesInAssignOperationTemplate => "\
+A component is assigned a value that is not an instance of a template.
+
+Erroneous code example:
+
+    template B() { signal input in; }
+    template A() {
+        component c;
+        c = 3;
+    }
+
+Fixed example:
+
+    template B() { signal input in; }
+    template A() {
+        component c;
+        c = B();
+    }
+",
+            WrongTypesInAssignOperationBus => "\
+A bus is assigned a value that is not a bus.
+
+Erroneous code example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        signal input in;
+        Point() p;
+        p <== in;
+    }
+
+Fixed example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        signal input in;
+        Point() p;
+        p.x <== in;
+        p.y <== in;
+    }
+",
+            WrongTypesInAssignOperationDifferentBus => "\
+A bus is assigned a bus of a different type.
+
+Erroneous code example:
+
+    bus Point() { signal x; signal y; }
+    bus Pair() { signal a; signal b; }
+    template A() {
+        input Pair() in;
+        Point() p;
+        p <== in;
+    }
+
+Fixed example:
+
+    bus Point() { signal x; signal y; }
+    bus Pair() { signal a; signal b; }
+    template A() {
+        input Pair() in;
+        Point() p;
+        p.x <== in.a;
+        p.y <== in.b;
+    }
+",
+            WrongTypesInAssignOperationExpression => "\
+A signal or variable is assigned a component, that is, an instance of a template.
+
+Erroneous code example:
+
+    template B() { signal output out; out <== 1; }
+    template A() {
+        signal x;
+        x <== B;
+    }
+
+Fixed example:
+
+    template B() { signal output out; out <== 1; }
+    template A() {
+        signal x;
+        x <== B()();
+    }
+",
+            WrongTypesInAssignOperationArrayTemplates => "\
+The positions of an array of components must be instances of the same template.
+
+Erroneous code example:
+
+    template B() { signal input in; }
+    template C() { signal input in; }
+    template A() {
+        component c[2];
+        c[0] = B();
+        c[1] = C();
+    }
+
+Fixed example:
+
+    template B() { signal input in; }
+    template A() {
+        component c[2];
+        c[0] = B();
+        c[1] = B();
+    }
+",
+            WrongTypesInAssignOperationArrayBuses => "\
+The positions of an array of buses must be buses of the same type.
+
+Erroneous code example:
+
+    bus Point() { signal x; signal y; }
+    bus Pair() { signal a; signal b; }
+    template A() {
+        input Pair() in;
+        Point() p[2];
+        p[0] <== in;
+    }
+
+Fixed example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() in;
+        Point() p[2];
+        p[0] <== in;
+        p[1] <== in;
+    }
+",
+            WrongTypesInAssignOperationDims(..) => "\
+The dimensions of the assigned expression do not match the dimensions of the
+element it is assigned to.
+
+Erroneous code example:
+
+    template A() {
+        signal input in[2];
+        signal output out;
+        out <== in;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in[2];
+        signal output out;
+        out <== in[0] + in[1];
+    }
+",
+            WrongNumberOfArguments(..) => "\
+A template, function or bus is instantiated with a number of arguments different
+from the number of its parameters.
+
+Erroneous code example:
+
+    template Pow(n) { signal input in; signal output out; out <== in; }
+    component main = Pow(2, 3);
+
+Fixed example:
+
+    template Pow(n) { signal input in; signal output out; out <== in; }
+    component main = Pow(2);
+",
+            UndefinedFunction => "\
+A function uses an element that only exists in templates: it declares signals or
+buses, instantiates templates or accesses the signals of a component. Functions
+only compute values from their arguments.
+
+Erroneous code example:
+
+    function f(x) {
+        signal y;
+        y <== x;
+        return y;
+    }
+
+Fixed example:
+
+    function f(x) {
+        var y = x;
+        return y;
+    }
+",
+            UndefinedBus => "\
+A bus contains a statement that is not a declaration. The body of a bus can only
+declare its fields, which are signals and other buses.
+
+Erroneous code example:
+
+    bus Point() {
+        signal x;
+        signal y;
+        x === y;
+    }
+
+Fixed example:
+
+    bus Point() {
+        signal x;
+        signal y;
+    }
+",
+            UninitializedSymbolInExpression => "\
+A component or bus whose type is not known yet, because it has not been assigned,
+is used in an expression.
+
+Erroneous code example:
+
+    template B() { signal output out; out <== 1; }
+    template A() {
+        signal output out;
+        component c;
+        out <== c.out;
+        c = B();
+    }
+
+Fixed example:
+
+    template B() { signal output out; out <== 1; }
+    template A() {
+        signal output out;
+        component c;
+        c = B();
+        out <== c.out;
+    }
+",
+            UnableToTypeFunction => "\
+The compiler could not infer the dimensions of the value returned by a function,
+usually because the function is recursive and no return statement gives a value
+whose dimensions do not depend on the recursion.
+
+Erroneous code example:
+
+    function f(n) {
+        return f(n - 1);
+    }
+
+Fixed example:
+
+    function f(n) {
+        if (n == 0) {
+            return 1;
+        }
+        return f(n - 1);
+    }
+",
+            UnreachableConstraints => "\
+A constraint, or an assignment with <==, is inside an if statement whose condition
+depends on the value of a signal. The constraints of a circuit are generated at
+compile time, when the values of the signals are unknown.
+
+Erroneous code example:
+
+    template A() {
+        signal input sel;
+        signal input in;
+        signal output out;
+        if (sel == 1) {
+            out <== in;
+        } else {
+            out <== 0;
+        }
+    }
+
+Fixed example:
+
+    template A() {
+        signal input sel;
+        signal input in;
+        signal output out;
+        sel * (sel - 1) === 0;
+        out <== sel * in;
+    }
+",
+            UnreachableTags => "\
+A tag is assigned inside an if statement whose condition depends on the value of
+a signal. Tags are known at compile time, when the values of the signals are not.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        signal output {maxbit} out;
+        if (in == 0) {
+            out.maxbit = 1;
+        }
+        out <== in;
+    }
+
+Fixed example:
+
+    template A(n) {
+        signal input in;
+        signal output {maxbit} out;
+        if (n == 0) {
+            out.maxbit = 1;
+        }
+        out <== in;
+    }
+",
+            UnreachableSignals => "\
+A signal, bus or component is declared inside an if statement whose condition
+depends on the value of a signal. The structure of the circuit is fixed at compile
+time, when the values of the signals are unknown.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        if (in == 0) {
+            signal aux;
+        }
+    }
+
+Fixed example:
+
+    template A(n) {
+        signal input in;
+        if (n == 0) {
+            signal aux;
+        }
+    }
+",
+            UnknownIndex | SameFunctionDeclaredTwice | SameTemplateDeclaredTwice
+            | StaticInfoWasOverwritten | SignalInLineInitialization | FunctionInconsistentTyping
+            | FunctionPathWithoutReturn | ForbiddenDeclarationInFunction | NonBooleanCondition
+            | NonEqualTypesInExpression | TemplateCallAsArgument | TemplateWrongNumberOfArguments
+            | TypeCantBeUseAsCondition | InconsistentReturnTypesInBlock | InconsistentStaticInformation
+            | ForStatementIllConstructed | BadArrayAccess | AssigningAComponentTwice
+            | AssigningASignalTwice | NotAllowedOperation | ConstraintGeneratorInFunction
+            | WrongSignalTags | NonComputableExpression | UndefinedTemplate | UnusedInput
+            | UnusedOutput | UnderscoreWithNoSignalWarning => NOT_EMITTED,
+            UnknownDimension => "\
+The length of an array of signals, buses or components depends on the value of a
+signal. The size of every array of the circuit must be known at compile time.
+
+Erroneous code example:
+
+    template A() {
+        signal input n;
+        signal bits[n];
+    }
+
+Fixed example:
+
+    template A(n) {
+        signal input in;
+        signal bits[n];
+    }
+",
+            UnknownTemplateAssignment => "\
+A signal of a component is assigned through an array of components accessed with
+an index that depends on the value of a signal. The compiler can not know which
+component is assigned.
+
+Erroneous code example:
+
+    template B() { signal input in; }
+    template A() {
+        signal input i;
+        component c[2];
+        c[0] = B();
+        c[1] = B();
+        c[i].in <== 1;
+    }
+
+Fixed example:
+
+    template B() { signal input in; }
+    template A() {
+        signal input i;
+        component c[2];
+        c[0] = B();
+        c[1] = B();
+        c[0].in <== 1 - i;
+        c[1].in <== i;
+    }
+",
+            SameSymbolDeclaredTwice => "\
+The same name is declared twice in the same scope, or a template, function, bus or
+global constant is defined twice in the program. Names must be unique in their
+scope, and definitions unique in the program.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        var in = 2;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in;
+        var n = 2;
+    }
+",
+            SignalOutsideOriginalScope => "\
+A signal, bus or component is declared inside a loop. Declarations of signals,
+buses and components can only appear in the initial scope of a template or inside
+if statements whose condition is known at compile time; arrays are used instead
+of declarations inside loops.
+
+Erroneous code example:
+
+    template A(n) {
+        for (var i = 0; i < n; i++) {
+            signal aux;
+        }
+    }
+
+Fixed example:
+
+    template A(n) {
+        signal aux[n];
+        for (var i = 0; i < n; i++) {
+            aux[i] <== i;
+        }
+    }
+",
+            FunctionWrongNumberOfArguments => "\
+A function or template is called with a number of arguments different from the
+number of its parameters.
+
+Erroneous code example:
+
+    function add(a, b) { return a + b; }
+    template A() { var x = add(1); }
+
+Fixed example:
+
+    function add(a, b) { return a + b; }
+    template A() { var x = add(1, 2); }
+",
+            FunctionReturnError => "\
+Some execution path of a function reaches the end of its body without a return
+statement, so the function would not produce a value.
+
+Erroneous code example:
+
+    function sign(x) {
+        if (x > 0) {
+            return 1;
+        }
+    }
+
+Fixed example:
+
+    function sign(x) {
+        if (x > 0) {
+            return 1;
+        }
+        return 0;
+    }
+",
+            NonHomogeneousArray(..) => "\
+The elements of an inline array have different dimensions. All the elements of an
+array must have the same type.
+
+Erroneous code example:
+
+    template A() {
+        var a[2] = [1, [2, 3]];
+    }
+
+Fixed example:
+
+    template A() {
+        var a[2][2] = [[1, 1], [2, 3]];
+    }
+",
+            NonCompatibleBranchTypes => "\
+The two branches of an inline conditional (cond ? a : b) have different types or
+dimensions.
+
+Erroneous code example:
+
+    template A() {
+        var a[2] = [1, 2];
+        var b = 1 == 1 ? a : 0;
+    }
+
+Fixed example:
+
+    template A() {
+        var a[2] = [1, 2];
+        var b = 1 == 1 ? a[0] : 0;
+    }
+",
+            NonExistentSymbol => "\
+A name that has not been declared is used: a variable, signal or component that
+is not in scope, or a call to a template, function or bus that is not defined in
+the program or in the included files.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        out <== inn;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        out <== in;
+    }
+",
+            MainComponentWithTags => "\
+The inputs of the main component can not have tags, since the values given to the
+circuit can not be checked to satisfy them.
+
+Erroneous code example:
+
+    template A() {
+        signal input {binary} in;
+    }
+    component main = A();
+
+Fixed example:
+
+    template A() {
+        signal input in;
+        in * (in - 1) === 0;
+    }
+    component main = A();
+",
+            IllegalMainExpression => "\
+The main component must be an instance of a template, not a function call or any
+other expression.
+
+Erroneous code example:
+
+    function f() { return 1; }
+    component main = f();
+
+Fixed example:
+
+    template A() { signal input in; }
+    component main = A();
+",
+            TemplateWithReturnStatement => "\
+A template contains a return statement. Templates produce their results through
+output signals; only functions return values.
+
+Erroneous code example:
+
+    template Square() {
+        signal input in;
+        return in * in;
+    }
+
+Fixed example:
+
+    template Square() {
+        signal input in;
+        signal output out;
+        out <== in * in;
+    }
+",
+            EmptyArrayInlineDeclaration => "\
+An inline array without elements is used. Arrays must have at least one element.
+
+Erroneous code example:
+
+    template A() {
+        var a[0] = [];
+    }
+
+Fixed example:
+
+    template A() {
+        var a[1] = [0];
+    }
+",
+            PrefixOperatorWithWrongTypes | InfixOperatorWithWrongTypes => "\
+An operator is applied to operands of a type it does not admit, for instance an
+arithmetic operator applied to a component or to a whole array.
+
+Erroneous code example:
+
+    template A() {
+        signal input in[2];
+        signal output out;
+        out <== in + 1;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in[2];
+        signal output out;
+        out <== in[0] + in[1] + 1;
+    }
+",
+            ParallelOperatorWithWrongTypes => "\
+The parallel operator is applied to an expression that is not an instance of a
+template.
+
+Erroneous code example:
+
+    template A() {
+        var x = parallel 3;
+    }
+
+Fixed example:
+
+    template B() { signal input in; }
+    template A() {
+        component c = parallel B();
+    }
+",
+            InvalidArgumentInCall | InvalidArgumentInBusInstantiationT => "\
+A component is given as the argument of a template, function or bus. Arguments
+must be arithmetic values or arrays of them.
+
+Erroneous code example:
+
+    template B() { signal output out; out <== 1; }
+    template C(x) { signal input in; }
+    template A() {
+        component b = B();
+        component c = C(b);
+    }
+
+Fixed example:
+
+    template B() { signal output out; out <== 1; }
+    template C(x) { signal input in; }
+    template A() {
+        component b = B();
+        component c = C(1);
+        c.in <== b.out;
+    }
+",
+            InvalidArgumentInCallBus | InvalidArgumentInBusInstantiationB => "\
+A bus is given as the argument of a template, function or bus. Arguments must be
+arithmetic values or arrays of them.
+
+Erroneous code example:
+
+    bus Point() { signal x; signal y; }
+    function norm(p) { return p; }
+    template A() {
+        input Point() p;
+        var n = norm(p);
+    }
+
+Fixed example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        signal output n;
+        n <== p.x * p.x + p.y * p.y;
+    }
+",
+            InvalidArrayAccess(..) => "\
+An array is accessed with more indexes than dimensions it has, or an element that
+is not an array is accessed with an index.
+
+Erroneous code example:
+
+    template A() {
+        signal input in[2];
+        signal output out;
+        out <== in[0][1];
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in[2];
+        signal output out;
+        out <== in[1];
+    }
+",
+            InvalidSignalAccess => "\
+A component is accessed with the name of a signal or bus that is not an input or
+an output of its template. Only the inputs and outputs of a subcomponent can be
+accessed from outside.
+
+Erroneous code example:
+
+    template B() { signal input in; signal output out; out <== in; }
+    template A() {
+        signal output out;
+        component b = B();
+        b.in <== 1;
+        out <== b.outt;
+    }
+
+Fixed example:
+
+    template B() { signal input in; signal output out; out <== in; }
+    template A() {
+        signal output out;
+        component b = B();
+        b.in <== 1;
+        out <== b.out;
+    }
+",
+            MustBeSameDimension(..) => "\
+Two arrays that have to match element by element, as the two sides of a
+constraint, have different dimensions.
+
+Erroneous code example:
+
+    template A() {
+        signal input a[2];
+        signal input b[3];
+        a === b;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input a[2];
+        signal input b[2];
+        a === b;
+    }
+",
+            InvalidSignalTagAccess => "\
+A tag is accessed on a signal or bus that does not declare it.
+
+Erroneous code example:
+
+    template A() {
+        signal input {binary} in;
+        signal output out;
+        out <== in.maxbit;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input {binary} in;
+        signal output out;
+        out <== in;
+    }
+",
+            MustBeArithmetic => "\
+A component is used where an arithmetic value or an array of arithmetic values is
+expected, for instance in the arguments of log.
+
+Erroneous code example:
+
+    template B() { signal output out; out <== 1; }
+    template A() {
+        component b = B();
+        log(b);
+    }
+
+Fixed example:
+
+    template B() { signal output out; out <== 1; }
+    template A() {
+        component b = B();
+        log(b.out);
+    }
+",
+            InvalidTagAccess => "\
+A tag that does not appear in the declaration of the signal is accessed or
+assigned. Only the tags declared between braces can be used.
+
+Erroneous code example:
+
+    template A() {
+        signal output {binary} out;
+        out.maxbit = 1;
+        out <== 1;
+    }
+
+Fixed example:
+
+    template A() {
+        signal output {binary, maxbit} out;
+        out.maxbit = 1;
+        out <== 1;
+    }
+",
+            OutputTagCannotBeModifiedOutside => "\
+The tag of an output of a subcomponent is assigned from the template that
+instantiates it. The tags of an output can only be set inside its own template.
+
+Erroneous code example:
+
+    template B() { signal output {maxbit} out; out.maxbit = 1; out <== 1; }
+    template A() {
+        component b = B();
+        b.out.maxbit = 2;
+    }
+
+Fixed example:
+
+    template B(n) { signal output {maxbit} out; out.maxbit = n; out <== 1; }
+    template A() {
+        component b = B(2);
+    }
+",
+            InputTagCannotBeModifiedOutside | InputTagCannotBeAccessedOutside => "\
+The tag of an input of a subcomponent is accessed or assigned from the template
+that instantiates it. The tags of an input are given by the signal connected to it.
+
+Erroneous code example:
+
+    template B() { signal input {maxbit} in; }
+    template A() {
+        signal input x;
+        component b = B();
+        b.in.maxbit = 1;
+        b.in <== x;
+    }
+
+Fixed example:
+
+    template B() { signal input {maxbit} in; }
+    template A() {
+        signal input {maxbit} x;
+        component b = B();
+        b.in <== x;
+    }
+",
+            InvalidTagAccessAfterArray => "\
+A tag is accessed through a position of an array. Tags belong to whole arrays, not
+to their positions, so the tag is accessed on the array itself.
+
+Erroneous code example:
+
+    template A() {
+        signal input {maxbit} in[2];
+        var m = in[0].maxbit;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input {maxbit} in[2];
+        var m = in.maxbit;
+    }
+",
+            InvalidArraySize(..) | InvalidArraySizeT => "\
+The length of an array, or an index used to access it, is not a single arithmetic
+value: it is an array or a component.
+
+Erroneous code example:
+
+    template A() {
+        var n[2] = [1, 2];
+        signal s[n];
+    }
+
+Fixed example:
+
+    template A() {
+        var n[2] = [1, 2];
+        signal s[n[1]];
+    }
+",
+            InvalidArraySizeB => "\
+The length of an array, or an index used to access it, is a bus instead of a
+single arithmetic value.
+
+Erroneous code example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        signal s[p];
+    }
+
+Fixed example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        signal s[2];
+    }
+",
+            InvalidArrayType => "\
+A component is used as an element of an inline array. Inline arrays can only
+contain arithmetic values, signals or buses.
+
+Erroneous code example:
+
+    template B() { signal output out; out <== 1; }
+    template A() {
+        component b = B();
+        var a[1] = [b];
+    }
+
+Fixed example:
+
+    template B() { signal output out; out <== 1; }
+    template A() {
+        component b = B();
+        var a[1] = [b.out];
+    }
+",
+            InvalidArrayTypeB => "\
+A bus is used as an element of an inline array where buses are not allowed.
+
+Erroneous code example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        var a[1] = [p];
+    }
+
+Fixed example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        signal a[1] <== [p.x];
+    }
+",
+            InvalidArrayTypeBandSignal => "\
+An inline array mixes signals and buses. All the elements of an array must have
+the same type.
+
+Erroneous code example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        signal input s;
+        Point() q[2] <== [p, s];
+    }
+
+Fixed example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        input Point() r;
+        Point() q[2] <== [p, r];
+    }
+",
+            InvalidArrayTypeDifferentBuses => "\
+An inline array contains buses of different types. All the buses of an array must
+have the same type.
+
+Erroneous code example:
+
+    bus Point() { signal x; signal y; }
+    bus Pair() { signal a; signal b; }
+    template A() {
+        input Point() p;
+        input Pair() q;
+        Point() r[2] <== [p, q];
+    }
+
+Fixed example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        input Point() q;
+        Point() r[2] <== [p, q];
+    }
+",
+            InvalidPartialArray => "\
+An array of signals, buses or components is accessed with fewer indexes than its
+number of dimensions where a complete element is required. Only arrays of
+variables can be used partially in that position.
+
+Erroneous code example:
+
+    template B() { signal input in; }
+    template A() {
+        component c[2];
+        c[0] = B();
+        c[1] = B();
+        c.in <== 1;
+    }
+
+Fixed example:
+
+    template B() { signal input in; }
+    template A() {
+        component c[2];
+        c[0] = B();
+        c[1] = B();
+        c[0].in <== 1;
+        c[1].in <== 1;
+    }
+",
+            MustBeSingleArithmetic(..) | MustBeSingleArithmeticT | MustBeSingleArithmeticB => "\
+A single arithmetic value is required, for instance in a condition, in an index or
+in the argument of an assert, but an array, a component or a bus was given.
+
+Erroneous code example:
+
+    template A() {
+        var a[2] = [1, 2];
+        if (a) {
+            log(1);
+        }
+    }
+
+Fixed example:
+
+    template A() {
+        var a[2] = [1, 2];
+        if (a[0] == 1) {
+            log(1);
+        }
+    }
+",
+            ExpectedDimDiffGotDim(..) => "\
+The return statements of a function return values of different dimensions. All the
+return statements of a function must return values of the same dimensions.
+
+Erroneous code example:
+
+    function f(x) {
+        var a[2] = [x, x];
+        if (x == 0) {
+            return a;
+        }
+        return x;
+    }
+
+Fixed example:
+
+    function f(x) {
+        var a[2] = [x, x];
+        if (x == 0) {
+            return a;
+        }
+        return [x, 0];
+    }
+",
+            RuntimeError => "\
+An error found while executing the program to generate the constraints, for
+instance an access out of the bounds of an array, a failed assert, a division by
+zero or a constraint that is not quadratic once the values are known.
+
+Erroneous code example:
+
+    template A(n) {
+        signal input in[n];
+        signal output out;
+        out <== in[n];
+    }
+    component main = A(2);
+
+Fixed example:
+
+    template A(n) {
+        signal input in[n];
+        signal output out;
+        out <== in[n - 1];
+    }
+    component main = A(2);
+",
+            RuntimeWarning => "\
+A problem found while generating the constraints that does not prevent the circuit
+from being built, for instance an assignment with <-- that could be replaced by <==
+because the assigned expression is quadratic. It is reported with --inspect.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        out <-- in / 4;
+        out * 4 === in;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        out <== in / 4;
+    }
+",
+            UnknownTemplate => "\
+The template of a component, or one of its arguments, depends on the value of a
+signal. The components of a circuit must be known at compile time.
+
+Erroneous code example:
+
+    template B(n) { signal input in; }
+    template A() {
+        signal input n;
+        component b = B(n);
+    }
+
+Fixed example:
+
+    template B(n) { signal input in; }
+    template A(n) {
+        signal input x;
+        component b = B(n);
+        b.in <== x;
+    }
+",
+            UnknownBus => "\
+The arguments of a bus depend on the value of a signal. The buses of a circuit must
+be known at compile time.
+
+Erroneous code example:
+
+    bus Array(n) { signal v[n]; }
+    template A() {
+        signal input n;
+        Array(n) a;
+    }
+
+Fixed example:
+
+    bus Array(n) { signal v[n]; }
+    template A(n) {
+        Array(n) a;
+    }
+",
+            NonQuadratic => "\
+A constraint, or an assignment with <==, is not quadratic: it multiplies more than
+two signals, divides by a signal or accesses an array with an index that depends
+on a signal. Every constraint must have the form A * B + C = 0, where A, B and C
+are linear combinations of signals.
+
+Erroneous code example:
+
+    template A() {
+        signal input a;
+        signal input b;
+        signal input c;
+        signal output out;
+        out <== a * b * c;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input a;
+        signal input b;
+        signal input c;
+        signal output out;
+        signal ab <== a * b;
+        out <== ab * c;
+    }
+",
+            NonValidTagAssignment => "\
+A tag is assigned a value that depends on a signal. Tags are known at compile time,
+when the values of the signals are not.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        signal output {maxbit} out;
+        out.maxbit = in;
+        out <== in;
+    }
+
+Fixed example:
+
+    template A(n) {
+        signal input in;
+        signal output {maxbit} out;
+        out.maxbit = n;
+        out <== in;
+    }
+",
+            NonConstantArrayLength => "\
+The length of an array declared in a function is not known when the function is
+analyzed.
+
+Erroneous code example:
+
+    function f(x) {
+        var a[x];
+        return 0;
+    }
+
+Fixed example:
+
+    function f(x) {
+        var a[10];
+        return 0;
+    }
+",
+            GlobalConstantAssigned => "\
+A global constant, declared with const at the top level of a file, is assigned in
+the body of a template or function. Global constants can not be modified.
+
+Erroneous code example:
+
+    const N = 4;
+    template A() {
+        N = 5;
+    }
+
+Fixed example:
+
+    const N = 4;
+    template A() {
+        var n = N + 1;
+    }
+",
+            InvalidGlobalConstant => "\
+The value of a global constant is not a single arithmetic value: it is an array or
+a component.
+
+Erroneous code example:
+
+    const N = [1, 2];
+
+Fixed example:
+
+    const N = 2;
+",
+            StaticAssertFailed => "\
+The condition of a static_assert is false, or can not be evaluated, when the
+constraints are generated.
+
+Erroneous code example:
+
+    template A(n) {
+        static_assert(n > 0, \"n must be positive\");
+    }
+    component main = A(0);
+
+Fixed example:
+
+    template A(n) {
+        static_assert(n > 0, \"n must be positive\");
+    }
+    component main = A(1);
+",
+            NonConstantStaticAssert => "\
+The condition of a static_assert depends on the value of a signal. Static
+assertions are checked at compile time, when the values of the signals are unknown.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        static_assert(in > 0, \"in must be positive\");
+    }
+
+Fixed example:
+
+    template A(n) {
+        signal input in;
+        static_assert(n > 0, \"n must be positive\");
+    }
+",
+            InvalidTemplateContract => "\
+A condition of the requires clause of a template is not a single arithmetic value.
+
+Erroneous code example:
+
+    template A(n) requires [n, 1] {
+        signal input in;
+    }
+
+Fixed example:
+
+    template A(n) requires n > 1 {
+        signal input in;
+    }
+",
+            TemplateContractViolated => "\
+A template is instantiated with arguments that do not satisfy a condition of its
+requires clause.
+
+Erroneous code example:
+
+    template A(n) requires n > 1 {
+        signal input in;
+    }
+    component main = A(1);
+
+Fixed example:
+
+    template A(n) requires n > 1 {
+        signal input in;
+    }
+    component main = A(2);
+",
+            BudgetExceeded => "\
+The circuit, or an instance of a template, is larger than its budget. The budgets
+of the whole circuit are given with --max_constraints and --max_wires, and the ones
+of a template with a comment '// budget: constraints = N, signals = M' before it or
+with --template_budget. An instance of a template counts the constraints and
+signals of its subcomponents. The error lists the components that contribute the
+most to the size.
+
+Erroneous code example:
+
+    // budget: constraints = 100
+    template A() {
+        signal input in;
+        signal output out[254];
+        component n2b = Num2Bits(254);
+        n2b.in <== in;
+        out <== n2b.out;
+    }
+
+Fixed example:
+
+    // budget: constraints = 300
+    template A() {
+        signal input in;
+        signal output out[254];
+        component n2b = Num2Bits(254);
+        n2b.in <== in;
+        out <== n2b.out;
+    }
+",
+            UnsatisfiableConstraint => "\
+A constraint does not depend on any signal and never holds, as 0 === 1, so no
+witness can satisfy the circuit and no proof of it can be generated. It usually
+comes from a constant branch or from the values of the parameters. The error is
+also reported when the simplification reduces some constraints to a false one, as
+x === 1 and x === 2 do.
+
+Erroneous code example:
+
+    template A(n) {
+        signal input in;
+        signal output out;
+        out <== in * n;
+        n === 1;
+    }
+
+Fixed example:
+
+    template A(n) {
+        signal input in;
+        signal output out;
+        out <== in * n;
+        assert(n == 1);
+    }
+",
+            TriviallyTrueConstraint => "\
+A constraint does not depend on any signal and always holds, as 0 === 0, so it
+does not constrain the circuit. It usually means that a signal was meant to be
+constrained and the expression cancels out, as in x - x, or that a condition
+over the parameters was written as a constraint instead of an assert.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        out <-- in * 2;
+        out - out === 0;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        out <== in * 2;
+    }
+",
+            UnmatchedSignalPattern => "\
+A name given to --keep_signals does not match any signal of the circuit, so it does
+not keep anything. The names are the qualified names of the sym file, as
+main.hasher.out, where the name of an array or of a component refers to all its
+signals and * matches any text.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        signal square;
+        square <== in * in;
+        out <== square * in;
+    }
+    component main = A();
+
+    circom a.circom --r1cs --sym --O2 --keep_signals main.sqare
+
+Fixed example:
+
+    circom a.circom --r1cs --sym --O2 --keep_signals main.square
+",
+            ExecutionLimitExceeded => "\
+The execution of the program during the constraint generation exceeded one of its
+limits: a loop ran more iterations than --max_loop_iterations (1000000 by
+default), function calls were nested deeper than --max_recursion_depth (1000 by
+default) or more statements than --max_steps were executed. It usually means that
+a loop condition or a recursion depends on a parameter with a wrong value. A
+limit equal to 0 is not checked.
+
+Erroneous code example:
+
+    function log2(n) {
+        var r = 0;
+        while (n != 1) {
+            n = n \\ 2;
+            r++;
+        }
+        return r;
+    }
+    template A() {
+        signal output out;
+        out <== log2(0);
+    }
+
+Fixed example:
+
+    function log2(n) {
+        var r = 0;
+        while (n > 1) {
+            n = n \\ 2;
+            r++;
+        }
+        return r;
+    }
+    template A() {
+        signal output out;
+        out <== log2(0);
+    }
+",
+            InvalidLogFormat => "\
+The first string of a log statement has placeholders {} but their number is different
+from the number of values that follow it, so the string is not used as a format string
+and it is printed literally followed by the values. This is a warning.
+
+Erroneous code example:
+
+    template A() {
+        signal input a;
+        signal input b;
+        log(\"a = {}, b = {}\", a);
+    }
+
+Fixed example:
+
+    template A() {
+        signal input a;
+        signal input b;
+        log(\"a = {}, b = {}\", a, b);
+    }
+",
+            InvalidTemplateArgument => "\
+An argument given to a template parameter (declared with 'template' in the list of
+parameters) is not the name of a template, a template is given to a parameter that
+expects a value, the interface of a template parameter is not an ordinary template,
+or the template given does not match the interface: it must have the same inputs,
+at least the same outputs and as many parameters left as the interface.
+
+Erroneous code example:
+
+    template Hash2() { signal input in[2]; signal output out; }
+    template Apply(template T: Hash2) { component c = T(); }
+    component main = Apply(3);
+
+Fixed example:
+
+    template Hash2() { signal input in[2]; signal output out; }
+    template Sum2() { signal input in[2]; signal output out; out <== in[0] + in[1]; }
+    template Apply(template T: Hash2) { component c = T(); }
+    component main = Apply(Sum2);
+",
+            ReservedName => "\
+The name of a built-in constant (FIELD_BITS, FIELD_MAX or FIELD_ID) is used for
+a definition, a parameter, a variable, a signal or a component. These names are
+reserved, so the programs written for previous versions that declare them must
+rename those symbols.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        var FIELD_BITS = 3;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in;
+        var field_bits = 3;
+    }
+",
+            UnconstrainedSignal => "\
+A signal does not appear in any constraint of the circuit, so its value is not
+checked by the verifier. Reported with --inspect.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        signal aux;
+        signal output out;
+        aux <-- in * 2;
+        out <== in;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in;
+        signal aux;
+        signal output out;
+        aux <== in * 2;
+        out <== aux;
+    }
+",
+            UnconstrainedIOSignal => "\
+An input or output signal of a subcomponent does not appear in any constraint.
+Reported with --inspect.
+
+Erroneous code example:
+
+    template B() { signal input in; signal output out; out <== 1; }
+    template A() {
+        signal input x;
+        component b = B();
+        b.in <== x;
+    }
+
+Fixed example:
+
+    template B() { signal input in; signal output out; out <== in; }
+    template A() {
+        signal input x;
+        component b = B();
+        b.in <== x;
+        _ <== b.out;
+    }
+",
+            SignalAssignmentNotConstrained => "\
+A signal assigned with <-- does not appear in any constraint of its template, so a
+malicious prover can give it any value.
+
+Erroneous code example:
+
+    template Inverse() {
+        signal input in;
+        signal output out;
+        out <-- 1 / in;
+    }
+
+Fixed example:
+
+    template Inverse() {
+        signal input in;
+        signal output out;
+        out <-- 1 / in;
+        out * in === 1;
+    }
+",
+            InsufficientRangeCheck => "\
+A signal is connected to an input with a {maxbit} tag, but the constraints of the
+circuit do not prove that its value fits in that number of bits. The tag was given
+to the signal without checking it. Reported with --inspect.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        signal {maxbit} x;
+        x.maxbit = 8;
+        x <== in;
+        component lt = LessThan(8);
+        lt.in[0] <== x;
+        lt.in[1] <== x;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in;
+        _ <== Num2Bits(8)(in);
+        signal {maxbit} x;
+        x.maxbit = 8;
+        x <== in;
+        component lt = LessThan(8);
+        lt.in[0] <== x;
+        lt.in[1] <== x;
+    }
+",
+            UnderconstrainedOutput => "\
+The constraints of a template do not prove that an output signal has a single
+value for every value of the inputs, so a malicious prover may be able to choose
+among several values. Reported with --inspect.
+
+Erroneous code example:
+
+    template Bits2() {
+        signal input in;
+        signal output bits[2];
+        bits[0] <-- in & 1;
+        bits[1] <-- (in >> 1) & 1;
+        bits[0] + 2 * bits[1] === in;
+    }
+
+Fixed example:
+
+    template Bits2() {
+        signal input in;
+        signal output bits[2];
+        bits[0] <-- in & 1;
+        bits[1] <-- (in >> 1) & 1;
+        bits[0] * (bits[0] - 1) === 0;
+        bits[1] * (bits[1] - 1) === 0;
+        bits[0] + 2 * bits[1] === in;
+    }
+",
+            UnusedDefinition => "\
+A template, function, bus or global constant defined in the main file is never
+used by the circuit.
+
+Erroneous code example:
+
+    template Unused() { signal input in; }
+    template A() { signal input in; }
+    component main = A();
+
+Fixed example:
+
+    template A() { signal input in; }
+    component main = A();
+",
+            UnusedVariable => "\
+A variable is declared but its value is never read.
+
+Erroneous code example:
+
+    function f(x) {
+        var y = x * 2;
+        return x;
+    }
+
+Fixed example:
+
+    function f(x) {
+        return x;
+    }
+",
+            UnassignedComponent => "\
+A component is declared but never instantiated.
+
+Erroneous code example:
+
+    template B() { signal input in; }
+    template A() {
+        component b;
+    }
+
+Fixed example:
+
+    template B() { signal input in; }
+    template A() {
+        component b = B();
+        b.in <== 1;
+    }
+",
+            UnreadSignal => "\
+An intermediate signal or bus is assigned but never read.
+
+Erroneous code example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        signal sq;
+        sq <== in * in;
+        out <== in;
+    }
+
+Fixed example:
+
+    template A() {
+        signal input in;
+        signal output out;
+        signal sq;
+        sq <== in * in;
+        out <== sq;
+    }
+",
+            UnreachableCode => "\
+Some statements of a function come after a return statement that is always
+executed, so they never run.
+
+Erroneous code example:
+
+    function f(x) {
+        return x;
+        x = x + 1;
+    }
+
+Fixed example:
+
+    function f(x) {
+        x = x + 1;
+        return x;
+    }
+",
+            ErrorWat2Wasm => "\
+The WebAssembly text generated for the witness calculator could not be translated
+into a binary WebAssembly module, or the result could not be written to disk. This
+is usually caused by an output directory that is not writable.
+
+Erroneous code example:
+
+    circom circuit.circom --wasm -o /read-only-directory
+
+Fixed example:
+
+    circom circuit.circom --wasm -o build
+",
+            CustomGateIntermediateSignalWarning => "\
+A custom template declares intermediate signals or buses. Custom templates only
+describe the interface of a gate of the proving system, so intermediate signals
+are not used.
+
+Erroneous code example:
+
+    pragma custom_templates;
+    template custom Gate() {
+        signal input in;
+        signal aux;
+        signal output out;
+        aux <-- in;
+        out <-- aux;
+    }
+
+Fixed example:
+
+    pragma custom_templates;
+    template custom Gate() {
+        signal input in;
+        signal output out;
+        out <-- in;
+    }
+",
+            CustomGateConstraintError => "\
+A custom template contains constraints. The constraints of a custom gate are
+defined by the proving system, so custom templates can only assign their outputs
+with <--.
+
+Erroneous code example:
+
+    pragma custom_templates;
+    template custom Gate() {
+        signal input in;
+        signal output out;
+        out <== in * in;
+    }
+
+Fixed example:
+
+    pragma custom_templates;
+    template custom Gate() {
+        signal input in;
+        signal output out;
+        out <-- in * in;
+    }
+",
+            CustomGateSubComponentError => "\
+A custom template instantiates other components. Custom templates can not contain
+subcomponents.
+
+Erroneous code example:
+
+    pragma custom_templates;
+    template Square() { signal input in; signal output out; out <== in * in; }
+    template custom Gate() {
+        signal input in;
+        signal output out;
+        component s = Square();
+    }
+
+Fixed example:
+
+    pragma custom_templates;
+    template custom Gate() {
+        signal input in;
+        signal output out;
+        out <-- in * in;
+    }
+",
+            CustomGatesPragmaError => "\
+A file uses custom templates, directly or through its includes, without the
+directive pragma custom_templates.
+
+Erroneous code example:
+
+    pragma circom 2.1.0;
+    template custom Gate() { signal input in; signal output out; out <-- in; }
+
+Fixed example:
+
+    pragma circom 2.1.0;
+    pragma custom_templates;
+    template custom Gate() { signal input in; signal output out; out <-- in; }
+",
+            CustomGatesVersionError => "\
+Custom templates require version 2.0.6 of the language or newer, but the file
+declares an older version.
+
+Erroneous code example:
+
+    pragma circom 2.0.0;
+    pragma custom_templates;
+
+Fixed example:
+
+    pragma circom 2.0.6;
+    pragma custom_templates;
+",
+            AnonymousCompError => "\
+An anonymous component is used where it is not allowed: in the middle of an
+operation, inside a condition, or with an operator other than <==, ==> or =.
+
+Erroneous code example:
+
+    template Square() { signal input in; signal output out; out <== in * in; }
+    template A() {
+        signal input x;
+        signal output out;
+        out <== Square()(x) + 1;
+    }
+
+Fixed example:
+
+    template Square() { signal input in; signal output out; out <== in * in; }
+    template A() {
+        signal input x;
+        signal output out;
+        signal sq <== Square()(x);
+        out <== sq + 1;
+    }
+",
+            TupleError => "\
+A tuple is used incorrectly: the two sides of a tuple assignment have a different
+number of elements, or a tuple appears where a single value is expected.
+
+Erroneous code example:
+
+    template A() {
+        var a;
+        var b;
+        (a, b) = (1, 2, 3);
+    }
+
+Fixed example:
+
+    template A() {
+        var a;
+        var b;
+        (a, b) = (1, 2);
+    }
+",
+            UninitializedComponent => "\
+A signal of a component is accessed before the component is instantiated.
+
+Erroneous code example:
+
+    template B() { signal input in; }
+    template A() {
+        component b;
+        b.in <== 1;
+    }
+
+Fixed example:
+
+    template B() { signal input in; }
+    template A() {
+        component b = B();
+        b.in <== 1;
+    }
+",
+            BusWrongNumberOfArguments => "\
+A bus is instantiated with a number of arguments different from the number of its
+parameters. The same code is used when the extern_c decorator is applied to a
+template that is not custom.
+
+Erroneous code example:
+
+    bus Array(n) { signal v[n]; }
+    template A() {
+        Array() a;
+    }
+
+Fixed example:
+
+    bus Array(n) { signal v[n]; }
+    template A() {
+        Array(2) a;
+    }
+",
+            InvalidSignalAccessInBus => "\
+A bus is accessed with the name of a field it does not declare.
+
+Erroneous code example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        signal output out;
+        out <== p.z;
+    }
+
+Fixed example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        signal output out;
+        out <== p.x;
+    }
+",
+            MustBeSameBus => "\
+Two buses that are connected, for instance the two sides of a constraint, have
+different types.
+
+Erroneous code example:
+
+    bus Point() { signal x; signal y; }
+    bus Pair() { signal a; signal b; }
+    template A() {
+        input Point() p;
+        input Pair() q;
+        p === q;
+    }
+
+Fixed example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        input Point() q;
+        p === q;
+    }
+",
+            MustBeBus => "\
+A bus is expected, for instance on the other side of an assignment to a bus, but a
+signal or an arithmetic value was found.
+
+Erroneous code example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        signal input s;
+        p === s;
+    }
+
+Fixed example:
+
+    bus Point() { signal x; signal y; }
+    template A() {
+        input Point() p;
+        input Point() q;
+        p === q;
+    }
+",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_code_has_an_explanation_with_examples() {
+        for code in ReportCode::all() {
+            let explanation = code.explanation();
+            assert!(!explanation.trim().is_empty(), "{:?} has no explanation", code);
+            if explanation != NOT_EMITTED {
+                assert!(explanation.contains("Erroneous code example:"), "{:?} has no failing example", code);
+                assert!(explanation.contains("Fixed example:"), "{:?} has no fixed example", code);
+            }
+            assert!(explanations_of(&code.to_string()).contains(&explanation), "{} can not be explained", code);
+        }
+    }
+
+    #[test]
+    fn codes_are_looked_up_ignoring_case() {
+        assert_eq!(explanations_of("t2021"), explanations_of("T2021"));
+        assert!(explanations_of("X9999").is_empty());
+    }
+}
@@ -31,6 +31,12 @@ impl FileLibrary {
             None => None,
         }
     }
+    pub fn get_name(&self, file_id: FileID) -> Option<String> {
+        self.files.name(file_id).map(|name| name.to_string())
+    }
+    pub fn get_source(&self, file_id: FileID) -> Option<&str> {
+        self.files.source(file_id)
+    }
     pub fn to_storage(&self) -> &FileStorage {
         &self.get_files()
     }
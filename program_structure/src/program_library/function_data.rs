@@ -1,4 +1,4 @@
-use super::ast::{FillMeta, Statement};
+use super::ast::{DocComment, FillMeta, Statement};
 use super::file_definition::FileID;
 use crate::file_definition::FileLocation;
 use std::collections::HashMap;
@@ -13,6 +13,9 @@ pub struct FunctionData {
     name_of_params: Vec<String>,
     param_location: FileLocation,
     body: Statement,
+    docs: DocComment,
+    // The location of the name in the definition
+    name_location: FileLocation,
 }
 
 impl FunctionData {
@@ -26,7 +29,16 @@ impl FunctionData {
         elem_id: &mut usize,
     ) -> FunctionData {
         body.fill(file_id, elem_id);
-        FunctionData { name, file_id, body, name_of_params, param_location, num_of_params }
+        FunctionData {
+            name,
+            file_id,
+            body,
+            name_of_params,
+            param_location,
+            num_of_params,
+            name_location: FileLocation::default(),
+            docs: DocComment::default(),
+        }
     }
     pub fn get_file_id(&self) -> FileID {
         self.file_id
@@ -67,4 +79,16 @@ impl FunctionData {
     pub fn get_name(&self) -> &str {
         &self.name
     }
+    pub fn get_docs(&self) -> &DocComment {
+        &self.docs
+    }
+    pub fn set_docs(&mut self, docs: DocComment) {
+        self.docs = docs;
+    }
+    pub fn get_name_location(&self) -> FileLocation {
+        self.name_location.clone()
+    }
+    pub fn set_name_location(&mut self, name_location: FileLocation) {
+        self.name_location = name_location;
+    }
 }
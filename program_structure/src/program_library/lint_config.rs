@@ -0,0 +1,145 @@
+use super::error_code::ReportCode;
+use super::error_definition::Report;
+use super::file_definition::{FileID, FileLocation};
+use std::collections::HashMap;
+use std::path::Path;
+
+// Special name that refers to every lint reported by default at once, as in '-D warnings'
+pub const ALL_WARNINGS: &str = "warnings";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+impl LintLevel {
+    pub fn from_name(name: &str) -> Option<LintLevel> {
+        match name {
+            "allow" => Option::Some(LintLevel::Allow),
+            "warn" => Option::Some(LintLevel::Warn),
+            "deny" => Option::Some(LintLevel::Deny),
+            _ => Option::None,
+        }
+    }
+}
+
+// Level given by a comment of the form '// lint: allow(...)' to the template or
+// statement that follows it
+#[derive(Clone)]
+pub struct LintScope {
+    pub file_id: FileID,
+    pub location: FileLocation,
+    pub template: Option<String>,
+    pub lint: String,
+    pub level: LintLevel,
+}
+
+#[derive(Clone)]
+pub struct LintConfig {
+    inspect: bool,
+    all: Option<LintLevel>,
+    levels: HashMap<String, LintLevel>,
+    scopes: Vec<LintScope>,
+}
+
+impl LintConfig {
+    // The lints that come from inspecting the constraints are only reported with
+    // --inspect, unless a level is explicitly given to them
+    pub fn new(inspect: bool) -> LintConfig {
+        LintConfig { inspect, all: Option::None, levels: HashMap::new(), scopes: Vec::new() }
+    }
+
+    pub fn set_level(&mut self, lint: &str, level: LintLevel) -> Result<(), String> {
+        if lint == ALL_WARNINGS {
+            self.all = Option::Some(level);
+            self.levels.clear();
+            Result::Ok(())
+        } else if let Option::Some(code) = ReportCode::from_lint_name(lint) {
+            self.levels.insert(code.to_string(), level);
+            Result::Ok(())
+        } else {
+            Result::Err(format!("Unknown lint {}", lint))
+        }
+    }
+
+    // Each line of the file has the form 'lint = level', where the lint is the name of
+    // a warning (e.g. UnconstrainedSignal), its code (e.g. CA01) or 'warnings'
+    pub fn load_file(&mut self, path: &Path) -> Result<(), String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|_| format!("Could not read the lint configuration file {}", path.display()))?;
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || format!("{}:{}: invalid lint configuration {}", path.display(), number + 1, line);
+            let (lint, level) = line.split_once('=').ok_or_else(invalid)?;
+            let level = LintLevel::from_name(level.trim()).ok_or_else(invalid)?;
+            self.set_level(lint.trim(), level)
+                .map_err(|e| format!("{}:{}: {}", path.display(), number + 1, e))?;
+        }
+        Result::Ok(())
+    }
+
+    pub fn add_scopes(&mut self, scopes: &[LintScope]) {
+        self.scopes.extend_from_slice(scopes);
+    }
+
+    // Whether the constraints have to be inspected to report some lint
+    pub fn requires_inspection(&self) -> bool {
+        ReportCode::lints()
+            .iter()
+            .filter(|code| code.is_inspect_lint())
+            .any(|code| self.level_of_code(code) != LintLevel::Allow)
+            || self.scopes.iter().any(|scope| {
+                scope.level != LintLevel::Allow
+                    && ReportCode::from_lint_name(&scope.lint)
+                        .is_some_and(|code| code.is_inspect_lint())
+            })
+    }
+
+    fn level_of_code(&self, code: &ReportCode) -> LintLevel {
+        if let Option::Some(level) = self.levels.get(&code.to_string()) {
+            *level
+        } else if code.is_inspect_lint() && !self.inspect {
+            // 'warnings' does not enable the lints that are off by default
+            LintLevel::Allow
+        } else if let Option::Some(level) = self.all {
+            level
+        } else {
+            LintLevel::Warn
+        }
+    }
+
+    // Errors can not be silenced. For warnings, the innermost scope containing the
+    // report decides its level, and otherwise the global configuration does.
+    pub fn level_of(&self, report: &Report) -> LintLevel {
+        if report.is_error() {
+            return LintLevel::Deny;
+        }
+        let code = report.get_code().to_string();
+        let mut best: Option<&LintScope> = Option::None;
+        for scope in &self.scopes {
+            let applies = ReportCode::from_lint_name(&scope.lint)
+                .is_some_and(|c| c.to_string() == code)
+                && (report.get_primary().iter().any(|label| {
+                    label.file_id == scope.file_id
+                        && scope.location.start <= label.range.start
+                        && label.range.end <= scope.location.end
+                }) || (report.get_primary().is_empty()
+                    && scope.template.is_some()
+                    && scope.template.as_ref() == report.get_template()));
+            let narrower = best.is_none_or(|b| {
+                scope.location.end - scope.location.start <= b.location.end - b.location.start
+            });
+            if applies && narrower {
+                best = Option::Some(scope);
+            }
+        }
+        match best {
+            Option::Some(scope) => scope.level,
+            Option::None => self.level_of_code(report.get_code()),
+        }
+    }
+}
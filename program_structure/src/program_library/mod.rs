@@ -1,10 +1,14 @@
 use super::ast;
 pub mod bus_data;
+pub mod constant_data;
 pub mod error_code;
 pub mod error_definition;
+pub mod error_explanation;
 pub mod file_definition;
 pub mod function_data;
+pub mod lint_config;
 pub mod program_archive;
 pub mod program_merger;
+pub mod template_budget;
 pub mod template_data;
 pub mod wire_data;
\ No newline at end of file
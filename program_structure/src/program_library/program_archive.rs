@@ -1,12 +1,18 @@
-use super::ast::{Definition, Expression, MainComponent};
+use super::ast::{Definition, Expression, MainComponent, Meta};
+use crate::abstract_syntax_tree::expression_builders::build_number_without_field;
 use super::file_definition::{FileID, FileLibrary};
 use super::function_data::{FunctionData, FunctionInfo};
 use super::program_merger::Merger;
 use super::template_data::{TemplateData, TemplateInfo};
 use super::bus_data::{BusData, BusInfo};
+use super::constant_data::{ConstantData, ConstantInfo};
 use crate::abstract_syntax_tree::ast::FillMeta;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use crate::error_definition::Report;
+use crate::lint_config::LintScope;
+use crate::template_budget::TemplateBudget;
+use num_bigint::BigInt;
 
 type Contents = Vec<(FileID, Vec<Definition>)>;
 
@@ -18,12 +24,22 @@ pub struct ProgramArchive {
     pub functions: FunctionInfo,
     pub templates: TemplateInfo,
     pub buses: BusInfo,
+    pub constants: ConstantInfo,
     pub function_keys: HashSet<String>,
     pub template_keys: HashSet<String>,
     pub bus_keys: HashSet<String>,
+    pub constant_keys: HashSet<String>,
     pub public_inputs: Vec<String>,
     pub initial_template_call: Expression,
     pub custom_gates: bool,
+    pub lint_scopes: Vec<LintScope>,
+    pub template_budgets: Vec<TemplateBudget>,
+    // Paths given with -l, where the included files are searched
+    pub link_libraries: Vec<PathBuf>,
+    // The generic template each template generated for some template arguments comes from
+    pub specializations: HashMap<String, String>,
+    // The files found in the directories given with -l or in a node_modules directory
+    pub library_files: HashSet<FileID>,
 }
 impl ProgramArchive {
     pub fn new(
@@ -40,10 +56,11 @@ impl ProgramArchive {
                 reports.append(&mut errs);
             }
         }
-        let (mut fresh_id, functions, templates, buses) = merger.decompose();
+        let (mut fresh_id, functions, templates, buses, constants) = merger.decompose();
         let mut function_keys = HashSet::new();
         let mut template_keys = HashSet::new();
         let mut bus_keys = HashSet::new();
+        let mut constant_keys = HashSet::new();
 
         for key in functions.keys() {
             function_keys.insert(key.clone());
@@ -54,6 +71,9 @@ impl ProgramArchive {
         for key in buses.keys() {
             bus_keys.insert(key.clone());
         }
+        for key in constants.keys() {
+            constant_keys.insert(key.clone());
+        }
         let (public_inputs, mut initial_template_call) = main_component;
         initial_template_call.fill(file_id_main, &mut fresh_id);
         if reports.is_empty() {
@@ -64,19 +84,40 @@ impl ProgramArchive {
                 functions,
                 templates,
                 buses,
+                constants,
                 public_inputs,
                 initial_template_call,
                 function_keys,
                 template_keys,
                 bus_keys,
+                constant_keys,
                 custom_gates,
+                lint_scopes: Vec::new(),
+                template_budgets: Vec::new(),
+                link_libraries: Vec::new(),
+                specializations: HashMap::new(),
+                library_files: HashSet::new(),
             })
         } else {
             Err((file_library, reports))
         }
 
     }
+    // A library of templates, buses and functions without a main component, whose
+    // definitions are read but never instantiated. Its initial call is the number 0.
+    pub fn new_library(
+        file_library: FileLibrary,
+        file_id_main: FileID,
+        program_contents: Contents,
+    ) -> Result<ProgramArchive, (FileLibrary, Vec<Report>)> {
+        let initial_template_call = build_number_without_field(Meta::new(0, 0), BigInt::from(0));
+        ProgramArchive::new(file_library, file_id_main, (Vec::new(), initial_template_call), program_contents, false)
+    }
     //file_id_main
+    // Some analyses of the source code only report the problems of the user files
+    pub fn is_library_file(&self, file_id: FileID) -> bool {
+        self.library_files.contains(&file_id)
+    }
     pub fn get_file_id_main(&self) -> &FileID {
         &self.file_id_main
     }
@@ -159,6 +200,28 @@ impl ProgramArchive {
         self.buses.remove(id);
     }
 
+    // constant functions
+    pub fn contains_constant(&self, constant_name: &str) -> bool {
+        self.get_constants().contains_key(constant_name)
+    }
+    pub fn get_constant_data(&self, constant_name: &str) -> &ConstantData {
+        assert!(self.contains_constant(constant_name));
+        self.get_constants().get(constant_name).unwrap()
+    }
+    pub fn get_constant_names(&self) -> &HashSet<String> {
+        &self.constant_keys
+    }
+    pub fn get_constants(&self) -> &ConstantInfo {
+        &self.constants
+    }
+    pub fn get_mut_constants(&mut self) -> &mut ConstantInfo {
+        &mut self.constants
+    }
+    pub fn remove_constant(&mut self, id: &str) {
+        self.constant_keys.remove(id);
+        self.constants.remove(id);
+    }
+
     //main_component functions
     pub fn get_public_inputs_main_component(&self) -> &Vec<String> {
         &self.public_inputs
@@ -166,6 +229,9 @@ impl ProgramArchive {
     pub fn get_main_expression(&self) -> &Expression {
         &self.initial_template_call
     }
+    pub fn get_mut_main_expression(&mut self) -> &mut Expression {
+        &mut self.initial_template_call
+    }
     // FileLibrary functions
     pub fn get_file_library(&self) -> &FileLibrary {
         &self.file_library
@@ -1,16 +1,19 @@
-use super::ast::Definition;
+use super::ast::{reserved_name_error, Definition};
+use crate::constants::UsefulConstants;
 use super::error_code::ReportCode;
 use super::error_definition::Report;
 use super::file_definition::FileID;
 use super::function_data::{FunctionData, FunctionInfo};
 use super::template_data::{TemplateData, TemplateInfo};
 use super::bus_data::{BusData, BusInfo};
+use super::constant_data::{ConstantData, ConstantInfo};
 
 pub struct Merger {
     fresh_id: usize,
     function_info: FunctionInfo,
     template_info: TemplateInfo,
     bus_info: BusInfo,
+    constant_info: ConstantInfo,
 }
 impl Default for Merger {
     fn default() -> Self {
@@ -18,7 +21,8 @@ impl Default for Merger {
             fresh_id: 0,
             function_info: FunctionInfo::new(),
             template_info: TemplateInfo::new(),
-            bus_info: BusInfo::new()
+            bus_info: BusInfo::new(),
+            constant_info: ConstantInfo::new(),
         }
     }
 }
@@ -32,11 +36,16 @@ impl Merger {
         let mut reports = vec![];
         for definition in definitions {
             let (name, meta) = match definition {
-                Definition::Template { name, args, arg_location, body, meta, parallel, is_custom_gate,is_extern_c} => {
-                    if self.contains_function(&name) || self.contains_template(&name) || self.contains_bus(&name) {
+                Definition::Template { name, name_location, mut args, arg_location, body, meta, parallel, is_custom_gate,is_extern_c, requires, docs, template_params } => {
+                    if self.contains_symbol(&name) {
                         (Option::Some(name), meta)
                     } else {
-                        let new_data = TemplateData::new(
+                        let positions = args.iter().enumerate().filter_map(|(position, arg)| {
+                            template_params.iter().find(|(param, _)| param == arg)
+                                .map(|(param, interface)| (position, param.clone(), interface.clone()))
+                        }).collect();
+                        args.retain(|arg| !template_params.iter().any(|(param, _)| param == arg));
+                        let mut new_data = TemplateData::new(
                             name.clone(),
                             file_id,
                             body,
@@ -46,17 +55,21 @@ impl Merger {
                             &mut self.fresh_id,
                             parallel,
                             is_custom_gate,
-                            is_extern_c
+                            is_extern_c,
+                            requires
                         );
+                        new_data.set_docs(docs);
+                        new_data.set_name_location(name_location);
+                        new_data.set_template_params(positions);
                         self.get_mut_template_info().insert(name.clone(), new_data);
                         (Option::None, meta)
                     }
                 }
-                Definition::Function { name, body, args, arg_location, meta } => {
-                    if self.contains_function(&name) || self.contains_template(&name) || self.contains_bus(&name) {
+                Definition::Function { name, name_location, body, args, arg_location, meta, docs } => {
+                    if self.contains_symbol(&name) {
                         (Option::Some(name), meta)
                     } else {
-                        let new_data = FunctionData::new(
+                        let mut new_data = FunctionData::new(
                             name.clone(),
                             file_id,
                             body,
@@ -65,15 +78,17 @@ impl Merger {
                             arg_location,
                             &mut self.fresh_id,
                         );
+                        new_data.set_docs(docs);
+                        new_data.set_name_location(name_location);
                         self.get_mut_function_info().insert(name.clone(), new_data);
                         (Option::None, meta)
                     }
                 }
-                Definition::Bus { name, body, args, arg_location, meta  } => {
-                    if self.contains_function(&name) || self.contains_template(&name) || self.contains_bus(&name) {
+                Definition::Bus { name, name_location, body, args, arg_location, meta, docs } => {
+                    if self.contains_symbol(&name) {
                         (Option::Some(name), meta)
                     } else {
-                        let new_data = BusData::new(
+                        let mut new_data = BusData::new(
                             file_id,
                             name.clone(),
                             body,
@@ -82,12 +97,33 @@ impl Merger {
                             arg_location,
                             &mut self.fresh_id,
                         );
+                        new_data.set_docs(docs);
+                        new_data.set_name_location(name_location);
                         self.get_mut_bus_info().insert(name.clone(), new_data);
                         (Option::None, meta)
                     }
                 }
+                Definition::Constant { name, value, meta } => {
+                    if self.contains_symbol(&name) {
+                        (Option::Some(name), meta)
+                    } else {
+                        let new_data = ConstantData::new(
+                            name.clone(),
+                            file_id,
+                            meta.file_location(),
+                            value,
+                            &mut self.fresh_id,
+                        );
+                        self.get_mut_constant_info().insert(name.clone(), new_data);
+                        (Option::None, meta)
+                    }
+                }
             };
             if let Option::Some(definition_name) = name {
+                if UsefulConstants::is_field_constant(&definition_name) {
+                    reports.push(reserved_name_error(&definition_name, meta.file_location(), file_id));
+                    continue;
+                }
                 let mut report = Report::error(
                     String::from("Duplicated callable symbol"),
                     ReportCode::SameSymbolDeclaredTwice,
@@ -102,6 +138,10 @@ impl Merger {
         }
         if reports.is_empty() { Ok(()) } else { Err(reports) }
     }
+    pub fn contains_symbol(&self, name: &str) -> bool {
+        self.contains_function(name) || self.contains_template(name)
+            || self.contains_bus(name) || self.contains_constant(name)
+    }
     pub fn contains_function(&self, function_name: &str) -> bool {
         self.get_function_info().contains_key(function_name)
     }
@@ -132,8 +172,17 @@ impl Merger {
         &mut self.bus_info
     }
 
+    pub fn contains_constant(&self, constant_name: &str) -> bool {
+        self.get_constant_info().contains_key(constant_name)
+    }
+    fn get_constant_info(&self) -> &ConstantInfo {
+        &self.constant_info
+    }
+    fn get_mut_constant_info(&mut self) -> &mut ConstantInfo {
+        &mut self.constant_info
+    }
 
-    pub fn decompose(self) -> (usize, FunctionInfo, TemplateInfo, BusInfo) {
-        (self.fresh_id, self.function_info, self.template_info, self.bus_info)
+    pub fn decompose(self) -> (usize, FunctionInfo, TemplateInfo, BusInfo, ConstantInfo) {
+        (self.fresh_id, self.function_info, self.template_info, self.bus_info, self.constant_info)
     }
 }
\ No newline at end of file
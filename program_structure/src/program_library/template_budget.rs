@@ -0,0 +1,46 @@
+use super::file_definition::{FileID, FileLocation};
+
+// Limits on the size of every instance of a template, counting the constraints and
+// signals of its subcomponents. They are declared with a comment of the form
+// '// budget: constraints = 1000, signals = 2000' before the template, or with
+// --template_budget.
+#[derive(Clone, Debug)]
+pub struct TemplateBudget {
+    pub template: String,
+    pub constraints: Option<usize>,
+    pub signals: Option<usize>,
+    // Position of the comment that declares the budget, if any
+    pub declaration: Option<(FileID, FileLocation)>,
+}
+
+impl TemplateBudget {
+    // Parses the limits 'constraints = N, signals = M', where any of them can be missing
+    pub fn parse_limits(template: &str, limits: &str) -> Option<TemplateBudget> {
+        let mut budget = TemplateBudget {
+            template: template.to_string(),
+            constraints: Option::None,
+            signals: Option::None,
+            declaration: Option::None,
+        };
+        for limit in limits.split(',') {
+            let (kind, value) = limit.split_once('=')?;
+            let value: usize = value.trim().parse().ok()?;
+            match kind.trim() {
+                "constraints" if budget.constraints.is_none() => budget.constraints = Option::Some(value),
+                "signals" if budget.signals.is_none() => budget.signals = Option::Some(value),
+                _ => return Option::None,
+            }
+        }
+        Option::Some(budget)
+    }
+
+    // Parses a budget given in the command line as 'Template: constraints = N, signals = M'
+    pub fn parse(text: &str) -> Option<TemplateBudget> {
+        let (template, limits) = text.split_once(':')?;
+        let template = template.trim();
+        if template.is_empty() {
+            return Option::None;
+        }
+        TemplateBudget::parse_limits(template, limits)
+    }
+}
@@ -1,5 +1,5 @@
 use super::ast;
-use super::ast::{FillMeta, Statement};
+use super::ast::{DocComment, Expression, FillMeta, Statement};
 use super::file_definition::{FileID, FileLocation};
 use super::wire_data::*;
 use std::collections::{HashMap};
@@ -19,9 +19,17 @@ pub struct TemplateData {
     is_parallel: bool,
     is_custom_gate: bool,
     is_extern_c: bool,
+    /* Conditions checked every time the template is instantiated */
+    requires: Vec<Expression>,
     /* Only used to know the order in which signals are declared.*/
     input_declarations: WireDeclarationOrder,
     output_declarations: WireDeclarationOrder,
+    docs: DocComment,
+    // The location of the name in the definition
+    name_location: FileLocation,
+    /* Parameters of generic templates whose value is a template: position in the list
+    of parameters, name and interface. They are not part of name_of_params. */
+    template_params: Vec<(usize, String, String)>,
 }
 
 impl TemplateData {
@@ -35,9 +43,13 @@ impl TemplateData {
         elem_id: &mut usize,
         is_parallel: bool,
         is_custom_gate: bool,
-        is_extern_c: bool
+        is_extern_c: bool,
+        mut requires: Vec<Expression>,
     ) -> TemplateData {
         body.fill(file_id, elem_id);
+        for clause in requires.iter_mut() {
+            clause.fill(file_id, elem_id);
+        }
         let mut input_wires = WireInfo::new();
         let mut output_wires = WireInfo::new();
         let mut input_declarations = WireDeclarationOrder::new();
@@ -55,8 +67,12 @@ impl TemplateData {
             is_parallel,
             is_custom_gate,
             is_extern_c,
+            requires,
             input_declarations,
-            output_declarations
+            output_declarations,
+            name_location: FileLocation::default(),
+            docs: DocComment::default(),
+            template_params: Vec::new(),
         }
     }
 
@@ -72,6 +88,7 @@ impl TemplateData {
         is_parallel: bool,
         is_custom_gate: bool,
         is_extern_c: bool,
+        requires: Vec<Expression>,
         input_declarations: WireDeclarationOrder,
         output_declarations: WireDeclarationOrder
     ) -> TemplateData {
@@ -87,8 +104,12 @@ impl TemplateData {
             is_parallel,
             is_custom_gate,
             is_extern_c,
+            requires,
             input_declarations,
-            output_declarations
+            output_declarations,
+            name_location: FileLocation::default(),
+            docs: DocComment::default(),
+            template_params: Vec::new(),
         }
     }
     pub fn get_file_id(&self) -> FileID {
@@ -155,6 +176,33 @@ impl TemplateData {
     pub fn is_extern_c(&self) -> bool {
         self.is_extern_c
     }
+    pub fn get_requires(&self) -> &Vec<Expression> {
+        &self.requires
+    }
+    pub fn get_mut_requires(&mut self) -> &mut Vec<Expression> {
+        &mut self.requires
+    }
+    pub fn get_docs(&self) -> &DocComment {
+        &self.docs
+    }
+    pub fn set_docs(&mut self, docs: DocComment) {
+        self.docs = docs;
+    }
+    pub fn get_name_location(&self) -> FileLocation {
+        self.name_location.clone()
+    }
+    pub fn set_name_location(&mut self, name_location: FileLocation) {
+        self.name_location = name_location;
+    }
+    pub fn get_template_params(&self) -> &Vec<(usize, String, String)> {
+        &self.template_params
+    }
+    pub fn set_template_params(&mut self, template_params: Vec<(usize, String, String)>) {
+        self.template_params = template_params;
+    }
+    pub fn is_generic(&self) -> bool {
+        !self.template_params.is_empty()
+    }
 }
 
 fn fill_inputs_and_outputs(
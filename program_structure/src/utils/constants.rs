@@ -12,15 +12,24 @@ const P_VESTA : &str = "28948022309329048855892746252171976963363056481941647379
 const P_SECQ256R1 : &str = "115792089210356248762697446949407573530086143415290314195533631308867097853951";
 const P_BLS12377 : &str = "8444461749428370424248824938781546531375899335154063827935233455917409239041";
 
+const FIELD_BITS: &str = "FIELD_BITS";
+const FIELD_MAX: &str = "FIELD_MAX";
+const FIELD_ID: &str = "FIELD_ID";
+
+// The value of FIELD_ID is the position of the prime in this list, starting at 1
+const PRIME_NAMES: [&str; 8] =
+    ["bn128", "bls12381", "goldilocks", "grumpkin", "pallas", "vesta", "secq256r1", "bls12377"];
+
 //const P_STR: &str = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
 
 pub struct UsefulConstants {
     p: BigInt,
+    name: String,
 }
 
 impl Clone for UsefulConstants {
     fn clone(&self) -> Self {
-        UsefulConstants { p: self.p.clone() }
+        UsefulConstants { p: self.p.clone(), name: self.name.clone() }
     }
 }
 
@@ -36,10 +45,30 @@ impl UsefulConstants {
           else if possible_prime.eq("bls12377") { P_BLS12377}
           else {unreachable!()};
 
-        UsefulConstants { p: BigInt::parse_bytes(prime_to_use.as_bytes(), 10).expect("can not parse p") }
+        UsefulConstants {
+            p: BigInt::parse_bytes(prime_to_use.as_bytes(), 10).expect("can not parse p"),
+            name: possible_prime.clone(),
+        }
     }
     
     pub fn get_p(&self) -> &BigInt {
         &self.p
     }
+
+    // Built-in constants of the language that depend on the chosen prime: the number
+    // of bits of p, the greatest element of the field and the number that identifies
+    // the prime. p itself is not one of them, as it is equal to 0 in the field
+    pub fn get_field_constants(&self) -> Vec<(String, BigInt)> {
+        let position = PRIME_NAMES.iter().position(|name| *name == self.name).unwrap();
+        vec![
+            (FIELD_BITS.to_string(), BigInt::from(self.p.bits())),
+            (FIELD_MAX.to_string(), &self.p - 1),
+            (FIELD_ID.to_string(), BigInt::from(position + 1)),
+        ]
+    }
+
+    // The names of the built-in constants are reserved, they can not be declared
+    pub fn is_field_constant(name: &str) -> bool {
+        [FIELD_BITS, FIELD_MAX, FIELD_ID].contains(&name)
+    }
 }
@@ -0,0 +1,130 @@
+use std::path::{Path, PathBuf};
+
+// Number of single-character insertions, deletions, substitutions and transpositions
+// of adjacent characters needed to transform one name into the other
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+// Returns the candidate closest to the given name, as long as it is close enough to
+// be a typo: at most one edit for every three characters of the name
+pub fn closest_name<'a, I>(name: &str, candidates: I) -> Option<&'a String>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let limit = std::cmp::max(1, name.chars().count() / 3);
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate.as_str() != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+// Looks for a template, function or bus with the given name in the circom files found
+// under the library paths. Returns the path of the file relative to the library, that
+// is, the one that has to be used in the include directive.
+pub fn find_definition_in_libraries(name: &str, libraries: &[PathBuf]) -> Option<PathBuf> {
+    for library in libraries {
+        let mut files = Vec::new();
+        collect_circom_files(library, &mut files);
+        files.sort();
+        for file in files {
+            let defined = std::fs::read_to_string(&file).is_ok_and(|src| defines(&src, name));
+            if defined {
+                return Option::Some(file.strip_prefix(library).unwrap_or(&file).to_path_buf());
+            }
+        }
+    }
+    Option::None
+}
+
+fn collect_circom_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    if let Result::Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                collect_circom_files(&path, files);
+            } else if path.extension().is_some_and(|ext| ext == "circom") {
+                files.push(path);
+            }
+        }
+    }
+}
+
+// Whether the source contains a definition such as 'template Name(' or
+// 'template parallel Name(', found by looking at the words that precede the name
+fn defines(src: &str, name: &str) -> bool {
+    let words: Vec<&str> = src
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .filter(|word| !word.is_empty())
+        .collect();
+    words.iter().enumerate().any(|(i, word)| {
+        *word == name
+            && match i {
+                0 => false,
+                1 => is_definition_keyword(words[0]),
+                _ => {
+                    is_definition_keyword(words[i - 1])
+                        || (matches!(words[i - 1], "custom" | "parallel")
+                            && is_definition_keyword(words[i - 2]))
+                }
+            }
+    })
+}
+
+fn is_definition_keyword(word: &str) -> bool {
+    matches!(word, "template" | "function" | "bus")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        assert_eq!(edit_distance("outt", "out"), 1);
+        assert_eq!(edit_distance("Num2Bits", "Num2bits"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("Sbu", "Sub"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn closest_name_ignores_distant_candidates() {
+        let candidates = vec!["in".to_string(), "out".to_string(), "IsZero".to_string()];
+        assert_eq!(closest_name("outt", &candidates), Option::Some(&candidates[1]));
+        assert_eq!(closest_name("IsZer", &candidates), Option::Some(&candidates[2]));
+        assert_eq!(closest_name("LessThan", &candidates), Option::None);
+    }
+
+    #[test]
+    fn definitions_are_found_after_their_keyword() {
+        let src = "pragma circom 2.0.0;\ntemplate parallel Num2Bits(n) { signal input in; }\n";
+        assert!(defines(src, "Num2Bits"));
+        assert!(!defines(src, "in"));
+        assert!(!defines("component c = Num2Bits(3);", "Num2Bits"));
+    }
+}
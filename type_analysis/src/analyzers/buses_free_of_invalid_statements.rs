@@ -81,6 +81,9 @@ fn analyse_statement(
         Assert { meta, .. } => {
             report_undefined_bus_error(meta, "Assert statement used inside the bus", file_id, None, reports)
         },
+        StaticAssert { meta, .. } => {
+            report_undefined_bus_error(meta, "Static assert statement used inside the bus", file_id, None, reports)
+        },
         Return { meta, .. } => {
             report_undefined_bus_error(meta, "Return statement used inside the bus", file_id, None, reports)
         },
@@ -0,0 +1,361 @@
+use program_structure::ast::*;
+use program_structure::constants::UsefulConstants;
+use program_structure::error_code::ReportCode;
+use program_structure::error_definition::{Report, ReportCollection};
+use program_structure::file_definition::{FileID, FileLocation};
+use program_structure::program_archive::ProgramArchive;
+use std::collections::{HashMap, HashSet};
+
+// Reports the templates, functions, buses and global constants defined in the main file
+// that are never used. The ones coming from other files are not reported, as libraries
+// usually define more than what a circuit needs.
+pub fn unused_definitions_analysis(
+    program_archive: &ProgramArchive,
+    reached: &HashSet<String>,
+) -> ReportCollection {
+    let main_file = *program_archive.get_file_id_main();
+    let reached = &with_generic_templates(program_archive, reached);
+    let mut warnings = ReportCollection::new();
+    let mut unused = Vec::new();
+    for (name, data) in program_archive.get_templates() {
+        // The specializations are generated by the compiler, their generic template is reported
+        if !program_archive.specializations.contains_key(name) {
+            unused.push(("template", name, data.get_file_id(), data.get_name_location()));
+        }
+    }
+    for (name, data) in program_archive.get_functions() {
+        unused.push(("function", name, data.get_file_id(), data.get_name_location()));
+    }
+    for (name, data) in program_archive.get_buses() {
+        unused.push(("bus", name, data.get_file_id(), data.get_name_location()));
+    }
+    unused.retain(|(_, name, file_id, _)| *file_id == main_file && !reached.contains(*name));
+
+    let used_constants = used_global_constants(program_archive, reached);
+    for (name, data) in program_archive.get_constants() {
+        if data.get_file_id() == main_file
+            && !used_constants.contains(name)
+            && !UsefulConstants::is_field_constant(name)
+        {
+            unused.push(("global constant", name, data.get_file_id(), data.get_location()));
+        }
+    }
+    unused.sort_by_key(|(_, _, _, location)| location.start);
+    for (kind, name, file_id, location) in unused {
+        let mut report = Report::warning(format!("Unused {}", kind), ReportCode::UnusedDefinition);
+        report.add_primary(location, file_id, format!("The {} {} is never used", kind, name));
+        warnings.push(report);
+    }
+    warnings
+}
+
+// A generic template is used when one of its specializations is, and then the templates
+// used as interfaces of its template parameters are used too
+fn with_generic_templates(program_archive: &ProgramArchive, reached: &HashSet<String>) -> HashSet<String> {
+    let mut used = reached.clone();
+    for (specialization, generic) in &program_archive.specializations {
+        if reached.contains(specialization) && program_archive.contains_template(generic) {
+            used.insert(generic.clone());
+            let data = program_archive.get_template_data(generic);
+            used.extend(data.get_template_params().iter().map(|(_, _, interface)| interface.clone()));
+        }
+    }
+    used
+}
+
+fn used_global_constants(program_archive: &ProgramArchive, reached: &HashSet<String>) -> HashSet<String> {
+    let mut usage = Usage::default();
+    for (name, data) in program_archive.get_templates() {
+        if reached.contains(name) {
+            usage.visit_statement(data.get_body());
+            for clause in data.get_requires() {
+                usage.visit_expression(clause);
+            }
+        }
+    }
+    for (name, data) in program_archive.get_functions() {
+        if reached.contains(name) {
+            usage.visit_statement(data.get_body());
+        }
+    }
+    for (name, data) in program_archive.get_buses() {
+        if reached.contains(name) {
+            usage.visit_statement(data.get_body());
+        }
+    }
+    for data in program_archive.get_constants().values() {
+        usage.visit_expression(data.get_value());
+    }
+    usage.visit_expression(program_archive.get_main_expression());
+    usage.read
+}
+
+// Reports the variables that are never read, the components that are never assigned
+// and the intermediate signals that are never read inside a template or function body,
+// together with the statements of functions that can never be executed
+pub fn unused_symbols_analysis(body: &Statement, is_function: bool) -> ReportCollection {
+    let mut usage = Usage::default();
+    usage.visit_statement(body);
+    let mut warnings = ReportCollection::new();
+    for declared in &usage.declarations {
+        let report = match &declared.xtype {
+            VariableType::Var if !declared.read => Option::Some((
+                "Unused variable",
+                ReportCode::UnusedVariable,
+                format!("The variable {} is never read", declared.name),
+            )),
+            VariableType::Component if !declared.written => Option::Some((
+                "Component never assigned",
+                ReportCode::UnassignedComponent,
+                format!("The component {} is declared but never instantiated", declared.name),
+            )),
+            VariableType::Signal(SignalType::Intermediate, _) | VariableType::Bus(_, SignalType::Intermediate, _)
+                if !declared.read =>
+            {
+                Option::Some((
+                    "Unused signal",
+                    ReportCode::UnreadSignal,
+                    format!("The signal {} is never read", declared.name),
+                ))
+            }
+            _ => Option::None,
+        };
+        if let Option::Some((message, code, label)) = report {
+            let mut report = Report::warning(message.to_string(), code);
+            report.add_primary(declared.location.clone(), declared.file_id, label);
+            warnings.push(report);
+        }
+    }
+    if is_function {
+        unreachable_statements(body, &mut warnings);
+    }
+    warnings
+}
+
+struct Declared {
+    name: String,
+    xtype: VariableType,
+    file_id: FileID,
+    location: FileLocation,
+    read: bool,
+    written: bool,
+}
+
+// Symbols read and written in a body. Each use refers to the declaration of the innermost
+// block that declares its name, so a variable of a block does not hide the declarations
+// with the same name of other blocks. The names that are not declared in the body, as the
+// parameters and the global constants, are collected in read. The target of an assignment
+// is not read, but the expressions used to index it are.
+#[derive(Default)]
+struct Usage {
+    declarations: Vec<Declared>,
+    scopes: Vec<HashMap<String, usize>>,
+    read: HashSet<String>,
+}
+
+impl Usage {
+    fn declare(&mut self, meta: &Meta, name: &str, xtype: &VariableType) {
+        if self.scopes.is_empty() {
+            self.scopes.push(HashMap::new());
+        }
+        let index = self.declarations.len();
+        self.declarations.push(Declared {
+            name: name.to_string(),
+            xtype: xtype.clone(),
+            file_id: meta.get_file_id(),
+            location: meta.location.clone(),
+            read: false,
+            written: false,
+        });
+        self.scopes.last_mut().unwrap().insert(name.to_string(), index);
+    }
+
+    fn lookup(&mut self, name: &str) -> Option<&mut Declared> {
+        let index = self.scopes.iter().rev().find_map(|scope| scope.get(name).copied())?;
+        Option::Some(&mut self.declarations[index])
+    }
+
+    fn mark_read(&mut self, name: &str) {
+        match self.lookup(name) {
+            Option::Some(declared) => declared.read = true,
+            Option::None => {
+                self.read.insert(name.to_string());
+            }
+        }
+    }
+
+    fn mark_written(&mut self, name: &str) {
+        if let Option::Some(declared) = self.lookup(name) {
+            declared.written = true;
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement) {
+        use Statement::*;
+        match stmt {
+            IfThenElse { cond, if_case, else_case, .. } => {
+                self.visit_expression(cond);
+                self.visit_statement(if_case);
+                if let Option::Some(else_case) = else_case {
+                    self.visit_statement(else_case);
+                }
+            }
+            While { cond, stmt, .. } => {
+                self.visit_expression(cond);
+                self.visit_statement(stmt);
+            }
+            Return { value, .. } => self.visit_expression(value),
+            InitializationBlock { initializations, .. } => {
+                for init in initializations {
+                    self.visit_statement(init);
+                }
+            }
+            Declaration { meta, xtype, name, dimensions, .. } => {
+                for dimension in dimensions {
+                    self.visit_expression(dimension);
+                }
+                self.declare(meta, name, xtype);
+            }
+            Substitution { var, access, rhe, .. } => {
+                self.mark_written(var);
+                self.visit_access(access);
+                self.visit_expression(rhe);
+            }
+            MultSubstitution { lhe, rhe, .. } => {
+                self.visit_target(lhe);
+                self.visit_expression(rhe);
+            }
+            UnderscoreSubstitution { rhe, .. } => self.visit_expression(rhe),
+            ConstraintEquality { lhe, rhe, .. } => {
+                self.visit_expression(lhe);
+                self.visit_expression(rhe);
+            }
+            LogCall { args, .. } => {
+                for arg in args {
+                    if let LogArgument::LogExp(exp) = arg {
+                        self.visit_expression(exp);
+                    }
+                }
+            }
+            Block { stmts, .. } => {
+                self.scopes.push(HashMap::new());
+                for stmt in stmts {
+                    self.visit_statement(stmt);
+                }
+                self.scopes.pop();
+            }
+            Assert { arg, .. } | StaticAssert { arg, .. } => self.visit_expression(arg),
+        }
+    }
+
+    fn visit_target(&mut self, target: &Expression) {
+        match target {
+            Expression::Variable { name, access, .. } => {
+                self.mark_written(name);
+                self.visit_access(access);
+            }
+            Expression::Tuple { values, .. } => {
+                for value in values {
+                    self.visit_target(value);
+                }
+            }
+            _ => self.visit_expression(target),
+        }
+    }
+
+    fn visit_access(&mut self, access: &[Access]) {
+        for acc in access {
+            if let Access::ArrayAccess(index) = acc {
+                self.visit_expression(index);
+            }
+        }
+    }
+
+    fn visit_expressions(&mut self, exprs: &[Expression]) {
+        for expr in exprs {
+            self.visit_expression(expr);
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        use Expression::*;
+        match expr {
+            InfixOp { lhe, rhe, .. } => {
+                self.visit_expression(lhe);
+                self.visit_expression(rhe);
+            }
+            PrefixOp { rhe, .. } | ParallelOp { rhe, .. } => self.visit_expression(rhe),
+            InlineSwitchOp { cond, if_true, if_false, .. } => {
+                self.visit_expression(cond);
+                self.visit_expression(if_true);
+                self.visit_expression(if_false);
+            }
+            Variable { name, access, .. } => {
+                self.mark_read(name);
+                self.visit_access(access);
+            }
+            Number(..) => {}
+            Call { args, .. } | BusCall { args, .. } => self.visit_expressions(args),
+            AnonymousComp { params, signals, .. } => {
+                self.visit_expressions(params);
+                self.visit_expressions(signals);
+            }
+            ArrayInLine { values, .. } | Tuple { values, .. } => self.visit_expressions(values),
+            UniformArray { value, dimension, .. } => {
+                self.visit_expression(value);
+                self.visit_expression(dimension);
+            }
+        }
+    }
+}
+
+// Whether the execution of the statement always ends in a return
+fn always_returns(stmt: &Statement) -> bool {
+    use Statement::*;
+    match stmt {
+        Return { .. } => true,
+        Block { stmts, .. } => stmts.iter().any(always_returns),
+        IfThenElse { if_case, else_case: Option::Some(else_case), .. } => {
+            always_returns(if_case) && always_returns(else_case)
+        }
+        _ => false,
+    }
+}
+
+fn unreachable_statements(stmt: &Statement, warnings: &mut ReportCollection) {
+    use Statement::*;
+    match stmt {
+        Block { stmts, .. } => {
+            if let Option::Some(index) = stmts.iter().position(always_returns) {
+                let unreachable = &stmts[index + 1..];
+                if let (Option::Some(first), Option::Some(last)) = (unreachable.first(), unreachable.last()) {
+                    let mut report =
+                        Report::warning("Unreachable code".to_string(), ReportCode::UnreachableCode);
+                    report.add_primary(
+                        first.get_meta().start..last.get_meta().end,
+                        first.get_meta().get_file_id(),
+                        "This code is never executed".to_string(),
+                    );
+                    let returning = stmts[index].get_meta();
+                    report.add_secondary(
+                        returning.location.clone(),
+                        returning.get_file_id(),
+                        Option::Some("Any execution returns before reaching it".to_string()),
+                    );
+                    warnings.push(report);
+                }
+            }
+            for stmt in stmts {
+                unreachable_statements(stmt, warnings);
+            }
+        }
+        IfThenElse { if_case, else_case, .. } => {
+            unreachable_statements(if_case, warnings);
+            if let Option::Some(else_case) = else_case {
+                unreachable_statements(else_case, warnings);
+            }
+        }
+        While { stmt, .. } => unreachable_statements(stmt, warnings),
+        _ => {}
+    }
+}
@@ -112,6 +112,17 @@ fn analyse_statement(
         Assert { arg, .. } => {
             analyse_expression(arg, function_names, reports);
         }
+        StaticAssert { meta, arg, .. } => {
+            let mut report = Report::error(
+                "Function uses template elements".to_string(),
+                ReportCode::UndefinedFunction,
+            );
+            let location =
+                file_definition::generate_file_location(meta.get_start(), meta.get_end());
+            report.add_primary(location, file_id, "Static assertions can only be used in templates".to_string());
+            reports.push(report);
+            analyse_expression(arg, function_names, reports);
+        }
         Return { value, .. } => {
             analyse_expression(value, function_names, reports);
         }
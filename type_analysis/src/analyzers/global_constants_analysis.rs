@@ -0,0 +1,197 @@
+use program_structure::ast::*;
+use program_structure::constant_data::{constant_dependencies, ConstantData};
+use program_structure::constants::UsefulConstants;
+use program_structure::error_code::ReportCode;
+use program_structure::error_definition::{Report, ReportCollection};
+use program_structure::file_definition::{FileID, FileLocation};
+use program_structure::program_archive::ProgramArchive;
+use std::collections::{HashMap, HashSet};
+
+pub fn check_global_constants(program_archive: &ProgramArchive) -> Result<(), ReportCollection> {
+    let constant_names = program_archive.get_constant_names();
+    let mut reports = ReportCollection::new();
+    if constant_names.is_empty() {
+        return Result::Ok(());
+    }
+    for constant in program_archive.get_constants().values() {
+        analyse_constant_value(constant.get_value(), program_archive, &mut reports);
+    }
+    look_for_cycles(program_archive, &mut reports);
+
+    let mut instances = Vec::new();
+    for data in program_archive.get_templates().values() {
+        instances.push((data.get_file_id(), data.get_param_location(), data.get_name_of_params(), data.get_body()));
+    }
+    for data in program_archive.get_functions().values() {
+        instances.push((data.get_file_id(), data.get_param_location(), data.get_name_of_params(), data.get_body()));
+    }
+    for data in program_archive.get_buses().values() {
+        instances.push((data.get_file_id(), data.get_param_location(), data.get_name_of_params(), data.get_body()));
+    }
+    for (file_id, param_location, params, body) in instances {
+        for param in params {
+            if constant_names.contains(param) {
+                shadowing_error(param, param_location.clone(), file_id, &mut reports);
+            }
+        }
+        look_for_constant_updates(body, constant_names, &mut reports);
+    }
+
+    if reports.is_empty() {
+        Result::Ok(())
+    } else {
+        Result::Err(reports)
+    }
+}
+
+// Global constants are single arithmetic values that can be computed at compile time:
+// they can only depend on numbers, other global constants and function calls.
+fn analyse_constant_value(expr: &Expression, program_archive: &ProgramArchive, reports: &mut ReportCollection) {
+    use Expression::*;
+    match expr {
+        InfixOp { lhe, rhe, .. } => {
+            analyse_constant_value(lhe, program_archive, reports);
+            analyse_constant_value(rhe, program_archive, reports);
+        }
+        PrefixOp { rhe, .. } => analyse_constant_value(rhe, program_archive, reports),
+        InlineSwitchOp { cond, if_true, if_false, .. } => {
+            analyse_constant_value(cond, program_archive, reports);
+            analyse_constant_value(if_true, program_archive, reports);
+            analyse_constant_value(if_false, program_archive, reports);
+        }
+        Variable { meta, access, .. } => {
+            if !access.is_empty() {
+                invalid_value_error(meta, "Global constants can not be accessed", reports);
+            }
+        }
+        Number(..) => {}
+        Call { meta, id, args } => {
+            if program_archive.contains_template(id) {
+                invalid_value_error(meta, "Components can not be used to define global constants", reports);
+            }
+            for arg in args {
+                analyse_constant_value(arg, program_archive, reports);
+            }
+        }
+        ParallelOp { meta, .. } | AnonymousComp { meta, .. } => {
+            invalid_value_error(meta, "Components can not be used to define global constants", reports);
+        }
+        BusCall { meta, .. } => {
+            invalid_value_error(meta, "Buses can not be used to define global constants", reports);
+        }
+        ArrayInLine { meta, .. } | UniformArray { meta, .. } | Tuple { meta, .. } => {
+            invalid_value_error(meta, "Global constants must be single arithmetic expressions", reports);
+        }
+    }
+}
+
+fn look_for_cycles(program_archive: &ProgramArchive, reports: &mut ReportCollection) {
+    let constants = program_archive.get_constants();
+    let functions = program_archive.get_functions();
+    let mut graph = HashMap::new();
+    for (name, data) in constants {
+        graph.insert(name.clone(), constant_dependencies(data.get_value(), constants, functions));
+    }
+    let mut names: Vec<&String> = graph.keys().collect();
+    names.sort();
+    for name in names {
+        let mut visited = HashSet::new();
+        let mut pending: Vec<&String> = graph.get(name).unwrap().iter().collect();
+        while let Option::Some(current) = pending.pop() {
+            if current == name {
+                cyclic_definition_error(program_archive.get_constant_data(name), reports);
+                break;
+            }
+            if visited.insert(current) {
+                pending.extend(graph.get(current).unwrap().iter());
+            }
+        }
+    }
+}
+
+fn look_for_constant_updates(stmt: &Statement, constant_names: &HashSet<String>, reports: &mut ReportCollection) {
+    use Statement::*;
+    match stmt {
+        IfThenElse { if_case, else_case, .. } => {
+            look_for_constant_updates(if_case, constant_names, reports);
+            if let Option::Some(else_block) = else_case {
+                look_for_constant_updates(else_block, constant_names, reports);
+            }
+        }
+        While { stmt, .. } => look_for_constant_updates(stmt, constant_names, reports),
+        Block { stmts, .. } => {
+            for stmt in stmts.iter() {
+                look_for_constant_updates(stmt, constant_names, reports);
+            }
+        }
+        InitializationBlock { initializations, .. } => {
+            // The initialization of a declaration that shadows a constant is already reported
+            let mut declared = HashSet::new();
+            for init in initializations.iter() {
+                match init {
+                    Declaration { name, .. } => {
+                        declared.insert(name.clone());
+                    }
+                    Substitution { var, .. } if declared.contains(var) => continue,
+                    _ => {}
+                }
+                look_for_constant_updates(init, constant_names, reports);
+            }
+        }
+        Declaration { meta, name, .. } if constant_names.contains(name) => {
+            shadowing_error(name, meta.file_location(), meta.get_file_id(), reports);
+        }
+        Substitution { meta, var, .. } if constant_names.contains(var) => {
+            let mut report = Report::error(
+                format!("The global constant {} can not be assigned", var),
+                ReportCode::GlobalConstantAssigned,
+            );
+            report.add_primary(
+                meta.file_location(),
+                meta.get_file_id(),
+                "Assigning a global constant".to_string(),
+            );
+            reports.push(report);
+        }
+        _ => {}
+    }
+}
+
+fn invalid_value_error(meta: &Meta, message: &str, reports: &mut ReportCollection) {
+    let mut report = Report::error(
+        "Invalid global constant definition".to_string(),
+        ReportCode::InvalidGlobalConstant,
+    );
+    report.add_primary(meta.file_location(), meta.get_file_id(), message.to_string());
+    reports.push(report);
+}
+
+fn cyclic_definition_error(constant: &ConstantData, reports: &mut ReportCollection) {
+    let mut report = Report::error(
+        format!("The global constant {} depends on itself", constant.get_name()),
+        ReportCode::InvalidGlobalConstant,
+    );
+    report.add_primary(
+        constant.get_location(),
+        constant.get_file_id(),
+        "Cyclic definition".to_string(),
+    );
+    reports.push(report);
+}
+
+fn shadowing_error(name: &str, location: FileLocation, file_id: FileID, reports: &mut ReportCollection) {
+    if UsefulConstants::is_field_constant(name) {
+        reports.push(reserved_name_error(name, location, file_id));
+        return;
+    }
+    let mut report = Report::error(
+        "Symbol declared twice".to_string(),
+        ReportCode::SameSymbolDeclaredTwice,
+    );
+    report.add_primary(
+        location,
+        file_id,
+        format!("{} is already declared as a global constant", name),
+    );
+    reports.push(report);
+}
@@ -1,21 +1,29 @@
 pub use buses_free_of_invalid_statements::free_of_invalid_statements;
 pub use custom_gate_analysis::custom_gate_analysis;
+pub use dead_code_analysis::{unused_definitions_analysis, unused_symbols_analysis};
 pub use functions_all_paths_with_return_statement::all_paths_with_return_check;
 pub use functions_free_of_template_elements::free_of_template_elements;
+pub use global_constants_analysis::check_global_constants;
 pub use no_returns_in_template::free_of_returns;
 pub use signal_declaration_analysis::check_signal_correctness;
 pub use symbol_analysis::check_naming_correctness;
+pub use template_contracts_analysis::check_template_contracts;
 pub use type_check::type_check;
+pub use unconstrained_assignment_analysis::unconstrained_assignment_analysis;
 pub use unknown_known_analysis::unknown_known_analysis;
 
 pub mod buses_free_of_invalid_statements;
 pub mod custom_gate_analysis;
+pub mod dead_code_analysis;
 pub mod functions_all_paths_with_return_statement;
 pub mod functions_free_of_template_elements;
+pub mod global_constants_analysis;
 pub mod no_returns_in_template;
 pub mod signal_declaration_analysis;
 pub mod symbol_analysis;
+pub mod template_contracts_analysis;
 pub mod type_check;
 pub mod type_given_function;
 pub mod type_register;
+pub mod unconstrained_assignment_analysis;
 pub mod unknown_known_analysis;
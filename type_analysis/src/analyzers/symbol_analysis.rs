@@ -6,7 +6,9 @@ use program_structure::program_archive::ProgramArchive;
 use program_structure::function_data::FunctionInfo;
 use program_structure::template_data::TemplateInfo;
 use program_structure::bus_data::BusInfo;
+use program_structure::suggestions::{closest_name, find_definition_in_libraries};
 use std::collections::HashSet;
+use std::path::PathBuf;
 type Block = HashSet<String>;
 type Environment = Vec<Block>;
 
@@ -14,6 +16,8 @@ pub fn check_naming_correctness(program_archive: &ProgramArchive) -> Result<(),
     let template_info = program_archive.get_templates();
     let function_info = program_archive.get_functions();
     let bus_info = program_archive.get_buses();
+    let libraries = &program_archive.link_libraries;
+    let constant_names = program_archive.get_constant_names();
     let mut reports = ReportCollection::new();
     let mut instances = Vec::new();
     for (_, data) in template_info {
@@ -46,6 +50,9 @@ pub fn check_naming_correctness(program_archive: &ProgramArchive) -> Result<(),
     if let Err(mut r) = analyze_main(program_archive) {
         reports.append(&mut r);
     }
+    if let Err(mut r) = analyze_constants(program_archive) {
+        reports.append(&mut r);
+    }
     for (file_id, param_location, params_names, body) in instances {
         let res = analyze_symbols(
             file_id,
@@ -55,6 +62,8 @@ pub fn check_naming_correctness(program_archive: &ProgramArchive) -> Result<(),
             function_info,
             template_info,
             bus_info,
+            libraries,
+            constant_names,
         );
         if let Result::Err(mut r) = res {
             reports.append(&mut r);
@@ -73,8 +82,11 @@ fn analyze_main(program: &ProgramArchive) -> Result<(), Vec<Report>> {
     let template_info = program.get_templates();
     let function_info = program.get_functions();
     let bus_info = program.get_buses();
+    let libraries = &program.link_libraries;
 
     let mut reports = vec![];
+    let mut environment = Environment::new();
+    environment.push(program.get_constant_names().clone());
     if let Expression::Call { id, .. } = call {
         if program.contains_template(id) {
             let inputs = program.get_template_data(id).get_inputs();
@@ -94,13 +106,13 @@ fn analyze_main(program: &ProgramArchive) -> Result<(), Vec<Report>> {
             }
         }
     }
-    let environment = Environment::new();
     analyze_expression(
         call,
         call.get_meta().get_file_id(),
         function_info,
         template_info,
         bus_info,
+        libraries,
         &mut reports,
         &environment,
     );
@@ -108,6 +120,29 @@ fn analyze_main(program: &ProgramArchive) -> Result<(), Vec<Report>> {
     if reports.is_empty() { Ok(()) } else { Err(reports) }
 }
 
+fn analyze_constants(program: &ProgramArchive) -> Result<(), Vec<Report>> {
+    let template_info = program.get_templates();
+    let function_info = program.get_functions();
+    let bus_info = program.get_buses();
+    let libraries = &program.link_libraries;
+    let mut reports = vec![];
+    let mut environment = Environment::new();
+    environment.push(program.get_constant_names().clone());
+    for constant in program.get_constants().values() {
+        analyze_expression(
+            constant.get_value(),
+            constant.get_file_id(),
+            function_info,
+            template_info,
+            bus_info,
+            libraries,
+            &mut reports,
+            &environment,
+        );
+    }
+    if reports.is_empty() { Ok(()) } else { Err(reports) }
+}
+
 pub fn analyze_symbols(
     file_id: FileID,
     param_location: FileLocation,
@@ -116,10 +151,13 @@ pub fn analyze_symbols(
     function_info: &FunctionInfo,
     template_info: &TemplateInfo,
     bus_info: &BusInfo,
+    libraries: &[PathBuf],
+    constant_names: &HashSet<String>,
 ) -> Result<(), ReportCollection> {
     let mut param_name_collision = false;
     let mut reports = ReportCollection::new();
     let mut environment = Environment::new();
+    environment.push(constant_names.clone());
     environment.push(Block::new());
     for param in params_names.iter() {
         let success = add_symbol_to_block(&mut environment, param);
@@ -142,6 +180,7 @@ pub fn analyze_symbols(
             function_info,
             template_info,
             bus_info,
+            libraries,
             &mut reports,
             &mut environment,
         );
@@ -162,6 +201,26 @@ fn symbol_in_environment(environment: &Environment, symbol: &String) -> bool {
     false
 }
 
+// Suggests the closest known name when the unknown one looks like a typo, and
+// otherwise the library file that defines it, if any
+fn add_suggestions<'a>(
+    report: &mut Report,
+    name: &str,
+    candidates: impl Iterator<Item = &'a String>,
+    libraries: &[PathBuf],
+) {
+    if let Option::Some(closest) = closest_name(name, candidates) {
+        report.add_note(format!("Did you mean {}?", closest));
+    } else if let Option::Some(file) = find_definition_in_libraries(name, libraries) {
+        report.add_note(format!(
+            "{} is defined in the library file {}, consider adding include \"{}\";",
+            name,
+            file.display(),
+            file.display()
+        ));
+    }
+}
+
 fn add_symbol_to_block(environment: &mut Environment, symbol: &String) -> bool {
     let last_block = environment.last_mut().unwrap();
     if last_block.contains(symbol) {
@@ -177,19 +236,20 @@ fn analyze_statement(
     function_info: &FunctionInfo,
     template_info: &TemplateInfo,
     bus_info: &BusInfo,
+    libraries: &[PathBuf],
     reports: &mut ReportCollection,
     environment: &mut Environment,
 ) {
     match stmt {
         Statement::MultSubstitution { .. } => unreachable!(),
         Statement::Return { value, .. } => {
-            analyze_expression(value, file_id, function_info, template_info, bus_info, reports, environment)
+            analyze_expression(value, file_id, function_info, template_info, bus_info, libraries, reports, environment)
         }
         Statement::UnderscoreSubstitution { rhe, .. } => {
-            analyze_expression(rhe, file_id, function_info, template_info, bus_info, reports, environment);
+            analyze_expression(rhe, file_id, function_info, template_info, bus_info, libraries, reports, environment);
         }
         Statement::Substitution { meta, var, access, rhe, .. } => {
-            analyze_expression(rhe, file_id, function_info, template_info, bus_info, reports, environment);
+            analyze_expression(rhe, file_id, function_info, template_info, bus_info, libraries, reports, environment);
             treat_variable(
                 meta,
                 var,
@@ -198,13 +258,14 @@ fn analyze_statement(
                 function_info,
                 template_info,
                 bus_info,
+                libraries,
                 reports,
                 environment,
             );
         }
         Statement::ConstraintEquality { lhe, rhe, .. } => {
-            analyze_expression(lhe, file_id, function_info, template_info, bus_info, reports, environment);
-            analyze_expression(rhe, file_id, function_info, template_info, bus_info, reports, environment);
+            analyze_expression(lhe, file_id, function_info, template_info, bus_info, libraries, reports, environment);
+            analyze_expression(rhe, file_id, function_info, template_info, bus_info, libraries, reports, environment);
         }
         Statement::InitializationBlock { initializations, .. } => {
             for initialization in initializations.iter() {
@@ -214,6 +275,7 @@ fn analyze_statement(
                     function_info,
                     template_info,
                     bus_info,
+                    libraries,
                     reports,
                     environment,
                 );
@@ -227,6 +289,7 @@ fn analyze_statement(
                     function_info,
                     template_info,
                     bus_info,
+                    libraries,
                     reports,
                     environment,
                 );
@@ -265,12 +328,12 @@ fn analyze_statement(
         Statement::LogCall { args, .. } => {
             for logarg in args {
                 if let LogArgument::LogExp(arg) = logarg {
-                    analyze_expression(arg, file_id, function_info, template_info, bus_info, reports, environment);
+                    analyze_expression(arg, file_id, function_info, template_info, bus_info, libraries, reports, environment);
                 }
             }
         }
-        Statement::Assert { arg, .. } => {
-            analyze_expression(arg, file_id, function_info, template_info, bus_info, reports, environment)
+        Statement::Assert { arg, .. } | Statement::StaticAssert { arg, .. } => {
+            analyze_expression(arg, file_id, function_info, template_info, bus_info, libraries, reports, environment)
         }
         Statement::Block { stmts, .. } => {
             environment.push(Block::new());
@@ -281,6 +344,7 @@ fn analyze_statement(
                     function_info,
                     template_info,
                     bus_info,
+                    libraries,
                     reports,
                     environment,
                 );
@@ -288,12 +352,12 @@ fn analyze_statement(
             environment.pop();
         }
         Statement::While { stmt, cond, .. } => {
-            analyze_expression(cond, file_id, function_info, template_info, bus_info, reports, environment);
-            analyze_statement(stmt, file_id, function_info, template_info, bus_info, reports, environment);
+            analyze_expression(cond, file_id, function_info, template_info, bus_info, libraries, reports, environment);
+            analyze_statement(stmt, file_id, function_info, template_info, bus_info, libraries, reports, environment);
         }
         Statement::IfThenElse { cond, if_case, else_case, .. } => {
-            analyze_expression(cond, file_id, function_info, template_info, bus_info, reports, environment);
-            analyze_statement(if_case, file_id, function_info, template_info, bus_info, reports, environment);
+            analyze_expression(cond, file_id, function_info, template_info, bus_info, libraries, reports, environment);
+            analyze_statement(if_case, file_id, function_info, template_info, bus_info, libraries, reports, environment);
             if let Option::Some(else_stmt) = else_case {
                 analyze_statement(
                     else_stmt,
@@ -301,6 +365,7 @@ fn analyze_statement(
                     function_info,
                     template_info,
                     bus_info,
+                    libraries,
                     reports,
                     environment,
                 );
@@ -317,6 +382,7 @@ fn treat_variable(
     function_info: &FunctionInfo,
     template_info: &TemplateInfo,
     bus_info: &BusInfo,
+    libraries: &[PathBuf],
     reports: &mut ReportCollection,
     environment: &Environment,
 ) {
@@ -327,12 +393,13 @@ fn treat_variable(
             file_id.clone(),
             format!("Using unknown symbol"),
         );
+        add_suggestions(&mut report, name, environment.iter().flatten(), &[]);
         reports.push(report);
     }
     for acc in access.iter() {
         match acc {
             Access::ArrayAccess(index) => {
-                analyze_expression(index, file_id, function_info, template_info, bus_info, reports, environment);
+                analyze_expression(index, file_id, function_info, template_info, bus_info, libraries, reports, environment);
             }
             Access::ComponentAccess(_) => {}
         }
@@ -345,28 +412,30 @@ fn analyze_expression(
     function_info: &FunctionInfo,
     template_info: &TemplateInfo,
     bus_info: &BusInfo,
+    libraries: &[PathBuf],
     reports: &mut ReportCollection,
     environment: &Environment,
 ) {
     match expression {
         Expression::InfixOp { lhe, rhe, .. } => {
-            analyze_expression(lhe, file_id, function_info, template_info, bus_info, reports, environment);
-            analyze_expression(rhe, file_id, function_info, template_info, bus_info, reports, environment);
+            analyze_expression(lhe, file_id, function_info, template_info, bus_info, libraries, reports, environment);
+            analyze_expression(rhe, file_id, function_info, template_info, bus_info, libraries, reports, environment);
         }
         Expression::PrefixOp { rhe, .. } => {
-            analyze_expression(rhe, file_id, function_info, template_info, bus_info, reports, environment)
+            analyze_expression(rhe, file_id, function_info, template_info, bus_info, libraries, reports, environment)
         }
         Expression::ParallelOp { rhe, .. } => {
-            analyze_expression(rhe, file_id, function_info, template_info, bus_info, reports, environment)
+            analyze_expression(rhe, file_id, function_info, template_info, bus_info, libraries, reports, environment)
         }
         Expression::InlineSwitchOp { cond, if_true, if_false, .. } => {
-            analyze_expression(cond, file_id, function_info, template_info, bus_info, reports, environment);
+            analyze_expression(cond, file_id, function_info, template_info, bus_info, libraries, reports, environment);
             analyze_expression(
                 if_true,
                 file_id,
                 function_info,
                 template_info,
                 bus_info,
+                libraries,
                 reports,
                 environment,
             );
@@ -376,6 +445,7 @@ fn analyze_expression(
                 function_info,
                 template_info,
                 bus_info,
+                libraries,
                 reports,
                 environment,
             );
@@ -388,6 +458,7 @@ fn analyze_expression(
             function_info,
             template_info,
             bus_info,
+            libraries,
             reports,
             environment,
         ),
@@ -400,6 +471,8 @@ fn analyze_expression(
                     file_id.clone(),
                     format!("Calling unknown symbol"),
                 );
+                let candidates = function_info.keys().chain(template_info.keys());
+                add_suggestions(&mut report, id, candidates, libraries);
                 reports.push(report);
                 return;
             }
@@ -428,6 +501,7 @@ fn analyze_expression(
                     function_info,
                     template_info,
                     bus_info,
+                    libraries,
                     reports,
                     environment,
                 );
@@ -441,6 +515,7 @@ fn analyze_expression(
                     function_info,
                     template_info,
                     bus_info,
+                    libraries,
                     reports,
                     environment,
                 );
@@ -453,6 +528,7 @@ fn analyze_expression(
                 function_info,
                 template_info,
                 bus_info,
+                libraries,
                 reports,
                 environment,
             );
@@ -462,6 +538,7 @@ fn analyze_expression(
                 function_info,
                 template_info,
                 bus_info,
+                libraries,
                 reports,
                 environment,
             );
@@ -476,6 +553,7 @@ fn analyze_expression(
                     file_id.clone(),
                     format!("Calling unknown symbol"),
                 );
+                add_suggestions(&mut report, id, bus_info.keys(), libraries);
                 reports.push(report);
                 return;
             }
@@ -500,6 +578,7 @@ fn analyze_expression(
                     function_info,
                     template_info,
                     bus_info,
+                    libraries,
                     reports,
                     environment,
                 );
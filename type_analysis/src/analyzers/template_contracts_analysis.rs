@@ -0,0 +1,106 @@
+use program_structure::ast::*;
+use program_structure::error_code::ReportCode;
+use program_structure::error_definition::{Report, ReportCollection};
+use program_structure::program_archive::ProgramArchive;
+use program_structure::template_data::TemplateData;
+
+pub fn check_template_contracts(program_archive: &ProgramArchive) -> Result<(), ReportCollection> {
+    let mut reports = ReportCollection::new();
+    for template in program_archive.get_templates().values() {
+        for clause in template.get_requires() {
+            analyse_clause(clause, template, program_archive, &mut reports);
+        }
+    }
+    if reports.is_empty() {
+        Result::Ok(())
+    } else {
+        Result::Err(reports)
+    }
+}
+
+// The conditions of a contract are evaluated before the body of the template is executed:
+// they can only depend on the parameters of the template, global constants, function calls
+// and the tags declared by its inputs.
+fn analyse_clause(
+    expr: &Expression,
+    template: &TemplateData,
+    program_archive: &ProgramArchive,
+    reports: &mut ReportCollection,
+) {
+    use Expression::*;
+    match expr {
+        InfixOp { lhe, rhe, .. } => {
+            analyse_clause(lhe, template, program_archive, reports);
+            analyse_clause(rhe, template, program_archive, reports);
+        }
+        PrefixOp { rhe, .. } => analyse_clause(rhe, template, program_archive, reports),
+        InlineSwitchOp { cond, if_true, if_false, .. } => {
+            analyse_clause(cond, template, program_archive, reports);
+            analyse_clause(if_true, template, program_archive, reports);
+            analyse_clause(if_false, template, program_archive, reports);
+        }
+        Variable { meta, name, access } => {
+            if let Option::Some(input) = template.get_input_info(name) {
+                match access.as_slice() {
+                    [Access::ComponentAccess(tag)] if input.contains_tag(tag) => {}
+                    [Access::ComponentAccess(tag)] => invalid_clause_error(
+                        meta,
+                        &format!("The input {} does not declare the tag {}", name, tag),
+                        reports,
+                    ),
+                    _ => invalid_clause_error(
+                        meta,
+                        "Inputs can only be used in contracts to access their tags",
+                        reports,
+                    ),
+                }
+            } else if template.get_name_of_params().contains(name) {
+                for acc in access {
+                    match acc {
+                        Access::ArrayAccess(index) => {
+                            analyse_clause(index, template, program_archive, reports)
+                        }
+                        Access::ComponentAccess(_) => invalid_clause_error(
+                            meta,
+                            "Parameters can only be accessed as arrays",
+                            reports,
+                        ),
+                    }
+                }
+            } else if !program_archive.get_constant_names().contains(name) {
+                invalid_clause_error(
+                    meta,
+                    "Contracts can only depend on parameters, global constants and tags of the inputs",
+                    reports,
+                );
+            }
+        }
+        Number(..) => {}
+        Call { meta, id, args } => {
+            if !program_archive.contains_function(id) {
+                invalid_clause_error(meta, "Only functions can be called in contracts", reports);
+            }
+            for arg in args {
+                analyse_clause(arg, template, program_archive, reports);
+            }
+        }
+        ParallelOp { meta, .. } | AnonymousComp { meta, .. } => {
+            invalid_clause_error(meta, "Components can not be used in contracts", reports);
+        }
+        BusCall { meta, .. } => {
+            invalid_clause_error(meta, "Buses can not be used in contracts", reports);
+        }
+        ArrayInLine { meta, .. } | UniformArray { meta, .. } | Tuple { meta, .. } => {
+            invalid_clause_error(meta, "Contracts must be single arithmetic expressions", reports);
+        }
+    }
+}
+
+fn invalid_clause_error(meta: &Meta, message: &str, reports: &mut ReportCollection) {
+    let mut report = Report::error(
+        "Invalid template contract".to_string(),
+        ReportCode::InvalidTemplateContract,
+    );
+    report.add_primary(meta.file_location(), meta.get_file_id(), message.to_string());
+    reports.push(report);
+}
@@ -7,6 +7,7 @@ use program_structure::error_code::ReportCode;
 use program_structure::error_definition::{Report, ReportCollection};
 use program_structure::file_definition::{generate_file_location, FileID};
 use program_structure::program_archive::ProgramArchive;
+use program_structure::suggestions::closest_name;
 use program_structure::wire_data::WireType;
 use std::collections::HashSet;
 
@@ -23,9 +24,12 @@ struct AnalysisInformation {
     file_id: FileID,
     reached: HashSet<String>,
     reports: ReportCollection,
+    warnings: ReportCollection,
     registered_calls: CallRegister,
     environment: TypingEnvironment,
     return_type: Option<ArithmeticType>,
+    // Definitions with errors found by previous phases, their bodies are not typed
+    skipped: HashSet<String>,
 }
 
 struct FoldedType {
@@ -76,19 +80,45 @@ impl FoldedType {
     }
 }
 
+// The errors found inside the templates and functions do not stop the analysis of the
+// rest of the program, so they are returned together with what was reached
 pub struct OutInfo {
     pub reached: HashSet<String>,
+    pub warnings: ReportCollection,
+    pub errors: ReportCollection,
 }
 
-pub fn type_check(program_archive: &ProgramArchive) -> Result<OutInfo, ReportCollection> {
+pub fn type_check(
+    program_archive: &ProgramArchive,
+    skipped: &HashSet<String>,
+) -> Result<OutInfo, ReportCollection> {
     let mut analysis_information = AnalysisInformation {
+        skipped: skipped.clone(),
         reached: HashSet::new(),
         file_id: *program_archive.get_file_id_main(),
         reports: ReportCollection::new(),
+        warnings: ReportCollection::new(),
         registered_calls: CallRegister::new(),
         environment: TypingEnvironment::new(),
         return_type: Option::None,
     };
+    for name in program_archive.get_constant_names() {
+        analysis_information.environment.add_variable(name, 0);
+    }
+    let mut constant_names: Vec<&String> = program_archive.get_constant_names().iter().collect();
+    constant_names.sort();
+    for name in constant_names {
+        let value = program_archive.get_constant_data(name).get_value();
+        let value_type = type_expression(value, program_archive, &mut analysis_information);
+        if let Result::Ok(value_type) = value_type {
+            if value_type.is_template() || value_type.is_bus() || value_type.dim() > 0 {
+                add_report(ReportCode::InvalidGlobalConstant, value.get_meta(), &mut analysis_information.reports);
+            }
+        }
+    }
+    if !analysis_information.reports.is_empty() {
+        return Result::Err(analysis_information.reports);
+    }
     let initial_expression = program_archive.get_main_expression();
     let type_analysis_response =
         type_expression(initial_expression, program_archive, &mut analysis_information);
@@ -97,13 +127,43 @@ pub fn type_check(program_archive: &ProgramArchive) -> Result<OutInfo, ReportCol
     }
 
     check_main_has_tags(initial_expression, program_archive, &mut analysis_information.reports);
+    type_generic_templates(program_archive, &mut analysis_information);
 
 
-    if analysis_information.reports.is_empty() {
-        Result::Ok(OutInfo { reached: analysis_information.reached })
-    } else {
-        Result::Err(analysis_information.reports)
+    Result::Ok(OutInfo {
+        reached: analysis_information.reached,
+        warnings: analysis_information.warnings,
+        errors: analysis_information.reports,
+    })
+}
+
+// Generic templates are only instantiated through their specializations, so they are typed
+// on their own, with their template parameters replaced by their interfaces and their
+// parameters taken as single values. The templates they use are not reached from main.
+fn type_generic_templates(program_archive: &ProgramArchive, analysis_information: &mut AnalysisInformation) {
+    let reached = analysis_information.reached.clone();
+    let mut generics: Vec<&String> = program_archive
+        .get_templates()
+        .iter()
+        .filter(|(_, data)| data.is_generic())
+        .map(|(name, _)| name)
+        .collect();
+    generics.sort();
+    for name in generics {
+        let data = program_archive.get_template_data(name);
+        let args_dims = vec![0; data.get_num_of_params()];
+        let mut environment = TypingEnvironment::new();
+        for constant in program_archive.get_constant_names() {
+            environment.add_variable(constant, 0);
+        }
+        for param in data.get_name_of_params() {
+            environment.add_variable(param, 0);
+        }
+        analysis_information.environment = environment;
+        analysis_information.file_id = data.get_file_id();
+        type_template(name, &args_dims, analysis_information, program_archive);
     }
+    analysis_information.reached = reached;
 }
 
 fn check_main_has_tags(initial_expression: &Expression, program_archive: &ProgramArchive, reports: &mut ReportCollection) {    if let Call { id, .. } = initial_expression {
@@ -483,6 +543,9 @@ fn type_statement(
             }
         }
         LogCall { args, meta } => {
+            if let Option::Some(LogArgument::LogStr(string)) = args.first() {
+                check_log_format(string, &args[1..], meta, &mut analysis_information.warnings);
+            }
             for arglog in args {
                 if let LogArgument::LogExp(arg) = arglog{
                     let arg_response = type_expression(&arg, program_archive, analysis_information);
@@ -513,7 +576,7 @@ fn type_statement(
                 }
             }
         }
-        Assert { arg, meta } => {
+        Assert { arg, meta } | StaticAssert { arg, meta, .. } => {
             let arg_response = type_expression(arg, program_archive, analysis_information);
             let arg_type = if let Result::Ok(t) = arg_response {
                 t
@@ -1110,7 +1173,7 @@ fn check_if_it_is_a_tag(
         } else if let Some(wire_data) = output {
             (wire_data.get_type(), wire_data.get_tags())
         } else {
-            return add_report_and_end(ReportCode::InvalidSignalAccess, meta, reports);
+            return add_signal_access_report(accessed_element, &template_name, meta, reports, program_archive);
         };
         (kind, atags.clone())
     } 
@@ -1242,7 +1305,7 @@ fn apply_access_to_symbol(
                 (Option::Some(wire_data), _) | (_, Option::Some(wire_data)) =>
                     (wire_data.get_dimension(), wire_data.get_type(), wire_data.get_tags()),
                 _ => {
-                    return add_report_and_end(ReportCode::InvalidSignalAccess, meta, reports);
+                    return add_signal_access_report(accessed_element, &template_name, meta, reports, program_archive);
                 }
             };
             
@@ -1393,6 +1456,9 @@ fn prepare_environment_for_call(
         add_report_and_end(error_code, meta, reports)?;
     }
     let mut environment = TypingEnvironment::new();
+    for name in program_archive.get_constant_names() {
+        environment.add_variable(name, 0);
+    }
     for (name, dim) in args_names.iter().zip(args_dims.iter()) {
         environment.add_variable(name, *dim);
     }
@@ -1406,10 +1472,21 @@ fn type_template(
     program_archive: &ProgramArchive,
 ) -> String {
     debug_assert!(program_archive.contains_template(call_id));
+    if analysis_information.skipped.contains(call_id) {
+        return call_id.to_string();
+    }
     if analysis_information.registered_calls.get_instance(call_id, args_dims).is_none() {
         analysis_information.registered_calls.add_instance(call_id, args_dims.to_vec(), 0);
         let stmts = program_archive.get_template_data(call_id).get_body_as_vec();
         treat_sequence_of_statements(stmts, program_archive, analysis_information);
+        for clause in program_archive.get_template_data(call_id).get_requires() {
+            let clause_type = type_expression(clause, program_archive, analysis_information);
+            if let Result::Ok(clause_type) = clause_type {
+                if clause_type.is_template() || clause_type.is_bus() || clause_type.dim() > 0 {
+                    add_report(ReportCode::InvalidTemplateContract, clause.get_meta(), &mut analysis_information.reports);
+                }
+            }
+        }
     }
     call_id.to_string()
 }
@@ -1417,6 +1494,9 @@ fn type_template(
 
 fn type_bus(id: &str, args_dims: &[ArithmeticType], analysis_information: &mut AnalysisInformation, program_archive: &ProgramArchive) -> Result<FoldedType,()> {
     debug_assert!(program_archive.contains_bus(id));
+    if analysis_information.skipped.contains(id) {
+        return Result::Ok(FoldedType::bus(id,0));
+    }
     if analysis_information.registered_calls.get_instance(id, args_dims).is_none() {
         analysis_information.registered_calls.add_instance(id, args_dims.to_vec(), 0);
         let stmts = program_archive.get_bus_data(id).get_body_as_vec();
@@ -1433,12 +1513,21 @@ fn type_function(
     program_archive: &ProgramArchive,
 ) -> Result<ArithmeticType, ()> {
     debug_assert!(program_archive.contains_function(call_id));
+    if analysis_information.skipped.contains(call_id) {
+        // The errors of the function have already been reported
+        return Result::Err(());
+    }
     if let Option::Some(instance) =
         analysis_information.registered_calls.get_instance(call_id, args_dims)
     {
         return Result::Ok(*instance.returns());
     }
-    let mut given_type = type_given_function(call_id, program_archive.get_functions(), args_dims);
+    let mut given_type = type_given_function(
+        call_id,
+        program_archive.get_functions(),
+        args_dims,
+        program_archive.get_constant_names(),
+    );
     if let Option::Some(raw) = &given_type {
         analysis_information.registered_calls.add_instance(call_id, args_dims.to_vec(), *raw);
     } else {
@@ -1458,6 +1547,24 @@ fn type_function(
 }
 
 //************************************************* Report handling *************************************************
+// A first string with valid placeholders that is printed literally because they do
+// not match the values is probably a mistake
+fn check_log_format(string: &str, values: &[LogArgument], meta: &Meta, reports: &mut ReportCollection) {
+    let only_values = values.iter().all(|value| matches!(value, LogArgument::LogExp(_)));
+    if let Option::Some(placeholders) = log_format_placeholders(string) {
+        if only_values && placeholders != values.len() {
+            let message = format!(
+                "The string has {} placeholders but {} values are given, so it is printed literally",
+                placeholders,
+                values.len()
+            );
+            let mut report = Report::warning("Log string is not used as a format string".to_string(), ReportCode::InvalidLogFormat);
+            report.add_primary(meta.file_location(), meta.get_file_id(), message);
+            reports.push(report);
+        }
+    }
+}
+
 fn add_report_and_end<Ok>(
     error_code: ReportCode,
     meta: &Meta,
@@ -1467,6 +1574,22 @@ fn add_report_and_end<Ok>(
     Result::Err(())
 }
 
+fn add_signal_access_report<Ok>(
+    accessed: &str,
+    template_name: &str,
+    meta: &Meta,
+    reports: &mut ReportCollection,
+    program_archive: &ProgramArchive,
+) -> Result<Ok, ()> {
+    add_report(ReportCode::InvalidSignalAccess, meta, reports);
+    let template = program_archive.get_template_data(template_name);
+    let wires = template.get_inputs().keys().chain(template.get_outputs().keys());
+    if let (Option::Some(closest), Option::Some(report)) = (closest_name(accessed, wires), reports.last_mut()) {
+        report.add_note(format!("Did you mean {}?", closest));
+    }
+    Result::Err(())
+}
+
 fn add_report(error_code: ReportCode, meta: &Meta, reports: &mut ReportCollection) {
     use ReportCode::*;
     let file_id = meta.get_file_id();
@@ -1558,6 +1681,8 @@ fn add_report(error_code: ReportCode, meta: &Meta, reports: &mut ReportCollectio
         MustBeBus => "Expected to be a bus".to_string(),
         InvalidSignalAccessInBus => format!("Field not defined in bus"),
         IllegalMainExpression => "Invalid main component: the main component should be a template, not a function call or expression".to_string(),
+        InvalidGlobalConstant => "Global constants must be single arithmetic expressions".to_string(),
+        InvalidTemplateContract => "The conditions of a contract must be single arithmetic expressions".to_string(),
         e => panic!("Unimplemented error code: {}", e),
     };
     report.add_primary(location, file_id, message);
@@ -11,9 +11,12 @@ pub fn type_given_function(
     function_name: &str,
     function_info: &HashMap<String, FunctionData>,
     params_types: &[Type],
+    global_constants: &HashSet<String>,
 ) -> Option<Type> {
     let mut explored_functions = NodeRegister::new();
-    start(function_name, &mut explored_functions, function_info, params_types)
+    // Global constants are always single arithmetic values
+    let constants_block: Block = global_constants.iter().map(|c| (c.clone(), 0)).collect();
+    start(function_name, &mut explored_functions, function_info, params_types, &constants_block)
 }
 
 fn add_variable_to_environment(
@@ -47,9 +50,11 @@ fn start(
     explored_functions: &mut NodeRegister,
     function_info: &HashMap<String, FunctionData>,
     params_types: &[Type],
+    constants_block: &Block,
 ) -> Option<Type> {
     let function_data = function_info.get(function_name).unwrap();
     let mut environment = Environment::new();
+    environment.push(constants_block.clone());
     let mut initial_block = Block::new();
     explored_functions.insert(function_name.to_string());
     for (name, t) in function_data.get_name_of_params().iter().zip(params_types.iter()) {
@@ -314,7 +319,7 @@ fn look_for_type_in_expression(
                 )?;
                 params_types.push(arg_type);
             }
-            let has_type = start(id, explored_functions, function_info, &params_types);
+            let has_type = start(id, explored_functions, function_info, &params_types, &environment[0]);
             has_type
         }
         _ => {unreachable!("Anonymous calls should not be reachable at this point."); }
@@ -0,0 +1,188 @@
+use program_structure::ast::*;
+use program_structure::error_code::ReportCode;
+use program_structure::error_definition::{Report, ReportCollection};
+use program_structure::template_data::TemplateData;
+use num_bigint::BigInt;
+use std::collections::HashSet;
+
+// A signal is referred to by its name followed by the accesses to its positions and to
+// the fields of its buses or components. Only the indexes given by a number are known at
+// this point: any other index, as the variable of a loop, may refer to every position, so
+// bits[i] <-- ... is covered by a constraint over bits[j] but x[0] <-- ... is not covered
+// by a constraint over x[1].
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Selector {
+    Field(String),
+    Index(Option<BigInt>),
+}
+type SignalPath = Vec<Selector>;
+
+// Reports the signals assigned with <-- that do not appear in any constraint (===, <== or
+// _ <==) of the same template. Such signals are not restricted by the circuit, so any
+// value can be given to them by a malicious prover.
+pub fn unconstrained_assignment_analysis(template_data: &TemplateData) -> ReportCollection {
+    let mut assignments = Vec::new();
+    let mut constrained = HashSet::new();
+    collect_statement(template_data.get_body(), &mut assignments, &mut constrained);
+    let mut warnings = ReportCollection::new();
+    for (meta, path) in assignments {
+        if !is_covered(&path, &constrained) {
+            let mut report = Report::warning(
+                "Signal assigned with <-- is not constrained".to_string(),
+                ReportCode::SignalAssignmentNotConstrained,
+            );
+            report.add_primary(
+                meta.location.clone(),
+                meta.get_file_id(),
+                format!(
+                    "The signal {} does not appear in any constraint of the template {}",
+                    display_path(&path),
+                    template_data.get_name()
+                ),
+            );
+            report.add_note(
+                "Add a constraint that restricts its value, or use <== if the assigned expression is quadratic".to_string(),
+            );
+            warnings.push(report);
+        }
+    }
+    warnings
+}
+
+// A constraint over a bus or an array covers the assignments to its fields or positions,
+// and a constraint over one of them covers the assignments to the whole bus or array
+fn is_covered(path: &SignalPath, constrained: &HashSet<SignalPath>) -> bool {
+    constrained.iter().any(|c| may_overlap(path, c))
+}
+
+fn may_overlap(first: &SignalPath, second: &SignalPath) -> bool {
+    first.iter().zip(second.iter()).all(|selectors| match selectors {
+        (Selector::Field(a), Selector::Field(b)) => a == b,
+        (Selector::Index(Option::Some(a)), Selector::Index(Option::Some(b))) => a == b,
+        (Selector::Index(_), Selector::Index(_)) => true,
+        _ => false,
+    })
+}
+
+fn display_path(path: &SignalPath) -> String {
+    let mut name = String::new();
+    for selector in path {
+        match selector {
+            Selector::Field(field) if name.is_empty() => name.push_str(field),
+            Selector::Field(field) => name.push_str(&format!(".{}", field)),
+            Selector::Index(Option::Some(index)) => name.push_str(&format!("[{}]", index)),
+            Selector::Index(Option::None) => name.push_str("[_]"),
+        }
+    }
+    name
+}
+
+fn collect_statement(
+    stmt: &Statement,
+    assignments: &mut Vec<(Meta, SignalPath)>,
+    constrained: &mut HashSet<SignalPath>,
+) {
+    use Statement::*;
+    match stmt {
+        IfThenElse { if_case, else_case, .. } => {
+            collect_statement(if_case, assignments, constrained);
+            if let Option::Some(else_case) = else_case {
+                collect_statement(else_case, assignments, constrained);
+            }
+        }
+        While { stmt, .. } => collect_statement(stmt, assignments, constrained),
+        Block { stmts, .. } => {
+            for stmt in stmts {
+                collect_statement(stmt, assignments, constrained);
+            }
+        }
+        InitializationBlock { initializations, .. } => {
+            for init in initializations {
+                collect_statement(init, assignments, constrained);
+            }
+        }
+        Substitution { meta, var, access, op, rhe } => match op {
+            AssignOp::AssignSignal => assignments.push((meta.clone(), signal_path(var, access))),
+            AssignOp::AssignConstraintSignal => {
+                constrained.insert(signal_path(var, access));
+                collect_signals(rhe, constrained);
+            }
+            AssignOp::AssignVar => {}
+        },
+        MultSubstitution { meta, lhe, op, rhe } => match op {
+            AssignOp::AssignSignal => {
+                let mut targets = HashSet::new();
+                collect_signals(lhe, &mut targets);
+                for path in targets {
+                    assignments.push((meta.clone(), path));
+                }
+            }
+            AssignOp::AssignConstraintSignal => {
+                collect_signals(lhe, constrained);
+                collect_signals(rhe, constrained);
+            }
+            AssignOp::AssignVar => {}
+        },
+        UnderscoreSubstitution { op: AssignOp::AssignConstraintSignal, rhe, .. } => {
+            collect_signals(rhe, constrained);
+        }
+        ConstraintEquality { lhe, rhe, .. } => {
+            collect_signals(lhe, constrained);
+            collect_signals(rhe, constrained);
+        }
+        _ => {}
+    }
+}
+
+fn signal_path(name: &str, access: &[Access]) -> SignalPath {
+    let mut path = vec![Selector::Field(name.to_string())];
+    for acc in access {
+        match acc {
+            Access::ComponentAccess(field) => path.push(Selector::Field(field.clone())),
+            Access::ArrayAccess(Expression::Number(_, index)) => path.push(Selector::Index(Option::Some(index.clone()))),
+            Access::ArrayAccess(_) => path.push(Selector::Index(Option::None)),
+        }
+    }
+    path
+}
+
+// The expressions used as array indexes are not collected, they do not constrain
+// the signals that appear in them
+fn collect_signals(expr: &Expression, signals: &mut HashSet<SignalPath>) {
+    use Expression::*;
+    match expr {
+        InfixOp { lhe, rhe, .. } => {
+            collect_signals(lhe, signals);
+            collect_signals(rhe, signals);
+        }
+        PrefixOp { rhe, .. } | ParallelOp { rhe, .. } => collect_signals(rhe, signals),
+        InlineSwitchOp { cond, if_true, if_false, .. } => {
+            collect_signals(cond, signals);
+            collect_signals(if_true, signals);
+            collect_signals(if_false, signals);
+        }
+        Variable { name, access, .. } => {
+            signals.insert(signal_path(name, access));
+        }
+        Number(..) => {}
+        Call { args, .. } | BusCall { args, .. } => {
+            for arg in args {
+                collect_signals(arg, signals);
+            }
+        }
+        AnonymousComp { params, signals: inputs, .. } => {
+            for arg in params.iter().chain(inputs.iter()) {
+                collect_signals(arg, signals);
+            }
+        }
+        ArrayInLine { values, .. } | Tuple { values, .. } => {
+            for value in values {
+                collect_signals(value, signals);
+            }
+        }
+        UniformArray { value, dimension, .. } => {
+            collect_signals(value, signals);
+            collect_signals(dimension, signals);
+        }
+    }
+}
@@ -44,6 +44,9 @@ pub fn unknown_known_analysis(
 ) -> Result<(), ReportCollection> {
     debug_assert!(Tag::Known < Tag::Unknown);
     let mut environment = Environment::new();
+    for constant in program_archive.get_constant_names() {
+        environment.add_variable(constant, (Tag::Known, false));
+    }
     let (body, file_id) = if program_archive.contains_template(name) {
         let template_data = program_archive.get_template_data(name);
         let template_body = template_data.get_body();
@@ -387,6 +390,11 @@ fn analyze(stmt: &Statement, entry_information: EntryInformation) -> ExitInforma
             tags_modified = tags;
             signals_declared = ns;
         }
+        StaticAssert { arg, .. } => {
+            if tag(arg, &environment) == Unknown {
+                add_report(ReportCode::NonConstantStaticAssert, arg.get_meta(), file_id, &mut reports);
+            }
+        }
         _ => {}
     }
     ExitInformation { 
@@ -558,6 +566,7 @@ fn add_report(
         UnreachableConstraints => "There are constraints depending on the value of the condition and it can be unknown during the constraint generation phase".to_string(),
         UnreachableTags => "There are tag assignments depending on the value of the condition and it can be unknown during the constraint generation phase".to_string(),
         UnreachableSignals => "There are signal, bus or component declarations depending on the value of the condition and it can be unknown during the constraint generation phase".to_string(),
+        NonConstantStaticAssert => "The condition of a static assertion must be known during the constraint generation phase, it cannot depend on signals".to_string(),
         _ => panic!("Unimplemented error code")
     };
     report.add_primary(location, file_id, message);
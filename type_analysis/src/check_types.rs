@@ -1,59 +1,90 @@
 use super::analyzers::*;
 use super::decorators::*;
-use program_structure::error_definition::ReportCollection;
+use program_structure::ast::{Access, Expression, LogArgument, Statement};
+use program_structure::error_definition::{Report, ReportCollection};
 use program_structure::program_archive::ProgramArchive;
+use std::collections::HashSet;
 
+// The specializations of a generic template share its body, so the problems found in the
+// body are reported once
 pub fn check_types(
     program_archive: &mut ProgramArchive,
+) -> Result<ReportCollection, ReportCollection> {
+    match check_program(program_archive) {
+        Result::Ok(warnings) => Result::Ok(without_repetitions(warnings)),
+        Result::Err(errors) => Result::Err(without_repetitions(errors)),
+    }
+}
+
+fn without_repetitions(reports: ReportCollection) -> ReportCollection {
+    let mut seen = HashSet::new();
+    reports.into_iter().filter(|report| seen.insert(report_key(report))).collect()
+}
+
+// Code, message and primary labels of a report
+type ReportKey = (String, String, Vec<(usize, usize, usize, String)>);
+
+fn report_key(report: &Report) -> ReportKey {
+    let labels = report
+        .get_primary()
+        .iter()
+        .map(|label| (label.file_id, label.range.start, label.range.end, label.message.clone()))
+        .collect();
+    (report.get_code().to_string(), report.get_message().clone(), labels)
+}
+
+fn check_program(
+    program_archive: &mut ProgramArchive,
 ) -> Result<ReportCollection, ReportCollection> {
     let mut errors = ReportCollection::new();
     let mut warnings = ReportCollection::new();
+    let mut failed = FailedDefinitions::default();
 
     // Structural analyses
     program_level_analyses(program_archive, &mut errors);
-    if !errors.is_empty() {
-        return Result::Err(errors);
-    }
+    failed.update(program_archive, &errors);
 
-    template_level_analyses(program_archive, &mut errors);
-    if !errors.is_empty() {
-        return Result::Err(errors);
-    }
-
-    function_level_analyses(program_archive, &mut errors);
-    if !errors.is_empty() {
-        return Result::Err(errors);
-    }
-
-    bus_level_analyses(program_archive, &mut errors);
-    if !errors.is_empty() {
-        return Result::Err(errors);
-    }
+    template_level_analyses(program_archive, &failed, &mut errors);
+    function_level_analyses(program_archive, &failed, &mut errors);
+    bus_level_analyses(program_archive, &failed, &mut errors);
+    failed.update(program_archive, &errors);
 
     // Decorators
-    template_level_decorators(program_archive, &mut errors);
-    if !errors.is_empty() {
-        return Result::Err(errors);
-    }
+    template_level_decorators(program_archive, &failed, &mut errors);
+    function_level_decorators(program_archive, &failed, &mut errors);
+    bus_level_decorators(program_archive, &failed, &mut errors);
+    failed.update(program_archive, &errors);
 
-    function_level_decorators(program_archive, &mut errors);
-    if !errors.is_empty() {
-        return Result::Err(errors);
-    }
-
-    bus_level_decorators(program_archive, &mut errors);
-    if !errors.is_empty() {
+    // Type analysis
+    if failed.program {
         return Result::Err(errors);
     }
-
-    // Type analysis
-    let typing_result = type_check(program_archive);
+    let skipped = failed.with_dependent_functions(program_archive);
+    let typing_result = type_check(program_archive, &skipped);
     match typing_result {
+        // The main component or the global constants can not be typed
         Err(mut type_reports) => {
             errors.append(&mut type_reports);
             return Result::Err(errors);
         }
-        Ok(info) => {
+        Ok(mut info) if !errors.is_empty() || !info.errors.is_empty() => {
+            // What is reached from main is not known when some definition fails, so the
+            // semantic analyses check every definition without errors
+            errors.append(&mut info.errors);
+            failed.update(program_archive, &errors);
+            let mut skipped = failed.with_dependent_functions(program_archive);
+            for (name, data) in program_archive.get_templates() {
+                // Their specializations share their bodies
+                if data.is_generic() {
+                    skipped.insert(name.clone());
+                }
+            }
+            semantic_analyses(program_archive, &skipped, &mut errors, &mut warnings);
+            return Result::Err(errors);
+        }
+        Ok(mut info) => {
+            warnings.append(&mut info.warnings);
+            warnings.append(&mut unused_definitions_analysis(program_archive, &info.reached));
             for name in program_archive.get_function_names().clone() {
                 if !info.reached.contains(&name) {
                     program_archive.remove_function(&name)
@@ -73,7 +104,7 @@ pub fn check_types(
     }
 
     // Semantics analyses
-    semantic_analyses(program_archive, &mut errors, &mut warnings);
+    semantic_analyses(program_archive, &HashSet::new(), &mut errors, &mut warnings);
 
     if !errors.is_empty() {
         Result::Err(errors)
@@ -82,6 +113,134 @@ pub fn check_types(
     }
 }
 
+// Definitions in which some error has been found. The later phases do not analyze
+// them, but keep checking the rest, so that the independent errors are all reported
+// in the same run.
+#[derive(Default)]
+struct FailedDefinitions {
+    names: HashSet<String>,
+    // Whether some error is not inside any definition (e.g. in the main component or
+    // in a global constant), in which case the program can not be type checked
+    program: bool,
+}
+
+impl FailedDefinitions {
+    fn update(&mut self, program_archive: &ProgramArchive, errors: &ReportCollection) {
+        let mut definitions = Vec::new();
+        for (name, data) in program_archive.get_templates() {
+            let end = data.get_body().get_meta().end;
+            definitions.push((name, data.get_file_id(), data.get_param_location().start..end));
+        }
+        for (name, data) in program_archive.get_functions() {
+            let end = data.get_body().get_meta().end;
+            definitions.push((name, data.get_file_id(), data.get_param_location().start..end));
+        }
+        for (name, data) in program_archive.get_buses() {
+            let end = data.get_body().get_meta().end;
+            definitions.push((name, data.get_file_id(), data.get_param_location().start..end));
+        }
+        for report in errors.iter().filter(|report| report.is_error()) {
+            let owner = report.get_primary().first().and_then(|label| {
+                definitions.iter().find(|(_, file_id, location)| {
+                    label.file_id == *file_id
+                        && location.start <= label.range.start
+                        && label.range.end <= location.end
+                })
+            });
+            match owner {
+                Option::Some((name, _, _)) => {
+                    self.names.insert(name.to_string());
+                }
+                Option::None => self.program = true,
+            }
+        }
+    }
+
+    // The type of a function is inferred from the functions it calls, so the ones
+    // that call a failed function can not be typed either
+    fn with_dependent_functions(&self, program_archive: &ProgramArchive) -> HashSet<String> {
+        let mut skipped = self.names.clone();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (name, data) in program_archive.get_functions() {
+                if !skipped.contains(name) && calls_any(data.get_body(), &skipped) {
+                    skipped.insert(name.clone());
+                    changed = true;
+                }
+            }
+        }
+        skipped
+    }
+}
+
+fn calls_any(stmt: &Statement, names: &HashSet<String>) -> bool {
+    use Statement::*;
+    match stmt {
+        IfThenElse { cond, if_case, else_case, .. } => {
+            expression_calls_any(cond, names)
+                || calls_any(if_case, names)
+                || else_case.as_ref().is_some_and(|else_case| calls_any(else_case, names))
+        }
+        While { cond, stmt, .. } => expression_calls_any(cond, names) || calls_any(stmt, names),
+        Block { stmts, .. } => stmts.iter().any(|stmt| calls_any(stmt, names)),
+        InitializationBlock { initializations, .. } => {
+            initializations.iter().any(|stmt| calls_any(stmt, names))
+        }
+        Declaration { dimensions, .. } => {
+            dimensions.iter().any(|dimension| expression_calls_any(dimension, names))
+        }
+        Substitution { access, rhe, .. } => {
+            access_calls_any(access, names) || expression_calls_any(rhe, names)
+        }
+        MultSubstitution { lhe, rhe, .. } | ConstraintEquality { lhe, rhe, .. } => {
+            expression_calls_any(lhe, names) || expression_calls_any(rhe, names)
+        }
+        UnderscoreSubstitution { rhe, .. } => expression_calls_any(rhe, names),
+        Return { value, .. } => expression_calls_any(value, names),
+        LogCall { args, .. } => args.iter().any(|arg| match arg {
+            LogArgument::LogExp(exp) => expression_calls_any(exp, names),
+            _ => false,
+        }),
+        Assert { arg, .. } | StaticAssert { arg, .. } => expression_calls_any(arg, names),
+    }
+}
+
+fn access_calls_any(access: &[Access], names: &HashSet<String>) -> bool {
+    access.iter().any(|acc| match acc {
+        Access::ArrayAccess(index) => expression_calls_any(index, names),
+        _ => false,
+    })
+}
+
+fn expression_calls_any(expr: &Expression, names: &HashSet<String>) -> bool {
+    use Expression::*;
+    match expr {
+        InfixOp { lhe, rhe, .. } => expression_calls_any(lhe, names) || expression_calls_any(rhe, names),
+        PrefixOp { rhe, .. } | ParallelOp { rhe, .. } => expression_calls_any(rhe, names),
+        InlineSwitchOp { cond, if_true, if_false, .. } => {
+            expression_calls_any(cond, names)
+                || expression_calls_any(if_true, names)
+                || expression_calls_any(if_false, names)
+        }
+        Variable { access, .. } => access_calls_any(access, names),
+        Number(..) => false,
+        Call { id, args, .. } => {
+            names.contains(id) || args.iter().any(|arg| expression_calls_any(arg, names))
+        }
+        BusCall { args, .. } => args.iter().any(|arg| expression_calls_any(arg, names)),
+        AnonymousComp { params, signals, .. } => {
+            params.iter().chain(signals.iter()).any(|arg| expression_calls_any(arg, names))
+        }
+        ArrayInLine { values, .. } | Tuple { values, .. } => {
+            values.iter().any(|value| expression_calls_any(value, names))
+        }
+        UniformArray { value, dimension, .. } => {
+            expression_calls_any(value, names) || expression_calls_any(dimension, names)
+        }
+    }
+}
+
 fn program_level_analyses(program_archive: &ProgramArchive, reports: &mut ReportCollection) {
     let symbols_in_body_well_defined_result = check_naming_correctness(program_archive);
     if let Result::Err(mut symbols_in_body_well_defined_reports) =
@@ -89,13 +248,26 @@ fn program_level_analyses(program_archive: &ProgramArchive, reports: &mut Report
     {
         reports.append(&mut symbols_in_body_well_defined_reports);
     }
+    if let Result::Err(mut global_constants_reports) = check_global_constants(program_archive) {
+        reports.append(&mut global_constants_reports);
+    }
+    if let Result::Err(mut template_contracts_reports) = check_template_contracts(program_archive) {
+        reports.append(&mut template_contracts_reports);
+    }
 }
 
-fn template_level_analyses(program_archive: &ProgramArchive, reports: &mut ReportCollection) {
+fn template_level_analyses(
+    program_archive: &ProgramArchive,
+    failed: &FailedDefinitions,
+    reports: &mut ReportCollection,
+) {
     use program_structure::error_code::ReportCode;
     use program_structure::error_definition::Report;
     
-    for template_data in program_archive.get_templates().values() {
+    for (name, template_data) in program_archive.get_templates() {
+        if failed.names.contains(name) {
+            continue;
+        }
         // check that extern_c only if custom gate
         if template_data.is_extern_c() && !template_data.is_custom_gate(){
             let mut report = Report::error(
@@ -122,18 +294,29 @@ fn template_level_analyses(program_archive: &ProgramArchive, reports: &mut Repor
 
 fn template_level_decorators(
     program_archive: &mut ProgramArchive,
+    failed: &FailedDefinitions,
     reports: &mut ReportCollection,
 ) {
     component_type_inference::inference(program_archive);
     let program_archive2 = program_archive.clone();
-    for template_data in program_archive.get_mut_templates().values_mut() {
+    for (name, template_data) in program_archive.get_mut_templates() {
+        if failed.names.contains(name) {
+            continue;
+        }
         reports.append(&mut type_reduction::reduce_template(template_data,&program_archive2));
     }
 }
 
-fn function_level_analyses(program_archive: &ProgramArchive, reports: &mut ReportCollection) {
+fn function_level_analyses(
+    program_archive: &ProgramArchive,
+    failed: &FailedDefinitions,
+    reports: &mut ReportCollection,
+) {
     let function_names = program_archive.get_function_names();
-    for function_data in program_archive.get_functions().values() {
+    for (name, function_data) in program_archive.get_functions() {
+        if failed.names.contains(name) {
+            continue;
+        }
         let free_of_template_elements_result = free_of_template_elements(function_data, function_names);
         let return_check_result = all_paths_with_return_check(function_data);
         if let Result::Err(mut functions_free_of_template_elements_reports) = free_of_template_elements_result {
@@ -145,19 +328,34 @@ fn function_level_analyses(program_archive: &ProgramArchive, reports: &mut Repor
     }
 }
 
-fn function_level_decorators(program_archive: &mut ProgramArchive, reports: &mut ReportCollection) {
+fn function_level_decorators(
+    program_archive: &mut ProgramArchive,
+    failed: &FailedDefinitions,
+    reports: &mut ReportCollection,
+) {
     let program_archive2 = program_archive.clone();
-    for function_data in program_archive.get_mut_functions().values_mut() {
+    let global_constants = program_archive2.get_constant_names();
+    for (name, function_data) in program_archive.get_mut_functions() {
+        if failed.names.contains(name) {
+            continue;
+        }
         let mut constant_handler_reports =
-            constants_handler::handle_function_constants(function_data);
+            constants_handler::handle_function_constants(function_data, global_constants);
         reports.append(&mut type_reduction::reduce_function(function_data,&program_archive2));
         reports.append(&mut constant_handler_reports);
     }
 }
 
-fn bus_level_analyses(program_archive: &ProgramArchive, reports: &mut ReportCollection) {
+fn bus_level_analyses(
+    program_archive: &ProgramArchive,
+    failed: &FailedDefinitions,
+    reports: &mut ReportCollection,
+) {
     let function_names = program_archive.get_function_names();
-    for bus_data in program_archive.get_buses().values() {
+    for (name, bus_data) in program_archive.get_buses() {
+        if failed.names.contains(name) {
+            continue;
+        }
         let free_of_invalid_statements_result = free_of_invalid_statements(bus_data, function_names);
         if let Result::Err(mut free_of_invalid_statements_reports) = free_of_invalid_statements_result {
             reports.append(&mut free_of_invalid_statements_reports);
@@ -165,28 +363,38 @@ fn bus_level_analyses(program_archive: &ProgramArchive, reports: &mut ReportColl
     }
 }
 
-fn bus_level_decorators(program_archive: &mut ProgramArchive, reports: &mut ReportCollection) {
+fn bus_level_decorators(
+    program_archive: &mut ProgramArchive,
+    failed: &FailedDefinitions,
+    reports: &mut ReportCollection,
+) {
     let program_archive2 = program_archive.clone();
-    for bus_data in program_archive.get_mut_buses().values_mut() {
+    let global_constants = program_archive2.get_constant_names();
+    for (name, bus_data) in program_archive.get_mut_buses() {
+        if failed.names.contains(name) {
+            continue;
+        }
         let mut constant_handler_reports =
-            constants_handler::handle_bus_constants(bus_data);
+            constants_handler::handle_bus_constants(bus_data, global_constants);
         reports.append(&mut type_reduction::reduce_bus(bus_data,&program_archive2));
         reports.append(&mut constant_handler_reports);
     }
 }
 
+// The definitions in skipped are not analyzed
 fn semantic_analyses(
     program_archive: &ProgramArchive,
+    skipped: &HashSet<String>,
     errors: &mut ReportCollection,
     warnings: &mut ReportCollection,
 ) {
-    for bus_name in program_archive.get_bus_names().iter() {
+    for bus_name in program_archive.get_bus_names().iter().filter(|name| !skipped.contains(*name)) {
         if let Result::Err(mut unknown_known_report) =
             unknown_known_analysis(&bus_name, program_archive) {
                 errors.append(&mut unknown_known_report);
             }
     }
-    for template_name in program_archive.get_template_names().iter() {
+    for template_name in program_archive.get_template_names().iter().filter(|name| !skipped.contains(*name)) {
         if let Result::Err(mut unknown_known_report) =
             unknown_known_analysis(template_name, program_archive) {
                 errors.append(&mut unknown_known_report);
@@ -197,6 +405,21 @@ fn semantic_analyses(
                 Result::Ok(mut custom_gate_report) => warnings.append(&mut custom_gate_report),
                 Result::Err(mut custom_gate_report) => errors.append(&mut custom_gate_report)
             }
+        } else {
+            let template_data = program_archive.get_template_data(template_name);
+            if !program_archive.is_library_file(template_data.get_file_id()) {
+                warnings.append(&mut unconstrained_assignment_analysis(template_data));
+            }
+        }
+        let template_data = program_archive.get_template_data(template_name);
+        if !program_archive.is_library_file(template_data.get_file_id()) {
+            warnings.append(&mut unused_symbols_analysis(template_data.get_body(), false));
+        }
+    }
+    for function_name in program_archive.get_function_names().iter().filter(|name| !skipped.contains(*name)) {
+        let function_data = program_archive.get_function_data(function_name);
+        if !program_archive.is_library_file(function_data.get_file_id()) {
+            warnings.append(&mut unused_symbols_analysis(function_data.get_body(), true));
         }
     }
 }
@@ -12,9 +12,12 @@ use std::collections::HashSet;
 type Constants = VarEnvironment<bool>;
 type ExpressionHolder = VarEnvironment<Expression>;
 
-pub fn handle_function_constants(function: &mut FunctionData) -> ReportCollection {
+pub fn handle_function_constants(function: &mut FunctionData, global_constants: &HashSet<String>) -> ReportCollection {
     let mut environment = Constants::new();
     let mut expression_holder = ExpressionHolder::new();
+    for c in global_constants {
+        environment.add_variable(c, true);
+    }
     for p in function.get_name_of_params() {
         environment.add_variable(p, false);
     }
@@ -40,9 +43,12 @@ pub fn _handle_template_constants(template: &mut TemplateData) -> ReportCollecti
     reports
 }
 
-pub fn handle_bus_constants(bus: &mut BusData) -> ReportCollection {
+pub fn handle_bus_constants(bus: &mut BusData, global_constants: &HashSet<String>) -> ReportCollection {
     let mut environment = Constants::new();
     let mut expression_holder = ExpressionHolder::new();
+    for c in global_constants {
+        environment.add_variable(c, true);
+    }
     for p in bus.get_name_of_params() {
         environment.add_variable(p, true);
         let meta = bus.get_body().get_meta().clone();
@@ -309,7 +315,7 @@ fn expand_statement(stmt: &mut Statement, environment: &mut ExpressionHolder) {
         Substitution { access, rhe, .. } => expand_substitution(access, rhe, environment),
         ConstraintEquality { lhe, rhe, .. } => expand_constraint_equality(lhe, rhe, environment),
         LogCall { args, .. } => expand_log_call(args, environment),
-        Assert { arg, .. } => expand_assert(arg, environment),
+        Assert { arg, .. } | StaticAssert { arg, .. } => expand_assert(arg, environment),
         Block { stmts, .. } => expand_block(stmts, environment),
         MultSubstitution { .. } => unreachable!(),
         UnderscoreSubstitution { rhe, .. } => expand_underscore_substitution(rhe, environment),
@@ -7,6 +7,7 @@ use program_structure::bus_data::BusData;
 use program_structure::environment::CircomEnvironment;
 use program_structure::function_data::FunctionData;
 use program_structure::template_data::TemplateData;
+use program_structure::suggestions::closest_name;
 
 type Environment = CircomEnvironment<TypeKnowledge, (), (), TypeKnowledge>;
 
@@ -57,7 +58,9 @@ fn reduce_types_in_statement(stmt: &mut Statement, environment: &mut Environment
                 reduce_types_in_log_call(args, environment,program_archive)
             
         },
-        Assert { arg, .. } => reduce_types_in_expression(arg, environment,program_archive),
+        Assert { arg, .. } | StaticAssert { arg, .. } => {
+            reduce_types_in_expression(arg, environment, program_archive)
+        }
         Return { value, .. } => reduce_types_in_expression(value, environment,program_archive),
         ConstraintEquality { lhe, rhe, .. } => {
             reduce_types_in_constraint_equality(lhe, rhe, environment,program_archive)
@@ -252,7 +255,7 @@ fn reduce_types_in_variable(
                                     WireType::Bus(new_name) => reduction = Bus(Some(new_name)),
                              }//If it is not a signal or a bus, it is expected to be tag. 
                             } else {//Then, type_check will finally check it.                          
-                                name_not_found_in_component_error(name.clone(), oname.to_string(), meta,&mut  reports); 
+                                name_not_found_in_component_error(name.clone(), oname.to_string(), template, meta,&mut  reports); 
                                 return reports;  
                             }
                         }
@@ -323,10 +326,14 @@ fn reduce_types_in_vec_of_expressions(vec: &mut [Expression], environment: &Envi
 //     reports.push(report);
 // }
 
-fn name_not_found_in_component_error(signal: String, what: String, meta: &Meta, reports: &mut ReportCollection) {
+fn name_not_found_in_component_error(signal: String, what: String, template: &TemplateData, meta: &Meta, reports: &mut ReportCollection) {
     let message = "Bus or signal not defined in component".to_string();
     let error_code = ReportCode::InvalidSignalAccess;
     let mut report = Report::error(message, error_code);
+    let wires = template.get_inputs().keys().chain(template.get_outputs().keys());
+    if let Option::Some(closest) = closest_name(&signal, wires) {
+        report.add_note(format!("Did you mean {}?", closest));
+    }
     let message = signal + &" is not defined in ".to_string() + what.as_str();
     report.add_primary(meta.file_location(), meta.get_file_id(), message);
     reports.push(report);